@@ -0,0 +1,78 @@
+//! Spatial grid pruning against the all-pairs conflict check
+//!
+//! Run with `cargo bench --bench spatial`.
+
+use std::time::Instant;
+
+use atc_safety::{detect_conflict, detect_conflicts_spatial, screening_range, AircraftState, SafetyConfig};
+
+fn scattered(n: usize, extent: f64) -> Vec<AircraftState> {
+    let mut seed = 987654321u64;
+    let mut next = move || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (seed >> 11) as f64 / (1u64 << 53) as f64
+    };
+    (0..n)
+        .map(|_| {
+            AircraftState::new(
+                next() * extent - extent / 2.0,
+                next() * extent - extent / 2.0,
+                (next() * 20.0).floor() * 1000.0 + 10000.0,
+                next() * 360.0,
+                250.0 + next() * 250.0,
+            )
+        })
+        .collect()
+}
+
+fn all_pairs(aircraft: &[AircraftState], config: &SafetyConfig) -> usize {
+    let mut conflicts = 0;
+    for i in 0..aircraft.len() {
+        for j in (i + 1)..aircraft.len() {
+            let info = detect_conflict(
+                &aircraft[i],
+                &aircraft[j],
+                &config.alert_standards(),
+                config.look_ahead_time,
+            );
+            if info.severity != atc_safety::ConflictSeverity::None {
+                conflicts += 1;
+            }
+        }
+    }
+    conflicts
+}
+
+fn main() {
+    let config = SafetyConfig::new(5.0, 1000.0, 120.0, 1.0);
+    let range = screening_range(&config, 500.0);
+
+    for &n in &[100, 300, 1000] {
+        let aircraft = scattered(n, 800.0);
+        let runs = 5;
+
+        let start = Instant::now();
+        let mut expected = 0;
+        for _ in 0..runs {
+            expected = all_pairs(&aircraft, &config);
+        }
+        let brute = start.elapsed() / runs;
+
+        let start = Instant::now();
+        let mut found = 0;
+        for _ in 0..runs {
+            found = detect_conflicts_spatial(&aircraft, &config, range).len();
+        }
+        let grid = start.elapsed() / runs;
+
+        assert_eq!(found, expected);
+        println!(
+            "{:>5} aircraft: all pairs {:>10.3?}  grid {:>10.3?}  speedup {:>5.1}x  ({} conflicts)",
+            n,
+            brute,
+            grid,
+            brute.as_secs_f64() / grid.as_secs_f64(),
+            found
+        );
+    }
+}
@@ -0,0 +1,59 @@
+/*!
+ * ANGLE MODULE
+ * Heading normalization and shortest-arc arithmetic
+ */
+
+/// Normalize a heading to [0, 360), so 360 becomes 0
+pub fn normalize_heading(heading: f64) -> f64 {
+    let result = heading % 360.0;
+    let result = if result < 0.0 { result + 360.0 } else { result };
+
+    // Tiny negative inputs can round up to exactly 360
+    if result >= 360.0 {
+        0.0
+    } else {
+        result
+    }
+}
+
+/// Signed turn from one heading to another in (-180, 180], positive is right
+pub fn heading_difference(from: f64, to: f64) -> f64 {
+    let diff = normalize_heading(to - from);
+    if diff > 180.0 {
+        diff - 360.0
+    } else {
+        diff
+    }
+}
+
+/// Interpolate between two headings along the shortest arc, `t` in [0, 1]
+pub fn interpolate_heading(from: f64, to: f64, t: f64) -> f64 {
+    normalize_heading(from + heading_difference(from, to) * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_heading() {
+        assert_eq!(normalize_heading(360.0), 0.0);
+        assert_eq!(normalize_heading(-90.0), 270.0);
+        assert_eq!(normalize_heading(725.0), 5.0);
+        assert_eq!(normalize_heading(-1e-15), 0.0);
+    }
+
+    #[test]
+    fn test_heading_difference() {
+        assert_eq!(heading_difference(350.0, 10.0), 20.0);
+        assert_eq!(heading_difference(10.0, 350.0), -20.0);
+        assert_eq!(heading_difference(0.0, 180.0), 180.0);
+        assert_eq!(heading_difference(90.0, 360.0), -90.0);
+    }
+
+    #[test]
+    fn test_interpolate_heading() {
+        assert_eq!(interpolate_heading(350.0, 10.0, 0.5), 0.0);
+        assert_eq!(interpolate_heading(90.0, 180.0, 0.5), 135.0);
+    }
+}
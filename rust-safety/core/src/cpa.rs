@@ -0,0 +1,130 @@
+/*!
+ * CPA MODULE
+ * Closest point of approach for straight-line tracks
+ */
+
+use crate::geometry::{horizontal_distance, velocity};
+use crate::math;
+use crate::prediction::predict;
+use crate::State;
+
+/// Time until two aircraft reach their closest point of approach
+///
+/// Returns `None` when there is no relative motion or the closest point
+/// is already behind them.
+pub fn time_to_closest_approach(a: &State, b: &State) -> Option<f64> {
+    let dx = b.x - a.x;
+    let dy = b.y - a.y;
+
+    let (v1x, v1y) = velocity(a);
+    let (v2x, v2y) = velocity(b);
+    let dvx = v2x - v1x;
+    let dvy = v2y - v1y;
+
+    let relative_speed_squared = dvx * dvx + dvy * dvy;
+    if relative_speed_squared < 1e-10 {
+        return None;
+    }
+
+    let time = -(dx * dvx + dy * dvy) / relative_speed_squared;
+    if time > 0.0 {
+        Some(time)
+    } else {
+        None
+    }
+}
+
+/// Horizontal miss distance within a horizon, and when it occurs
+pub fn closest_approach_within(a: &State, b: &State, horizon: f64) -> (f64, f64) {
+    let time = time_to_closest_approach(a, b).map_or(0.0, |t| t.min(horizon));
+    let distance = horizontal_distance(&predict(a, time), &predict(b, time));
+    (distance, time)
+}
+
+/// Interval (start, end) in which two aircraft are within `distance` nm of each other
+///
+/// Solves |p + v t| = distance for the relative position p and velocity v.
+/// The start is zero when they are already inside and the end is infinite
+/// when there is no relative motion. `None` when they never get that close.
+pub fn horizontal_distance_window(a: &State, b: &State, distance: f64) -> Option<(f64, f64)> {
+    let dx = b.x - a.x;
+    let dy = b.y - a.y;
+    let c = dx * dx + dy * dy - distance * distance;
+
+    let (v1x, v1y) = velocity(a);
+    let (v2x, v2y) = velocity(b);
+    let dvx = v2x - v1x;
+    let dvy = v2y - v1y;
+
+    let a2 = dvx * dvx + dvy * dvy;
+    if a2 < 1e-10 {
+        return if c < 0.0 { Some((0.0, f64::INFINITY)) } else { None };
+    }
+
+    let b2 = 2.0 * (dx * dvx + dy * dvy);
+    let discriminant = b2 * b2 - 4.0 * a2 * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let root = math::sqrt(discriminant);
+    let start = (-b2 - root) / (2.0 * a2);
+    let end = (-b2 + root) / (2.0 * a2);
+    if end < 0.0 {
+        None
+    } else {
+        Some((start.max(0.0), end))
+    }
+}
+
+/// Time until two aircraft first come within `distance` nm of each other
+///
+/// Returns zero when they are already inside, and `None` when they never
+/// get that close.
+pub fn time_to_horizontal_distance(a: &State, b: &State, distance: f64) -> Option<f64> {
+    horizontal_distance_window(a, b, distance).map(|(start, _)| start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_head_on_cpa() {
+        let a = State::new(0.0, 0.0, 10000.0, 0.0, 250.0);
+        let b = State::new(0.0, 10.0, 10000.0, 180.0, 250.0);
+
+        let time = time_to_closest_approach(&a, &b).unwrap();
+        assert!((time - 72.0).abs() < 1e-6);
+
+        let (distance, at) = closest_approach_within(&a, &b, 300.0);
+        assert!(distance < 1e-6);
+        assert!((at - 72.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_diverging_has_no_cpa() {
+        let a = State::new(0.0, 0.0, 10000.0, 180.0, 250.0);
+        let b = State::new(0.0, 10.0, 10000.0, 0.0, 250.0);
+        assert!(time_to_closest_approach(&a, &b).is_none());
+        assert_eq!(closest_approach_within(&a, &b, 300.0).0, 10.0);
+        assert!(time_to_horizontal_distance(&a, &b, 5.0).is_none());
+    }
+
+    #[test]
+    fn test_time_to_distance() {
+        let a = State::new(0.0, 0.0, 10000.0, 0.0, 250.0);
+        let b = State::new(0.0, 10.0, 10000.0, 180.0, 250.0);
+
+        // Closing at 500 kt, 5 nm gone after 36 s
+        let time = time_to_horizontal_distance(&a, &b, 5.0).unwrap();
+        assert!((time - 36.0).abs() < 1e-6);
+        assert_eq!(time_to_horizontal_distance(&a, &b, 12.0), Some(0.0));
+
+        let (start, end) = horizontal_distance_window(&a, &b, 5.0).unwrap();
+        assert!((start - 36.0).abs() < 1e-6 && (end - 108.0).abs() < 1e-6);
+
+        let offset = State::new(6.0, 10.0, 10000.0, 180.0, 250.0);
+        assert!(time_to_horizontal_distance(&a, &offset, 5.0).is_none());
+    }
+}
@@ -0,0 +1,61 @@
+/*!
+ * GEOMETRY MODULE
+ * Distances and velocity components
+ */
+
+use crate::angle::normalize_heading;
+use crate::math;
+use crate::State;
+
+/// Horizontal distance between two aircraft (nm)
+pub fn horizontal_distance(a: &State, b: &State) -> f64 {
+    let dx = a.x - b.x;
+    let dy = a.y - b.y;
+    math::sqrt(dx * dx + dy * dy)
+}
+
+/// Vertical distance between two aircraft (ft)
+pub fn vertical_distance(a: &State, b: &State) -> f64 {
+    (a.altitude - b.altitude).abs()
+}
+
+/// True when either the horizontal or the vertical minimum is met
+pub fn is_separated(a: &State, b: &State, min_horizontal: f64, min_vertical: f64) -> bool {
+    horizontal_distance(a, b) >= min_horizontal || vertical_distance(a, b) >= min_vertical
+}
+
+/// Ground velocity in nm per second as (east, north)
+pub fn velocity(state: &State) -> (f64, f64) {
+    let heading_rad = state.heading.to_radians();
+    let speed = state.speed / 3600.0;
+    (math::sin(heading_rad) * speed, math::cos(heading_rad) * speed)
+}
+
+/// Bearing from one point to another in degrees [0, 360)
+pub fn bearing(from_x: f64, from_y: f64, to_x: f64, to_y: f64) -> f64 {
+    normalize_heading(math::atan2(to_x - from_x, to_y - from_y).to_degrees())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distances() {
+        let a = State::new(0.0, 0.0, 10000.0, 0.0, 250.0);
+        let b = State::new(3.0, 4.0, 10500.0, 180.0, 250.0);
+        assert_eq!(horizontal_distance(&a, &b), 5.0);
+        assert_eq!(vertical_distance(&a, &b), 500.0);
+        assert!(is_separated(&a, &b, 3.0, 1000.0));
+        assert!(!is_separated(&a, &b, 6.0, 1000.0));
+    }
+
+    #[test]
+    fn test_velocity_and_bearing() {
+        let (vx, vy) = velocity(&State::new(0.0, 0.0, 0.0, 90.0, 360.0));
+        assert!((vx - 0.1).abs() < 1e-12);
+        assert!(vy.abs() < 1e-12);
+
+        assert!((bearing(0.0, 0.0, -1.0, 0.0) - 270.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,52 @@
+/*!
+ * ATC SAFETY CORE
+ * Allocation-free separation math usable without the standard library
+ *
+ * Units follow the main crate: x/y in nautical miles, altitude in feet,
+ * heading in degrees, speed in knots, vertical rate in feet per minute and
+ * time in seconds.
+ */
+
+#![cfg_attr(not(test), no_std)]
+
+mod angle;
+mod cpa;
+mod geometry;
+mod math;
+mod prediction;
+
+pub use angle::*;
+pub use cpa::*;
+pub use geometry::*;
+pub use prediction::*;
+
+/// Plain kinematic state of one aircraft
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct State {
+    pub x: f64,
+    pub y: f64,
+    pub altitude: f64,
+    pub heading: f64,
+    pub speed: f64,
+    /// Feet per minute, positive climbing
+    pub vertical_rate: f64,
+}
+
+impl State {
+    /// Level state
+    pub fn new(x: f64, y: f64, altitude: f64, heading: f64, speed: f64) -> Self {
+        State {
+            x,
+            y,
+            altitude,
+            heading,
+            speed,
+            vertical_rate: 0.0,
+        }
+    }
+
+    pub fn with_vertical_rate(mut self, vertical_rate: f64) -> Self {
+        self.vertical_rate = vertical_rate;
+        self
+    }
+}
@@ -0,0 +1,20 @@
+/*!
+ * MATH MODULE
+ * Float functions routed through libm so results match on every target
+ */
+
+pub(crate) fn sqrt(value: f64) -> f64 {
+    libm::sqrt(value)
+}
+
+pub(crate) fn sin(value: f64) -> f64 {
+    libm::sin(value)
+}
+
+pub(crate) fn cos(value: f64) -> f64 {
+    libm::cos(value)
+}
+
+pub(crate) fn atan2(y: f64, x: f64) -> f64 {
+    libm::atan2(y, x)
+}
@@ -0,0 +1,70 @@
+/*!
+ * PREDICTION MODULE
+ * Straight-line dead reckoning with constant vertical rate
+ */
+
+use crate::geometry::velocity;
+use crate::State;
+
+/// Predict a state after `time_seconds` on constant heading, speed and vertical rate
+pub fn predict(state: &State, time_seconds: f64) -> State {
+    let (vx, vy) = velocity(state);
+
+    State {
+        x: state.x + vx * time_seconds,
+        y: state.y + vy * time_seconds,
+        altitude: state.altitude + state.vertical_rate / 60.0 * time_seconds,
+        ..*state
+    }
+}
+
+/// Seconds until a climb or descent reaches `target_altitude`
+///
+/// `None` when level or when the vertical rate points away from the target.
+pub fn time_to_level_off(state: &State, target_altitude: f64) -> Option<f64> {
+    let to_go = target_altitude - state.altitude;
+    if state.vertical_rate == 0.0 || to_go * state.vertical_rate < 0.0 {
+        return None;
+    }
+    Some(to_go / state.vertical_rate * 60.0)
+}
+
+/// Like `predict`, but levelling off at `target_altitude` once reached
+pub fn predict_to_level(state: &State, time_seconds: f64, target_altitude: f64) -> State {
+    match time_to_level_off(state, target_altitude) {
+        Some(level_off) if time_seconds >= level_off => State {
+            altitude: target_altitude,
+            vertical_rate: 0.0,
+            ..predict(state, time_seconds)
+        },
+        _ => predict(state, time_seconds),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_predict_north() {
+        let state = State::new(0.0, 0.0, 10000.0, 0.0, 360.0);
+        let future = predict(&state, 60.0);
+        assert!(future.x.abs() < 1e-9);
+        assert!((future.y - 6.0).abs() < 1e-9);
+        assert_eq!(future.altitude, 10000.0);
+    }
+
+    #[test]
+    fn test_climb_levels_off() {
+        let state = State::new(0.0, 0.0, 10000.0, 0.0, 360.0).with_vertical_rate(2000.0);
+        assert_eq!(predict(&state, 60.0).altitude, 12000.0);
+        assert_eq!(time_to_level_off(&state, 11000.0), Some(30.0));
+        assert_eq!(time_to_level_off(&state, 9000.0), None);
+
+        let levelled = predict_to_level(&state, 60.0, 11000.0);
+        assert_eq!(levelled.altitude, 11000.0);
+        assert_eq!(levelled.vertical_rate, 0.0);
+        assert!((levelled.y - 6.0).abs() < 1e-9);
+        assert_eq!(predict_to_level(&state, 15.0, 11000.0).altitude, 10500.0);
+    }
+}
@@ -0,0 +1,139 @@
+/*!
+ * ACTIVITY MODULE
+ * Scheduled vertical activity columns (parachute drop zones, glider areas)
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::separation::predict_position;
+use crate::AircraftState;
+
+/// Kind of activity taking place in a column
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityKind {
+    Parachuting,
+    Gliding,
+}
+
+/// Cylinder of airspace with scheduled activation windows
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityColumn {
+    pub name: String,
+    pub kind: ActivityKind,
+    pub x: f64,
+    pub y: f64,
+    pub radius: f64,
+    pub floor: f64,
+    pub ceiling: f64,
+    /// Active (start, end) simulation times in seconds
+    pub schedule: Vec<(f64, f64)>,
+}
+
+impl ActivityColumn {
+    pub fn new(name: &str, kind: ActivityKind, x: f64, y: f64, radius: f64, floor: f64, ceiling: f64) -> Self {
+        ActivityColumn {
+            name: name.to_string(),
+            kind,
+            x,
+            y,
+            radius,
+            floor,
+            ceiling,
+            schedule: Vec::new(),
+        }
+    }
+
+    pub fn with_window(mut self, start: f64, end: f64) -> Self {
+        self.schedule.push((start, end));
+        self
+    }
+
+    pub fn is_active(&self, time: f64) -> bool {
+        self.schedule.iter().any(|&(start, end)| time >= start && time < end)
+    }
+
+    /// Check whether a position is inside the column widened by `buffer` nm
+    pub fn contains(&self, x: f64, y: f64, altitude: f64, buffer: f64) -> bool {
+        let dx = x - self.x;
+        let dy = y - self.y;
+        (dx * dx + dy * dy).sqrt() < self.radius + buffer
+            && altitude >= self.floor
+            && altitude <= self.ceiling
+    }
+}
+
+/// Aircraft predicted to pass through an active column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnAdvisory {
+    pub column: String,
+    pub kind: ActivityKind,
+    pub time_to_entry: f64,
+}
+
+/// Advisories for every active column the aircraft is predicted to enter
+///
+/// Columns are checked against their schedule at the predicted time, so a
+/// column activating during the look-ahead is included.
+pub fn probe_activity_columns(
+    aircraft: &AircraftState,
+    columns: &[ActivityColumn],
+    now: f64,
+    look_ahead_time: f64,
+    buffer: f64,
+) -> Vec<ColumnAdvisory> {
+    let mut advisories = Vec::new();
+
+    for column in columns {
+        let mut time = 0.0;
+        while time <= look_ahead_time {
+            let state = predict_position(aircraft, time);
+            if column.is_active(now + time) && column.contains(state.x, state.y, state.altitude, buffer) {
+                advisories.push(ColumnAdvisory {
+                    column: column.name.clone(),
+                    kind: column.kind,
+                    time_to_entry: time,
+                });
+                break;
+            }
+            time += 1.0;
+        }
+    }
+
+    advisories.sort_by(|a, b| a.time_to_entry.total_cmp(&b.time_to_entry));
+    advisories
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drop_zone() -> ActivityColumn {
+        ActivityColumn::new("DZ1", ActivityKind::Parachuting, 10.0, 0.0, 1.5, 0.0, 13000.0).with_window(600.0, 1800.0)
+    }
+
+    #[test]
+    fn test_schedule() {
+        let column = drop_zone();
+        assert!(!column.is_active(599.0));
+        assert!(column.is_active(600.0));
+        assert!(!column.is_active(1800.0));
+    }
+
+    #[test]
+    fn test_probe_respects_activation() {
+        // 10 nm west of the drop zone, eastbound at 240 kt: reaches it in about 2 minutes
+        let aircraft = AircraftState::new(0.0, 0.0, 8000.0, 90.0, 240.0);
+
+        assert!(probe_activity_columns(&aircraft, &[drop_zone()], 0.0, 300.0, 1.0).is_empty());
+
+        let advisories = probe_activity_columns(&aircraft, &[drop_zone()], 550.0, 300.0, 1.0);
+        assert_eq!(advisories.len(), 1);
+        assert_eq!(advisories[0].column, "DZ1");
+        assert_eq!(advisories[0].time_to_entry, 113.0);
+
+        // Above the column ceiling
+        let high = AircraftState::new(0.0, 0.0, 15000.0, 90.0, 240.0);
+        assert!(probe_activity_columns(&high, &[drop_zone()], 550.0, 300.0, 1.0).is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+/*!
+ * ADS-B MODULE
+ * Synthetic ADS-B style output feed of the simulated traffic
+ */
+
+use std::collections::HashMap;
+
+use crate::AircraftState;
+
+/// Nautical miles per degree of latitude
+const NM_PER_DEGREE: f64 = 60.0;
+
+/// Output line format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdsbFormat {
+    /// BaseStation (SBS-1) MSG,3 position and MSG,4 velocity messages
+    Sbs1,
+    /// One JSON object per report
+    Json,
+}
+
+/// Aircraft as broadcast on the feed
+#[derive(Debug, Clone)]
+pub struct AdsbTarget {
+    pub icao: u32,
+    pub callsign: String,
+    pub state: AircraftState,
+}
+
+/// Emits reports for each target at a nominal interval with jitter and dropouts
+///
+/// Simulation x/y (nm) are converted to latitude/longitude around a
+/// reference point with a flat-earth approximation. Jitter and dropouts
+/// come from a seeded generator, so a feed is reproducible.
+#[derive(Debug, Clone)]
+pub struct AdsbEncoder {
+    format: AdsbFormat,
+    reference: (f64, f64),
+    interval: f64,
+    jitter: f64,
+    dropout_probability: f64,
+    rng: u64,
+    next_due: HashMap<u32, f64>,
+}
+
+impl AdsbEncoder {
+    pub fn new(format: AdsbFormat, reference_lat: f64, reference_lon: f64, interval: f64, seed: u64) -> Self {
+        AdsbEncoder {
+            format,
+            reference: (reference_lat, reference_lon),
+            interval,
+            jitter: 0.0,
+            dropout_probability: 0.0,
+            rng: seed | 1,
+            next_due: HashMap::new(),
+        }
+    }
+
+    /// Vary each report time by up to `jitter` seconds either way
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.max(0.0);
+        self
+    }
+
+    /// Drop each due report with this probability
+    pub fn with_dropouts(mut self, probability: f64) -> Self {
+        self.dropout_probability = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Uniform value in [0, 1) from a xorshift generator
+    fn next_random(&mut self) -> f64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn lat_lon(&self, state: &AircraftState) -> (f64, f64) {
+        let (lat, lon) = self.reference;
+        let lat_out = lat + state.y / NM_PER_DEGREE;
+        let lon_out = lon + state.x / (NM_PER_DEGREE * lat.to_radians().cos());
+        (lat_out, lon_out)
+    }
+
+    /// Lines for every target due a report at simulation `time` (seconds)
+    pub fn emit(&mut self, time: f64, targets: &[AdsbTarget]) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        for target in targets {
+            let due = *self.next_due.get(&target.icao).unwrap_or(&time);
+            if time < due {
+                continue;
+            }
+
+            let offset = (self.next_random() * 2.0 - 1.0) * self.jitter;
+            self.next_due.insert(target.icao, time + (self.interval + offset).max(0.0));
+
+            if self.next_random() < self.dropout_probability {
+                continue;
+            }
+            self.encode(time, target, &mut lines);
+        }
+
+        lines
+    }
+
+    /// Stop tracking a target that left the simulation
+    pub fn remove(&mut self, icao: u32) {
+        self.next_due.remove(&icao);
+    }
+
+    fn encode(&self, time: f64, target: &AdsbTarget, lines: &mut Vec<String>) {
+        let (lat, lon) = self.lat_lon(&target.state);
+        let state = &target.state;
+
+        match self.format {
+            AdsbFormat::Sbs1 => {
+                let stamp = format!("1970/01/01,{}", time_of_day(time));
+                lines.push(format!(
+                    "MSG,3,1,1,{:06X},1,{},{},{},{:.0},,,{:.5},{:.5},,,0,0,0,0",
+                    target.icao, stamp, stamp, target.callsign, state.altitude, lat, lon
+                ));
+                lines.push(format!(
+                    "MSG,4,1,1,{:06X},1,{},{},,,{:.0},{:.0},,,0,,0,0,0,0",
+                    target.icao, stamp, stamp, state.speed, state.heading
+                ));
+            }
+            AdsbFormat::Json => lines.push(format!(
+                "{{\"now\":{:.1},\"hex\":\"{:06x}\",\"flight\":\"{}\",\"alt_baro\":{:.0},\"gs\":{:.0},\"track\":{:.0},\"lat\":{:.5},\"lon\":{:.5}}}",
+                time, target.icao, target.callsign, state.altitude, state.speed, state.heading, lat, lon
+            )),
+        }
+    }
+}
+
+/// `HH:MM:SS.mmm` of a simulation time measured from midnight
+fn time_of_day(time: f64) -> String {
+    let millis = (time.max(0.0) * 1000.0).round() as u64 % 86_400_000;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target() -> AdsbTarget {
+        AdsbTarget {
+            icao: 0x4CA1B2,
+            callsign: "EIN123".to_string(),
+            state: AircraftState::new(0.0, 60.0, 35000.0, 90.0, 450.0),
+        }
+    }
+
+    #[test]
+    fn test_sbs1_and_json_lines() {
+        let mut sbs = AdsbEncoder::new(AdsbFormat::Sbs1, 53.0, -6.0, 1.0, 7);
+        let lines = sbs.emit(3723.5, &[target()]);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            "MSG,3,1,1,4CA1B2,1,1970/01/01,01:02:03.500,1970/01/01,01:02:03.500,EIN123,35000,,,54.00000,-6.00000,,,0,0,0,0"
+        );
+        assert!(lines[1].starts_with("MSG,4,1,1,4CA1B2,"));
+        assert!(lines[1].contains(",,,450,90,,,"));
+
+        let mut json = AdsbEncoder::new(AdsbFormat::Json, 53.0, -6.0, 1.0, 7);
+        let line = &json.emit(10.0, &[target()])[0];
+        assert!(line.contains("\"hex\":\"4ca1b2\""));
+        assert!(line.contains("\"lat\":54.00000"));
+    }
+
+    #[test]
+    fn test_rate_jitter_and_dropouts() {
+        let mut encoder = AdsbEncoder::new(AdsbFormat::Json, 53.0, -6.0, 5.0, 42);
+        let reports = (0..60).filter(|&t| !encoder.emit(t as f64, &[target()]).is_empty()).count();
+        assert_eq!(reports, 12);
+
+        let mut lossy = AdsbEncoder::new(AdsbFormat::Json, 53.0, -6.0, 1.0, 42)
+            .with_jitter(0.4)
+            .with_dropouts(0.3);
+        let reports = (0..1000).filter(|&t| !lossy.emit(t as f64 * 0.5, &[target()]).is_empty()).count();
+        // Roughly one due report every 1.25 s of sampling, 30% of them dropped
+        assert!(reports > 200 && reports < 360, "{}", reports);
+    }
+}
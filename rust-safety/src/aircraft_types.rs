@@ -0,0 +1,166 @@
+/*!
+ * AIRCRAFT TYPES MODULE
+ * ICAO type designator lookups for wake and performance defaults
+ */
+
+use wasm_bindgen::prelude::*;
+
+/// ICAO wake turbulence category
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WakeCategory {
+    Light,
+    Medium,
+    Heavy,
+    Super,
+}
+
+/// Engine type from the ICAO type description
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineType {
+    Piston,
+    Turboprop,
+    Jet,
+}
+
+/// ICAO approach category based on threshold speed
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApproachCategory {
+    A,
+    B,
+    C,
+    D,
+    E,
+}
+
+impl ApproachCategory {
+    /// Representative final approach speed in knots
+    pub fn reference_speed(&self) -> f64 {
+        match self {
+            ApproachCategory::A => 90.0,
+            ApproachCategory::B => 120.0,
+            ApproachCategory::C => 140.0,
+            ApproachCategory::D => 160.0,
+            ApproachCategory::E => 180.0,
+        }
+    }
+
+    /// Category for a given threshold speed in knots
+    pub fn from_speed(speed: f64) -> Self {
+        if speed < 91.0 {
+            ApproachCategory::A
+        } else if speed < 121.0 {
+            ApproachCategory::B
+        } else if speed < 141.0 {
+            ApproachCategory::C
+        } else if speed < 166.0 {
+            ApproachCategory::D
+        } else {
+            ApproachCategory::E
+        }
+    }
+}
+
+/// Wake and performance defaults for an aircraft type
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypeInfo {
+    pub wake: WakeCategory,
+    pub engine: EngineType,
+    pub approach_category: ApproachCategory,
+    pub cruise_speed: f64,
+    pub climb_rate: f64,
+}
+
+impl TypeInfo {
+    pub fn new(
+        wake: WakeCategory,
+        engine: EngineType,
+        approach_category: ApproachCategory,
+        cruise_speed: f64,
+        climb_rate: f64,
+    ) -> Self {
+        TypeInfo {
+            wake,
+            engine,
+            approach_category,
+            cruise_speed,
+            climb_rate,
+        }
+    }
+
+    /// Final approach speed used for performance defaults
+    pub fn approach_speed(&self) -> f64 {
+        self.approach_category.reference_speed()
+    }
+}
+
+/// Bundled table of common type designators
+#[cfg(feature = "type-database")]
+const TYPE_TABLE: &[(&str, WakeCategory, EngineType, ApproachCategory, f64, f64)] = &[
+    ("A319", WakeCategory::Medium, EngineType::Jet, ApproachCategory::C, 450.0, 2500.0),
+    ("A320", WakeCategory::Medium, EngineType::Jet, ApproachCategory::C, 450.0, 2500.0),
+    ("A321", WakeCategory::Medium, EngineType::Jet, ApproachCategory::C, 450.0, 2300.0),
+    ("A332", WakeCategory::Heavy, EngineType::Jet, ApproachCategory::C, 470.0, 2000.0),
+    ("A333", WakeCategory::Heavy, EngineType::Jet, ApproachCategory::C, 470.0, 2000.0),
+    ("A359", WakeCategory::Heavy, EngineType::Jet, ApproachCategory::D, 488.0, 2200.0),
+    ("A388", WakeCategory::Super, EngineType::Jet, ApproachCategory::D, 490.0, 1500.0),
+    ("AT76", WakeCategory::Medium, EngineType::Turboprop, ApproachCategory::B, 275.0, 1500.0),
+    ("B38M", WakeCategory::Medium, EngineType::Jet, ApproachCategory::D, 453.0, 2500.0),
+    ("B737", WakeCategory::Medium, EngineType::Jet, ApproachCategory::C, 450.0, 2500.0),
+    ("B738", WakeCategory::Medium, EngineType::Jet, ApproachCategory::D, 453.0, 2500.0),
+    ("B739", WakeCategory::Medium, EngineType::Jet, ApproachCategory::D, 453.0, 2300.0),
+    ("B744", WakeCategory::Heavy, EngineType::Jet, ApproachCategory::D, 490.0, 1800.0),
+    ("B748", WakeCategory::Heavy, EngineType::Jet, ApproachCategory::D, 495.0, 1800.0),
+    ("B752", WakeCategory::Medium, EngineType::Jet, ApproachCategory::C, 460.0, 3000.0),
+    ("B763", WakeCategory::Heavy, EngineType::Jet, ApproachCategory::D, 470.0, 2200.0),
+    ("B77W", WakeCategory::Heavy, EngineType::Jet, ApproachCategory::D, 490.0, 2000.0),
+    ("B788", WakeCategory::Heavy, EngineType::Jet, ApproachCategory::D, 488.0, 2300.0),
+    ("B789", WakeCategory::Heavy, EngineType::Jet, ApproachCategory::D, 488.0, 2200.0),
+    ("BE20", WakeCategory::Light, EngineType::Turboprop, ApproachCategory::B, 290.0, 2000.0),
+    ("C172", WakeCategory::Light, EngineType::Piston, ApproachCategory::A, 120.0, 700.0),
+    ("CRJ9", WakeCategory::Medium, EngineType::Jet, ApproachCategory::C, 450.0, 2500.0),
+    ("DH8D", WakeCategory::Medium, EngineType::Turboprop, ApproachCategory::B, 360.0, 1800.0),
+    ("E175", WakeCategory::Medium, EngineType::Jet, ApproachCategory::C, 430.0, 2500.0),
+    ("PA28", WakeCategory::Light, EngineType::Piston, ApproachCategory::A, 125.0, 650.0),
+];
+
+/// Look up wake and performance defaults for an ICAO type designator
+#[cfg(feature = "type-database")]
+pub fn lookup_type_designator(designator: &str) -> Option<TypeInfo> {
+    let designator = designator.trim().to_uppercase();
+
+    TYPE_TABLE
+        .iter()
+        .find(|entry| entry.0 == designator)
+        .map(|&(_, wake, engine, approach, cruise, climb)| {
+            TypeInfo::new(wake, engine, approach, cruise, climb)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_approach_category_from_speed() {
+        assert_eq!(ApproachCategory::from_speed(85.0), ApproachCategory::A);
+        assert_eq!(ApproachCategory::from_speed(145.0), ApproachCategory::D);
+        assert_eq!(ApproachCategory::from_speed(170.0), ApproachCategory::E);
+    }
+
+    #[cfg(feature = "type-database")]
+    #[test]
+    fn test_lookup_type_designator() {
+        let info = lookup_type_designator("b738").unwrap();
+        assert_eq!(info.wake, WakeCategory::Medium);
+        assert_eq!(info.engine, EngineType::Jet);
+        assert_eq!(info.approach_speed(), 160.0);
+
+        assert_eq!(lookup_type_designator("A388").unwrap().wake, WakeCategory::Super);
+        assert!(lookup_type_designator("ZZZZ").is_none());
+    }
+}
@@ -0,0 +1,179 @@
+/*!
+ * AIRPORT MODULE
+ * Airport database and nearest-suitable-airport queries
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::normalize_heading;
+
+/// Feet per nautical mile
+const FEET_PER_NM: f64 = 6076.12;
+
+/// Airport with the runway lengths available (ft)
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Airport {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+    pub elevation: f64,
+    pub runway_lengths: Vec<f64>,
+}
+
+impl Airport {
+    pub fn new(name: &str, x: f64, y: f64, elevation: f64) -> Self {
+        Airport {
+            name: name.to_string(),
+            x,
+            y,
+            elevation,
+            runway_lengths: Vec::new(),
+        }
+    }
+
+    pub fn with_runway(mut self, length: f64) -> Self {
+        self.runway_lengths.push(length);
+        self
+    }
+
+    pub fn longest_runway(&self) -> f64 {
+        self.runway_lengths.iter().copied().fold(0.0, f64::max)
+    }
+
+    pub fn distance_from(&self, x: f64, y: f64) -> f64 {
+        let dx = self.x - x;
+        let dy = self.y - y;
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    pub fn bearing_from(&self, x: f64, y: f64) -> f64 {
+        normalize_heading((self.x - x).atan2(self.y - y).to_degrees())
+    }
+}
+
+/// Airport meeting a suitability query
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AirportCandidate {
+    pub name: String,
+    pub distance: f64,
+    pub bearing: f64,
+    pub longest_runway: f64,
+}
+
+/// Still-air gliding range (nm) from a height above the ground (ft)
+pub fn glide_range(height: f64, glide_ratio: f64) -> f64 {
+    (height.max(0.0) * glide_ratio.max(0.0)) / FEET_PER_NM
+}
+
+/// Collection of airports available for diversions
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct AirportDatabase {
+    airports: Vec<Airport>,
+}
+
+#[wasm_bindgen]
+impl AirportDatabase {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> AirportDatabase {
+        AirportDatabase::default()
+    }
+
+    /// Add or replace an airport
+    pub fn add(&mut self, name: &str, x: f64, y: f64, elevation: f64, runway_lengths: Vec<f64>) {
+        let airport = Airport {
+            runway_lengths,
+            ..Airport::new(name, x, y, elevation)
+        };
+        self.insert(airport);
+    }
+
+    pub fn len(&self) -> usize {
+        self.airports.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.airports.is_empty()
+    }
+
+    /// Airports with a runway of at least `min_runway_length` within `max_range`, nearest first
+    pub fn nearest_suitable(
+        &self,
+        x: f64,
+        y: f64,
+        min_runway_length: f64,
+        max_range: f64,
+    ) -> Vec<AirportCandidate> {
+        let mut candidates: Vec<AirportCandidate> = self
+            .airports
+            .iter()
+            .filter(|a| a.longest_runway() >= min_runway_length)
+            .map(|a| AirportCandidate {
+                name: a.name.clone(),
+                distance: a.distance_from(x, y),
+                bearing: a.bearing_from(x, y),
+                longest_runway: a.longest_runway(),
+            })
+            .filter(|c| c.distance <= max_range)
+            .collect();
+
+        candidates.sort_by(|a, b| a.distance.total_cmp(&b.distance).then_with(|| a.name.cmp(&b.name)));
+        candidates
+    }
+}
+
+impl AirportDatabase {
+    pub fn insert(&mut self, airport: Airport) {
+        match self.airports.iter_mut().find(|a| a.name == airport.name) {
+            Some(existing) => *existing = airport,
+            None => self.airports.push(airport),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Airport> {
+        self.airports.iter().find(|a| a.name == name)
+    }
+
+    pub fn airports(&self) -> &[Airport] {
+        &self.airports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn database() -> AirportDatabase {
+        let mut db = AirportDatabase::new();
+        db.insert(Airport::new("STRIP", 0.0, 5.0, 200.0).with_runway(2500.0));
+        db.insert(Airport::new("REGIONAL", 10.0, 0.0, 300.0).with_runway(6000.0).with_runway(4000.0));
+        db.add("MAJOR", 0.0, -30.0, 50.0, vec![10000.0, 12000.0]);
+        db
+    }
+
+    #[test]
+    fn test_runway_length_and_range_filtering() {
+        let db = database();
+
+        let all = db.nearest_suitable(0.0, 0.0, 0.0, 100.0);
+        let names: Vec<&str> = all.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["STRIP", "REGIONAL", "MAJOR"]);
+
+        let jets = db.nearest_suitable(0.0, 0.0, 5000.0, 100.0);
+        assert_eq!(jets[0].name, "REGIONAL");
+        assert_eq!(jets[0].bearing, 90.0);
+        assert_eq!(jets[0].longest_runway, 6000.0);
+
+        assert_eq!(db.nearest_suitable(0.0, 0.0, 5000.0, 20.0).len(), 1);
+    }
+
+    #[test]
+    fn test_glide_range() {
+        // 10,000 ft at 15:1 is just under 25 nm
+        let range = glide_range(10000.0, 15.0);
+        assert!(range > 24.0 && range < 25.0);
+        assert_eq!(glide_range(-100.0, 15.0), 0.0);
+    }
+}
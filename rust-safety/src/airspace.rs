@@ -0,0 +1,232 @@
+/*!
+ * AIRSPACE MODULE
+ * Restricted, prohibited and danger areas with area proximity warnings (APW)
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::conflict::{classify_severity, ConflictSeverity};
+use crate::sector::point_near_polygon;
+use crate::separation::predict_position;
+use crate::AircraftState;
+
+/// Legal status of a special use area
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RestrictionKind {
+    /// Entry subject to restrictions, usually while active
+    Restricted,
+    /// Entry never permitted
+    Prohibited,
+    /// Hazardous activity; entry permitted at the pilot's risk
+    Danger,
+}
+
+/// Polygonal area with vertical limits and activation windows
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestrictedArea {
+    pub name: String,
+    pub kind: RestrictionKind,
+    pub boundary: Vec<(f64, f64)>,
+    pub floor: f64,
+    pub ceiling: f64,
+    /// Active (start, end) simulation times in seconds; always active when empty
+    pub schedule: Vec<(f64, f64)>,
+}
+
+impl RestrictedArea {
+    pub fn new(name: &str, kind: RestrictionKind, boundary: Vec<(f64, f64)>, floor: f64, ceiling: f64) -> Self {
+        RestrictedArea {
+            name: name.to_string(),
+            kind,
+            boundary,
+            floor,
+            ceiling,
+            schedule: Vec::new(),
+        }
+    }
+
+    pub fn with_window(mut self, start: f64, end: f64) -> Self {
+        self.schedule.push((start, end));
+        self
+    }
+
+    pub fn is_active(&self, time: f64) -> bool {
+        self.schedule.is_empty() || self.schedule.iter().any(|&(start, end)| time >= start && time < end)
+    }
+
+    /// Check whether a position is inside the area widened by the buffers
+    pub fn contains(&self, x: f64, y: f64, altitude: f64, lateral_buffer: f64, vertical_buffer: f64) -> bool {
+        altitude >= self.floor - vertical_buffer
+            && altitude <= self.ceiling + vertical_buffer
+            && point_near_polygon(x, y, &self.boundary, lateral_buffer)
+    }
+}
+
+/// Special use areas registered by the host, with the buffers APW protects
+#[derive(Debug, Clone, PartialEq)]
+pub struct Airspace {
+    areas: Vec<RestrictedArea>,
+    /// Horizontal buffer (nm) around every area
+    pub lateral_buffer: f64,
+    /// Vertical buffer (ft) below the floor and above the ceiling
+    pub vertical_buffer: f64,
+}
+
+impl Airspace {
+    /// No areas, 1 nm and 500 ft buffers
+    pub fn new() -> Self {
+        Airspace {
+            areas: Vec::new(),
+            lateral_buffer: 1.0,
+            vertical_buffer: 500.0,
+        }
+    }
+
+    pub fn with_buffers(mut self, lateral_buffer: f64, vertical_buffer: f64) -> Self {
+        self.lateral_buffer = lateral_buffer;
+        self.vertical_buffer = vertical_buffer;
+        self
+    }
+
+    /// Add an area, replacing any registered under the same name
+    pub fn register(&mut self, area: RestrictedArea) {
+        self.remove(&area.name);
+        self.areas.push(area);
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<RestrictedArea> {
+        let index = self.areas.iter().position(|area| area.name == name)?;
+        Some(self.areas.remove(index))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RestrictedArea> {
+        self.areas.iter().find(|area| area.name == name)
+    }
+
+    pub fn areas(&self) -> &[RestrictedArea] {
+        &self.areas
+    }
+}
+
+impl Default for Airspace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Aircraft predicted to penetrate an active area
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AreaWarning {
+    pub area: String,
+    pub kind: RestrictionKind,
+    /// Seconds until the buffered area is entered, zero if already inside
+    pub time_to_penetration: f64,
+    pub severity: ConflictSeverity,
+}
+
+/// Warnings for every active area the aircraft is predicted to penetrate within `look_ahead_time`
+///
+/// The aircraft is extrapolated along its heading, speed and vertical rate
+/// as for conflict detection. Areas are checked against their schedule at
+/// the predicted time, so one activating during the look-ahead is included.
+/// Severity follows the conflict time bands; a penetration predicted
+/// beyond them is still an advisory. Warnings are ordered by time, then name.
+pub fn area_proximity_warning(
+    aircraft: &AircraftState,
+    airspace: &Airspace,
+    now: f64,
+    look_ahead_time: f64,
+) -> Vec<AreaWarning> {
+    let mut warnings = Vec::new();
+
+    for area in airspace.areas() {
+        let mut time = 0.0;
+        while time <= look_ahead_time {
+            let state = predict_position(aircraft, time);
+            if area.is_active(now + time)
+                && area.contains(state.x, state.y, state.altitude, airspace.lateral_buffer, airspace.vertical_buffer)
+            {
+                let severity = match classify_severity(time, f64::INFINITY, 0.0).severity {
+                    ConflictSeverity::None => ConflictSeverity::Advisory,
+                    severity => severity,
+                };
+                warnings.push(AreaWarning {
+                    area: area.name.clone(),
+                    kind: area.kind,
+                    time_to_penetration: time,
+                    severity,
+                });
+                break;
+            }
+            time += 1.0;
+        }
+    }
+
+    warnings.sort_by(|a, b| {
+        a.time_to_penetration
+            .total_cmp(&b.time_to_penetration)
+            .then_with(|| a.area.cmp(&b.area))
+    });
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f64, y: f64, size: f64) -> Vec<(f64, f64)> {
+        vec![(x, y), (x + size, y), (x + size, y + size), (x, y + size)]
+    }
+
+    fn airspace() -> Airspace {
+        let mut airspace = Airspace::new();
+        airspace.register(RestrictedArea::new("R101", RestrictionKind::Restricted, square(10.0, -2.0, 4.0), 0.0, 8000.0));
+        airspace.register(
+            RestrictedArea::new("D201", RestrictionKind::Danger, square(30.0, -2.0, 4.0), 0.0, 20000.0)
+                .with_window(600.0, 1200.0),
+        );
+        airspace.register(RestrictedArea::new("P1", RestrictionKind::Prohibited, square(0.0, 20.0, 2.0), 0.0, 3000.0));
+        airspace
+    }
+
+    #[test]
+    fn test_predicts_penetration() {
+        let airspace = airspace();
+        // Eastbound at 360 kt and 6000 ft: 90 s to the buffered R101, 290 s to D201
+        let aircraft = AircraftState::new(0.0, 0.0, 6000.0, 90.0, 360.0);
+
+        let warnings = area_proximity_warning(&aircraft, &airspace, 0.0, 300.0);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].area, "R101");
+        assert_eq!(warnings[0].time_to_penetration, 90.0);
+        assert_eq!(warnings[0].severity, ConflictSeverity::Advisory);
+
+        // D201 activates during the look-ahead when it starts later
+        let warnings = area_proximity_warning(&aircraft, &airspace, 400.0, 300.0);
+        let names: Vec<&str> = warnings.iter().map(|w| w.area.as_str()).collect();
+        assert_eq!(names, vec!["R101", "D201"]);
+
+        // Overflying R101 above its ceiling and buffer is clear
+        let high = AircraftState::new(0.0, 0.0, 9000.0, 90.0, 360.0);
+        assert!(area_proximity_warning(&high, &airspace, 0.0, 200.0).is_empty());
+    }
+
+    #[test]
+    fn test_inside_and_registry() {
+        let mut airspace = airspace();
+        let inside = AircraftState::new(1.0, 21.0, 2000.0, 0.0, 120.0);
+        let warnings = area_proximity_warning(&inside, &airspace, 0.0, 60.0);
+        assert_eq!(warnings[0].kind, RestrictionKind::Prohibited);
+        assert_eq!(warnings[0].time_to_penetration, 0.0);
+        assert_eq!(warnings[0].severity, ConflictSeverity::Critical);
+
+        assert!(airspace.remove("P1").is_some());
+        assert!(area_proximity_warning(&inside, &airspace, 0.0, 60.0).is_empty());
+        airspace.register(RestrictedArea::new("R101", RestrictionKind::Danger, square(0.0, 0.0, 1.0), 0.0, 1000.0));
+        assert_eq!(airspace.areas().len(), 2);
+        assert_eq!(airspace.get("R101").unwrap().kind, RestrictionKind::Danger);
+    }
+}
@@ -0,0 +1,472 @@
+/*!
+ * APPROACH MODULE
+ * Final approach glidepath, stabilization and speed gate monitoring
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::atmosphere::corrected_minimum_altitude;
+use crate::state::{calculate_rate_of_change, TimedState};
+use crate::{heading_difference, AircraftState};
+
+/// Feet per nautical mile
+const FEET_PER_NM: f64 = 6076.12;
+
+/// Furthest distance before the threshold an aircraft counts as on final (nm)
+const FINAL_APPROACH_LENGTH: f64 = 15.0;
+
+/// Largest offset from the extended centerline an aircraft counts as on final (nm)
+const FINAL_APPROACH_HALF_WIDTH: f64 = 1.0;
+
+/// Values per segment in packed display geometry: x1, y1, x2, y2
+pub const SEGMENT_STRIDE: usize = 4;
+
+/// Runway threshold and final approach geometry
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Runway {
+    pub threshold_x: f64,
+    pub threshold_y: f64,
+    pub elevation: f64,
+    pub course: f64,
+    pub glidepath_angle: f64,
+    pub threshold_crossing_height: f64,
+    /// Aerodrome temperature (deg C) for the cold temperature correction; none at 15 or above
+    pub temperature: f64,
+}
+
+#[wasm_bindgen]
+impl Runway {
+    /// Runway with a standard 3 degree glidepath and 50 ft threshold crossing height
+    #[wasm_bindgen(constructor)]
+    pub fn new(threshold_x: f64, threshold_y: f64, elevation: f64, course: f64) -> Runway {
+        Runway {
+            threshold_x,
+            threshold_y,
+            elevation,
+            course,
+            glidepath_angle: 3.0,
+            threshold_crossing_height: 50.0,
+            temperature: 15.0,
+        }
+    }
+
+    /// Glidepath altitude at a distance from the threshold (nm)
+    pub fn glidepath_altitude(&self, distance: f64) -> f64 {
+        self.elevation
+            + self.threshold_crossing_height
+            + distance * FEET_PER_NM * self.glidepath_angle.to_radians().tan()
+    }
+
+    /// Indicated altitude that keeps the aircraft on the glidepath at the aerodrome temperature
+    pub fn corrected_glidepath_altitude(&self, distance: f64) -> f64 {
+        corrected_minimum_altitude(self.glidepath_altitude(distance), self.elevation, self.temperature)
+    }
+
+    /// Extended centerline out to `length` nm with a tick every `tick_spacing` nm, as packed segments
+    ///
+    /// The first segment is the centerline from the threshold outwards; each
+    /// tick is `tick_length` nm long, centred on the centerline.
+    pub fn centerline_segments(&self, length: f64, tick_spacing: f64, tick_length: f64) -> Vec<f64> {
+        let (x1, y1) = self.position_on_final(0.0, 0.0);
+        let (x2, y2) = self.position_on_final(length, 0.0);
+        let mut segments = vec![x1, y1, x2, y2];
+
+        if tick_spacing > 0.0 {
+            let mut distance = tick_spacing;
+            while distance <= length {
+                let (x1, y1) = self.position_on_final(distance, -tick_length / 2.0);
+                let (x2, y2) = self.position_on_final(distance, tick_length / 2.0);
+                segments.extend_from_slice(&[x1, y1, x2, y2]);
+                distance += tick_spacing;
+            }
+        }
+        segments
+    }
+
+    /// Outline of the area in which an aircraft counts as established on final, as packed x, y corners
+    pub fn final_approach_area(&self) -> Vec<f64> {
+        [
+            (0.0, -FINAL_APPROACH_HALF_WIDTH),
+            (FINAL_APPROACH_LENGTH, -FINAL_APPROACH_HALF_WIDTH),
+            (FINAL_APPROACH_LENGTH, FINAL_APPROACH_HALF_WIDTH),
+            (0.0, FINAL_APPROACH_HALF_WIDTH),
+        ]
+        .iter()
+        .flat_map(|&(along, cross)| {
+            let (x, y) = self.position_on_final(along, cross);
+            [x, y]
+        })
+        .collect()
+    }
+}
+
+impl Runway {
+    /// Distance before the threshold along the final approach course and
+    /// lateral offset from the extended centerline (nm, positive right of course)
+    pub fn final_approach_position(&self, aircraft: &AircraftState) -> (f64, f64) {
+        let dx = aircraft.x - self.threshold_x;
+        let dy = aircraft.y - self.threshold_y;
+        let course_rad = self.course.to_radians();
+
+        let along = -(dx * course_rad.sin() + dy * course_rad.cos());
+        let cross = dx * course_rad.cos() - dy * course_rad.sin();
+
+        (along, cross)
+    }
+
+    /// Position `along` nm before the threshold and `cross` nm right of the
+    /// extended centerline; the inverse of `final_approach_position`
+    pub fn position_on_final(&self, along: f64, cross: f64) -> (f64, f64) {
+        let (sin, cos) = self.course.to_radians().sin_cos();
+        (
+            self.threshold_x - along * sin + cross * cos,
+            self.threshold_y - along * cos - cross * sin,
+        )
+    }
+}
+
+/// Final approach monitoring result
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GlidepathStatus {
+    pub established: bool,
+    pub distance_to_threshold: f64,
+    pub glidepath_altitude: f64,
+    pub deviation: f64,
+    pub angular_deviation: f64,
+    pub well_below_glidepath: bool,
+    pub unstable: bool,
+}
+
+/// Check whether an aircraft is established on final for the runway
+pub fn is_established_on_final(runway: &Runway, aircraft: &AircraftState) -> bool {
+    let (along, cross) = runway.final_approach_position(aircraft);
+
+    let heading_diff = heading_difference(runway.course, aircraft.heading).abs();
+
+    along > 0.0 && along <= FINAL_APPROACH_LENGTH && cross.abs() <= FINAL_APPROACH_HALF_WIDTH && heading_diff <= 30.0
+}
+
+/// Monitor an aircraft's vertical profile against the runway glidepath
+///
+/// `reference_speed` is the expected final approach speed (kt); the previous
+/// observation provides the descent rate, and is ignored unless it is older.
+pub fn monitor_glidepath(
+    runway: &Runway,
+    previous: &TimedState,
+    current: &TimedState,
+    reference_speed: f64,
+) -> GlidepathStatus {
+    let rates = calculate_rate_of_change(previous, current);
+    let current = &current.state;
+    let (along, _) = runway.final_approach_position(current);
+    let established = is_established_on_final(runway, current);
+
+    // Cold air puts the aircraft lower than it indicates, so compare against the corrected glidepath
+    let glidepath_altitude = runway.corrected_glidepath_altitude(along.max(0.0));
+    let deviation = current.altitude - glidepath_altitude;
+    let correction = glidepath_altitude - runway.glidepath_altitude(along.max(0.0));
+
+    // Angle from the threshold crossing point to the aircraft
+    let height_above_tch = current.altitude - correction - runway.elevation - runway.threshold_crossing_height;
+    let actual_angle = height_above_tch.atan2(along.max(0.0) * FEET_PER_NM).to_degrees();
+    let angular_deviation = actual_angle - runway.glidepath_angle;
+
+    // More than 0.5 degrees low is treated as well below the glidepath
+    let well_below_glidepath = established && angular_deviation < -0.5;
+
+    let mut unstable = false;
+    let height_above_runway = current.altitude - runway.elevation;
+    if let Some((_, _, altitude_rate)) = rates.filter(|_| established && height_above_runway < 1000.0) {
+        let descent_rate = -altitude_rate * 60.0; // feet per minute
+
+        unstable = current.speed > reference_speed + 20.0 || descent_rate > 1000.0;
+    }
+
+    GlidepathStatus {
+        established,
+        distance_to_threshold: along,
+        glidepath_altitude,
+        deviation,
+        angular_deviation,
+        well_below_glidepath,
+        unstable,
+    }
+}
+
+/// Typical deceleration achievable in level flight on approach (kt per nm)
+const APPROACH_DECELERATION: f64 = 10.0;
+
+/// Maximum speed to be achieved by a distance from the threshold
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ApproachGate {
+    pub distance: f64,
+    pub max_speed: f64,
+}
+
+/// Speed schedule made of approach gates ordered from the farthest out
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeedSchedule {
+    gates: Vec<ApproachGate>,
+}
+
+#[wasm_bindgen]
+impl SpeedSchedule {
+    /// Empty schedule
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> SpeedSchedule {
+        SpeedSchedule { gates: Vec::new() }
+    }
+
+    /// 210 kt by 12 nm, 180 kt by 8 nm, 160 kt to 4 nm
+    pub fn standard() -> SpeedSchedule {
+        let mut schedule = SpeedSchedule::new();
+        schedule.add_gate(12.0, 210.0);
+        schedule.add_gate(8.0, 180.0);
+        schedule.add_gate(4.0, 160.0);
+        schedule
+    }
+
+    pub fn add_gate(&mut self, distance: f64, max_speed: f64) {
+        self.gates.push(ApproachGate { distance, max_speed });
+        self.gates.sort_by(|a, b| b.distance.total_cmp(&a.distance));
+    }
+
+    /// Speed an arrival following the schedule is expected to fly at a distance from the threshold
+    pub fn expected_speed_at(&self, distance: f64, current_speed: f64) -> f64 {
+        let Some(first) = self.gates.first() else {
+            return current_speed;
+        };
+        if distance >= first.distance {
+            return current_speed.min(
+                first.max_speed + (distance - first.distance) * APPROACH_DECELERATION,
+            );
+        }
+
+        // Decelerate linearly between gates and hold the last gate speed inside it
+        for pair in self.gates.windows(2) {
+            let (outer, inner) = (pair[0], pair[1]);
+            if distance >= inner.distance {
+                let t = (outer.distance - distance) / (outer.distance - inner.distance);
+                let speed = outer.max_speed + (inner.max_speed - outer.max_speed) * t;
+                return current_speed.min(speed);
+            }
+        }
+
+        current_speed.min(self.gates[self.gates.len() - 1].max_speed)
+    }
+
+    /// Time to fly from a distance to the threshold following the schedule (seconds)
+    pub fn time_to_threshold(&self, distance: f64, current_speed: f64) -> f64 {
+        let step = 0.1; // nm
+        let mut remaining = distance.max(0.0);
+        let mut time = 0.0;
+
+        while remaining > 0.0 {
+            let segment = remaining.min(step);
+            let speed = self.expected_speed_at(remaining - segment / 2.0, current_speed);
+            if speed <= 0.0 {
+                return f64::INFINITY;
+            }
+            time += segment / speed * 3600.0;
+            remaining -= segment;
+        }
+
+        time
+    }
+
+    /// Each gate as a packed segment across the final approach area of `runway`,
+    /// farthest first, matching where `check_approach_gates` measures
+    pub fn gate_segments(&self, runway: &Runway) -> Vec<f64> {
+        self.gates
+            .iter()
+            .flat_map(|gate| {
+                let (x1, y1) = runway.position_on_final(gate.distance, -FINAL_APPROACH_HALF_WIDTH);
+                let (x2, y2) = runway.position_on_final(gate.distance, FINAL_APPROACH_HALF_WIDTH);
+                [x1, y1, x2, y2]
+            })
+            .collect()
+    }
+}
+
+impl Default for SpeedSchedule {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl SpeedSchedule {
+    pub fn gates(&self) -> &[ApproachGate] {
+        &self.gates
+    }
+}
+
+/// Prediction of an arrival's speed at an approach gate
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GateCheck {
+    pub gate_distance: f64,
+    pub required_speed: f64,
+    pub predicted_speed: f64,
+    pub will_miss: bool,
+}
+
+/// Check an arrival against every approach gate still ahead of it
+pub fn check_approach_gates(
+    schedule: &SpeedSchedule,
+    runway: &Runway,
+    aircraft: &AircraftState,
+) -> Vec<GateCheck> {
+    let (along, _) = runway.final_approach_position(aircraft);
+
+    schedule
+        .gates()
+        .iter()
+        .filter(|gate| gate.distance <= along)
+        .map(|gate| {
+            let predicted_speed = (aircraft.speed
+                - (along - gate.distance) * APPROACH_DECELERATION)
+                .max(gate.max_speed.min(aircraft.speed));
+
+            GateCheck {
+                gate_distance: gate.distance,
+                required_speed: gate.max_speed,
+                predicted_speed,
+                will_miss: predicted_speed > gate.max_speed,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runway() -> Runway {
+        // Threshold at the origin, landing north
+        Runway::new(0.0, 0.0, 0.0, 360.0)
+    }
+
+    #[test]
+    fn test_on_glidepath() {
+        let runway = runway();
+        let altitude = runway.glidepath_altitude(5.0);
+        let previous = AircraftState::new(0.0, -5.05, altitude + 40.0, 0.0, 150.0);
+        let current = AircraftState::new(0.0, -5.0, altitude, 0.0, 150.0);
+
+        let status = monitor_glidepath(&runway, &TimedState::new(previous, 0.0), &TimedState::new(current, 1.0), 140.0);
+        assert!(status.established);
+        assert!(status.deviation.abs() < 1.0);
+        assert!(!status.well_below_glidepath);
+        assert!(!status.unstable);
+    }
+
+    #[test]
+    fn test_display_geometry_matches_monitors() {
+        let runway = Runway::new(2.0, 1.0, 0.0, 270.0);
+
+        let segments = runway.centerline_segments(10.0, 5.0, 0.5);
+        assert_eq!(segments.len(), 3 * SEGMENT_STRIDE);
+        // Westbound runway: the centerline extends east of the threshold
+        assert_eq!(&segments[..2], &[2.0, 1.0]);
+        assert!((segments[2] - 12.0).abs() < 1e-9 && (segments[3] - 1.0).abs() < 1e-9);
+
+        // Every drawn point maps back to the same place on final
+        for point in segments.chunks_exact(2) {
+            let aircraft = AircraftState::new(point[0], point[1], 3000.0, 270.0, 160.0);
+            let (along, cross) = runway.final_approach_position(&aircraft);
+            assert!((-1e-9..=10.0 + 1e-9).contains(&along) && cross.abs() <= 0.25 + 1e-9);
+        }
+
+        let gates = SpeedSchedule::standard().gate_segments(&runway);
+        assert_eq!(gates.len(), 3 * SEGMENT_STRIDE);
+        let gate_end = AircraftState::new(gates[0], gates[1], 3000.0, 270.0, 160.0);
+        let (along, cross) = runway.final_approach_position(&gate_end);
+        assert!((along - 12.0).abs() < 1e-9 && (cross.abs() - 1.0).abs() < 1e-9);
+
+        // Inside the outline is established, just outside is not
+        let area = runway.final_approach_area();
+        assert_eq!(area.len(), 8);
+        assert!(is_established_on_final(&runway, &AircraftState::new(8.0, 1.9, 3000.0, 270.0, 160.0)));
+        assert!(!is_established_on_final(&runway, &AircraftState::new(8.0, 2.1, 3000.0, 270.0, 160.0)));
+    }
+
+    #[test]
+    fn test_well_below_glidepath() {
+        let runway = runway();
+        let current = AircraftState::new(0.0, -5.0, 800.0, 0.0, 150.0);
+
+        let current = TimedState::new(current, 0.0);
+        let status = monitor_glidepath(&runway, &current, &current, 140.0);
+        assert!(status.well_below_glidepath);
+    }
+
+    #[test]
+    fn test_cold_temperature_glidepath() {
+        let mut runway = Runway::new(0.0, 0.0, 1000.0, 360.0);
+        let altitude = runway.glidepath_altitude(5.0);
+        let current = TimedState::new(AircraftState::new(0.0, -5.0, altitude, 0.0, 150.0), 0.0);
+        assert!(!monitor_glidepath(&runway, &current, &current, 140.0).well_below_glidepath);
+
+        // At -40 C the indicated glidepath is flown well below the true one
+        runway.temperature = -40.0;
+        let status = monitor_glidepath(&runway, &current, &current, 140.0);
+        assert!(status.glidepath_altitude > altitude + 300.0);
+        assert!(status.well_below_glidepath);
+    }
+
+    #[test]
+    fn test_unstable_approach() {
+        let runway = runway();
+        let previous = AircraftState::new(0.0, -2.05, 800.0, 0.0, 190.0);
+        let current = AircraftState::new(0.0, -2.0, 760.0, 0.0, 190.0);
+
+        let status = monitor_glidepath(&runway, &TimedState::new(previous, 0.0), &TimedState::new(current, 1.0), 140.0);
+        assert!(status.unstable);
+    }
+
+    #[test]
+    fn test_not_established_when_offset() {
+        let aircraft = AircraftState::new(3.0, -5.0, 1600.0, 0.0, 150.0);
+        assert!(!is_established_on_final(&runway(), &aircraft));
+    }
+
+    #[test]
+    fn test_expected_speed_follows_schedule() {
+        let schedule = SpeedSchedule::standard();
+
+        assert_eq!(schedule.expected_speed_at(12.0, 250.0), 210.0);
+        assert_eq!(schedule.expected_speed_at(10.0, 250.0), 195.0);
+        assert_eq!(schedule.expected_speed_at(2.0, 250.0), 160.0);
+        assert_eq!(schedule.expected_speed_at(10.0, 170.0), 170.0);
+    }
+
+    #[test]
+    fn test_gate_miss_predicted() {
+        let schedule = SpeedSchedule::standard();
+
+        // 250 kt at 13 nm cannot slow to 210 kt by 12 nm
+        let fast = AircraftState::new(0.0, -13.0, 4000.0, 0.0, 250.0);
+        let checks = check_approach_gates(&schedule, &runway(), &fast);
+        assert_eq!(checks.len(), 3);
+        assert!(checks[0].will_miss);
+
+        let slow = AircraftState::new(0.0, -13.0, 4000.0, 0.0, 215.0);
+        let checks = check_approach_gates(&schedule, &runway(), &slow);
+        assert!(checks.iter().all(|c| !c.will_miss));
+    }
+
+    #[test]
+    fn test_time_to_threshold() {
+        let schedule = SpeedSchedule::standard();
+        let scheduled = schedule.time_to_threshold(12.0, 210.0);
+
+        // Slower than flying the whole way at 210 kt
+        assert!(scheduled > 12.0 / 210.0 * 3600.0);
+    }
+}
@@ -0,0 +1,91 @@
+/*!
+ * OPERATING AREA MODULE
+ * Horizontal shape and altitude limits of the airspace a facility accepts traffic in
+ */
+
+use crate::sector::point_in_polygon;
+
+/// Horizontal outline of an operating area
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AreaShape {
+    Circle { x: f64, y: f64, radius: f64 },
+    Polygon(Vec<(f64, f64)>),
+}
+
+/// Airspace within which positions are valid and traffic is in coverage
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperatingArea {
+    pub shape: AreaShape,
+    pub floor: f64,
+    pub ceiling: f64,
+}
+
+impl OperatingArea {
+    /// Circle of `radius` nm around the origin, 0 to 60000 ft
+    pub fn circle(radius: f64) -> Self {
+        OperatingArea {
+            shape: AreaShape::Circle { x: 0.0, y: 0.0, radius },
+            floor: 0.0,
+            ceiling: 60000.0,
+        }
+    }
+
+    pub fn polygon(boundary: Vec<(f64, f64)>) -> Self {
+        OperatingArea {
+            shape: AreaShape::Polygon(boundary),
+            floor: 0.0,
+            ceiling: 60000.0,
+        }
+    }
+
+    pub fn with_altitude_limits(mut self, floor: f64, ceiling: f64) -> Self {
+        self.floor = floor;
+        self.ceiling = ceiling;
+        self
+    }
+
+    /// True if a position is inside the outline; non-finite positions never are
+    pub fn contains_position(&self, x: f64, y: f64) -> bool {
+        if !(x.is_finite() && y.is_finite()) {
+            return false;
+        }
+        match &self.shape {
+            AreaShape::Circle { x: cx, y: cy, radius } => ((x - cx).powi(2) + (y - cy).powi(2)).sqrt() <= *radius,
+            AreaShape::Polygon(boundary) => point_in_polygon(x, y, boundary),
+        }
+    }
+
+    /// True if inside the outline and altitude limits
+    ///
+    /// A NaN altitude (primary-only target) is judged on position alone.
+    pub fn contains(&self, x: f64, y: f64, altitude: f64) -> bool {
+        self.contains_position(x, y) && (altitude.is_nan() || (self.floor..=self.ceiling).contains(&altitude))
+    }
+}
+
+impl Default for OperatingArea {
+    /// 100 nm around the origin
+    fn default() -> Self {
+        OperatingArea::circle(100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circle_and_polygon() {
+        let circle = OperatingArea::circle(150.0).with_altitude_limits(1000.0, 45000.0);
+        assert!(circle.contains_position(120.0, 0.0));
+        assert!(!circle.contains_position(f64::NAN, 0.0));
+        assert!(circle.contains(120.0, 0.0, f64::NAN));
+        assert!(!circle.contains(120.0, 0.0, 500.0));
+
+        let polygon = OperatingArea::polygon(vec![(0.0, 0.0), (200.0, 0.0), (200.0, 50.0), (0.0, 50.0)]);
+        assert!(polygon.contains(180.0, 25.0, 10000.0));
+        assert!(!polygon.contains(-10.0, 25.0, 10000.0));
+    }
+}
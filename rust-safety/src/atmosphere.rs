@@ -0,0 +1,113 @@
+/*!
+ * ATMOSPHERE MODULE
+ * Standard atmosphere corrections for altimetry
+ */
+
+/// Heights above the aerodrome (ft) used as table columns
+const CORRECTION_HEIGHTS: [f64; 14] = [
+    200.0, 300.0, 400.0, 500.0, 600.0, 700.0, 800.0, 900.0, 1000.0, 1500.0, 2000.0, 3000.0,
+    4000.0, 5000.0,
+];
+
+/// ICAO cold temperature correction table (ft), one row per aerodrome temperature (deg C)
+const CORRECTION_TABLE: [(f64, [f64; 14]); 7] = [
+    (15.0, [0.0; 14]),
+    (0.0, [20.0, 20.0, 30.0, 30.0, 40.0, 40.0, 50.0, 50.0, 60.0, 90.0, 120.0, 170.0, 230.0, 280.0]),
+    (-10.0, [20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 90.0, 100.0, 150.0, 200.0, 290.0, 390.0, 490.0]),
+    (-20.0, [30.0, 50.0, 60.0, 70.0, 90.0, 100.0, 120.0, 130.0, 140.0, 210.0, 280.0, 420.0, 570.0, 710.0]),
+    (-30.0, [40.0, 60.0, 80.0, 100.0, 120.0, 140.0, 150.0, 170.0, 190.0, 280.0, 380.0, 570.0, 760.0, 950.0]),
+    (-40.0, [50.0, 80.0, 100.0, 120.0, 150.0, 170.0, 190.0, 220.0, 240.0, 360.0, 480.0, 720.0, 970.0, 1210.0]),
+    (-50.0, [60.0, 90.0, 120.0, 150.0, 180.0, 210.0, 240.0, 270.0, 300.0, 450.0, 590.0, 890.0, 1190.0, 1500.0]),
+];
+
+/// Cold temperature correction to add to a minimum altitude
+///
+/// `height_above_aerodrome` is the height of the minimum above the aerodrome (ft)
+/// and `aerodrome_temperature` the reported aerodrome temperature (deg C).
+pub fn cold_temperature_correction(height_above_aerodrome: f64, aerodrome_temperature: f64) -> f64 {
+    if !height_above_aerodrome.is_finite()
+        || !aerodrome_temperature.is_finite()
+        || height_above_aerodrome <= 0.0
+        || aerodrome_temperature >= 15.0
+    {
+        return 0.0;
+    }
+
+    // Find the two temperature rows bracketing the reported temperature,
+    // extrapolating the coldest pair below the table
+    let mut upper = CORRECTION_TABLE.len() - 2;
+    for i in 0..CORRECTION_TABLE.len() - 1 {
+        if aerodrome_temperature >= CORRECTION_TABLE[i + 1].0 {
+            upper = i;
+            break;
+        }
+    }
+    let (warm_temp, warm_row) = &CORRECTION_TABLE[upper];
+    let (cold_temp, cold_row) = &CORRECTION_TABLE[upper + 1];
+
+    let warm = interpolate_height(warm_row, height_above_aerodrome);
+    let cold = interpolate_height(cold_row, height_above_aerodrome);
+    let t = (warm_temp - aerodrome_temperature) / (warm_temp - cold_temp);
+
+    (warm + (cold - warm) * t).max(0.0)
+}
+
+/// Interpolate a table row by height, scaling proportionally outside the table
+fn interpolate_height(row: &[f64; 14], height: f64) -> f64 {
+    let first = CORRECTION_HEIGHTS[0];
+    let last = CORRECTION_HEIGHTS[CORRECTION_HEIGHTS.len() - 1];
+
+    if height <= first {
+        return row[0] * height / first;
+    }
+    if height >= last {
+        return row[row.len() - 1] * height / last;
+    }
+
+    for i in 0..CORRECTION_HEIGHTS.len() - 1 {
+        let (h0, h1) = (CORRECTION_HEIGHTS[i], CORRECTION_HEIGHTS[i + 1]);
+        if height <= h1 {
+            let t = (height - h0) / (h1 - h0);
+            return row[i] + (row[i + 1] - row[i]) * t;
+        }
+    }
+
+    row[row.len() - 1]
+}
+
+/// Minimum altitude corrected for cold temperature
+pub fn corrected_minimum_altitude(
+    minimum_altitude: f64,
+    aerodrome_elevation: f64,
+    aerodrome_temperature: f64,
+) -> f64 {
+    minimum_altitude
+        + cold_temperature_correction(minimum_altitude - aerodrome_elevation, aerodrome_temperature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_values() {
+        assert_eq!(cold_temperature_correction(1000.0, -10.0), 100.0);
+        assert_eq!(cold_temperature_correction(500.0, -30.0), 100.0);
+        assert_eq!(cold_temperature_correction(2000.0, 20.0), 0.0);
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let correction = cold_temperature_correction(1250.0, -15.0);
+        assert!(correction > 100.0 && correction < 210.0);
+
+        // Colder than the table still increases the correction
+        assert!(cold_temperature_correction(1000.0, -60.0) > 300.0);
+    }
+
+    #[test]
+    fn test_corrected_minimum_altitude() {
+        let corrected = corrected_minimum_altitude(3000.0, 1000.0, -20.0);
+        assert_eq!(corrected, 3280.0);
+    }
+}
@@ -0,0 +1,124 @@
+/*!
+ * ATTRIBUTION MODULE
+ * Where a predicted conflict happens and which position should handle it
+ */
+
+use crate::region::ConfigOverlay;
+use crate::sector::SectorMap;
+use crate::separation::predict_position;
+use crate::AircraftState;
+
+/// Location of a pair's closest point of approach and the positions involved
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictLocation {
+    /// Time of closest approach within the look-ahead (seconds)
+    pub time: f64,
+    pub x: f64,
+    pub y: f64,
+    pub altitude: f64,
+    /// Sector containing the closest point of approach
+    pub sector: Option<String>,
+    /// Config region containing the closest point of approach
+    pub region: Option<String>,
+    pub first_owner: Option<String>,
+    pub second_owner: Option<String>,
+}
+
+impl ConflictLocation {
+    /// Position the alert should be routed to
+    ///
+    /// The sector the conflict occurs in, else the owner of either track.
+    pub fn responsible_position(&self) -> Option<&str> {
+        self.sector
+            .as_deref()
+            .or(self.first_owner.as_deref())
+            .or(self.second_owner.as_deref())
+    }
+}
+
+/// Attribute a pair's closest approach to a sector, region and owners
+///
+/// The closest approach is searched within the overlay's base look-ahead and
+/// located at the midpoint between the two predicted positions.
+pub fn attribute_conflict(
+    first: (u64, &AircraftState),
+    second: (u64, &AircraftState),
+    sectors: &SectorMap,
+    overlay: &ConfigOverlay,
+) -> ConflictLocation {
+    let (first_id, a) = first;
+    let (second_id, b) = second;
+
+    let (_, time) = atc_safety_core::closest_approach_within(&(*a).into(), &(*b).into(), overlay.base.look_ahead_time);
+    let pa = predict_position(a, time);
+    let pb = predict_position(b, time);
+    let midpoint = AircraftState {
+        x: (pa.x + pb.x) / 2.0,
+        y: (pa.y + pb.y) / 2.0,
+        altitude: (pa.altitude + pb.altitude) / 2.0,
+        ..pa
+    };
+
+    ConflictLocation {
+        time,
+        x: midpoint.x,
+        y: midpoint.y,
+        altitude: midpoint.altitude,
+        sector: sectors.sector_at(&midpoint).map(str::to_string),
+        region: overlay
+            .region_at(midpoint.x, midpoint.y, midpoint.altitude)
+            .map(|r| r.name.clone()),
+        first_owner: sectors.owner(first_id).map(str::to_string),
+        second_owner: sectors.owner(second_id).map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SafetyConfig;
+    use crate::region::RegionOverride;
+    use crate::sector::Sector;
+
+    fn sectors() -> SectorMap {
+        SectorMap::new(vec![
+            Sector::new("WEST", vec![(-50.0, -50.0), (0.0, -50.0), (0.0, 50.0), (-50.0, 50.0)], 0.0, 45000.0),
+            Sector::new("EAST", vec![(0.0, -50.0), (50.0, -50.0), (50.0, 50.0), (0.0, 50.0)], 0.0, 45000.0),
+        ])
+    }
+
+    #[test]
+    fn test_conflict_attributed_to_cpa_sector() {
+        let mut sectors = sectors();
+        let a = AircraftState::new(-10.0, 0.0, 12000.0, 90.0, 300.0);
+        let b = AircraftState::new(30.0, 0.0, 12000.0, 270.0, 300.0);
+        sectors.update_track(1, &a);
+        sectors.update_track(2, &b);
+
+        let tma = RegionOverride::new("TMA", vec![(5.0, -5.0), (15.0, -5.0), (15.0, 5.0), (5.0, 5.0)], 0.0, 20000.0);
+        let overlay = ConfigOverlay::new(SafetyConfig::default()).with_region(tma);
+
+        // Both owned from their own sides; the aircraft meet at (10, 0) in the east
+        let location = attribute_conflict((1, &a), (2, &b), &sectors, &overlay);
+        assert!((location.time - 240.0).abs() < 1e-6);
+        assert!((location.x - 10.0).abs() < 1e-6);
+        assert_eq!(location.sector.as_deref(), Some("EAST"));
+        assert_eq!(location.region.as_deref(), Some("TMA"));
+        assert_eq!(location.first_owner.as_deref(), Some("WEST"));
+        assert_eq!(location.responsible_position(), Some("EAST"));
+    }
+
+    #[test]
+    fn test_falls_back_to_track_owner() {
+        let mut sectors = sectors();
+        sectors.update_track(1, &AircraftState::new(-10.0, 0.0, 12000.0, 270.0, 300.0));
+
+        // Track 1 has since climbed above every sector, where the pair meets
+        let a = AircraftState::new(-10.0, 0.0, 50000.0, 270.0, 300.0);
+        let b = AircraftState::new(-12.0, 0.0, 50000.0, 270.0, 300.0);
+        let overlay = ConfigOverlay::new(SafetyConfig::default());
+        let location = attribute_conflict((1, &a), (2, &b), &sectors, &overlay);
+        assert_eq!(location.sector, None);
+        assert_eq!(location.responsible_position(), Some("WEST"));
+    }
+}
@@ -0,0 +1,187 @@
+/*!
+ * AURAL MODULE
+ * Recommended audio cues for alert transitions
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::conflict::ConflictSeverity;
+use crate::engine::{AlertChange, AlertEngine};
+
+/// Sound a front-end should play
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AuralCueId {
+    AdvisoryChime,
+    WarningTone,
+    CriticalAlarm,
+    ClearedChime,
+}
+
+/// What happened to an alert
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AlertTransition {
+    Raised,
+    Escalated,
+    Cleared,
+}
+
+/// Playback metadata for one cue
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuralCue {
+    pub cue: AuralCueId,
+    /// Higher plays first
+    pub priority: u8,
+    /// Seconds between repeats until acknowledged; 0 plays once
+    pub repeat_interval: f64,
+    /// Stop any lower-priority cue that is playing
+    pub preempts: bool,
+}
+
+/// Aural alerting rules shared by every front-end
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AuralPolicy {
+    /// Lowest severity that makes a sound at all
+    pub audible_from: ConflictSeverity,
+    pub warning_repeat: f64,
+    pub critical_repeat: f64,
+    /// Lowest severity whose cue cuts off quieter cues
+    pub preempt_from: ConflictSeverity,
+    pub chime_on_clear: bool,
+}
+
+#[wasm_bindgen]
+impl AuralPolicy {
+    /// Advisories chime once; warnings and alarms repeat; alarms preempt
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> AuralPolicy {
+        AuralPolicy {
+            audible_from: ConflictSeverity::Advisory,
+            warning_repeat: 10.0,
+            critical_repeat: 3.0,
+            preempt_from: ConflictSeverity::Critical,
+            chime_on_clear: true,
+        }
+    }
+
+    /// Cue for an alert of `severity` going through `transition`, if any
+    pub fn cue(&self, severity: ConflictSeverity, transition: AlertTransition) -> Option<AuralCue> {
+        if transition == AlertTransition::Cleared {
+            return self.chime_on_clear.then_some(AuralCue {
+                cue: AuralCueId::ClearedChime,
+                priority: 0,
+                repeat_interval: 0.0,
+                preempts: false,
+            });
+        }
+        if severity == ConflictSeverity::None || severity < self.audible_from {
+            return None;
+        }
+
+        let (cue, priority, repeat_interval) = match severity {
+            ConflictSeverity::Critical => (AuralCueId::CriticalAlarm, 3, self.critical_repeat),
+            ConflictSeverity::Warning => (AuralCueId::WarningTone, 2, self.warning_repeat),
+            _ => (AuralCueId::AdvisoryChime, 1, 0.0),
+        };
+        Some(AuralCue {
+            cue,
+            priority,
+            repeat_interval,
+            preempts: severity >= self.preempt_from,
+        })
+    }
+}
+
+impl Default for AuralPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cue to play for one alert transition
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlertCue {
+    pub pair: (u64, u64),
+    pub transition: AlertTransition,
+    pub cue: AuralCue,
+}
+
+/// Cues for every transition in `change`, highest priority first
+///
+/// Severities of raised and escalated alerts are read from the engine, so
+/// call this straight after the update that produced `change`.
+pub fn alert_cues(engine: &AlertEngine, change: &AlertChange, policy: &AuralPolicy) -> Vec<AlertCue> {
+    let raised = change.added.iter().map(|&pair| (pair, AlertTransition::Raised));
+    let escalated = change.escalated.iter().map(|&pair| (pair, AlertTransition::Escalated));
+    let cleared = change.removed.iter().map(|&pair| (pair, AlertTransition::Cleared));
+
+    let mut cues: Vec<AlertCue> = raised
+        .chain(escalated)
+        .chain(cleared)
+        .filter_map(|(pair, transition)| {
+            let severity = engine.alerts().get(&pair).map_or(ConflictSeverity::None, |info| info.severity);
+            let cue = policy.cue(severity, transition)?;
+            Some(AlertCue { pair, transition, cue })
+        })
+        .collect();
+
+    cues.sort_by_key(|c| std::cmp::Reverse(c.cue.priority));
+    cues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AircraftState, SafetyConfig, TrafficSet};
+
+    #[test]
+    fn test_cue_rules() {
+        let policy = AuralPolicy::new();
+
+        let alarm = policy.cue(ConflictSeverity::Critical, AlertTransition::Raised).unwrap();
+        assert_eq!(alarm.cue, AuralCueId::CriticalAlarm);
+        assert_eq!(alarm.repeat_interval, 3.0);
+        assert!(alarm.preempts);
+
+        let chime = policy.cue(ConflictSeverity::Advisory, AlertTransition::Raised).unwrap();
+        assert_eq!(chime.repeat_interval, 0.0);
+        assert!(!chime.preempts);
+
+        assert!(policy.cue(ConflictSeverity::None, AlertTransition::Raised).is_none());
+        assert_eq!(
+            policy.cue(ConflictSeverity::None, AlertTransition::Cleared).unwrap().cue,
+            AuralCueId::ClearedChime
+        );
+
+        let quiet = AuralPolicy { audible_from: ConflictSeverity::Warning, chime_on_clear: false, ..policy };
+        assert!(quiet.cue(ConflictSeverity::Advisory, AlertTransition::Raised).is_none());
+        assert!(quiet.cue(ConflictSeverity::Warning, AlertTransition::Cleared).is_none());
+    }
+
+    #[test]
+    fn test_cues_for_engine_transitions() {
+        let mut traffic = TrafficSet::new();
+        // Head-on 2 nm apart: critical at once
+        traffic.insert(1, AircraftState::new(0.0, 0.0, 12000.0, 90.0, 250.0)).unwrap();
+        traffic.insert(2, AircraftState::new(2.0, 0.0, 12000.0, 270.0, 250.0)).unwrap();
+
+        let mut engine = AlertEngine::new(SafetyConfig::default()).unwrap();
+        let change = engine.update(&traffic);
+        let cues = alert_cues(&engine, &change, &AuralPolicy::new());
+        assert_eq!(cues.len(), 1);
+        assert_eq!(cues[0].transition, AlertTransition::Raised);
+        assert_eq!(cues[0].cue.cue, AuralCueId::CriticalAlarm);
+
+        traffic.remove(2);
+        let change = engine.update(&traffic);
+        let cues = alert_cues(&engine, &change, &AuralPolicy::new());
+        assert_eq!(cues[0].transition, AlertTransition::Cleared);
+    }
+}
@@ -0,0 +1,149 @@
+/*!
+ * BLOCK ALTITUDE MODULE
+ * Block altitude assignments treated as occupied airspace
+ */
+
+use std::collections::HashMap;
+
+use wasm_bindgen::prelude::*;
+
+use crate::conflict::{scan_conflict, ConflictInfo};
+use crate::AircraftState;
+
+/// Assigned block of altitudes, e.g. FL310 to FL350
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AltitudeBlock {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+#[wasm_bindgen]
+impl AltitudeBlock {
+    #[wasm_bindgen(constructor)]
+    pub fn new(lower: f64, upper: f64) -> AltitudeBlock {
+        AltitudeBlock {
+            lower: lower.min(upper),
+            upper: upper.max(lower),
+        }
+    }
+
+    /// Block holding a single altitude
+    pub fn level(altitude: f64) -> AltitudeBlock {
+        AltitudeBlock::new(altitude, altitude)
+    }
+
+    pub fn contains(&self, altitude: f64) -> bool {
+        (self.lower..=self.upper).contains(&altitude)
+    }
+
+    /// Vertical gap between two blocks (0 when they overlap)
+    pub fn vertical_distance(&self, other: &AltitudeBlock) -> f64 {
+        (other.lower - self.upper).max(self.lower - other.upper).max(0.0)
+    }
+}
+
+/// Block altitudes assigned to tracks
+#[derive(Debug, Clone, Default)]
+pub struct BlockAssignments {
+    blocks: HashMap<u64, AltitudeBlock>,
+}
+
+impl BlockAssignments {
+    pub fn new() -> Self {
+        BlockAssignments::default()
+    }
+
+    pub fn assign(&mut self, track: u64, block: AltitudeBlock) {
+        self.blocks.insert(track, block);
+    }
+
+    pub fn release(&mut self, track: u64) -> Option<AltitudeBlock> {
+        self.blocks.remove(&track)
+    }
+
+    pub fn block(&self, track: u64) -> Option<AltitudeBlock> {
+        self.blocks.get(&track).copied()
+    }
+
+    /// Altitudes a track occupies: its block, or just its current altitude
+    pub fn occupied(&self, track: u64, aircraft: &AircraftState) -> AltitudeBlock {
+        self.block(track)
+            .unwrap_or_else(|| AltitudeBlock::level(aircraft.altitude))
+    }
+}
+
+/// Detect conflicts treating each aircraft's whole block as occupied
+///
+/// Aircraft without a block occupy only their current altitude.
+pub fn detect_block_conflict(
+    aircraft1: &AircraftState,
+    block1: Option<AltitudeBlock>,
+    aircraft2: &AircraftState,
+    block2: Option<AltitudeBlock>,
+    horizontal_separation: f64,
+    vertical_separation: f64,
+    look_ahead_time: f64,
+) -> ConflictInfo {
+    scan_conflict(
+        aircraft1,
+        aircraft2,
+        horizontal_separation,
+        vertical_separation,
+        look_ahead_time,
+        1.0,
+        |a, b| {
+            let first = block1.unwrap_or_else(|| AltitudeBlock::level(a.altitude));
+            let second = block2.unwrap_or_else(|| AltitudeBlock::level(b.altitude));
+            first.vertical_distance(&second)
+        },
+    )
+}
+
+/// Altitude conformance allowing free movement within an assigned block
+///
+/// Without a block the aircraft must be within `tolerance` of
+/// `cleared_altitude`; with one, anywhere inside the block plus tolerance.
+pub fn is_altitude_conforming(
+    altitude: f64,
+    cleared_altitude: f64,
+    block: Option<AltitudeBlock>,
+    tolerance: f64,
+) -> bool {
+    let block = block.unwrap_or_else(|| AltitudeBlock::level(cleared_altitude));
+    altitude >= block.lower - tolerance && altitude <= block.upper + tolerance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SeparationStandards;
+    use crate::conflict::{detect_conflict, ConflictSeverity};
+
+    #[test]
+    fn test_block_occupies_whole_range() {
+        let a = AircraftState::new(0.0, 0.0, 31000.0, 90.0, 450.0);
+        let b = AircraftState::new(20.0, 0.0, 34000.0, 270.0, 450.0);
+
+        // Level aircraft 3000 ft apart are separated...
+        assert_eq!(detect_conflict(&a, &b, &SeparationStandards::en_route(), 300.0).severity, ConflictSeverity::None);
+
+        // ...but not when one holds FL310-FL350
+        let block = Some(AltitudeBlock::new(31000.0, 35000.0));
+        let info = detect_block_conflict(&a, block, &b, None, 5.0, 1000.0, 300.0);
+        assert_ne!(info.severity, ConflictSeverity::None);
+    }
+
+    #[test]
+    fn test_conformance_within_block() {
+        let block = Some(AltitudeBlock::new(31000.0, 35000.0));
+        assert!(is_altitude_conforming(33500.0, 31000.0, block, 200.0));
+        assert!(!is_altitude_conforming(35500.0, 31000.0, block, 200.0));
+        assert!(!is_altitude_conforming(33500.0, 31000.0, None, 200.0));
+
+        let mut assignments = BlockAssignments::new();
+        assignments.assign(9, AltitudeBlock::new(35000.0, 31000.0));
+        assert_eq!(assignments.block(9).unwrap().lower, 31000.0);
+        assert_eq!(assignments.occupied(1, &AircraftState::new(0.0, 0.0, 8000.0, 0.0, 250.0)).upper, 8000.0);
+    }
+}
@@ -0,0 +1,165 @@
+/*!
+ * BOUNDARY MODULE
+ * Facility boundary crossing estimates and coordination prompts
+ */
+
+use crate::sector::Sector;
+use crate::separation::predict_position;
+use crate::AircraftState;
+
+/// Predicted transfer between facilities (`None` is uncontrolled airspace)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundaryCrossing {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub time: f64,
+    pub x: f64,
+    pub y: f64,
+    pub altitude: f64,
+}
+
+/// Letter-of-agreement conditions for traffic crossing between two facilities
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundaryAgreement {
+    pub from: String,
+    pub to: String,
+    pub min_altitude: f64,
+    pub max_altitude: f64,
+    pub max_speed: f64,
+}
+
+impl BoundaryAgreement {
+    pub fn new(from: &str, to: &str, min_altitude: f64, max_altitude: f64, max_speed: f64) -> Self {
+        BoundaryAgreement {
+            from: from.to_string(),
+            to: to.to_string(),
+            min_altitude,
+            max_altitude,
+            max_speed,
+        }
+    }
+}
+
+/// Why a crossing needs coordination
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoordinationReason {
+    NoAgreement,
+    AltitudeOutsideAgreement,
+    SpeedAboveAgreement,
+}
+
+/// Crossing that must be coordinated before the aircraft reaches it
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoordinationPrompt {
+    pub crossing: BoundaryCrossing,
+    pub reason: CoordinationReason,
+}
+
+fn facility_at<'a>(facilities: &'a [Sector], state: &AircraftState) -> Option<&'a str> {
+    facilities
+        .iter()
+        .find(|f| f.contains(state.x, state.y, state.altitude))
+        .map(|f| f.name.as_str())
+}
+
+/// Predicted facility boundary crossings within the look-ahead, in time order
+pub fn predict_boundary_crossings(
+    aircraft: &AircraftState,
+    facilities: &[Sector],
+    look_ahead_time: f64,
+) -> Vec<BoundaryCrossing> {
+    let mut crossings = Vec::new();
+    let mut current = facility_at(facilities, aircraft);
+    let mut time = 1.0;
+
+    while time <= look_ahead_time {
+        let state = predict_position(aircraft, time);
+        let facility = facility_at(facilities, &state);
+        if facility != current {
+            crossings.push(BoundaryCrossing {
+                from: current.map(str::to_string),
+                to: facility.map(str::to_string),
+                time,
+                x: state.x,
+                y: state.y,
+                altitude: state.altitude,
+            });
+            current = facility;
+        }
+        time += 1.0;
+    }
+
+    crossings
+}
+
+/// Prompts for crossings within `lead_time` that break the boundary agreements
+///
+/// Crossings between two facilities with no agreement always need
+/// coordination; flights into or out of uncontrolled airspace never do.
+pub fn coordination_prompts(
+    aircraft: &AircraftState,
+    facilities: &[Sector],
+    agreements: &[BoundaryAgreement],
+    lead_time: f64,
+) -> Vec<CoordinationPrompt> {
+    predict_boundary_crossings(aircraft, facilities, lead_time)
+        .into_iter()
+        .filter_map(|crossing| {
+            let (from, to) = (crossing.from.as_deref()?, crossing.to.as_deref()?);
+            let agreement = agreements.iter().find(|a| a.from == from && a.to == to);
+
+            let reason = match agreement {
+                None => CoordinationReason::NoAgreement,
+                Some(a) if crossing.altitude < a.min_altitude || crossing.altitude > a.max_altitude => {
+                    CoordinationReason::AltitudeOutsideAgreement
+                }
+                Some(a) if aircraft.speed > a.max_speed => CoordinationReason::SpeedAboveAgreement,
+                Some(_) => return None,
+            };
+            Some(CoordinationPrompt { crossing, reason })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facilities() -> Vec<Sector> {
+        vec![
+            Sector::new("APP", vec![(-20.0, -20.0), (0.0, -20.0), (0.0, 20.0), (-20.0, 20.0)], 0.0, 45000.0),
+            Sector::new("CTR", vec![(0.0, -20.0), (40.0, -20.0), (40.0, 20.0), (0.0, 20.0)], 0.0, 45000.0),
+        ]
+    }
+
+    #[test]
+    fn test_crossing_times_and_points() {
+        let aircraft = AircraftState::new(-10.0, 0.0, 11000.0, 90.0, 360.0);
+        let crossings = predict_boundary_crossings(&aircraft, &facilities(), 600.0);
+
+        assert_eq!(crossings.len(), 2);
+        assert_eq!(crossings[0].from.as_deref(), Some("APP"));
+        assert_eq!(crossings[0].to.as_deref(), Some("CTR"));
+        assert_eq!(crossings[0].time, 100.0);
+        assert!(crossings[0].x.abs() < 0.11);
+        assert_eq!(crossings[1].to, None);
+    }
+
+    #[test]
+    fn test_prompts_only_when_agreement_violated() {
+        let agreements = vec![BoundaryAgreement::new("APP", "CTR", 10000.0, 12000.0, 400.0)];
+        let conforming = AircraftState::new(-10.0, 0.0, 11000.0, 90.0, 360.0);
+        assert!(coordination_prompts(&conforming, &facilities(), &agreements, 300.0).is_empty());
+
+        let low = AircraftState { altitude: 8000.0, ..conforming };
+        let prompts = coordination_prompts(&low, &facilities(), &agreements, 300.0);
+        assert_eq!(prompts[0].reason, CoordinationReason::AltitudeOutsideAgreement);
+
+        // Not yet within the coordination lead time
+        assert!(coordination_prompts(&low, &facilities(), &agreements, 60.0).is_empty());
+
+        let westbound = AircraftState::new(10.0, 0.0, 11000.0, 270.0, 360.0);
+        let prompts = coordination_prompts(&westbound, &facilities(), &agreements, 300.0);
+        assert_eq!(prompts[0].reason, CoordinationReason::NoAgreement);
+    }
+}
@@ -0,0 +1,313 @@
+/*!
+ * BUILDER MODULE
+ * Named-field construction of aircraft states with build-time validation
+ */
+
+use std::fmt;
+
+use wasm_bindgen::prelude::*;
+
+use crate::validation::{validate_altitude, validate_heading, validate_position, validate_speed, ValidationConfig};
+use crate::{normalize_heading, AircraftState};
+
+/// Reason an aircraft could not be built
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BuildError {
+    MissingField(&'static str),
+    InvalidValue { field: &'static str, value: f64 },
+    InvalidSquawk(u16),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::MissingField(field) => write!(f, "missing required field '{}'", field),
+            BuildError::InvalidValue { field, value } => {
+                write!(f, "invalid value {} for '{}'", value, field)
+            }
+            BuildError::InvalidSquawk(code) => write!(f, "invalid squawk code {:04}", code),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Aircraft state together with the optional extended fields
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct AircraftDetails {
+    pub state: AircraftState,
+    pub vertical_speed: f64,
+    pub callsign: Option<String>,
+    pub type_designator: Option<String>,
+    pub squawk: Option<u16>,
+    pub timestamp: Option<f64>,
+}
+
+/// Builder for `AircraftState` and `AircraftDetails`
+///
+/// Position, altitude and speed are required; heading defaults to north
+/// and vertical speed to level flight.
+#[derive(Debug, Clone, Default)]
+pub struct AircraftStateBuilder {
+    x: Option<f64>,
+    y: Option<f64>,
+    altitude: Option<f64>,
+    heading: Option<f64>,
+    speed: Option<f64>,
+    vertical_speed: Option<f64>,
+    callsign: Option<String>,
+    type_designator: Option<String>,
+    squawk: Option<u16>,
+    timestamp: Option<f64>,
+    validation: Option<ValidationConfig>,
+}
+
+impl AircraftStateBuilder {
+    pub fn new() -> Self {
+        AircraftStateBuilder::default()
+    }
+
+    pub fn position(mut self, x: f64, y: f64) -> Self {
+        self.x = Some(x);
+        self.y = Some(y);
+        self
+    }
+
+    pub fn altitude(mut self, altitude: f64) -> Self {
+        self.altitude = Some(altitude);
+        self
+    }
+
+    pub fn heading(mut self, heading: f64) -> Self {
+        self.heading = Some(heading);
+        self
+    }
+
+    pub fn speed(mut self, speed: f64) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+
+    /// Vertical speed in feet per minute, positive when climbing
+    pub fn vertical_speed(mut self, vertical_speed: f64) -> Self {
+        self.vertical_speed = Some(vertical_speed);
+        self
+    }
+
+    pub fn callsign(mut self, callsign: &str) -> Self {
+        self.callsign = Some(callsign.trim().to_uppercase());
+        self
+    }
+
+    pub fn type_designator(mut self, designator: &str) -> Self {
+        self.type_designator = Some(designator.trim().to_uppercase());
+        self
+    }
+
+    /// Transponder code written as four octal digits, e.g. 7700
+    pub fn squawk(mut self, code: u16) -> Self {
+        self.squawk = Some(code);
+        self
+    }
+
+    /// Time of the state in seconds
+    pub fn timestamp(mut self, timestamp: f64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Limits the state is validated against; the defaults if unset
+    pub fn validation_config(mut self, config: ValidationConfig) -> Self {
+        self.validation = Some(config);
+        self
+    }
+
+    /// Build the core state, validating the kinematic fields
+    pub fn build(&self) -> Result<AircraftState, BuildError> {
+        let x = required("x", self.x)?;
+        let y = required("y", self.y)?;
+        let altitude = required("altitude", self.altitude)?;
+        let speed = required("speed", self.speed)?;
+        let heading = self.heading.unwrap_or(0.0);
+
+        let default_config = ValidationConfig::default();
+        let config = self.validation.as_ref().unwrap_or(&default_config);
+        if !validate_position(x, y, config) {
+            let value = if x.is_finite() { y } else { x };
+            return Err(BuildError::InvalidValue { field: "position", value });
+        }
+        check("altitude", altitude, validate_altitude(altitude, config))?;
+        check("heading", heading, validate_heading(heading))?;
+        check("speed", speed, validate_speed(speed, config))?;
+
+        let state = AircraftState::new(x, y, altitude, normalize_heading(heading), speed);
+        #[cfg(feature = "type-database")]
+        if let Some(info) = self.type_designator.as_deref().and_then(crate::aircraft_types::lookup_type_designator) {
+            return Ok(state.with_wake(info.wake));
+        }
+        Ok(state)
+    }
+
+    /// Build the state with every extended field
+    pub fn build_details(&self) -> Result<AircraftDetails, BuildError> {
+        let state = self.build()?;
+
+        let vertical_speed = self.vertical_speed.unwrap_or(0.0);
+        check("vertical_speed", vertical_speed, vertical_speed.is_finite())?;
+
+        if let Some(code) = self.squawk {
+            if !is_valid_squawk(code) {
+                return Err(BuildError::InvalidSquawk(code));
+            }
+        }
+
+        if let Some(timestamp) = self.timestamp {
+            check("timestamp", timestamp, timestamp.is_finite())?;
+        }
+
+        Ok(AircraftDetails {
+            state,
+            vertical_speed,
+            callsign: self.callsign.clone(),
+            type_designator: self.type_designator.clone(),
+            squawk: self.squawk,
+            timestamp: self.timestamp,
+        })
+    }
+}
+
+fn required(field: &'static str, value: Option<f64>) -> Result<f64, BuildError> {
+    value.ok_or(BuildError::MissingField(field))
+}
+
+fn check(field: &'static str, value: f64, valid: bool) -> Result<(), BuildError> {
+    if valid {
+        Ok(())
+    } else {
+        Err(BuildError::InvalidValue { field, value })
+    }
+}
+
+/// Squawk codes are four octal digits
+fn is_valid_squawk(code: u16) -> bool {
+    code <= 7777 && [code / 1000, code / 100 % 10, code / 10 % 10, code % 10].iter().all(|&d| d < 8)
+}
+
+/// Options object for building an aircraft from JavaScript
+///
+/// Create with `new AircraftOptions()`, assign the fields needed and pass
+/// it to `build_aircraft`. Unset fields take the builder defaults.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, Default)]
+pub struct AircraftOptions {
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+    pub altitude: Option<f64>,
+    pub heading: Option<f64>,
+    pub speed: Option<f64>,
+    pub vertical_speed: Option<f64>,
+    pub callsign: Option<String>,
+    pub type_designator: Option<String>,
+    pub squawk: Option<u16>,
+    pub timestamp: Option<f64>,
+}
+
+#[wasm_bindgen]
+impl AircraftOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> AircraftOptions {
+        AircraftOptions::default()
+    }
+}
+
+impl From<&AircraftOptions> for AircraftStateBuilder {
+    fn from(options: &AircraftOptions) -> Self {
+        AircraftStateBuilder {
+            x: options.x,
+            y: options.y,
+            altitude: options.altitude,
+            heading: options.heading,
+            speed: options.speed,
+            vertical_speed: options.vertical_speed,
+            callsign: options.callsign.as_deref().map(|c| c.trim().to_uppercase()),
+            type_designator: options.type_designator.as_deref().map(|t| t.trim().to_uppercase()),
+            squawk: options.squawk,
+            timestamp: options.timestamp,
+            validation: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_with_defaults() {
+        let state = AircraftStateBuilder::new()
+            .position(5.0, -3.0)
+            .altitude(12000.0)
+            .speed(280.0)
+            .build()
+            .unwrap();
+        assert_eq!(state.heading, 0.0);
+        assert_eq!(state.altitude, 12000.0);
+
+        // 360 is accepted and stored as north
+        let state = AircraftStateBuilder::new()
+            .position(0.0, 0.0)
+            .altitude(5000.0)
+            .heading(360.0)
+            .speed(200.0)
+            .build()
+            .unwrap();
+        assert_eq!(state.heading, 0.0);
+        assert_eq!(state.wake, None);
+
+        #[cfg(feature = "type-database")]
+        {
+            let heavy = AircraftStateBuilder::new()
+                .position(0.0, 0.0)
+                .altitude(5000.0)
+                .speed(200.0)
+                .type_designator("b77w")
+                .build()
+                .unwrap();
+            assert_eq!(heavy.wake, Some(crate::aircraft_types::WakeCategory::Heavy));
+        }
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let missing = AircraftStateBuilder::new().position(0.0, 0.0).speed(250.0).build();
+        assert_eq!(missing.unwrap_err(), BuildError::MissingField("altitude"));
+
+        let slow = AircraftStateBuilder::new().position(0.0, 0.0).altitude(5000.0).speed(20.0).build();
+        assert_eq!(slow.unwrap_err(), BuildError::InvalidValue { field: "speed", value: 20.0 });
+
+        let squawk = AircraftStateBuilder::new()
+            .position(0.0, 0.0)
+            .altitude(5000.0)
+            .speed(200.0)
+            .squawk(7800)
+            .build_details();
+        assert_eq!(squawk.unwrap_err(), BuildError::InvalidSquawk(7800));
+    }
+
+    #[test]
+    fn test_build_from_options() {
+        let mut options = AircraftOptions::new();
+        options.x = Some(1.0);
+        options.y = Some(2.0);
+        options.altitude = Some(8000.0);
+        options.speed = Some(250.0);
+        options.callsign = Some(" baw219 ".to_string());
+        options.squawk = Some(7700);
+
+        let details = AircraftStateBuilder::from(&options).build_details().unwrap();
+        assert_eq!(details.callsign.as_deref(), Some("BAW219"));
+        assert_eq!(details.squawk, Some(7700));
+        assert_eq!(details.vertical_speed, 0.0);
+    }
+}
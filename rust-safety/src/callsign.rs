@@ -0,0 +1,586 @@
+/*!
+ * CALLSIGN MODULE
+ * Airline telephony, spoken callsign recognition and similar-callsign safety net
+ */
+
+use wasm_bindgen::prelude::*;
+
+/// Airline designators and their radiotelephony callsigns
+const AIRLINE_TELEPHONY: &[(&str, &str)] = &[
+    ("AAL", "AMERICAN"),
+    ("UAL", "UNITED"),
+    ("DAL", "DELTA"),
+    ("SWA", "SOUTHWEST"),
+    ("JBU", "JETBLUE"),
+    ("ASA", "ALASKA"),
+    ("FFT", "FRONTIER FLIGHT"),
+    ("NKS", "SPIRIT WINGS"),
+    ("BAW", "SPEEDBIRD"),
+    ("DLH", "LUFTHANSA"),
+    ("AFR", "AIRFRANS"),
+    ("KLM", "KLM"),
+    ("ACA", "AIR CANADA"),
+    ("UAE", "EMIRATES"),
+    ("FDX", "FEDEX"),
+    ("UPS", "UPS"),
+];
+
+/// ICAO phonetic alphabet
+const PHONETIC_ALPHABET: [&str; 26] = [
+    "ALPHA", "BRAVO", "CHARLIE", "DELTA", "ECHO", "FOXTROT", "GOLF", "HOTEL", "INDIA", "JULIET",
+    "KILO", "LIMA", "MIKE", "NOVEMBER", "OSCAR", "PAPA", "QUEBEC", "ROMEO", "SIERRA", "TANGO",
+    "UNIFORM", "VICTOR", "WHISKEY", "XRAY", "YANKEE", "ZULU",
+];
+
+/// Minimum confidence for a match to be accepted
+const MIN_CONFIDENCE: f64 = 0.6;
+
+/// Candidates within this confidence of the best match make the result ambiguous
+const AMBIGUITY_MARGIN: f64 = 0.1;
+
+/// Result of resolving a spoken callsign
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallsignResolution {
+    pub heard: String,
+    pub callsign: Option<String>,
+    pub confidence: f64,
+    pub ambiguous: bool,
+    pub alternatives: Vec<String>,
+}
+
+/// Airline designator to radiotelephony callsign mapping, extendable with custom entries
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct TelephonyDirectory {
+    entries: Vec<(String, String)>,
+}
+
+impl Default for TelephonyDirectory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl TelephonyDirectory {
+    /// Directory preloaded with the built-in airline table
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> TelephonyDirectory {
+        TelephonyDirectory {
+            entries: AIRLINE_TELEPHONY
+                .iter()
+                .map(|(designator, telephony)| (designator.to_string(), telephony.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Add or replace the telephony for a 3-letter designator
+    pub fn register(&mut self, designator: &str, telephony: &str) -> bool {
+        let designator = designator.trim().to_uppercase();
+        let telephony = telephony
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_uppercase();
+
+        if designator.len() != 3
+            || !designator.chars().all(|c| c.is_ascii_alphabetic())
+            || telephony.is_empty()
+        {
+            return false;
+        }
+
+        match self.entries.iter_mut().find(|(d, _)| *d == designator) {
+            Some(entry) => entry.1 = telephony,
+            None => self.entries.push((designator, telephony)),
+        }
+        true
+    }
+
+    /// Radiotelephony callsign for a designator ("AAL" -> "American")
+    pub fn telephony(&self, designator: &str) -> Option<String> {
+        let designator = designator.trim().to_uppercase();
+        self.entries
+            .iter()
+            .find(|(d, _)| *d == designator)
+            .map(|(_, telephony)| title_case(telephony))
+    }
+
+    /// Designator for a radiotelephony callsign ("Speedbird" -> "BAW")
+    pub fn designator(&self, telephony: &str) -> Option<String> {
+        let words: Vec<String> = telephony
+            .split_whitespace()
+            .map(|w| w.to_uppercase())
+            .collect();
+
+        match self.match_telephony(&words) {
+            Some((designator, consumed)) if consumed == words.len() => Some(designator.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Spoken form of a written callsign for clearance and advisory text ("AAL123" -> "American one two three")
+    pub fn spoken(&self, callsign: &str) -> String {
+        let callsign = callsign.trim().to_uppercase();
+        let (prefix, number) = split_callsign(&callsign);
+
+        let mut words: Vec<String> = Vec::new();
+        let rest = match self.telephony(prefix) {
+            Some(telephony) => {
+                words.push(telephony);
+                number
+            }
+            None => callsign.as_str(),
+        };
+
+        for c in rest.chars() {
+            if let Some(digit) = c.to_digit(10) {
+                words.push(spoken_digit(digit).to_string());
+            } else if c.is_ascii_uppercase() {
+                words.push(title_case(PHONETIC_ALPHABET[(c as u8 - b'A') as usize]));
+            }
+        }
+
+        words.join(" ")
+    }
+
+    /// Convert a spoken phrase ("American one twenty three") to written form ("AAL123")
+    pub fn normalize(&self, phrase: &str) -> String {
+        let words: Vec<String> = phrase
+            .split(|c: char| c.is_whitespace() || c == '-' || c == ',')
+            .filter(|w| !w.is_empty())
+            .map(|w| w.to_uppercase())
+            .collect();
+
+        let mut result = String::new();
+        let mut index = 0;
+
+        // Airline telephony may span several words ("AIR CANADA")
+        if let Some((designator, consumed)) = self.match_telephony(&words) {
+            result.push_str(designator);
+            index = consumed;
+        }
+
+        let mut pending_tens: Option<u32> = None;
+        for word in &words[index..] {
+            if let Some(digit) = digit_value(word) {
+                match pending_tens.take() {
+                    Some(tens) if digit > 0 => result.push_str(&(tens + digit).to_string()),
+                    Some(tens) => {
+                        result.push_str(&tens.to_string());
+                        result.push('0');
+                    }
+                    None => result.push_str(&digit.to_string()),
+                }
+                continue;
+            }
+
+            if let Some(tens) = pending_tens.take() {
+                result.push_str(&tens.to_string());
+            }
+
+            if let Some(tens) = tens_value(word) {
+                pending_tens = Some(tens);
+            } else if let Some(teen) = teen_value(word) {
+                result.push_str(&teen.to_string());
+            } else if word == "HUNDRED" {
+                result.push_str("00");
+            } else if word == "THOUSAND" {
+                result.push_str("000");
+            } else if let Some(letter) = phonetic_letter(word) {
+                result.push(letter);
+            } else {
+                // Already-written fragments such as "123" or "AAL" pass through
+                result.extend(word.chars().filter(|c| c.is_ascii_alphanumeric()));
+            }
+        }
+
+        if let Some(tens) = pending_tens {
+            result.push_str(&tens.to_string());
+        }
+
+        result
+    }
+
+    /// Resolve a spoken callsign against the active traffic (exported to JavaScript)
+    pub fn resolve(&self, phrase: &str, traffic: Vec<String>) -> CallsignResolution {
+        self.resolve_callsign(phrase, &traffic)
+    }
+}
+
+impl TelephonyDirectory {
+    /// Resolve a spoken callsign against the callsigns currently in the traffic set
+    pub fn resolve_callsign(&self, phrase: &str, traffic: &[String]) -> CallsignResolution {
+        let heard = self.normalize(phrase);
+
+        let mut scored: Vec<(f64, &String)> = traffic
+            .iter()
+            .map(|callsign| (similarity(&heard, &callsign.to_uppercase()), callsign))
+            .filter(|(confidence, _)| *confidence >= MIN_CONFIDENCE)
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+
+        let Some(&(confidence, best)) = scored.first() else {
+            return CallsignResolution {
+                heard,
+                callsign: None,
+                confidence: 0.0,
+                ambiguous: false,
+                alternatives: Vec::new(),
+            };
+        };
+
+        let alternatives: Vec<String> = scored[1..]
+            .iter()
+            .filter(|(c, _)| confidence - c <= AMBIGUITY_MARGIN)
+            .map(|(_, callsign)| (*callsign).clone())
+            .collect();
+
+        CallsignResolution {
+            heard,
+            callsign: Some(best.clone()),
+            confidence,
+            ambiguous: !alternatives.is_empty(),
+            alternatives,
+        }
+    }
+
+    /// Find the longest airline telephony at the start of the phrase
+    fn match_telephony(&self, words: &[String]) -> Option<(&str, usize)> {
+        let mut best: Option<(&str, usize)> = None;
+
+        for (designator, telephony) in &self.entries {
+            let parts: Vec<&str> = telephony.split(' ').collect();
+            if parts.len() <= words.len()
+                && parts.iter().zip(words).all(|(p, w)| *p == w.as_str())
+                && best.is_none_or(|(_, len)| parts.len() > len)
+            {
+                best = Some((designator, parts.len()));
+            }
+        }
+
+        best
+    }
+}
+
+/// Convert a spoken phrase to written form using the built-in airline table
+pub fn normalize_spoken_callsign(phrase: &str) -> String {
+    TelephonyDirectory::new().normalize(phrase)
+}
+
+/// Resolve a spoken callsign against the active traffic using the built-in airline table
+pub fn resolve_callsign(phrase: &str, traffic: &[String]) -> CallsignResolution {
+    TelephonyDirectory::new().resolve_callsign(phrase, traffic)
+}
+
+fn digit_value(word: &str) -> Option<u32> {
+    match word {
+        "ZERO" => Some(0),
+        "ONE" => Some(1),
+        "TWO" => Some(2),
+        "THREE" | "TREE" => Some(3),
+        "FOUR" | "FOWER" => Some(4),
+        "FIVE" | "FIFE" => Some(5),
+        "SIX" => Some(6),
+        "SEVEN" => Some(7),
+        "EIGHT" => Some(8),
+        "NINE" | "NINER" => Some(9),
+        _ => None,
+    }
+}
+
+fn tens_value(word: &str) -> Option<u32> {
+    match word {
+        "TWENTY" => Some(20),
+        "THIRTY" => Some(30),
+        "FORTY" => Some(40),
+        "FIFTY" => Some(50),
+        "SIXTY" => Some(60),
+        "SEVENTY" => Some(70),
+        "EIGHTY" => Some(80),
+        "NINETY" => Some(90),
+        _ => None,
+    }
+}
+
+fn teen_value(word: &str) -> Option<u32> {
+    match word {
+        "TEN" => Some(10),
+        "ELEVEN" => Some(11),
+        "TWELVE" => Some(12),
+        "THIRTEEN" => Some(13),
+        "FOURTEEN" => Some(14),
+        "FIFTEEN" => Some(15),
+        "SIXTEEN" => Some(16),
+        "SEVENTEEN" => Some(17),
+        "EIGHTEEN" => Some(18),
+        "NINETEEN" => Some(19),
+        _ => None,
+    }
+}
+
+/// Letter for an ICAO phonetic alphabet word
+fn phonetic_letter(word: &str) -> Option<char> {
+    let word = match word {
+        "ALFA" => "ALPHA",
+        "JULIETT" => "JULIET",
+        other => other,
+    };
+
+    PHONETIC_ALPHABET
+        .iter()
+        .position(|w| *w == word)
+        .map(|i| (b'A' + i as u8) as char)
+}
+
+/// Radiotelephony word for a digit
+fn spoken_digit(digit: u32) -> &'static str {
+    const DIGITS: [&str; 10] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "niner",
+    ];
+    DIGITS[digit as usize % 10]
+}
+
+/// "AIR CANADA" -> "Air Canada"
+fn title_case(text: &str) -> String {
+    text.split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_string() + &chars.as_str().to_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Levenshtein edit distance between two strings
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Similarity between two written callsigns from 0.0 to 1.0
+fn similarity(a: &str, b: &str) -> f64 {
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 0.0;
+    }
+    1.0 - edit_distance(a, b) as f64 / longest as f64
+}
+
+/// Why two callsigns are likely to be confused on frequency
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfusionKind {
+    TransposedDigits,
+    SimilarNumber,
+    SameNumber,
+}
+
+/// Advisory raised when confusable callsigns are active in the same sector
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallsignConfusion {
+    pub first: String,
+    pub second: String,
+    pub sector: String,
+    pub kind: ConfusionKind,
+}
+
+/// Split a callsign into its airline prefix and flight number ("BAW123" -> "BAW", "123")
+fn split_callsign(callsign: &str) -> (&str, &str) {
+    let index = callsign
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or(callsign.len());
+    callsign.split_at(index)
+}
+
+/// Classify whether two callsigns are confusable
+pub fn classify_confusion(first: &str, second: &str) -> Option<ConfusionKind> {
+    let first = first.to_uppercase();
+    let second = second.to_uppercase();
+    if first == second {
+        return None;
+    }
+
+    let (prefix1, number1) = split_callsign(&first);
+    let (prefix2, number2) = split_callsign(&second);
+    if number1.is_empty() || number2.is_empty() {
+        return None;
+    }
+
+    if prefix1 != prefix2 {
+        // Different operators with the same flight number are easily mixed up
+        return if number1 == number2 {
+            Some(ConfusionKind::SameNumber)
+        } else {
+            None
+        };
+    }
+
+    let mut digits1: Vec<char> = number1.chars().collect();
+    let mut digits2: Vec<char> = number2.chars().collect();
+    digits1.sort_unstable();
+    digits2.sort_unstable();
+
+    if digits1 == digits2 {
+        Some(ConfusionKind::TransposedDigits)
+    } else if edit_distance(number1, number2) == 1 {
+        Some(ConfusionKind::SimilarNumber)
+    } else {
+        None
+    }
+}
+
+/// Find confusable callsign pairs among (callsign, sector) entries sharing a sector
+pub fn detect_callsign_confusion(active: &[(String, String)]) -> Vec<CallsignConfusion> {
+    let mut confusions = Vec::new();
+
+    for i in 0..active.len() {
+        for j in (i + 1)..active.len() {
+            let (callsign1, sector1) = &active[i];
+            let (callsign2, sector2) = &active[j];
+            if sector1 != sector2 {
+                continue;
+            }
+
+            if let Some(kind) = classify_confusion(callsign1, callsign2) {
+                confusions.push(CallsignConfusion {
+                    first: callsign1.clone(),
+                    second: callsign2.clone(),
+                    sector: sector1.clone(),
+                    kind,
+                });
+            }
+        }
+    }
+
+    confusions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn traffic(callsigns: &[&str]) -> Vec<String> {
+        callsigns.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn test_normalize_spoken_callsign() {
+        assert_eq!(normalize_spoken_callsign("American one twenty three"), "AAL123");
+        assert_eq!(normalize_spoken_callsign("speedbird two one niner"), "BAW219");
+        assert_eq!(normalize_spoken_callsign("Air Canada eight fifty"), "ACA850");
+        assert_eq!(normalize_spoken_callsign("november four two alpha"), "N42A");
+    }
+
+    #[test]
+    fn test_resolve_exact_match() {
+        let set = traffic(&["AAL123", "UAL456", "DAL789"]);
+        let result = resolve_callsign("American one twenty three", &set);
+
+        assert_eq!(result.callsign.as_deref(), Some("AAL123"));
+        assert_eq!(result.confidence, 1.0);
+        assert!(!result.ambiguous);
+    }
+
+    #[test]
+    fn test_resolve_reports_ambiguity() {
+        let set = traffic(&["AAL124", "AAL125", "UAL456"]);
+        let result = resolve_callsign("American one twenty three", &set);
+
+        assert!(result.ambiguous);
+        assert_eq!(result.alternatives.len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_rejects_unknown() {
+        let set = traffic(&["UAL456", "DAL789"]);
+        let result = resolve_callsign("American one twenty three", &set);
+
+        assert!(result.callsign.is_none());
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("BAW123", "BAW132"), 2);
+        assert_eq!(edit_distance("AAL123", "AAL123"), 0);
+        assert_eq!(edit_distance("", "ABC"), 3);
+    }
+
+    #[test]
+    fn test_classify_confusion() {
+        assert_eq!(
+            classify_confusion("BAW123", "BAW132"),
+            Some(ConfusionKind::TransposedDigits)
+        );
+        assert_eq!(
+            classify_confusion("AAL123", "AAL128"),
+            Some(ConfusionKind::SimilarNumber)
+        );
+        assert_eq!(
+            classify_confusion("AAL123", "UAL123"),
+            Some(ConfusionKind::SameNumber)
+        );
+        assert_eq!(classify_confusion("AAL123", "DAL789"), None);
+    }
+
+    #[test]
+    fn test_confusion_requires_same_sector() {
+        let active = vec![
+            ("BAW123".to_string(), "NORTH".to_string()),
+            ("BAW132".to_string(), "SOUTH".to_string()),
+            ("BAW213".to_string(), "NORTH".to_string()),
+        ];
+
+        let confusions = detect_callsign_confusion(&active);
+        assert_eq!(confusions.len(), 1);
+        assert_eq!(confusions[0].second, "BAW213");
+        assert_eq!(confusions[0].sector, "NORTH");
+    }
+
+    #[test]
+    fn test_telephony_lookup() {
+        let directory = TelephonyDirectory::new();
+        assert_eq!(directory.telephony("aal").as_deref(), Some("American"));
+        assert_eq!(directory.telephony("ACA").as_deref(), Some("Air Canada"));
+        assert_eq!(directory.designator("Speedbird").as_deref(), Some("BAW"));
+        assert!(directory.telephony("XYZ").is_none());
+    }
+
+    #[test]
+    fn test_register_custom_telephony() {
+        let mut directory = TelephonyDirectory::new();
+        assert!(directory.register("xyz", "zulu  express"));
+        assert!(!directory.register("TOOLONG", "Nope"));
+
+        assert_eq!(directory.telephony("XYZ").as_deref(), Some("Zulu Express"));
+        assert_eq!(directory.normalize("zulu express four one"), "XYZ41");
+    }
+
+    #[test]
+    fn test_spoken_callsign() {
+        let directory = TelephonyDirectory::new();
+        assert_eq!(directory.spoken("AAL129"), "American one two niner");
+        assert_eq!(directory.spoken("N42A"), "November four two Alpha");
+    }
+}
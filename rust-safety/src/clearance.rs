@@ -0,0 +1,162 @@
+/*!
+ * CLEARANCE MODULE
+ * Trajectory prediction towards the heading, level and speed an aircraft has been cleared to
+ */
+
+use crate::holding::TurnDirection;
+use crate::{heading_difference, normalize_heading, AircraftState};
+
+/// Standard rate turn (degrees per second)
+const RATE_ONE_TURN: f64 = 3.0;
+
+/// Speed change towards a cleared speed (kt per second)
+const SPEED_CHANGE_RATE: f64 = 1.0;
+
+/// Climb or descent rate assumed for a level aircraft cleared to a new level (ft/min)
+const DEFAULT_VERTICAL_RATE: f64 = 1500.0;
+
+/// Integration step for turns and speed changes (seconds)
+pub(crate) const PREDICTION_STEP: f64 = 1.0;
+
+/// What an aircraft has been instructed to do; unset targets are held constant
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Intent {
+    pub cleared_heading: Option<f64>,
+    pub cleared_altitude: Option<f64>,
+    pub cleared_speed: Option<f64>,
+    /// Turn direction given with the heading; the shorter turn if unset
+    pub turn: Option<TurnDirection>,
+}
+
+impl Intent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_heading(mut self, heading: f64, turn: Option<TurnDirection>) -> Self {
+        self.cleared_heading = Some(normalize_heading(heading));
+        self.turn = turn;
+        self
+    }
+
+    pub fn with_altitude(mut self, altitude: f64) -> Self {
+        self.cleared_altitude = Some(altitude);
+        self
+    }
+
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.cleared_speed = Some(speed);
+        self
+    }
+
+    /// True if the intent changes the horizontal path, which then has to be stepped
+    pub fn is_lateral(&self) -> bool {
+        self.cleared_heading.is_some() || self.cleared_speed.is_some()
+    }
+}
+
+/// The aircraft with a vertical rate towards its cleared level
+///
+/// A level aircraft, or one climbing away from the clearance, is given the
+/// default rate; the observed rate is kept if it already leads there.
+pub(crate) fn towards_cleared_level(aircraft: &AircraftState, intent: &Intent) -> AircraftState {
+    let Some(target) = intent.cleared_altitude else {
+        return *aircraft;
+    };
+    let to_go = target - aircraft.altitude;
+    if to_go == 0.0 || to_go * aircraft.vertical_rate > 0.0 {
+        return *aircraft;
+    }
+    aircraft.with_vertical_rate(DEFAULT_VERTICAL_RATE.copysign(to_go))
+}
+
+/// Heading change still to fly towards the cleared heading, positive is right
+fn turn_to_go(heading: f64, intent: &Intent) -> f64 {
+    let Some(target) = intent.cleared_heading else {
+        return 0.0;
+    };
+    match intent.turn {
+        Some(TurnDirection::Right) => normalize_heading(target - heading),
+        Some(TurnDirection::Left) => -normalize_heading(heading - target),
+        None => heading_difference(heading, target),
+    }
+}
+
+/// Predict a state after `time_seconds` following the cleared intent
+///
+/// Turns are flown at rate one and speed changes at 1 kt/s; the level is
+/// captured exactly, the horizontal path in one-second steps.
+pub fn predict_with_intent(aircraft: &AircraftState, intent: &Intent, time_seconds: f64) -> AircraftState {
+    let start = towards_cleared_level(aircraft, intent);
+    let vertical = match intent.cleared_altitude {
+        Some(target) => atc_safety_core::predict_to_level(&start.into(), time_seconds, target),
+        None => atc_safety_core::predict(&start.into(), time_seconds),
+    };
+
+    let mut state = start;
+    if intent.is_lateral() {
+        let mut elapsed = 0.0;
+        while elapsed < time_seconds {
+            let step = PREDICTION_STEP.min(time_seconds - elapsed);
+
+            let turn = turn_to_go(state.heading, intent);
+            state.heading = normalize_heading(state.heading + turn.clamp(-RATE_ONE_TURN * step, RATE_ONE_TURN * step));
+            if let Some(target) = intent.cleared_speed {
+                let change = SPEED_CHANGE_RATE * step;
+                state.speed += (target - state.speed).clamp(-change, change);
+            }
+
+            let moved = atc_safety_core::predict(&AircraftState { vertical_rate: 0.0, ..state }.into(), step);
+            state.x = moved.x;
+            state.y = moved.y;
+            elapsed += step;
+        }
+    } else {
+        state.x = vertical.x;
+        state.y = vertical.y;
+    }
+
+    state.altitude = vertical.altitude;
+    state.vertical_rate = vertical.vertical_rate;
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turn_speed_and_level_capture() {
+        let aircraft = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0);
+        let intent = Intent::new().with_heading(90.0, None).with_altitude(12000.0).with_speed(220.0);
+
+        let after = predict_with_intent(&aircraft, &intent, 120.0);
+        assert_eq!(after.heading, 90.0);
+        assert_eq!(after.speed, 220.0);
+        assert_eq!(after.altitude, 12000.0);
+        assert_eq!(after.vertical_rate, 0.0);
+        // Now heading east, well north of the start after the turn
+        assert!(after.x > 4.0 && after.y > 0.5);
+
+        let halfway = predict_with_intent(&aircraft, &intent, 40.0);
+        assert!((halfway.altitude - 11000.0).abs() < 1e-9);
+        assert_eq!(halfway.vertical_rate, 1500.0);
+    }
+
+    #[test]
+    fn test_turn_direction_is_respected() {
+        let aircraft = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0);
+
+        let right = predict_with_intent(&aircraft, &Intent::new().with_heading(90.0, None), 20.0);
+        assert!((right.heading - 60.0).abs() < 1e-9);
+
+        // Left onto 090 is a 270 degree turn, through south after 60 s
+        let left = Intent::new().with_heading(90.0, Some(TurnDirection::Left));
+        assert!((predict_with_intent(&aircraft, &left, 60.0).heading - 180.0).abs() < 1e-9);
+        assert_eq!(predict_with_intent(&aircraft, &left, 120.0).heading, 90.0);
+
+        // No intent is plain dead reckoning
+        let straight = predict_with_intent(&aircraft, &Intent::new(), 60.0);
+        assert!(straight.x.abs() < 1e-9 && (straight.y - 250.0 / 60.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,200 @@
+/*!
+ * COMMUNICATIONS MODULE
+ * Per-track frequency state integrated with sector handoffs
+ */
+
+use std::collections::HashMap;
+
+use crate::sector::SectorEvent;
+
+/// Where a track's radio contact currently stands
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommStatus {
+    /// Checked in and monitoring the sector's frequency
+    OnFrequency { sector: String },
+    /// Told to contact another sector but not yet checked in
+    Transferred { to: String },
+    /// Simulated radio failure (NORDO), squawking 7600
+    RadioFailure { last_sector: Option<String> },
+}
+
+/// Communication state of one track
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackComms {
+    pub status: CommStatus,
+    pub since: f64,
+}
+
+/// Communication state for every track
+#[derive(Debug, Clone, Default)]
+pub struct CommTracker {
+    tracks: HashMap<u64, TrackComms>,
+}
+
+impl CommTracker {
+    pub fn new() -> Self {
+        CommTracker::default()
+    }
+
+    pub fn status(&self, track: u64) -> Option<&CommStatus> {
+        self.tracks.get(&track).map(|t| &t.status)
+    }
+
+    /// Time the track entered its current state
+    pub fn since(&self, track: u64) -> Option<f64> {
+        self.tracks.get(&track).map(|t| t.since)
+    }
+
+    /// Pilot checks in on a sector's frequency; ignored while NORDO
+    pub fn check_in(&mut self, track: u64, sector: &str, time: f64) -> bool {
+        if self.is_radio_failed(track) {
+            return false;
+        }
+        self.set(track, CommStatus::OnFrequency { sector: sector.to_string() }, time);
+        true
+    }
+
+    /// Instruct a track to contact another sector; ignored while NORDO
+    pub fn transfer(&mut self, track: u64, to: &str, time: f64) -> bool {
+        if self.is_radio_failed(track) {
+            return false;
+        }
+        self.set(track, CommStatus::Transferred { to: to.to_string() }, time);
+        true
+    }
+
+    /// Simulate a radio failure on a track
+    pub fn fail_radio(&mut self, track: u64, time: f64) {
+        let last_sector = match self.status(track) {
+            Some(CommStatus::OnFrequency { sector }) => Some(sector.clone()),
+            Some(CommStatus::Transferred { to }) => Some(to.clone()),
+            Some(CommStatus::RadioFailure { last_sector }) => last_sector.clone(),
+            None => None,
+        };
+        self.set(track, CommStatus::RadioFailure { last_sector }, time);
+    }
+
+    /// Restore a failed radio; the pilot calls the last assigned sector
+    pub fn restore_radio(&mut self, track: u64, time: f64) -> bool {
+        match self.status(track) {
+            Some(CommStatus::RadioFailure { last_sector: Some(sector) }) => {
+                let sector = sector.clone();
+                self.set(track, CommStatus::OnFrequency { sector }, time);
+                true
+            }
+            Some(CommStatus::RadioFailure { last_sector: None }) => {
+                self.tracks.remove(&track);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_radio_failed(&self, track: u64) -> bool {
+        matches!(self.status(track), Some(CommStatus::RadioFailure { .. }))
+    }
+
+    /// Check whether a track can be reached on a sector's frequency
+    pub fn is_on_frequency(&self, track: u64, sector: &str) -> bool {
+        matches!(self.status(track), Some(CommStatus::OnFrequency { sector: s }) if s == sector)
+    }
+
+    /// Tracks among `tracks` that a sector cannot currently talk to
+    pub fn off_frequency(&self, sector: &str, tracks: &[u64]) -> Vec<u64> {
+        tracks
+            .iter()
+            .copied()
+            .filter(|&track| !self.is_on_frequency(track, sector))
+            .collect()
+    }
+
+    /// Tracks with a simulated radio failure, in id order
+    pub fn nordo_tracks(&self) -> Vec<u64> {
+        let mut tracks: Vec<u64> = self
+            .tracks
+            .iter()
+            .filter(|(_, t)| matches!(t.status, CommStatus::RadioFailure { .. }))
+            .map(|(&track, _)| track)
+            .collect();
+        tracks.sort_unstable();
+        tracks
+    }
+
+    /// Follow ownership changes from the sector map
+    ///
+    /// A newly picked-up track is assumed on the owner's frequency; a track
+    /// changing owner is transferred and must check in with the new sector.
+    /// Radio failures are unaffected.
+    pub fn apply_sector_event(&mut self, event: &SectorEvent, time: f64) {
+        if let SectorEvent::OwnershipChanged { track, from, to } = event {
+            if self.is_radio_failed(*track) {
+                return;
+            }
+            match (from, to) {
+                (None, Some(sector)) => {
+                    self.check_in(*track, sector, time);
+                }
+                (Some(_), Some(sector)) => {
+                    if !self.is_on_frequency(*track, sector) {
+                        self.transfer(*track, sector, time);
+                    }
+                }
+                (_, None) => {
+                    self.tracks.remove(track);
+                }
+            }
+        }
+    }
+
+    pub fn remove_track(&mut self, track: u64) {
+        self.tracks.remove(&track);
+    }
+
+    fn set(&mut self, track: u64, status: CommStatus, time: f64) {
+        self.tracks.insert(track, TrackComms { status, since: time });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handoff_transfers_until_check_in() {
+        let mut comms = CommTracker::new();
+        comms.apply_sector_event(
+            &SectorEvent::OwnershipChanged { track: 1, from: None, to: Some("WEST".to_string()) },
+            0.0,
+        );
+        assert!(comms.is_on_frequency(1, "WEST"));
+
+        comms.apply_sector_event(
+            &SectorEvent::OwnershipChanged {
+                track: 1,
+                from: Some("WEST".to_string()),
+                to: Some("EAST".to_string()),
+            },
+            30.0,
+        );
+        assert_eq!(comms.status(1), Some(&CommStatus::Transferred { to: "EAST".to_string() }));
+        assert_eq!(comms.off_frequency("EAST", &[1]), vec![1]);
+
+        comms.check_in(1, "EAST", 45.0);
+        assert!(comms.off_frequency("EAST", &[1]).is_empty());
+    }
+
+    #[test]
+    fn test_radio_failure() {
+        let mut comms = CommTracker::new();
+        comms.check_in(7, "WEST", 0.0);
+        comms.fail_radio(7, 10.0);
+
+        assert_eq!(comms.nordo_tracks(), vec![7]);
+        assert!(!comms.transfer(7, "EAST", 20.0));
+        assert!(!comms.is_on_frequency(7, "WEST"));
+
+        assert!(comms.restore_radio(7, 60.0));
+        assert!(comms.is_on_frequency(7, "WEST"));
+        assert_eq!(comms.since(7), Some(60.0));
+    }
+}
@@ -0,0 +1,411 @@
+/*!
+ * CONFIGURATION MODULE
+ * Engine-wide safety parameters
+ */
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use wasm_bindgen::prelude::*;
+
+use crate::area::{AreaShape, OperatingArea};
+use crate::conflict::ADVISORY_TIME;
+use crate::validation::ValidationConfig;
+
+/// Horizontal (nm) and vertical (ft) separation minima for one airspace regime
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeparationStandards {
+    pub horizontal: f64,
+    pub vertical: f64,
+}
+
+#[wasm_bindgen]
+impl SeparationStandards {
+    #[wasm_bindgen(constructor)]
+    pub fn new(horizontal: f64, vertical: f64) -> SeparationStandards {
+        SeparationStandards { horizontal, vertical }
+    }
+
+    /// Terminal radar: 3 nm / 1000 ft
+    pub fn terminal() -> SeparationStandards {
+        SeparationStandards::new(3.0, 1000.0)
+    }
+
+    /// En-route radar: 5 nm / 1000 ft
+    pub fn en_route() -> SeparationStandards {
+        SeparationStandards::new(5.0, 1000.0)
+    }
+
+    /// Oceanic with ADS-C: 30 nm / 1000 ft
+    pub fn oceanic() -> SeparationStandards {
+        SeparationStandards::new(30.0, 1000.0)
+    }
+
+    /// Reduced separation between arrivals on the same final: 2.5 nm / 1000 ft
+    pub fn reduced_on_final() -> SeparationStandards {
+        SeparationStandards::new(2.5, 1000.0)
+    }
+}
+
+impl Default for SeparationStandards {
+    fn default() -> Self {
+        SeparationStandards::terminal()
+    }
+}
+
+/// Separation minima and prediction settings shared by the batch engine
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SafetyConfig {
+    pub horizontal_separation: f64,
+    pub vertical_separation: f64,
+    pub look_ahead_time: f64,
+    pub time_step: f64,
+    /// Extra horizontal distance (nm) added to the minimum when alerting
+    pub buffer: f64,
+    /// Conflict probability at which an alert is raised
+    pub alert_on_probability: f64,
+    /// Conflict probability below which a raised alert clears
+    pub alert_off_probability: f64,
+    validation: ValidationConfig,
+    /// Areas replacing the operating area for named surveillance feeds
+    feed_areas: Vec<(String, OperatingArea)>,
+}
+
+#[wasm_bindgen]
+impl SafetyConfig {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        horizontal_separation: f64,
+        vertical_separation: f64,
+        look_ahead_time: f64,
+        time_step: f64,
+    ) -> SafetyConfig {
+        SafetyConfig {
+            horizontal_separation,
+            vertical_separation,
+            look_ahead_time,
+            time_step,
+            buffer: 0.0,
+            alert_on_probability: 0.5,
+            alert_off_probability: 0.3,
+            validation: ValidationConfig::default(),
+            feed_areas: Vec::new(),
+        }
+    }
+}
+
+impl SafetyConfig {
+    /// Hash of every setting, used to invalidate cached results when the config changes
+    pub fn config_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.horizontal_separation.to_bits().hash(&mut hasher);
+        self.vertical_separation.to_bits().hash(&mut hasher);
+        self.look_ahead_time.to_bits().hash(&mut hasher);
+        self.time_step.to_bits().hash(&mut hasher);
+        self.buffer.to_bits().hash(&mut hasher);
+        self.alert_on_probability.to_bits().hash(&mut hasher);
+        self.alert_off_probability.to_bits().hash(&mut hasher);
+        let validation = &self.validation;
+        for value in [
+            validation.min_speed,
+            validation.max_speed,
+            validation.min_altitude,
+            validation.max_altitude,
+            validation.max_turn_rate,
+            validation.max_acceleration,
+            validation.max_climb_rate,
+        ] {
+            value.to_bits().hash(&mut hasher);
+        }
+        hash_area(validation.area(), &mut hasher);
+        for (feed, area) in &self.feed_areas {
+            feed.hash(&mut hasher);
+            hash_area(area, &mut hasher);
+        }
+        hasher.finish()
+    }
+
+    pub fn with_standards(mut self, standards: SeparationStandards) -> Self {
+        self.horizontal_separation = standards.horizontal;
+        self.vertical_separation = standards.vertical;
+        self
+    }
+
+    pub fn with_operating_area(mut self, area: OperatingArea) -> Self {
+        self.validation = self.validation.with_operating_area(area);
+        self
+    }
+
+    pub fn with_validation(mut self, validation: ValidationConfig) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    pub fn validation(&self) -> &ValidationConfig {
+        &self.validation
+    }
+
+    /// Validation limits for reports from `feed`, with its area override applied
+    pub fn validation_for(&self, feed: &str) -> ValidationConfig {
+        self.validation.clone().with_operating_area(self.operating_area_for(feed).clone())
+    }
+
+    /// Use `area` instead of the operating area for reports from `feed`
+    pub fn with_feed_area(mut self, feed: &str, area: OperatingArea) -> Self {
+        self.feed_areas.retain(|(name, _)| name != feed);
+        self.feed_areas.push((feed.to_string(), area));
+        self
+    }
+
+    pub fn operating_area(&self) -> &OperatingArea {
+        self.validation.area()
+    }
+
+    /// Operating area for reports from `feed`, falling back to the facility's
+    pub fn operating_area_for(&self, feed: &str) -> &OperatingArea {
+        self.feed_areas
+            .iter()
+            .find(|(name, _)| name == feed)
+            .map_or(self.validation.area(), |(_, area)| area)
+    }
+
+    pub fn with_buffer(mut self, buffer: f64) -> Self {
+        self.buffer = buffer;
+        self
+    }
+
+    pub fn with_alert_hysteresis(mut self, on_probability: f64, off_probability: f64) -> Self {
+        self.alert_on_probability = on_probability;
+        self.alert_off_probability = off_probability;
+        self
+    }
+
+    /// Horizontal distance (nm) below which the sweep alerts
+    pub fn alert_distance(&self) -> f64 {
+        self.horizontal_separation + self.buffer
+    }
+
+    /// Separation minima without the alerting buffer
+    pub fn standards(&self) -> SeparationStandards {
+        SeparationStandards::new(self.horizontal_separation, self.vertical_separation)
+    }
+
+    /// Minima the sweep alerts on, with the buffer added horizontally
+    pub fn alert_standards(&self) -> SeparationStandards {
+        SeparationStandards::new(self.alert_distance(), self.vertical_separation)
+    }
+
+    /// Check every setting and their consistency with each other
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let issues = config_issues(self);
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError { issues })
+        }
+    }
+}
+
+fn hash_area(area: &OperatingArea, hasher: &mut DefaultHasher) {
+    match &area.shape {
+        AreaShape::Circle { x, y, radius } => {
+            for value in [x, y, radius] {
+                value.to_bits().hash(hasher);
+            }
+        }
+        AreaShape::Polygon(boundary) => {
+            for (x, y) in boundary {
+                x.to_bits().hash(hasher);
+                y.to_bits().hash(hasher);
+            }
+        }
+    }
+    area.floor.to_bits().hash(hasher);
+    area.ceiling.to_bits().hash(hasher);
+}
+
+impl Default for SafetyConfig {
+    /// Terminal radar standards with a 5 minute look-ahead in 1 second steps
+    fn default() -> Self {
+        SafetyConfig::new(3.0, 1000.0, 300.0, 1.0)
+    }
+}
+
+/// Single problem found in a config
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigIssue {
+    /// Setting is negative, zero where it must be positive, or not finite
+    OutOfRange { field: &'static str, value: f64 },
+    /// Prediction step longer than the look-ahead
+    TimeStepExceedsLookAhead { time_step: f64, look_ahead: f64 },
+    /// Look-ahead too short to ever raise an advisory by time
+    LookAheadBelowSeverityThreshold { look_ahead: f64, threshold: f64 },
+    /// Alerting buffer larger than the separation minimum it widens
+    BufferExceedsMinimum { buffer: f64, minimum: f64 },
+    /// Alerts would clear above the probability that raises them
+    HysteresisInverted { on: f64, off: f64 },
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigIssue::OutOfRange { field, value } => write!(f, "{} out of range: {}", field, value),
+            ConfigIssue::TimeStepExceedsLookAhead { time_step, look_ahead } => {
+                write!(f, "time step {} s exceeds look-ahead {} s", time_step, look_ahead)
+            }
+            ConfigIssue::LookAheadBelowSeverityThreshold { look_ahead, threshold } => write!(
+                f,
+                "look-ahead {} s is shorter than the {} s advisory threshold",
+                look_ahead, threshold
+            ),
+            ConfigIssue::BufferExceedsMinimum { buffer, minimum } => {
+                write!(f, "buffer {} nm exceeds the {} nm minimum", buffer, minimum)
+            }
+            ConfigIssue::HysteresisInverted { on, off } => {
+                write!(f, "alert off probability {} is above on probability {}", off, on)
+            }
+        }
+    }
+}
+
+/// Config rejected by validation, with every issue found
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid config: ")?;
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Every issue in a config, in field order
+pub fn config_issues(config: &SafetyConfig) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+
+    let positive = [
+        ("horizontal_separation", config.horizontal_separation),
+        ("vertical_separation", config.vertical_separation),
+        ("look_ahead_time", config.look_ahead_time),
+        ("time_step", config.time_step),
+    ];
+    for (field, value) in positive {
+        if !(value.is_finite() && value > 0.0) {
+            issues.push(ConfigIssue::OutOfRange { field, value });
+        }
+    }
+    if !(config.buffer.is_finite() && config.buffer >= 0.0) {
+        issues.push(ConfigIssue::OutOfRange { field: "buffer", value: config.buffer });
+    }
+    for (field, value) in [
+        ("alert_on_probability", config.alert_on_probability),
+        ("alert_off_probability", config.alert_off_probability),
+    ] {
+        if !(0.0..=1.0).contains(&value) {
+            issues.push(ConfigIssue::OutOfRange { field, value });
+        }
+    }
+
+    if config.time_step > config.look_ahead_time {
+        issues.push(ConfigIssue::TimeStepExceedsLookAhead {
+            time_step: config.time_step,
+            look_ahead: config.look_ahead_time,
+        });
+    }
+    if config.look_ahead_time < ADVISORY_TIME {
+        issues.push(ConfigIssue::LookAheadBelowSeverityThreshold {
+            look_ahead: config.look_ahead_time,
+            threshold: ADVISORY_TIME,
+        });
+    }
+    if config.buffer > 0.0 && config.buffer > config.horizontal_separation {
+        issues.push(ConfigIssue::BufferExceedsMinimum {
+            buffer: config.buffer,
+            minimum: config.horizontal_separation,
+        });
+    }
+    if config.alert_off_probability > config.alert_on_probability {
+        issues.push(ConfigIssue::HysteresisInverted {
+            on: config.alert_on_probability,
+            off: config.alert_off_probability,
+        });
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert_eq!(SafetyConfig::default().validate(), Ok(()));
+        assert_eq!(SafetyConfig::default().with_buffer(0.5).alert_distance(), 3.5);
+    }
+
+    #[test]
+    fn test_standards_presets() {
+        let config = SafetyConfig::default().with_standards(SeparationStandards::en_route()).with_buffer(1.0);
+        assert_eq!(config.standards(), SeparationStandards::new(5.0, 1000.0));
+        assert_eq!(config.alert_standards(), SeparationStandards::new(6.0, 1000.0));
+        assert_eq!(SafetyConfig::default().standards(), SeparationStandards::default());
+        assert!(SeparationStandards::reduced_on_final().horizontal < SeparationStandards::terminal().horizontal);
+        assert!(SeparationStandards::oceanic().horizontal > SeparationStandards::en_route().horizontal);
+    }
+
+    #[test]
+    fn test_feed_area_overrides() {
+        let config = SafetyConfig::default()
+            .with_operating_area(OperatingArea::circle(250.0))
+            .with_feed_area("radar", OperatingArea::circle(60.0))
+            .with_feed_area("radar", OperatingArea::circle(80.0));
+
+        assert_eq!(config.operating_area_for("adsb"), &OperatingArea::circle(250.0));
+        assert_eq!(config.operating_area_for("radar"), &OperatingArea::circle(80.0));
+        assert_eq!(config.validation_for("radar").area(), &OperatingArea::circle(80.0));
+        assert_ne!(config.config_hash(), SafetyConfig::default().config_hash());
+    }
+
+    #[test]
+    fn test_reports_each_inconsistency() {
+        let config = SafetyConfig::new(3.0, 1000.0, 60.0, 1.0)
+            .with_buffer(4.0)
+            .with_alert_hysteresis(0.4, 0.6);
+
+        let error = config.validate().unwrap_err();
+        assert_eq!(
+            error.issues,
+            vec![
+                ConfigIssue::LookAheadBelowSeverityThreshold { look_ahead: 60.0, threshold: 120.0 },
+                ConfigIssue::BufferExceedsMinimum { buffer: 4.0, minimum: 3.0 },
+                ConfigIssue::HysteresisInverted { on: 0.4, off: 0.6 },
+            ]
+        );
+        assert!(error.to_string().contains("buffer 4 nm exceeds the 3 nm minimum"));
+
+        let broken = SafetyConfig::new(-3.0, 1000.0, 300.0, 600.0);
+        assert_eq!(
+            config_issues(&broken),
+            vec![
+                ConfigIssue::OutOfRange { field: "horizontal_separation", value: -3.0 },
+                ConfigIssue::TimeStepExceedsLookAhead { time_step: 600.0, look_ahead: 300.0 },
+            ]
+        );
+    }
+}
@@ -1,12 +1,25 @@
-/**
+/*!
  * CONFLICT DETECTION MODULE
  * Predictive conflict analysis and alerting
  */
 
-use crate::AircraftState;
+use wasm_bindgen::prelude::*;
 
-/// Conflict severity levels
-#[derive(Debug, Clone, Copy, PartialEq)]
+use crate::approach::SEGMENT_STRIDE;
+use crate::clearance::{predict_with_intent, towards_cleared_level, Intent, PREDICTION_STEP};
+use crate::config::SeparationStandards;
+use crate::{heading_difference, normalize_heading, AircraftState};
+
+/// Time to conflict within which a conflict is at least advisory (seconds)
+pub(crate) const ADVISORY_TIME: f64 = 120.0;
+
+/// Look-ahead a resolution must stay conflict-free for (seconds)
+pub(crate) const RESOLUTION_LOOK_AHEAD: f64 = 300.0;
+
+/// Conflict severity levels, ordered from least to most severe
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConflictSeverity {
     None,
     Advisory,
@@ -14,12 +27,38 @@ pub enum ConflictSeverity {
     Critical,
 }
 
+/// Track angle difference at or above which aircraft are on reciprocal tracks (degrees)
+const HEAD_ON_TRACK_DIFFERENCE: f64 = 135.0;
+
+/// Track angle difference at or below which aircraft are on the same track (degrees)
+const SAME_TRACK_DIFFERENCE: f64 = 45.0;
+
+/// Relative bearing beyond which an aircraft is behind the other (degrees off the nose)
+const ASTERN_RELATIVE_BEARING: f64 = 110.0;
+
+/// How two aircraft close on each other, which decides the usual resolution
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConflictGeometry {
+    /// Not classified, or an aircraft is not moving
+    Undetermined,
+    /// Reciprocal tracks
+    HeadOn,
+    /// Converging tracks, or same tracks side by side
+    Crossing,
+    /// Same track with one aircraft closing from behind
+    Overtaking,
+}
+
 /// Conflict information structure
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConflictInfo {
     pub severity: ConflictSeverity,
     pub time_to_conflict: f64,
     pub minimum_distance: f64,
+    pub geometry: ConflictGeometry,
 }
 
 impl ConflictInfo {
@@ -28,68 +67,441 @@ impl ConflictInfo {
             severity,
             time_to_conflict,
             minimum_distance,
+            geometry: ConflictGeometry::Undetermined,
         }
     }
+
+    pub fn with_geometry(mut self, geometry: ConflictGeometry) -> Self {
+        self.geometry = geometry;
+        self
+    }
+}
+
+/// Compass bearing from one aircraft to another
+fn bearing_between(from: &AircraftState, to: &AircraftState) -> f64 {
+    normalize_heading((to.x - from.x).atan2(to.y - from.y).to_degrees())
+}
+
+/// Bearing of `to` relative to the nose of `from`, -180 to 180 (positive right)
+fn relative_bearing(from: &AircraftState, to: &AircraftState) -> f64 {
+    heading_difference(from.heading, bearing_between(from, to))
+}
+
+/// Classify the encounter from the track angle difference and relative bearings
+///
+/// Tracks within 45 degrees of reciprocal are head-on. Tracks within 45
+/// degrees of each other are overtaking when one aircraft is more than
+/// 110 degrees off the other's nose, and crossing when they are abeam.
+/// Everything else is crossing.
+pub fn classify_geometry(aircraft1: &AircraftState, aircraft2: &AircraftState) -> ConflictGeometry {
+    if !(aircraft1.speed > 0.0 && aircraft2.speed > 0.0) {
+        return ConflictGeometry::Undetermined;
+    }
+
+    let track_difference = heading_difference(aircraft1.heading, aircraft2.heading).abs();
+    if track_difference >= HEAD_ON_TRACK_DIFFERENCE {
+        ConflictGeometry::HeadOn
+    } else if track_difference <= SAME_TRACK_DIFFERENCE
+        && (relative_bearing(aircraft1, aircraft2).abs() > ASTERN_RELATIVE_BEARING
+            || relative_bearing(aircraft2, aircraft1).abs() > ASTERN_RELATIVE_BEARING)
+    {
+        ConflictGeometry::Overtaking
+    } else {
+        ConflictGeometry::Crossing
+    }
+}
+
+/// Closest point of approach within a look-ahead
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClosestApproach {
+    /// Seconds from now, zero if the aircraft are already diverging
+    pub time: f64,
+    /// Horizontal miss distance (nm)
+    pub distance: f64,
+}
+
+/// Exact closest point of approach from the relative velocity, capped at `look_ahead_time`
+pub fn closest_point_of_approach(
+    aircraft1: &AircraftState,
+    aircraft2: &AircraftState,
+    look_ahead_time: f64,
+) -> ClosestApproach {
+    let (distance, time) =
+        atc_safety_core::closest_approach_within(&(*aircraft1).into(), &(*aircraft2).into(), look_ahead_time);
+    ClosestApproach { time, distance }
+}
+
+/// Predicted loss of separation from entry to exit
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConflictInterval {
+    /// Seconds until separation is lost
+    pub start: f64,
+    /// Seconds until separation is regained, capped at the look-ahead
+    pub end: f64,
+    pub duration: f64,
+    first: [f64; SEGMENT_STRIDE],
+    second: [f64; SEGMENT_STRIDE],
+}
+
+#[wasm_bindgen]
+impl ConflictInterval {
+    /// Conflict segment of each predicted track as start x, y, end x, y; first aircraft then second
+    pub fn segments(&self) -> Vec<f64> {
+        [self.first, self.second].concat()
+    }
+}
+
+impl ConflictInterval {
+    /// Positions of the first aircraft at conflict start and end
+    pub fn first_segment(&self) -> [f64; SEGMENT_STRIDE] {
+        self.first
+    }
+
+    /// Positions of the second aircraft at conflict start and end
+    pub fn second_segment(&self) -> [f64; SEGMENT_STRIDE] {
+        self.second
+    }
+}
+
+/// Times within which the vertical distance is below the minimum, from current vertical rates
+fn vertical_window(aircraft1: &AircraftState, aircraft2: &AircraftState, vertical_separation: f64) -> Option<(f64, f64)> {
+    let gap = aircraft1.altitude - aircraft2.altitude;
+    let rate = (aircraft1.vertical_rate - aircraft2.vertical_rate) / 60.0;
+    if gap.is_nan() {
+        // Primary-only targets, as in conflict detection
+        return None;
+    }
+    if rate.abs() < 1e-12 {
+        return (gap.abs() < vertical_separation).then_some((0.0, f64::INFINITY));
+    }
+    let entry = (-vertical_separation - gap) / rate;
+    let exit = (vertical_separation - gap) / rate;
+    Some((entry.min(exit), entry.max(exit)))
+}
+
+/// Full interval during which separation is predicted lost within `look_ahead_time`
+///
+/// Both aircraft fly straight at their current speed and vertical rate, as
+/// in `detect_conflict`, and the interval is solved exactly: the start
+/// matches its `time_to_conflict`. `None` when separation is kept.
+pub fn conflict_interval(
+    aircraft1: &AircraftState,
+    aircraft2: &AircraftState,
+    standards: &SeparationStandards,
+    look_ahead_time: f64,
+) -> Option<ConflictInterval> {
+    let (h_start, h_end) =
+        atc_safety_core::horizontal_distance_window(&(*aircraft1).into(), &(*aircraft2).into(), standards.horizontal)?;
+    let (v_start, v_end) = vertical_window(aircraft1, aircraft2, standards.vertical)?;
+
+    let start = h_start.max(v_start).max(0.0);
+    let end = h_end.min(v_end).min(look_ahead_time);
+    if start >= end {
+        return None;
+    }
+
+    let segment = |aircraft: &AircraftState| {
+        let (entry, exit) = (predict_position(aircraft, start), predict_position(aircraft, end));
+        [entry.x, entry.y, exit.x, exit.y]
+    };
+    Some(ConflictInterval {
+        start,
+        end,
+        duration: end - start,
+        first: segment(aircraft1),
+        second: segment(aircraft2),
+    })
 }
 
 /// Detect potential conflict between two aircraft
+///
+/// Uses the closed-form closest point of approach, so the time separation
+/// is first lost and the minimum distance are exact rather than sampled.
+/// Climbing and descending aircraft are projected at their vertical rate.
 pub fn detect_conflict(
     aircraft1: &AircraftState,
     aircraft2: &AircraftState,
+    standards: &SeparationStandards,
+    look_ahead_time: f64,
+) -> ConflictInfo {
+    detect_conflict_with_intent(
+        aircraft1,
+        &Intent::new(),
+        aircraft2,
+        &Intent::new(),
+        standards.horizontal,
+        standards.vertical,
+        look_ahead_time,
+    )
+}
+
+/// Detect a conflict with each aircraft following its cleared intent
+///
+/// Level-only clearances keep the exact closed form; a cleared heading or
+/// speed bends the path, which is then sampled every second.
+pub fn detect_conflict_with_intent(
+    aircraft1: &AircraftState,
+    intent1: &Intent,
+    aircraft2: &AircraftState,
+    intent2: &Intent,
     horizontal_separation: f64,
     vertical_separation: f64,
     look_ahead_time: f64,
+) -> ConflictInfo {
+    if !intent1.is_lateral() && !intent2.is_lateral() {
+        return detect_conflict_to_levels(
+            &towards_cleared_level(aircraft1, intent1),
+            intent1.cleared_altitude,
+            &towards_cleared_level(aircraft2, intent2),
+            intent2.cleared_altitude,
+            horizontal_separation,
+            vertical_separation,
+            look_ahead_time,
+        );
+    }
+
+    let mut first = *aircraft1;
+    let mut second = *aircraft2;
+    let mut min_distance = calculate_horizontal_distance(&first, &second);
+    let mut conflict_time = -1.0;
+    let mut time = 0.0;
+    loop {
+        let horizontal = calculate_horizontal_distance(&first, &second);
+        min_distance = min_distance.min(horizontal);
+        if conflict_time < 0.0
+            && horizontal < horizontal_separation
+            && (first.altitude - second.altitude).abs() < vertical_separation
+        {
+            conflict_time = time;
+        }
+        if time >= look_ahead_time {
+            break;
+        }
+
+        let step = PREDICTION_STEP.min(look_ahead_time - time);
+        first = predict_with_intent(&first, intent1, step);
+        second = predict_with_intent(&second, intent2, step);
+        time += step;
+    }
+
+    let severity = calculate_severity(conflict_time, min_distance, horizontal_separation);
+    ConflictInfo::new(severity, conflict_time, min_distance).with_geometry(classify_geometry(aircraft1, aircraft2))
+}
+
+/// Detect a conflict with each aircraft levelling off at an optional cleared altitude
+pub fn detect_conflict_to_levels(
+    aircraft1: &AircraftState,
+    target1: Option<f64>,
+    aircraft2: &AircraftState,
+    target2: Option<f64>,
+    horizontal_separation: f64,
+    vertical_separation: f64,
+    look_ahead_time: f64,
+) -> ConflictInfo {
+    let cpa = closest_point_of_approach(aircraft1, aircraft2, look_ahead_time);
+
+    let conflict_time = atc_safety_core::horizontal_distance_window(
+        &(*aircraft1).into(),
+        &(*aircraft2).into(),
+        horizontal_separation,
+    )
+    .filter(|&(start, _)| start <= look_ahead_time)
+    .and_then(|(start, end)| {
+        let first = (aircraft1, target1);
+        let second = (aircraft2, target2);
+        vertical_entry(first, second, vertical_separation, start, end.min(look_ahead_time))
+    })
+    .unwrap_or(-1.0);
+
+    let severity = calculate_severity(conflict_time, cpa.distance, horizontal_separation);
+    ConflictInfo::new(severity, conflict_time, cpa.distance).with_geometry(classify_geometry(aircraft1, aircraft2))
+}
+
+/// Altitude after `time` seconds, levelling off at `target` if one is given
+fn altitude_at(aircraft: &AircraftState, target: Option<f64>, time: f64) -> f64 {
+    let state = (*aircraft).into();
+    match target {
+        Some(target) => atc_safety_core::predict_to_level(&state, time, target).altitude,
+        None => atc_safety_core::predict(&state, time).altitude,
+    }
+}
+
+/// Earliest time in `from..=to` at which the vertical distance is below the minimum
+///
+/// The vertical distance is piecewise linear, changing slope only where an
+/// aircraft levels off, so each piece is solved directly.
+fn vertical_entry(
+    first: (&AircraftState, Option<f64>),
+    second: (&AircraftState, Option<f64>),
+    vertical_separation: f64,
+    from: f64,
+    to: f64,
+) -> Option<f64> {
+    let mut breaks = vec![from];
+    for (aircraft, target) in [first, second] {
+        let level_off = target.and_then(|t| atc_safety_core::time_to_level_off(&(*aircraft).into(), t));
+        if let Some(time) = level_off.filter(|&t| t > from && t < to) {
+            breaks.push(time);
+        }
+    }
+    breaks.sort_by(f64::total_cmp);
+    breaks.push(to);
+
+    let separation_at = |t: f64| altitude_at(first.0, first.1, t) - altitude_at(second.0, second.1, t);
+    for piece in breaks.windows(2) {
+        let (start, end) = (piece[0], piece[1]);
+        let gap = separation_at(start);
+        if gap.abs() < vertical_separation {
+            return Some(start);
+        }
+        if end <= start {
+            continue;
+        }
+
+        let slope = (separation_at(end) - gap) / (end - start);
+        let boundary = if gap > 0.0 { vertical_separation } else { -vertical_separation };
+        let time = start + (boundary - gap) / slope;
+        if time >= start && time < end {
+            return Some(time);
+        }
+    }
+    None
+}
+
+/// Detect potential conflict using a custom prediction time step (seconds)
+pub fn detect_conflict_with_step(
+    aircraft1: &AircraftState,
+    aircraft2: &AircraftState,
+    horizontal_separation: f64,
+    vertical_separation: f64,
+    look_ahead_time: f64,
+    time_step: f64,
+) -> ConflictInfo {
+    scan_conflict(
+        aircraft1,
+        aircraft2,
+        horizontal_separation,
+        vertical_separation,
+        look_ahead_time,
+        time_step,
+        |a, b| (a.altitude - b.altitude).abs(),
+    )
+}
+
+/// Step both aircraft forward, measuring vertical distance with `vertical_distance`
+pub(crate) fn scan_conflict(
+    aircraft1: &AircraftState,
+    aircraft2: &AircraftState,
+    horizontal_separation: f64,
+    vertical_separation: f64,
+    look_ahead_time: f64,
+    time_step: f64,
+    vertical_distance: impl Fn(&AircraftState, &AircraftState) -> f64,
 ) -> ConflictInfo {
     let mut min_distance = calculate_distance(aircraft1, aircraft2);
     let mut conflict_time = -1.0;
     
-    let time_step = 1.0; // 1 second steps
+    let time_step = if time_step > 0.0 { time_step } else { 1.0 };
     let mut current_time = 0.0;
     
     let mut temp1 = *aircraft1;
     let mut temp2 = *aircraft2;
     
+    // Sample each step before moving on, so a conflict is timed when it starts
     while current_time <= look_ahead_time {
-        temp1 = predict_position(&temp1, time_step);
-        temp2 = predict_position(&temp2, time_step);
-        
         let horizontal_dist = calculate_horizontal_distance(&temp1, &temp2);
-        let vertical_dist = (temp1.altitude - temp2.altitude).abs();
+        let vertical_dist = vertical_distance(&temp1, &temp2);
         
         if horizontal_dist < min_distance {
             min_distance = horizontal_dist;
         }
         
         // Check for conflict
-        if horizontal_dist < horizontal_separation && vertical_dist < vertical_separation {
-            if conflict_time < 0.0 {
-                conflict_time = current_time;
-            }
+        if horizontal_dist < horizontal_separation
+            && vertical_dist < vertical_separation
+            && conflict_time < 0.0
+        {
+            conflict_time = current_time;
         }
         
+        temp1 = predict_position(&temp1, time_step);
+        temp2 = predict_position(&temp2, time_step);
         current_time += time_step;
     }
     
     let severity = calculate_severity(conflict_time, min_distance, horizontal_separation);
     
-    ConflictInfo::new(severity, conflict_time, min_distance)
+    ConflictInfo::new(severity, conflict_time, min_distance).with_geometry(classify_geometry(aircraft1, aircraft2))
 }
 
 /// Calculate conflict severity based on time and distance
-fn calculate_severity(time_to_conflict: f64, min_distance: f64, separation_min: f64) -> ConflictSeverity {
+pub(crate) fn calculate_severity(time_to_conflict: f64, min_distance: f64, separation_min: f64) -> ConflictSeverity {
+    classify_severity(time_to_conflict, min_distance, separation_min).severity
+}
+
+/// Which measurement set a conflict's severity
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SeverityTrigger {
+    /// No conflict predicted, or one too distant to alert on
+    None,
+    TimeToConflict,
+    MinimumDistance,
+}
+
+/// Severity with the rule that set it
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeverityBasis {
+    pub severity: ConflictSeverity,
+    pub trigger: SeverityTrigger,
+    /// Measured time (s) or distance (nm) the rule compared
+    pub measured: f64,
+    /// Value it had to be below
+    pub threshold: f64,
+}
+
+/// Severity of a conflict and the first rule that fired, time before distance
+pub fn classify_severity(time_to_conflict: f64, min_distance: f64, separation_min: f64) -> SeverityBasis {
+    let none = SeverityBasis {
+        severity: ConflictSeverity::None,
+        trigger: SeverityTrigger::None,
+        measured: time_to_conflict,
+        threshold: ADVISORY_TIME,
+    };
     if time_to_conflict < 0.0 {
-        return ConflictSeverity::None;
+        return none;
     }
-    
-    if time_to_conflict < 30.0 || min_distance < separation_min * 0.5 {
-        ConflictSeverity::Critical
-    } else if time_to_conflict < 60.0 || min_distance < separation_min * 0.75 {
-        ConflictSeverity::Warning
-    } else if time_to_conflict < 120.0 || min_distance < separation_min {
-        ConflictSeverity::Advisory
-    } else {
-        ConflictSeverity::None
+
+    let levels = [
+        (ConflictSeverity::Critical, 30.0, 0.5),
+        (ConflictSeverity::Warning, 60.0, 0.75),
+        (ConflictSeverity::Advisory, ADVISORY_TIME, 1.0),
+    ];
+    for (severity, time, fraction) in levels {
+        let distance = separation_min * fraction;
+        if time_to_conflict < time {
+            return SeverityBasis {
+                severity,
+                trigger: SeverityTrigger::TimeToConflict,
+                measured: time_to_conflict,
+                threshold: time,
+            };
+        }
+        if min_distance < distance {
+            return SeverityBasis {
+                severity,
+                trigger: SeverityTrigger::MinimumDistance,
+                measured: min_distance,
+                threshold: distance,
+            };
+        }
     }
+    none
 }
 
 /// Calculate 2D distance between aircraft
@@ -99,25 +511,14 @@ fn calculate_distance(aircraft1: &AircraftState, aircraft2: &AircraftState) -> f
 
 /// Calculate horizontal distance
 fn calculate_horizontal_distance(aircraft1: &AircraftState, aircraft2: &AircraftState) -> f64 {
-    let dx = aircraft1.x - aircraft2.x;
-    let dy = aircraft1.y - aircraft2.y;
-    (dx * dx + dy * dy).sqrt()
+    atc_safety_core::horizontal_distance(&(*aircraft1).into(), &(*aircraft2).into())
 }
 
 /// Predict future position
 fn predict_position(aircraft: &AircraftState, time_seconds: f64) -> AircraftState {
-    let speed_nm_per_sec = aircraft.speed / 3600.0;
-    let heading_rad = aircraft.heading.to_radians();
-    
-    let dx = heading_rad.sin() * speed_nm_per_sec * time_seconds;
-    let dy = heading_rad.cos() * speed_nm_per_sec * time_seconds;
-    
     AircraftState {
-        x: aircraft.x + dx,
-        y: aircraft.y + dy,
-        altitude: aircraft.altitude,
-        heading: aircraft.heading,
-        speed: aircraft.speed,
+        wake: aircraft.wake,
+        ..atc_safety_core::predict(&(*aircraft).into(), time_seconds).into()
     }
 }
 
@@ -135,28 +536,47 @@ pub fn is_resolution_effective(
     let conflict = detect_conflict(
         &modified_aircraft1,
         aircraft2,
-        horizontal_separation,
-        vertical_separation,
-        300.0, // Look ahead 5 minutes
+        &SeparationStandards::new(horizontal_separation, vertical_separation),
+        RESOLUTION_LOOK_AHEAD,
     );
     
     matches!(conflict.severity, ConflictSeverity::None)
 }
 
+/// Heading change an overtaking aircraft makes to pass on the right (degrees)
+const OVERTAKING_TURN: f64 = 30.0;
+
 /// Calculate recommended heading change to avoid conflict
+///
+/// The maneuver for `aircraft1` follows the geometry: turn right of the
+/// other aircraft when head-on, aim behind its tail when crossing, and
+/// pass on the right when overtaking. An aircraft being overtaken has the
+/// right of way and keeps its heading.
 pub fn calculate_avoidance_heading(
     aircraft1: &AircraftState,
     aircraft2: &AircraftState,
 ) -> f64 {
-    let dx = aircraft2.x - aircraft1.x;
-    let dy = aircraft2.y - aircraft1.y;
-    
-    let bearing_to_aircraft2 = dy.atan2(dx).to_degrees();
-    
-    // Turn 90 degrees right from bearing to other aircraft
-    let avoidance_heading = (bearing_to_aircraft2 + 90.0) % 360.0;
-    
-    avoidance_heading
+    let bearing_to_aircraft2 = bearing_between(aircraft1, aircraft2);
+
+    match classify_geometry(aircraft1, aircraft2) {
+        ConflictGeometry::Crossing => {
+            // Point as far behind the other aircraft as it is away from us
+            let distance = calculate_horizontal_distance(aircraft1, aircraft2);
+            let (sin, cos) = aircraft2.heading.to_radians().sin_cos();
+            let tail = AircraftState {
+                x: aircraft2.x - sin * distance,
+                y: aircraft2.y - cos * distance,
+                ..*aircraft2
+            };
+            bearing_between(aircraft1, &tail)
+        }
+        ConflictGeometry::Overtaking if relative_bearing(aircraft1, aircraft2).abs() > ASTERN_RELATIVE_BEARING => {
+            aircraft1.heading
+        }
+        ConflictGeometry::Overtaking => normalize_heading(aircraft1.heading + OVERTAKING_TURN),
+        // Turn 90 degrees right from bearing to other aircraft
+        ConflictGeometry::HeadOn | ConflictGeometry::Undetermined => normalize_heading(bearing_to_aircraft2 + 90.0),
+    }
 }
 
 #[cfg(test)]
@@ -168,7 +588,7 @@ mod tests {
         let aircraft1 = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0);
         let aircraft2 = AircraftState::new(10.0, 10.0, 10000.0, 180.0, 250.0);
         
-        let conflict = detect_conflict(&aircraft1, &aircraft2, 3.0, 1000.0, 300.0);
+        let conflict = detect_conflict(&aircraft1, &aircraft2, &SeparationStandards::terminal(), 300.0);
         assert_eq!(conflict.severity, ConflictSeverity::None);
     }
 
@@ -177,7 +597,133 @@ mod tests {
         let aircraft1 = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0);
         let aircraft2 = AircraftState::new(0.0, 5.0, 10000.0, 180.0, 250.0);
         
-        let conflict = detect_conflict(&aircraft1, &aircraft2, 3.0, 1000.0, 300.0);
+        let conflict = detect_conflict(&aircraft1, &aircraft2, &SeparationStandards::terminal(), 300.0);
         assert_ne!(conflict.severity, ConflictSeverity::None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_analytical_cpa_is_exact() {
+        // Head-on with a 1 nm lateral offset, closing at 500 kt
+        let aircraft1 = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0);
+        let aircraft2 = AircraftState::new(1.0, 10.0, 10000.0, 180.0, 250.0);
+
+        let cpa = closest_point_of_approach(&aircraft1, &aircraft2, 300.0);
+        assert!((cpa.time - 72.0).abs() < 1e-9);
+        assert!((cpa.distance - 1.0).abs() < 1e-9);
+
+        // 3 nm is reached when the along-track gap is sqrt(8) nm
+        let conflict = detect_conflict(&aircraft1, &aircraft2, &SeparationStandards::terminal(), 300.0);
+        assert!((conflict.time_to_conflict - (10.0 - 8f64.sqrt()) * 7.2).abs() < 1e-9);
+        assert_eq!(conflict.minimum_distance, cpa.distance);
+
+        let stepped = detect_conflict_with_step(&aircraft1, &aircraft2, 3.0, 1000.0, 300.0, 1.0);
+        // Stepping finds the conflict at the first sample inside it, never a step late
+        assert!((0.0..1.0).contains(&(stepped.time_to_conflict - conflict.time_to_conflict)));
+        assert!(stepped.minimum_distance >= cpa.distance - 1e-9);
+    }
+
+    #[test]
+    fn test_climb_through_and_level_off() {
+        // Head-on, 40 nm apart closing at 500 kt: within 3 nm from 266 s to 310 s
+        let own = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0);
+        let level = AircraftState::new(0.0, 40.0, 6000.0, 180.0, 250.0);
+        assert_eq!(detect_conflict(&own, &level, &SeparationStandards::terminal(), 400.0).severity, ConflictSeverity::None);
+
+        // Climbing 1000 fpm reaches 9000 ft after 180 s and is still climbing at the encounter
+        let climbing = level.with_vertical_rate(1000.0);
+        let conflict = detect_conflict(&own, &climbing, &SeparationStandards::terminal(), 400.0);
+        assert!((conflict.time_to_conflict - (40.0 - 3.0) * 7.2).abs() < 1e-6);
+
+        // Cleared to 9000 ft it levels off with exactly the minimum
+        let capped = detect_conflict_to_levels(&own, None, &climbing, Some(9000.0), 3.0, 1000.0, 400.0);
+        assert_eq!(capped.severity, ConflictSeverity::None);
+        let through = detect_conflict_to_levels(&own, None, &climbing, Some(14000.0), 3.0, 1000.0, 400.0);
+        assert_eq!(through.time_to_conflict, conflict.time_to_conflict);
+    }
+
+    #[test]
+    fn test_cleared_turn_into_traffic() {
+        // Opposite direction tracks 8 nm apart at the same level
+        let aircraft1 = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 300.0);
+        let aircraft2 = AircraftState::new(8.0, 30.0, 10000.0, 180.0, 300.0);
+        assert_eq!(detect_conflict(&aircraft1, &aircraft2, &SeparationStandards::terminal(), 300.0).severity, ConflictSeverity::None);
+
+        let turn = Intent::new().with_heading(30.0, None);
+        let info = detect_conflict_with_intent(&aircraft1, &turn, &aircraft2, &Intent::new(), 3.0, 1000.0, 300.0);
+        assert_ne!(info.severity, ConflictSeverity::None);
+        assert!(info.time_to_conflict > 0.0 && info.time_to_conflict < 300.0);
+
+        // The same turn is harmless with a cleared descent through 2000 ft
+        let descend = turn.with_altitude(8000.0);
+        let aircraft1 = aircraft1.with_vertical_rate(-4000.0);
+        let info = detect_conflict_with_intent(&aircraft1, &descend, &aircraft2, &Intent::new(), 3.0, 1000.0, 300.0);
+        assert_eq!(info.severity, ConflictSeverity::None);
+    }
+
+    #[test]
+    fn test_geometry_classification() {
+        let own = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0);
+        let head_on = AircraftState::new(0.5, 10.0, 10000.0, 185.0, 250.0);
+        let crossing = AircraftState::new(-8.0, 8.0, 10000.0, 90.0, 250.0);
+        let behind = AircraftState::new(0.2, -4.0, 10000.0, 10.0, 320.0);
+        let abeam = AircraftState::new(3.0, 0.0, 10000.0, 340.0, 250.0);
+
+        assert_eq!(classify_geometry(&own, &head_on), ConflictGeometry::HeadOn);
+        assert_eq!(classify_geometry(&own, &crossing), ConflictGeometry::Crossing);
+        assert_eq!(classify_geometry(&own, &behind), ConflictGeometry::Overtaking);
+        assert_eq!(classify_geometry(&behind, &own), ConflictGeometry::Overtaking);
+        assert_eq!(classify_geometry(&own, &abeam), ConflictGeometry::Crossing);
+        assert_eq!(
+            classify_geometry(&own, &AircraftState::new(0.0, 5.0, 10000.0, 0.0, 0.0)),
+            ConflictGeometry::Undetermined
+        );
+
+        let info = detect_conflict(&own, &head_on, &SeparationStandards::terminal(), 300.0);
+        assert_eq!(info.geometry, ConflictGeometry::HeadOn);
+    }
+
+    #[test]
+    fn test_avoidance_follows_geometry() {
+        let own = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0);
+
+        // Head-on: turn right
+        let head_on = AircraftState::new(0.0, 10.0, 10000.0, 180.0, 250.0);
+        assert!((calculate_avoidance_heading(&own, &head_on) - 90.0).abs() < 1e-9);
+
+        // Crossing from the left: aim behind it, turning left of its position
+        let crossing = AircraftState::new(-8.0, 8.0, 10000.0, 90.0, 250.0);
+        let heading = calculate_avoidance_heading(&own, &crossing);
+        assert!(heading > 270.0 && heading < 315.0);
+
+        // Overtaking from behind passes right; the aircraft ahead holds its heading
+        let behind = AircraftState::new(0.0, -4.0, 10000.0, 0.0, 320.0);
+        assert_eq!(calculate_avoidance_heading(&behind, &own), 30.0);
+        assert_eq!(calculate_avoidance_heading(&own, &behind), 0.0);
+    }
+
+    #[test]
+    fn test_conflict_interval() {
+        // Head-on at 500 kt closing with a 1 nm offset: inside 3 nm while the along-track gap is under sqrt(8) nm
+        let aircraft1 = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0);
+        let aircraft2 = AircraftState::new(1.0, 10.0, 10000.0, 180.0, 250.0);
+        let standards = SeparationStandards::terminal();
+
+        let interval = conflict_interval(&aircraft1, &aircraft2, &standards, 300.0).unwrap();
+        let half = 8.0_f64.sqrt() / 500.0 * 3600.0;
+        assert!((interval.start - (72.0 - half)).abs() < 1e-9);
+        assert!((interval.end - (72.0 + half)).abs() < 1e-9);
+        assert!((interval.duration - 2.0 * half).abs() < 1e-9);
+        let info = detect_conflict(&aircraft1, &aircraft2, &standards, 300.0);
+        assert!((interval.start - info.time_to_conflict).abs() < 1e-9);
+
+        let first = interval.first_segment();
+        assert!((first[1] - (10.0 - 8.0_f64.sqrt()) / 2.0).abs() < 1e-9);
+        assert_eq!(interval.segments().len(), 2 * SEGMENT_STRIDE);
+
+        // A climb out of the other's level ends the conflict early
+        let climbing = aircraft1.with_vertical_rate(1000.0);
+        let interval = conflict_interval(&climbing, &aircraft2, &standards, 300.0).unwrap();
+        assert!((interval.end - 60.0).abs() < 1e-9);
+        assert!(conflict_interval(&aircraft1, &aircraft2, &standards, 30.0).is_none());
+    }
+}
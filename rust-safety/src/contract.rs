@@ -0,0 +1,201 @@
+/*!
+ * CONTRACT MODULE
+ * Trajectory-based operations: 4D contract adherence checking
+ */
+
+use std::collections::HashMap;
+
+use crate::aircraft_types::TypeInfo;
+use crate::AircraftState;
+
+/// Distance within which a contract point counts as reached (nm)
+const CAPTURE_RADIUS: f64 = 1.0;
+
+/// Position the flight must pass within a time window (simulation seconds)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContractPoint {
+    pub x: f64,
+    pub y: f64,
+    pub earliest: f64,
+    pub latest: f64,
+}
+
+/// Ground speeds (kt) the aircraft can fly
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeedEnvelope {
+    pub min_speed: f64,
+    pub max_speed: f64,
+}
+
+impl From<TypeInfo> for SpeedEnvelope {
+    /// From approach speed up to cruise speed
+    fn from(info: TypeInfo) -> Self {
+        SpeedEnvelope {
+            min_speed: info.approach_speed(),
+            max_speed: info.cruise_speed,
+        }
+    }
+}
+
+/// Ordered contract points with progress along them
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contract4D {
+    pub points: Vec<ContractPoint>,
+    reached: usize,
+}
+
+/// Feasibility of one remaining contract point
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContractPointStatus {
+    pub index: usize,
+    /// Arrival at the current speed
+    pub estimated: f64,
+    pub earliest_feasible: f64,
+    pub latest_feasible: f64,
+    /// The window can still be met within the speed envelope
+    pub feasible: bool,
+    /// The current speed meets the window
+    pub on_schedule: bool,
+}
+
+impl Contract4D {
+    pub fn new(points: Vec<ContractPoint>) -> Self {
+        Contract4D { points, reached: 0 }
+    }
+
+    /// Index of the next point to reach
+    pub fn next_point(&self) -> usize {
+        self.reached
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.reached >= self.points.len()
+    }
+
+    /// Advance past every point the aircraft has reached
+    pub fn update(&mut self, aircraft: &AircraftState) {
+        while let Some(point) = self.points.get(self.reached) {
+            let dx = point.x - aircraft.x;
+            let dy = point.y - aircraft.y;
+            if (dx * dx + dy * dy).sqrt() > CAPTURE_RADIUS {
+                break;
+            }
+            self.reached += 1;
+        }
+    }
+
+    /// Earliest, latest and estimated arrival at each remaining point
+    ///
+    /// The flight is assumed to route direct through the remaining points.
+    pub fn check(&self, aircraft: &AircraftState, now: f64, envelope: &SpeedEnvelope) -> Vec<ContractPointStatus> {
+        let mut distance = 0.0;
+        let (mut x, mut y) = (aircraft.x, aircraft.y);
+
+        self.points[self.reached.min(self.points.len())..]
+            .iter()
+            .enumerate()
+            .map(|(offset, point)| {
+                distance += ((point.x - x).powi(2) + (point.y - y).powi(2)).sqrt();
+                x = point.x;
+                y = point.y;
+
+                let arrival = |speed: f64| if speed > 0.0 { now + distance / speed * 3600.0 } else { f64::INFINITY };
+                let earliest_feasible = arrival(envelope.max_speed);
+                let latest_feasible = arrival(envelope.min_speed);
+                let estimated = arrival(aircraft.speed);
+
+                ContractPointStatus {
+                    index: self.reached + offset,
+                    estimated,
+                    earliest_feasible,
+                    latest_feasible,
+                    feasible: earliest_feasible <= point.latest && latest_feasible >= point.earliest,
+                    on_schedule: estimated >= point.earliest && estimated <= point.latest,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Contracts attached to flights by track id
+#[derive(Debug, Clone, Default)]
+pub struct ContractRegistry {
+    contracts: HashMap<u64, Contract4D>,
+}
+
+impl ContractRegistry {
+    pub fn new() -> Self {
+        ContractRegistry::default()
+    }
+
+    pub fn attach(&mut self, track: u64, contract: Contract4D) {
+        self.contracts.insert(track, contract);
+    }
+
+    pub fn detach(&mut self, track: u64) -> Option<Contract4D> {
+        self.contracts.remove(&track)
+    }
+
+    pub fn contract(&self, track: u64) -> Option<&Contract4D> {
+        self.contracts.get(&track)
+    }
+
+    /// Record a track update and check its contract
+    pub fn verify(
+        &mut self,
+        track: u64,
+        aircraft: &AircraftState,
+        now: f64,
+        envelope: &SpeedEnvelope,
+    ) -> Option<Vec<ContractPointStatus>> {
+        let contract = self.contracts.get_mut(&track)?;
+        contract.update(aircraft);
+        Some(contract.check(aircraft, now, envelope))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract() -> Contract4D {
+        Contract4D::new(vec![
+            ContractPoint { x: 0.0, y: 30.0, earliest: 400.0, latest: 480.0 },
+            ContractPoint { x: 0.0, y: 60.0, earliest: 1000.0, latest: 1100.0 },
+        ])
+    }
+
+    #[test]
+    fn test_feasible_arrival_windows() {
+        let envelope = SpeedEnvelope { min_speed: 200.0, max_speed: 300.0 };
+        let aircraft = AircraftState::new(0.0, 0.0, 20000.0, 0.0, 250.0);
+        let status = contract().check(&aircraft, 0.0, &envelope);
+
+        // 30 nm: 360 s at 300 kt, 540 s at 200 kt, 432 s at 250 kt
+        assert!((status[0].earliest_feasible - 360.0).abs() < 1e-9);
+        assert!((status[0].latest_feasible - 540.0).abs() < 1e-9);
+        assert!(status[0].on_schedule);
+
+        // 60 nm: 720 to 1080 s feasible, 864 s as flown is early
+        assert!(status[1].feasible);
+        assert!(!status[1].on_schedule);
+
+        let slow = SpeedEnvelope { min_speed: 100.0, max_speed: 150.0 };
+        assert!(!contract().check(&aircraft, 0.0, &slow)[0].feasible);
+    }
+
+    #[test]
+    fn test_registry_tracks_progress() {
+        let envelope = SpeedEnvelope { min_speed: 200.0, max_speed: 300.0 };
+        let mut registry = ContractRegistry::new();
+        registry.attach(7, contract());
+
+        let at_first = AircraftState::new(0.0, 29.5, 20000.0, 0.0, 250.0);
+        let status = registry.verify(7, &at_first, 430.0, &envelope).unwrap();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].index, 1);
+        assert_eq!(registry.contract(7).unwrap().next_point(), 1);
+
+        assert!(registry.verify(8, &at_first, 430.0, &envelope).is_none());
+    }
+}
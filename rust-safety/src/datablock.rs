@@ -0,0 +1,164 @@
+/*!
+ * DATA BLOCK MODULE
+ * Radar label field content computed in bulk from the traffic picture
+ */
+
+use std::collections::HashMap;
+
+use crate::sector::SectorMap;
+use crate::traffic::TrafficSet;
+
+/// Altitude difference from the assignment shown as level (ft)
+const LEVEL_TOLERANCE: f64 = 200.0;
+
+/// Flight plan fields shown on a label
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LabelData {
+    pub callsign: String,
+    pub assigned_altitude: Option<f64>,
+    pub scratchpad: String,
+}
+
+/// Direction to the assigned altitude
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerticalArrow {
+    Level,
+    Climb,
+    Descend,
+}
+
+impl VerticalArrow {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            VerticalArrow::Level => "",
+            VerticalArrow::Climb => "\u{2191}",
+            VerticalArrow::Descend => "\u{2193}",
+        }
+    }
+}
+
+/// Formatted fields of one radar label
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataBlock {
+    pub track: u64,
+    pub callsign: String,
+    /// Actual altitude in hundreds of feet, `XXX` when unknown
+    pub altitude: String,
+    /// Assigned altitude in hundreds of feet, empty when level at it or unassigned
+    pub assigned: String,
+    pub arrow: VerticalArrow,
+    /// Ground speed in tens of knots
+    pub ground_speed: String,
+    pub scratchpad: String,
+    /// Receiving sector of a pending handoff
+    pub handoff: Option<String>,
+}
+
+impl DataBlock {
+    /// Label text as two lines: callsign and handoff, then altitudes, speed and scratchpad
+    pub fn lines(&self) -> [String; 2] {
+        let first = match &self.handoff {
+            Some(to) => format!("{} H{}", self.callsign, to),
+            None => self.callsign.clone(),
+        };
+        let mut second = format!("{}{}{} {}", self.altitude, self.arrow.symbol(), self.assigned, self.ground_speed);
+        if !self.scratchpad.is_empty() {
+            second.push(' ');
+            second.push_str(&self.scratchpad);
+        }
+        [first, second]
+    }
+}
+
+fn hundreds(altitude: f64) -> String {
+    format!("{:03}", (altitude / 100.0).round() as i64)
+}
+
+/// Data blocks for every track, in traffic storage order
+///
+/// Tracks without label data show their id as the callsign.
+pub fn data_blocks(traffic: &TrafficSet, labels: &HashMap<u64, LabelData>, sectors: &SectorMap) -> Vec<DataBlock> {
+    traffic
+        .iter()
+        .map(|(track, state)| {
+            let label = labels.get(&track);
+            let altitude_known = state.altitude.is_finite();
+
+            let (arrow, assigned) = match label.and_then(|l| l.assigned_altitude) {
+                Some(target) if altitude_known && target - state.altitude > LEVEL_TOLERANCE => {
+                    (VerticalArrow::Climb, hundreds(target))
+                }
+                Some(target) if altitude_known && state.altitude - target > LEVEL_TOLERANCE => {
+                    (VerticalArrow::Descend, hundreds(target))
+                }
+                Some(target) if !altitude_known => (VerticalArrow::Level, hundreds(target)),
+                _ => (VerticalArrow::Level, String::new()),
+            };
+
+            DataBlock {
+                track,
+                callsign: label.map_or_else(|| track.to_string(), |l| l.callsign.clone()),
+                altitude: if altitude_known { hundreds(state.altitude) } else { "XXX".to_string() },
+                assigned,
+                arrow,
+                ground_speed: format!("{:02}", (state.speed / 10.0).round() as i64),
+                scratchpad: label.map(|l| l.scratchpad.clone()).unwrap_or_default(),
+                handoff: sectors.pending_handoff(track).map(|h| h.to.clone()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sector::Sector;
+    use crate::AircraftState;
+
+    fn setup() -> (TrafficSet, HashMap<u64, LabelData>) {
+        let mut traffic = TrafficSet::new();
+        traffic.insert(1, AircraftState::new(0.0, 0.0, 8040.0, 90.0, 250.0)).unwrap();
+        traffic.insert(2, AircraftState::new(5.0, 0.0, 12000.0, 90.0, 318.0)).unwrap();
+        traffic.insert(3, AircraftState::new(9.0, 0.0, f64::NAN, 90.0, 120.0)).unwrap();
+
+        let mut labels = HashMap::new();
+        labels.insert(1, LabelData { callsign: "BAW12".into(), assigned_altitude: Some(11000.0), scratchpad: "ILS".into() });
+        labels.insert(2, LabelData { callsign: "EZY3".into(), assigned_altitude: Some(12100.0), scratchpad: String::new() });
+        (traffic, labels)
+    }
+
+    #[test]
+    fn test_altitude_arrow_and_speed() {
+        let (traffic, labels) = setup();
+        let blocks = data_blocks(&traffic, &labels, &SectorMap::default());
+
+        assert_eq!(blocks[0].altitude, "080");
+        assert_eq!(blocks[0].arrow, VerticalArrow::Climb);
+        assert_eq!(blocks[0].assigned, "110");
+        assert_eq!(blocks[0].ground_speed, "25");
+        assert_eq!(blocks[0].lines(), ["BAW12".to_string(), "080\u{2191}110 25 ILS".to_string()]);
+
+        // Within tolerance of the assignment
+        assert_eq!(blocks[1].arrow, VerticalArrow::Level);
+        assert_eq!(blocks[1].lines()[1], "120 32");
+
+        assert_eq!(blocks[2].callsign, "3");
+        assert_eq!(blocks[2].altitude, "XXX");
+    }
+
+    #[test]
+    fn test_handoff_indicator() {
+        let (traffic, labels) = setup();
+        let mut sectors = SectorMap::new(vec![
+            Sector::new("N", vec![(-20.0, -20.0), (20.0, -20.0), (20.0, 20.0), (-20.0, 20.0)], 0.0, 45000.0),
+            Sector::new("S", vec![(-20.0, -60.0), (20.0, -60.0), (20.0, -20.0), (-20.0, -20.0)], 0.0, 45000.0),
+        ]);
+        sectors.update_track(1, &traffic.get(1).unwrap());
+        assert!(sectors.initiate_handoff(1, "S"));
+
+        let blocks = data_blocks(&traffic, &labels, &sectors);
+        assert_eq!(blocks[0].handoff.as_deref(), Some("S"));
+        assert_eq!(blocks[0].lines()[0], "BAW12 HS");
+        assert_eq!(blocks[1].handoff, None);
+    }
+}
@@ -0,0 +1,159 @@
+/*!
+ * DISPLAY MODULE
+ * Authoritative mapping from alert severity and state to display categories
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::conflict::ConflictSeverity;
+
+/// Where an alert is in its lifecycle
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertState {
+    /// Raised and not yet acknowledged by the controller
+    Active,
+    Acknowledged,
+    /// No longer predicted, shown briefly before removal
+    Resolved,
+}
+
+/// How a front-end should render an alert
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayCategory {
+    Normal,
+    Advisory,
+    Warning,
+    Critical,
+    Acknowledged,
+    Resolved,
+}
+
+/// Category, colour (0xRRGGBB) and blinking for one alert
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlertDisplay {
+    pub category: DisplayCategory,
+    pub color: u32,
+    pub blinking: bool,
+}
+
+/// Display policy shared by every front-end
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayPolicy {
+    pub normal_color: u32,
+    pub advisory_color: u32,
+    pub warning_color: u32,
+    pub critical_color: u32,
+    pub acknowledged_color: u32,
+    pub resolved_color: u32,
+    /// Lowest severity that blinks until acknowledged
+    pub blink_from: ConflictSeverity,
+    /// Acknowledged alerts keep their severity colour instead of the acknowledged colour
+    pub acknowledged_keeps_color: bool,
+}
+
+#[wasm_bindgen]
+impl DisplayPolicy {
+    /// Green/yellow/amber/red with warnings and above blinking until acknowledged
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> DisplayPolicy {
+        DisplayPolicy {
+            normal_color: 0x00C000,
+            advisory_color: 0xFFFF00,
+            warning_color: 0xFFA500,
+            critical_color: 0xFF0000,
+            acknowledged_color: 0x00FFFF,
+            resolved_color: 0x808080,
+            blink_from: ConflictSeverity::Warning,
+            acknowledged_keeps_color: true,
+        }
+    }
+
+    /// Display for an alert of `severity` in `state`
+    ///
+    /// A severity of `None` always renders as normal traffic; only active
+    /// alerts blink.
+    pub fn display(&self, severity: ConflictSeverity, state: AlertState) -> AlertDisplay {
+        let (category, color) = match severity {
+            ConflictSeverity::None => {
+                return AlertDisplay {
+                    category: DisplayCategory::Normal,
+                    color: self.normal_color,
+                    blinking: false,
+                }
+            }
+            ConflictSeverity::Advisory => (DisplayCategory::Advisory, self.advisory_color),
+            ConflictSeverity::Warning => (DisplayCategory::Warning, self.warning_color),
+            ConflictSeverity::Critical => (DisplayCategory::Critical, self.critical_color),
+        };
+
+        match state {
+            AlertState::Active => AlertDisplay {
+                category,
+                color,
+                blinking: severity >= self.blink_from && self.blink_from > ConflictSeverity::None,
+            },
+            AlertState::Acknowledged => AlertDisplay {
+                category: DisplayCategory::Acknowledged,
+                color: if self.acknowledged_keeps_color { color } else { self.acknowledged_color },
+                blinking: false,
+            },
+            AlertState::Resolved => AlertDisplay {
+                category: DisplayCategory::Resolved,
+                color: self.resolved_color,
+                blinking: false,
+            },
+        }
+    }
+}
+
+impl Default for DisplayPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_mapping() {
+        let policy = DisplayPolicy::default();
+
+        let advisory = policy.display(ConflictSeverity::Advisory, AlertState::Active);
+        assert_eq!(advisory.category, DisplayCategory::Advisory);
+        assert!(!advisory.blinking);
+
+        let critical = policy.display(ConflictSeverity::Critical, AlertState::Active);
+        assert_eq!(critical, AlertDisplay { category: DisplayCategory::Critical, color: 0xFF0000, blinking: true });
+
+        let acknowledged = policy.display(ConflictSeverity::Critical, AlertState::Acknowledged);
+        assert_eq!(acknowledged.category, DisplayCategory::Acknowledged);
+        assert_eq!(acknowledged.color, 0xFF0000);
+        assert!(!acknowledged.blinking);
+
+        let normal = policy.display(ConflictSeverity::None, AlertState::Acknowledged);
+        assert_eq!(normal.category, DisplayCategory::Normal);
+        assert_eq!(policy.display(ConflictSeverity::Warning, AlertState::Resolved).category, DisplayCategory::Resolved);
+    }
+
+    #[test]
+    fn test_policy_overrides() {
+        let policy = DisplayPolicy {
+            blink_from: ConflictSeverity::Critical,
+            acknowledged_keeps_color: false,
+            ..DisplayPolicy::new()
+        };
+
+        assert!(!policy.display(ConflictSeverity::Warning, AlertState::Active).blinking);
+        assert!(policy.display(ConflictSeverity::Critical, AlertState::Active).blinking);
+        assert_eq!(policy.display(ConflictSeverity::Warning, AlertState::Acknowledged).color, 0x00FFFF);
+
+        let never = DisplayPolicy { blink_from: ConflictSeverity::None, ..DisplayPolicy::new() };
+        assert!(!never.display(ConflictSeverity::Critical, AlertState::Active).blinking);
+    }
+}
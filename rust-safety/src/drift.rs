@@ -0,0 +1,186 @@
+/*!
+ * DRIFT MODULE
+ * Drift-only motion for balloons and other objects carried by the wind
+ */
+
+use crate::conflict::{calculate_severity, ConflictInfo};
+use crate::separation::predict_position;
+use crate::{normalize_heading, AircraftState};
+
+/// Wind from `direction` (degrees) at `speed` (kt), from `floor` (ft) upwards
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindLayer {
+    pub floor: f64,
+    pub direction: f64,
+    pub speed: f64,
+}
+
+/// Wind by altitude band; calm when no layer is defined
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindField {
+    layers: Vec<WindLayer>,
+}
+
+impl WindField {
+    pub fn new() -> Self {
+        WindField::default()
+    }
+
+    pub fn uniform(direction: f64, speed: f64) -> Self {
+        WindField::new().with_layer(0.0, direction, speed)
+    }
+
+    pub fn with_layer(mut self, floor: f64, direction: f64, speed: f64) -> Self {
+        self.layers.push(WindLayer { floor, direction, speed });
+        self.layers.sort_by(|a, b| a.floor.total_cmp(&b.floor));
+        self
+    }
+
+    /// Wind (direction from, speed) at an altitude; below every layer the lowest applies
+    pub fn wind_at(&self, altitude: f64) -> (f64, f64) {
+        self.layers
+            .iter()
+            .rev()
+            .find(|layer| layer.floor <= altitude)
+            .or(self.layers.first())
+            .map_or((0.0, 0.0), |layer| (layer.direction, layer.speed))
+    }
+}
+
+/// Object with no airspeed of its own, moving with the wind
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DriftObject {
+    pub x: f64,
+    pub y: f64,
+    pub altitude: f64,
+    /// Climb (positive) or descent rate in ft/min
+    pub vertical_rate: f64,
+    /// Altitude at which the climb or descent stops
+    pub target_altitude: f64,
+}
+
+impl DriftObject {
+    pub fn new(x: f64, y: f64, altitude: f64) -> Self {
+        DriftObject {
+            x,
+            y,
+            altitude,
+            vertical_rate: 0.0,
+            target_altitude: altitude,
+        }
+    }
+
+    pub fn with_vertical_rate(mut self, vertical_rate: f64, target_altitude: f64) -> Self {
+        self.vertical_rate = vertical_rate;
+        self.target_altitude = target_altitude;
+        self
+    }
+
+    /// Equivalent state for the traffic set: heading and speed are the wind's track
+    pub fn state(&self, wind: &WindField) -> AircraftState {
+        let (direction, speed) = wind.wind_at(self.altitude);
+        AircraftState::new(self.x, self.y, self.altitude, normalize_heading(direction + 180.0), speed)
+    }
+
+    /// Position after `time` seconds, drifting with the wind at each altitude passed through
+    pub fn predict(&self, wind: &WindField, time: f64) -> DriftObject {
+        let mut object = *self;
+        let mut elapsed = 0.0;
+        while elapsed < time {
+            let step = (time - elapsed).min(1.0);
+            object = object.advance(wind, step);
+            elapsed += step;
+        }
+        object
+    }
+
+    fn advance(&self, wind: &WindField, dt: f64) -> DriftObject {
+        let moved = predict_position(&self.state(wind), dt);
+
+        let climb = self.vertical_rate / 60.0 * dt;
+        let altitude = if self.vertical_rate >= 0.0 {
+            (self.altitude + climb).min(self.target_altitude.max(self.altitude))
+        } else {
+            (self.altitude + climb).max(self.target_altitude.min(self.altitude))
+        };
+
+        DriftObject {
+            x: moved.x,
+            y: moved.y,
+            altitude,
+            ..*self
+        }
+    }
+}
+
+/// Probe an aircraft on its current track against a drifting object
+pub fn probe_drift_conflict(
+    object: &DriftObject,
+    wind: &WindField,
+    aircraft: &AircraftState,
+    horizontal_separation: f64,
+    vertical_separation: f64,
+    look_ahead_time: f64,
+) -> ConflictInfo {
+    let mut drifter = *object;
+    let mut min_distance = f64::INFINITY;
+    let mut conflict_time = -1.0;
+    let mut time = 0.0;
+
+    while time <= look_ahead_time {
+        let own = predict_position(aircraft, time);
+        let dx = own.x - drifter.x;
+        let dy = own.y - drifter.y;
+        let horizontal = (dx * dx + dy * dy).sqrt();
+        let vertical = (own.altitude - drifter.altitude).abs();
+
+        min_distance = min_distance.min(horizontal);
+        if horizontal < horizontal_separation && vertical < vertical_separation && conflict_time < 0.0 {
+            conflict_time = time;
+        }
+
+        drifter = drifter.advance(wind, 1.0);
+        time += 1.0;
+    }
+
+    let severity = calculate_severity(conflict_time, min_distance, horizontal_separation);
+    ConflictInfo::new(severity, conflict_time, min_distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conflict::ConflictSeverity;
+
+    #[test]
+    fn test_drift_with_layered_wind() {
+        // Westerly 36 kt up to 5000 ft, northerly 72 kt above
+        let wind = WindField::uniform(270.0, 36.0).with_layer(5000.0, 0.0, 72.0);
+        assert_eq!(wind.wind_at(100.0), (270.0, 36.0));
+        assert_eq!(wind.wind_at(9000.0), (0.0, 72.0));
+
+        let balloon = DriftObject::new(0.0, 0.0, 4000.0).with_vertical_rate(600.0, 6000.0);
+        assert_eq!(balloon.state(&wind).heading, 90.0);
+
+        // 100 s drifting east while climbing, then 200 s southward at the target altitude
+        let later = balloon.predict(&wind, 300.0);
+        assert_eq!(later.altitude, 6000.0);
+        assert!((later.x - 1.0).abs() < 0.01);
+        assert!((later.y + 4.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_probe_against_drifting_balloon() {
+        let wind = WindField::uniform(270.0, 20.0);
+        let balloon = DriftObject::new(10.0, 0.0, 5000.0);
+
+        let crossing = AircraftState::new(10.5, -10.0, 5000.0, 0.0, 240.0);
+        let info = probe_drift_conflict(&balloon, &wind, &crossing, 1.0, 500.0, 300.0);
+        assert!(info.time_to_conflict > 0.0);
+        assert_ne!(info.severity, ConflictSeverity::None);
+
+        let above = AircraftState::new(10.5, -10.0, 8000.0, 0.0, 240.0);
+        let info = probe_drift_conflict(&balloon, &wind, &above, 1.0, 500.0, 300.0);
+        assert_eq!(info.time_to_conflict, -1.0);
+    }
+}
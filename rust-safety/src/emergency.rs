@@ -0,0 +1,243 @@
+/*!
+ * EMERGENCY MODULE
+ * Priority diversion routing and the interventions it requires
+ */
+
+use crate::airport::{Airport, AirportDatabase};
+use crate::separation::predict_position;
+use crate::terrain::TerrainGrid;
+use crate::AircraftState;
+
+/// Descent gradient flown towards the diversion airport (ft per nm, about 3 degrees)
+const DESCENT_GRADIENT: f64 = 300.0;
+
+/// Height above the airport at which the direct profile ends (ft)
+const PATTERN_HEIGHT: f64 = 1500.0;
+
+/// Traffic that must be moved to clear the emergency aircraft's path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Intervention {
+    pub track: u64,
+    pub time_to_conflict: f64,
+    pub horizontal_distance: f64,
+    pub vertical_distance: f64,
+}
+
+/// Suggested direct diversion with the interventions it needs, most urgent first
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmergencyRouting {
+    pub airport: String,
+    pub heading: f64,
+    pub distance: f64,
+    pub time_to_airport: f64,
+    pub interventions: Vec<Intervention>,
+}
+
+/// Predicted position on a direct descending route to an airport
+pub fn direct_diversion_state(aircraft: &AircraftState, airport: &Airport, time: f64) -> AircraftState {
+    let distance = airport.distance_from(aircraft.x, aircraft.y);
+    let speed = aircraft.speed / 3600.0;
+    let time = if speed > 0.0 { time.min(distance / speed) } else { 0.0 };
+
+    let direct = AircraftState {
+        heading: diversion_heading(aircraft, airport),
+        ..*aircraft
+    };
+    let mut state = predict_position(&direct, time);
+
+    let remaining = (distance - speed * time).max(0.0);
+    let profile = airport.elevation + PATTERN_HEIGHT + DESCENT_GRADIENT * remaining;
+    state.altitude = aircraft.altitude.min(profile);
+    state
+}
+
+fn diversion_heading(aircraft: &AircraftState, airport: &Airport) -> f64 {
+    airport.bearing_from(aircraft.x, aircraft.y)
+}
+
+/// Route an emergency aircraft direct to the nearest suitable airport
+///
+/// Only airports with a runway of at least `min_runway_length` (ft) are
+/// considered.
+/// The aircraft flies direct, descending on a 3 degree profile to pattern
+/// height. Every traffic aircraft (on its current track) that would lose
+/// separation with it along the way is listed, soonest conflict first.
+pub fn suggest_emergency_routing(
+    aircraft: &AircraftState,
+    airports: &AirportDatabase,
+    min_runway_length: f64,
+    traffic: &[(u64, AircraftState)],
+    horizontal_separation: f64,
+    vertical_separation: f64,
+) -> Option<EmergencyRouting> {
+    let nearest = airports.nearest_suitable(aircraft.x, aircraft.y, min_runway_length, f64::INFINITY);
+    let airport = airports.get(&nearest.first()?.name)?;
+
+    let distance = airport.distance_from(aircraft.x, aircraft.y);
+    let time_to_airport = if aircraft.speed > 0.0 {
+        distance / aircraft.speed * 3600.0
+    } else {
+        f64::INFINITY
+    };
+
+    let horizon = time_to_airport.min(3600.0);
+    let mut interventions = Vec::new();
+
+    for &(track, other) in traffic {
+        let mut time = 0.0;
+        while time <= horizon {
+            let own = direct_diversion_state(aircraft, airport, time);
+            let theirs = predict_position(&other, time);
+
+            let dx = own.x - theirs.x;
+            let dy = own.y - theirs.y;
+            let horizontal_distance = (dx * dx + dy * dy).sqrt();
+            let vertical_distance = (own.altitude - theirs.altitude).abs();
+
+            if horizontal_distance < horizontal_separation && vertical_distance < vertical_separation {
+                interventions.push(Intervention {
+                    track,
+                    time_to_conflict: time,
+                    horizontal_distance,
+                    vertical_distance,
+                });
+                break;
+            }
+            time += 1.0;
+        }
+    }
+
+    interventions.sort_by(|a, b| {
+        a.time_to_conflict
+            .total_cmp(&b.time_to_conflict)
+            .then(a.track.cmp(&b.track))
+    });
+
+    Some(EmergencyRouting {
+        airport: airport.name.clone(),
+        heading: diversion_heading(aircraft, airport),
+        distance,
+        time_to_airport,
+        interventions,
+    })
+}
+
+/// Terrain check of an emergency descent profile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DescentAdvisory {
+    pub safe: bool,
+    /// Time the direct profile first infringes terrain clearance
+    pub first_violation: Option<f64>,
+    /// Lowest altitude (ft, rounded up to 100 ft) to hold until the terrain is
+    /// passed; the current altitude when the profile is clear
+    pub initial_safe_altitude: f64,
+}
+
+/// Check that a direct descending diversion keeps `clearance` above terrain
+///
+/// Terrain within `lateral_buffer` nm of the path counts, so the check is
+/// tolerant of small track deviations.
+pub fn check_emergency_descent(
+    aircraft: &AircraftState,
+    airport: &Airport,
+    terrain: &TerrainGrid,
+    clearance: f64,
+    lateral_buffer: f64,
+) -> DescentAdvisory {
+    let distance = airport.distance_from(aircraft.x, aircraft.y);
+    let duration = if aircraft.speed > 0.0 {
+        distance / aircraft.speed * 3600.0
+    } else {
+        0.0
+    };
+
+    let mut first_violation = None;
+    let mut required: f64 = 0.0;
+    let mut time = 0.0;
+
+    while time <= duration {
+        let state = direct_diversion_state(aircraft, airport, time);
+        let minimum = terrain.max_elevation_near(state.x, state.y, lateral_buffer) + clearance;
+        if state.altitude < minimum {
+            first_violation.get_or_insert(time);
+            required = required.max(minimum);
+        }
+        time += 1.0;
+    }
+
+    let initial_safe_altitude = if first_violation.is_some() {
+        (required / 100.0).ceil() * 100.0
+    } else {
+        aircraft.altitude
+    };
+
+    DescentAdvisory {
+        safe: first_violation.is_none(),
+        first_violation,
+        initial_safe_altitude,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn airports() -> AirportDatabase {
+        let mut db = AirportDatabase::new();
+        db.insert(Airport::new("NEAR", 0.0, -20.0, 500.0).with_runway(8000.0));
+        db.insert(Airport::new("FAR", 50.0, 50.0, 0.0).with_runway(10000.0));
+        db.insert(Airport::new("SHORT", 0.0, -5.0, 0.0).with_runway(2000.0));
+        db
+    }
+
+    #[test]
+    fn test_routes_to_nearest_airport_with_descent() {
+        let aircraft = AircraftState::new(0.0, 0.0, 20000.0, 90.0, 300.0);
+        let routing = suggest_emergency_routing(&aircraft, &airports(), 6000.0, &[], 3.0, 1000.0).unwrap();
+
+        assert_eq!(routing.airport, "NEAR");
+        assert_eq!(routing.heading, 180.0);
+        assert_eq!(routing.time_to_airport, 240.0);
+
+        // On the 3 degree profile 10 nm out: 500 + 1500 + 3000 ft
+        let midway = direct_diversion_state(&aircraft, airports().get("NEAR").unwrap(), 120.0);
+        assert!((midway.altitude - 5000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_interventions_ranked_by_urgency() {
+        let aircraft = AircraftState::new(0.0, 0.0, 8000.0, 90.0, 300.0);
+        let traffic = vec![
+            (1, AircraftState::new(0.0, -15.0, 2500.0, 0.0, 0.0)),
+            (2, AircraftState::new(0.5, -3.0, 8000.0, 0.0, 0.0)),
+            (3, AircraftState::new(30.0, 30.0, 8000.0, 0.0, 0.0)),
+        ];
+
+        let routing = suggest_emergency_routing(&aircraft, &airports(), 6000.0, &traffic, 3.0, 1000.0).unwrap();
+        let tracks: Vec<u64> = routing.interventions.iter().map(|i| i.track).collect();
+        assert_eq!(tracks, vec![2, 1]);
+    }
+
+    #[test]
+    fn test_descent_over_terrain() {
+        // 4500 ft ridge in the 1 nm band from 4 to 5 nm south of the origin
+        let mut elevations = vec![0.0; 3 * 30];
+        for column in 0..3 {
+            elevations[15 * 3 + column] = 4500.0;
+        }
+        let terrain = TerrainGrid::new(-1.5, -30.0, 1.0, 3, 30, elevations).unwrap();
+        let airports = airports();
+        let near = airports.get("NEAR").unwrap();
+
+        // The direct profile crosses the ridge near 3650 ft, below the 5500 ft needed
+        let aircraft = AircraftState::new(0.0, 0.0, 6000.0, 180.0, 300.0);
+        let advisory = check_emergency_descent(&aircraft, near, &terrain, 1000.0, 0.0);
+        assert!(!advisory.safe);
+        assert_eq!(advisory.initial_safe_altitude, 5500.0);
+
+        let flat = TerrainGrid::new(-1.5, -30.0, 1.0, 3, 30, vec![0.0; 3 * 30]).unwrap();
+        let advisory = check_emergency_descent(&aircraft, near, &flat, 1000.0, 0.0);
+        assert!(advisory.safe);
+        assert_eq!(advisory.initial_safe_altitude, 6000.0);
+    }
+}
@@ -0,0 +1,174 @@
+/*!
+ * ENGINE MODULE
+ * Stateful conflict alerting with hot-reloadable configuration
+ */
+
+use std::collections::BTreeMap;
+
+use crate::config::{ConfigError, SafetyConfig};
+use crate::conflict::ConflictInfo;
+use crate::filter::DisplayFilter;
+use crate::sweep::ConflictCache;
+use crate::traffic::TrafficSet;
+use crate::AircraftState;
+
+/// Alerts raised, escalated and cleared by an update or config change, as track id pairs
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlertChange {
+    pub added: Vec<(u64, u64)>,
+    /// Alerts kept with a higher severity than before
+    pub escalated: Vec<(u64, u64)>,
+    pub removed: Vec<(u64, u64)>,
+}
+
+impl AlertChange {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.escalated.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Active conflict alerts for a traffic set under one config
+#[derive(Debug, Clone)]
+pub struct AlertEngine {
+    config: SafetyConfig,
+    cache: ConflictCache,
+    alerts: BTreeMap<(u64, u64), ConflictInfo>,
+    filter: DisplayFilter,
+}
+
+impl AlertEngine {
+    pub fn new(config: SafetyConfig) -> Result<Self, ConfigError> {
+        config.validate()?;
+        Ok(AlertEngine {
+            config,
+            cache: ConflictCache::new(),
+            alerts: BTreeMap::new(),
+            filter: DisplayFilter::new(),
+        })
+    }
+
+    pub fn config(&self) -> &SafetyConfig {
+        &self.config
+    }
+
+    /// Active alerts keyed by (lower, higher) track id
+    pub fn alerts(&self) -> &BTreeMap<(u64, u64), ConflictInfo> {
+        &self.alerts
+    }
+
+    pub fn cache_mut(&mut self) -> &mut ConflictCache {
+        &mut self.cache
+    }
+
+    pub fn display_filter(&self) -> &DisplayFilter {
+        &self.filter
+    }
+
+    pub fn set_display_filter(&mut self, filter: DisplayFilter) {
+        self.filter = filter;
+    }
+
+    /// Everything to draw this frame: traffic passing the display filter
+    ///
+    /// Aircraft in an active alert are always included so a filter can
+    /// never hide a conflict.
+    pub fn frame(&self, traffic: &TrafficSet) -> Vec<(u64, AircraftState)> {
+        traffic
+            .iter()
+            .filter(|(id, state)| {
+                self.filter.matches(state)
+                    || self.alerts.keys().any(|&(a, b)| a == *id || b == *id)
+            })
+            .collect()
+    }
+
+    /// Sweep the traffic and replace the active alerts
+    pub fn update(&mut self, traffic: &TrafficSet) -> AlertChange {
+        let report = traffic.sweep(&self.config, None, &mut self.cache);
+
+        let mut alerts = BTreeMap::new();
+        for conflict in &report.conflicts {
+            if let (Some(a), Some(b)) = (traffic.id_at(conflict.first), traffic.id_at(conflict.second)) {
+                alerts.insert((a.min(b), a.max(b)), conflict.info);
+            }
+        }
+
+        let change = AlertChange {
+            added: alerts.keys().filter(|k| !self.alerts.contains_key(k)).copied().collect(),
+            escalated: alerts
+                .iter()
+                .filter(|(k, info)| self.alerts.get(k).is_some_and(|old| info.severity > old.severity))
+                .map(|(k, _)| *k)
+                .collect(),
+            removed: self.alerts.keys().filter(|k| !alerts.contains_key(k)).copied().collect(),
+        };
+        self.alerts = alerts;
+        change
+    }
+
+    /// Swap in a new config and re-evaluate every alert under it
+    ///
+    /// An invalid config is rejected and leaves the engine untouched. Tracks
+    /// and the alert set carry over, so the returned change lists only the
+    /// alerts the new config adds or removes.
+    pub fn reload(&mut self, config: SafetyConfig, traffic: &TrafficSet) -> Result<AlertChange, ConfigError> {
+        config.validate()?;
+        self.config = config;
+        Ok(self.update(traffic))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AircraftState;
+
+    fn traffic() -> TrafficSet {
+        let mut traffic = TrafficSet::new();
+        // Head-on pair passing 4 nm apart laterally
+        traffic.insert(1, AircraftState::new(0.0, 0.0, 12000.0, 90.0, 250.0)).unwrap();
+        traffic.insert(2, AircraftState::new(20.0, 4.0, 12000.0, 270.0, 250.0)).unwrap();
+        traffic.insert(3, AircraftState::new(-40.0, 40.0, 12000.0, 0.0, 250.0)).unwrap();
+        traffic
+    }
+
+    #[test]
+    fn test_reload_reports_alert_changes() {
+        let traffic = traffic();
+        let mut engine = AlertEngine::new(SafetyConfig::default()).unwrap();
+        assert!(engine.update(&traffic).is_empty());
+
+        let change = engine.reload(SafetyConfig::new(5.0, 1000.0, 300.0, 1.0), &traffic).unwrap();
+        assert_eq!(change.added, vec![(1, 2)]);
+        assert!(engine.alerts().contains_key(&(1, 2)));
+
+        let change = engine.reload(SafetyConfig::default(), &traffic).unwrap();
+        assert_eq!(change.removed, vec![(1, 2)]);
+    }
+
+    #[test]
+    fn test_frame_applies_filter_but_keeps_alerts() {
+        let traffic = traffic();
+        let mut engine = AlertEngine::new(SafetyConfig::new(5.0, 1000.0, 300.0, 1.0)).unwrap();
+        engine.update(&traffic);
+        engine.set_display_filter(DisplayFilter::new().with_range(0.0, 0.0, 10.0));
+
+        let ids: Vec<u64> = engine.frame(&traffic).iter().map(|(id, _)| *id).collect();
+        // Track 2 is outside the range but in conflict with track 1
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_invalid_reload_keeps_state() {
+        let traffic = traffic();
+        let wide = SafetyConfig::new(5.0, 1000.0, 300.0, 1.0);
+        let mut engine = AlertEngine::new(wide.clone()).unwrap();
+        engine.update(&traffic);
+
+        let inverted = SafetyConfig::default().with_alert_hysteresis(0.2, 0.8);
+        assert!(engine.reload(inverted, &traffic).is_err());
+        assert_eq!(engine.config(), &wide);
+        assert_eq!(engine.alerts().len(), 1);
+    }
+}
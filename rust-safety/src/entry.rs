@@ -0,0 +1,249 @@
+/*!
+ * ENTRY MODULE
+ * Operating area entry and exit events for strip creation and deletion
+ */
+
+use std::collections::HashMap;
+
+use crate::area::{AreaShape, OperatingArea};
+use crate::separation::predict_position;
+use crate::AircraftState;
+
+/// Extrapolation step when searching for a polygon area entry (seconds)
+const ENTRY_STEP: f64 = 1.0;
+
+/// Change in a track's relation to the operating area
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AreaEvent {
+    /// Outside and predicted to enter at `entry_time` (seconds)
+    Inbound { track: u64, entry_time: f64 },
+    /// No longer predicted to enter
+    InboundCancelled { track: u64 },
+    Entered { track: u64, time: f64 },
+    Exited { track: u64, time: f64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AreaStatus {
+    Outside,
+    Inbound(f64),
+    Inside,
+}
+
+/// Seconds until `aircraft` enters `area` on its present track, if within `look_ahead`
+///
+/// Zero when it is already inside. Circles are solved exactly; polygons are
+/// searched in `ENTRY_STEP` increments.
+pub fn predict_entry(aircraft: &AircraftState, area: &OperatingArea, look_ahead: f64) -> Option<f64> {
+    if let AreaShape::Circle { x, y, radius } = area.shape {
+        return circle_entry(aircraft, (x, y, radius), (area.floor, area.ceiling), look_ahead);
+    }
+
+    let mut time = 0.0;
+    while time <= look_ahead {
+        let state = predict_position(aircraft, time);
+        if area.contains(state.x, state.y, state.altitude) {
+            return Some(time);
+        }
+        time += ENTRY_STEP;
+    }
+    None
+}
+
+/// First time within `[0, look_ahead]` that a straight, constant-rate track is
+/// inside both the circle and the altitude limits
+fn circle_entry(aircraft: &AircraftState, circle: (f64, f64, f64), limits: (f64, f64), look_ahead: f64) -> Option<f64> {
+    let (cx, cy, radius) = circle;
+    let (dx, dy) = (aircraft.x - cx, aircraft.y - cy);
+    if !(dx.is_finite() && dy.is_finite()) {
+        return None;
+    }
+
+    // Horizontal: |d + v t| <= radius
+    let (vx, vy) = atc_safety_core::velocity(&(*aircraft).into());
+    let a = vx * vx + vy * vy;
+    let b = 2.0 * (dx * vx + dy * vy);
+    let c = dx * dx + dy * dy - radius * radius;
+    let (h_start, h_end) = if a < 1e-12 {
+        if c > 0.0 {
+            return None;
+        }
+        (f64::NEG_INFINITY, f64::INFINITY)
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        ((-b - root) / (2.0 * a), (-b + root) / (2.0 * a))
+    };
+
+    // Vertical: floor <= altitude + rate t <= ceiling; a primary-only target is judged on position alone
+    let (floor, ceiling) = limits;
+    let rate = aircraft.vertical_rate / 60.0;
+    let (v_start, v_end) = if aircraft.altitude.is_nan() {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    } else if rate == 0.0 {
+        if aircraft.altitude < floor || aircraft.altitude > ceiling {
+            return None;
+        }
+        (f64::NEG_INFINITY, f64::INFINITY)
+    } else {
+        let (to_floor, to_ceiling) = ((floor - aircraft.altitude) / rate, (ceiling - aircraft.altitude) / rate);
+        (to_floor.min(to_ceiling), to_floor.max(to_ceiling))
+    };
+
+    let start = h_start.max(v_start).max(0.0);
+    let end = h_end.min(v_end).min(look_ahead);
+    (start <= end).then_some(start)
+}
+
+/// Tracks each aircraft's position relative to the operating area
+///
+/// A track first seen inside counts as entering, so every track inside
+/// has had exactly one `Entered` event since it was last outside.
+#[derive(Debug, Clone)]
+pub struct AreaMonitor {
+    area: OperatingArea,
+    /// How far ahead inbound traffic is announced (seconds)
+    pub look_ahead: f64,
+    status: HashMap<u64, AreaStatus>,
+}
+
+impl AreaMonitor {
+    pub fn new(area: OperatingArea, look_ahead: f64) -> Self {
+        AreaMonitor {
+            area,
+            look_ahead,
+            status: HashMap::new(),
+        }
+    }
+
+    pub fn area(&self) -> &OperatingArea {
+        &self.area
+    }
+
+    /// Record a new position for `track` at `time`, returning what changed
+    ///
+    /// A track leaving the area is recorded as outside even if it is already
+    /// predicted to come back, so its `Inbound` follows on the next update.
+    pub fn update_track(&mut self, track: u64, aircraft: &AircraftState, time: f64) -> Option<AreaEvent> {
+        let previous = self.status.get(&track).copied();
+        let current = if self.area.contains(aircraft.x, aircraft.y, aircraft.altitude) {
+            AreaStatus::Inside
+        } else {
+            predict_entry(aircraft, &self.area, self.look_ahead)
+                .map_or(AreaStatus::Outside, |t| AreaStatus::Inbound(time + t))
+        };
+        let exited = previous == Some(AreaStatus::Inside) && current != AreaStatus::Inside;
+        self.status.insert(track, if exited { AreaStatus::Outside } else { current });
+
+        match (previous, current) {
+            (Some(AreaStatus::Inside), AreaStatus::Inside) => None,
+            (_, AreaStatus::Inside) => Some(AreaEvent::Entered { track, time }),
+            (Some(AreaStatus::Inside), _) => Some(AreaEvent::Exited { track, time }),
+            (Some(AreaStatus::Inbound(_)), AreaStatus::Inbound(_)) => None,
+            (_, AreaStatus::Inbound(entry_time)) => Some(AreaEvent::Inbound { track, entry_time }),
+            (Some(AreaStatus::Inbound(_)), AreaStatus::Outside) => Some(AreaEvent::InboundCancelled { track }),
+            (_, AreaStatus::Outside) => None,
+        }
+    }
+
+    /// Latest predicted entry time of an inbound track
+    pub fn entry_time(&self, track: u64) -> Option<f64> {
+        match self.status.get(&track) {
+            Some(AreaStatus::Inbound(time)) => Some(*time),
+            _ => None,
+        }
+    }
+
+    pub fn is_inside(&self, track: u64) -> bool {
+        self.status.get(&track) == Some(&AreaStatus::Inside)
+    }
+
+    /// Forget a dropped track without raising an event
+    pub fn remove_track(&mut self, track: u64) {
+        self.status.remove(&track);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inbound_entry_and_exit() {
+        let mut monitor = AreaMonitor::new(OperatingArea::circle(50.0), 600.0);
+        // 360 kt towards the boundary from 60 nm: 10 nm in 100 s
+        let inbound = AircraftState::new(-60.0, 0.0, 20000.0, 90.0, 360.0);
+
+        assert_eq!(
+            monitor.update_track(1, &inbound, 1000.0),
+            Some(AreaEvent::Inbound { track: 1, entry_time: 1100.0 })
+        );
+        // The estimate follows each update without a new event
+        let closer = AircraftState { x: -59.0, ..inbound };
+        assert_eq!(monitor.update_track(1, &closer, 1010.0), None);
+        assert_eq!(monitor.entry_time(1), Some(1100.0));
+
+        let inside = AircraftState::new(-40.0, 0.0, 20000.0, 90.0, 360.0);
+        assert_eq!(monitor.update_track(1, &inside, 1200.0), Some(AreaEvent::Entered { track: 1, time: 1200.0 }));
+        assert!(monitor.is_inside(1));
+
+        let departed = AircraftState::new(55.0, 0.0, 20000.0, 90.0, 360.0);
+        assert_eq!(monitor.update_track(1, &departed, 2000.0), Some(AreaEvent::Exited { track: 1, time: 2000.0 }));
+        assert_eq!(monitor.update_track(1, &departed, 2001.0), None);
+    }
+
+    #[test]
+    fn test_exit_then_inbound_again() {
+        let mut monitor = AreaMonitor::new(OperatingArea::circle(50.0), 600.0);
+        monitor.update_track(3, &AircraftState::new(-45.0, 0.0, 20000.0, 270.0, 360.0), 0.0);
+
+        // Leaves the area already turning back: the exit first, the inbound on the next update
+        let turning = AircraftState::new(-50.5, 0.0, 20000.0, 90.0, 360.0);
+        assert_eq!(monitor.update_track(3, &turning, 10.0), Some(AreaEvent::Exited { track: 3, time: 10.0 }));
+        assert_eq!(monitor.entry_time(3), None);
+        let event = monitor.update_track(3, &AircraftState { x: -50.4, ..turning }, 11.0);
+        assert!(matches!(event, Some(AreaEvent::Inbound { track: 3, entry_time }) if (entry_time - 15.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_circle_entry_matches_stepping() {
+        let area = OperatingArea::circle(50.0).with_altitude_limits(1000.0, 24000.0);
+        let step = |aircraft: &AircraftState| {
+            (0..=3600).map(f64::from).find(|&t| {
+                let state = predict_position(aircraft, t);
+                area.contains(state.x, state.y, state.altitude)
+            })
+        };
+
+        let cases = [
+            AircraftState::new(-80.0, 10.0, 20000.0, 80.0, 420.0),
+            AircraftState::new(0.0, -90.0, 30000.0, 0.0, 400.0).with_vertical_rate(-2000.0),
+            AircraftState::new(0.0, -60.0, 30000.0, 0.0, 400.0).with_vertical_rate(-1000.0),
+            AircraftState::new(70.0, 70.0, 20000.0, 0.0, 400.0),
+        ];
+        for aircraft in &cases {
+            let exact = predict_entry(aircraft, &area, 3600.0);
+            assert_eq!(exact.map(f64::ceil), step(aircraft), "{aircraft:?}");
+        }
+    }
+
+    #[test]
+    fn test_turning_away_cancels_inbound() {
+        let mut monitor = AreaMonitor::new(OperatingArea::circle(50.0), 300.0);
+        let inbound = AircraftState::new(-60.0, 0.0, 20000.0, 90.0, 360.0);
+        monitor.update_track(7, &inbound, 0.0);
+
+        let turned = AircraftState { heading: 270.0, ..inbound };
+        assert_eq!(monitor.update_track(7, &turned, 10.0), Some(AreaEvent::InboundCancelled { track: 7 }));
+        assert_eq!(monitor.entry_time(7), None);
+
+        // Beyond the look-ahead nothing is announced
+        let distant = AircraftState::new(-200.0, 0.0, 20000.0, 90.0, 360.0);
+        assert_eq!(monitor.update_track(8, &distant, 0.0), None);
+        assert!((predict_entry(&distant, monitor.area(), 3600.0).unwrap() - 1500.0).abs() < 1e-6);
+    }
+}
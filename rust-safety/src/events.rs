@@ -0,0 +1,156 @@
+/*!
+ * EVENTS MODULE
+ * Listener registry so the front end is called back instead of polling monitor results
+ */
+
+use std::fmt;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+
+use crate::conflict::ConflictSeverity;
+
+/// Category of monitor event a listener subscribes to
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MonitorEventKind {
+    /// Conflict raised or escalated
+    ConflictDetected,
+    /// Conflict no longer predicted
+    ConflictResolved,
+    /// Pair currently below the separation minima
+    SeparationViolation,
+    /// Track dropped after coasting
+    TrackDropped,
+}
+
+impl MonitorEventKind {
+    /// Category from its JavaScript name, e.g. `conflict_detected`
+    pub fn from_name(name: &str) -> Option<MonitorEventKind> {
+        match name {
+            "conflict_detected" => Some(MonitorEventKind::ConflictDetected),
+            "conflict_resolved" => Some(MonitorEventKind::ConflictResolved),
+            "separation_violation" => Some(MonitorEventKind::SeparationViolation),
+            "track_dropped" => Some(MonitorEventKind::TrackDropped),
+            _ => None,
+        }
+    }
+}
+
+/// Event passed to listeners
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorEvent {
+    pub kind: MonitorEventKind,
+    /// Simulation time of the tick (seconds)
+    pub time: f64,
+    pub first: String,
+    /// Other callsign of the pair; empty for track events
+    pub second: String,
+    /// Severity of conflict events, `None` otherwise
+    pub severity: ConflictSeverity,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    /// JavaScript function registered as a listener, called with a `MonitorEvent`
+    pub type EventCallback;
+
+    #[wasm_bindgen(method, catch, js_name = call)]
+    fn call_with(this: &EventCallback, context: &JsValue, event: JsValue) -> Result<JsValue, JsValue>;
+}
+
+impl EventCallback {
+    /// Call the function, ignoring anything it throws so other listeners still run
+    pub fn invoke(&self, event: &MonitorEvent) {
+        let _ = self.call_with(&JsValue::NULL, JsValue::from(event.clone()));
+    }
+}
+
+type Listener = Rc<dyn Fn(&MonitorEvent)>;
+
+/// Listeners by category, called in registration order
+#[derive(Clone, Default)]
+pub struct EventBus {
+    listeners: Vec<(u32, MonitorEventKind, Listener)>,
+    next_id: u32,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a listener and return the ID that removes it
+    pub fn subscribe(&mut self, kind: MonitorEventKind, listener: Listener) -> u32 {
+        self.next_id += 1;
+        self.listeners.push((self.next_id, kind, listener));
+        self.next_id
+    }
+
+    pub fn unsubscribe(&mut self, id: u32) -> bool {
+        let before = self.listeners.len();
+        self.listeners.retain(|(listener_id, _, _)| *listener_id != id);
+        self.listeners.len() != before
+    }
+
+    pub fn emit(&self, event: &MonitorEvent) {
+        for (_, kind, listener) in &self.listeners {
+            if *kind == event.kind {
+                listener(event);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.listeners.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.listeners.is_empty()
+    }
+}
+
+impl fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventBus")
+            .field("listeners", &self.listeners.len())
+            .field("next_id", &self.next_id)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_emit_by_kind_and_unsubscribe() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let mut bus = EventBus::new();
+        let log = Rc::clone(&seen);
+        let id = bus.subscribe(
+            MonitorEventKind::TrackDropped,
+            Rc::new(move |event: &MonitorEvent| log.borrow_mut().push(event.first.clone())),
+        );
+
+        let dropped = MonitorEvent {
+            kind: MonitorEventKind::TrackDropped,
+            time: 5.0,
+            first: "BAW1".to_string(),
+            second: String::new(),
+            severity: ConflictSeverity::None,
+        };
+        bus.emit(&dropped);
+        bus.emit(&MonitorEvent { kind: MonitorEventKind::ConflictResolved, ..dropped.clone() });
+        assert_eq!(*seen.borrow(), vec!["BAW1".to_string()]);
+
+        assert!(bus.unsubscribe(id));
+        assert!(!bus.unsubscribe(id));
+        bus.emit(&dropped);
+        assert_eq!(seen.borrow().len(), 1);
+        assert_eq!(MonitorEventKind::from_name("separation_violation"), Some(MonitorEventKind::SeparationViolation));
+        assert_eq!(MonitorEventKind::from_name("landed"), None);
+    }
+}
@@ -0,0 +1,362 @@
+/*!
+ * EXPLANATION MODULE
+ * Why an alert or advisory was raised: rule, measured value and trajectory assumptions
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::config::{SafetyConfig, SeparationStandards};
+use crate::conflict::{
+    classify_severity, closest_point_of_approach, detect_conflict_to_levels, ConflictInfo, ConflictSeverity,
+    SeverityTrigger, ADVISORY_TIME,
+};
+use crate::engine::AlertEngine;
+use crate::AircraftState;
+
+/// How the aircraft were assumed to fly when the conflict was predicted
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrajectoryModel {
+    /// Present heading, speed and vertical rate held, solved in closed form
+    StraightLine,
+    /// Straight line stepped at a fixed interval
+    SampledStraightLine,
+    /// Cleared heading, level and speed followed
+    ClearedIntent,
+}
+
+/// Audit record for one conflict alert or advisory
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlertExplanation {
+    pub severity: ConflictSeverity,
+    pub trigger: SeverityTrigger,
+    /// Value the firing rule compared: seconds for time rules, nm for distance rules
+    pub measured: f64,
+    /// Value it had to be below
+    pub threshold: f64,
+    /// Seconds until separation is first lost, negative if it never is
+    pub time_to_conflict: f64,
+    pub minimum_distance: f64,
+    /// Minima applied, including any alerting buffer
+    pub horizontal_separation: f64,
+    pub vertical_separation: f64,
+    pub model: TrajectoryModel,
+    pub look_ahead_time: f64,
+    /// Prediction step (seconds), zero for closed-form prediction
+    pub time_step: f64,
+}
+
+#[wasm_bindgen]
+impl AlertExplanation {
+    /// Stable name of the rule that fired
+    pub fn rule(&self) -> String {
+        let level = match self.severity {
+            ConflictSeverity::None => "none",
+            ConflictSeverity::Advisory => "advisory",
+            ConflictSeverity::Warning => "warning",
+            ConflictSeverity::Critical => "critical",
+        };
+        match self.trigger {
+            SeverityTrigger::None => "no_alert".to_string(),
+            SeverityTrigger::TimeToConflict => format!("{}_time_to_conflict", level),
+            SeverityTrigger::MinimumDistance => format!("{}_minimum_distance", level),
+        }
+    }
+
+    /// Readable account for a controller or tuning log
+    pub fn message(&self) -> String {
+        let prediction = format!(
+            "{:?} prediction over {:.0} s against {:.1} nm / {:.0} ft",
+            self.model, self.look_ahead_time, self.horizontal_separation, self.vertical_separation
+        );
+        match self.trigger {
+            SeverityTrigger::None => format!("No alert: {}", prediction),
+            SeverityTrigger::TimeToConflict => format!(
+                "{:?}: separation lost in {:.0} s, below {:.0} s; {}",
+                self.severity, self.measured, self.threshold, prediction
+            ),
+            SeverityTrigger::MinimumDistance => format!(
+                "{:?}: minimum distance {:.2} nm, below {:.2} nm; {}",
+                self.severity, self.measured, self.threshold, prediction
+            ),
+        }
+    }
+}
+
+/// Explain a conflict prediction made against `standards`
+pub fn explain_conflict(
+    info: &ConflictInfo,
+    standards: &SeparationStandards,
+    model: TrajectoryModel,
+    look_ahead_time: f64,
+    time_step: f64,
+) -> AlertExplanation {
+    let basis = classify_severity(info.time_to_conflict, info.minimum_distance, standards.horizontal);
+    AlertExplanation {
+        severity: basis.severity,
+        trigger: basis.trigger,
+        measured: basis.measured,
+        threshold: basis.threshold,
+        time_to_conflict: info.time_to_conflict,
+        minimum_distance: info.minimum_distance,
+        horizontal_separation: standards.horizontal,
+        vertical_separation: standards.vertical,
+        model,
+        look_ahead_time,
+        time_step,
+    }
+}
+
+/// Explain a conflict found by the sweep under `config`
+pub fn explain_sweep_conflict(info: &ConflictInfo, config: &SafetyConfig) -> AlertExplanation {
+    explain_conflict(info, &config.alert_standards(), TrajectoryModel::StraightLine, config.look_ahead_time, 0.0)
+}
+
+impl AlertEngine {
+    /// Explanation of the active alert between two tracks, in either order
+    pub fn explain(&self, a: u64, b: u64) -> Option<AlertExplanation> {
+        let info = self.alerts().get(&(a.min(b), a.max(b)))?;
+        Some(explain_sweep_conflict(info, self.config()))
+    }
+}
+
+/// One condition of the alerting rule
+///
+/// An alert needs separation lost within the look-ahead, i.e. horizontal and
+/// vertical distance below the minima together before the look-ahead ends,
+/// and then either the time rule or the distance rule of `classify_severity`.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AlertCondition {
+    /// Closest approach below the horizontal minimum; also the distance rule
+    HorizontalDistance,
+    /// Vertical distance below the minimum while horizontally close
+    VerticalDistance,
+    /// Horizontal minimum lost within the look-ahead
+    LookAheadTime,
+    /// Separation lost soon enough for the time rule; not needed when the distance rule fires
+    AdvisoryTime,
+}
+
+/// How close one condition came to firing
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConditionMargin {
+    pub condition: AlertCondition,
+    pub measured: f64,
+    /// Value `measured` must be below for the condition to fire
+    pub threshold: f64,
+    pub fired: bool,
+}
+
+impl ConditionMargin {
+    fn new(condition: AlertCondition, measured: f64, threshold: f64) -> Self {
+        ConditionMargin {
+            condition,
+            measured,
+            threshold,
+            fired: measured < threshold,
+        }
+    }
+
+    /// Distance from firing as a fraction of the threshold; negative once fired
+    pub fn relative_margin(&self) -> f64 {
+        (self.measured - self.threshold) / self.threshold
+    }
+}
+
+/// Margins of a pair against every alerting condition
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SuppressionReport {
+    /// Whether the sweep alerts on the pair, as decided by `detect_conflict_to_levels`
+    pub alerted: bool,
+    pub horizontal: ConditionMargin,
+    pub vertical: ConditionMargin,
+    pub look_ahead: ConditionMargin,
+    pub advisory_time: ConditionMargin,
+    /// Unfired condition holding the alert back that is furthest from firing, relative to its threshold
+    pub furthest: Option<AlertCondition>,
+}
+
+impl SuppressionReport {
+    pub fn margins(&self) -> [ConditionMargin; 4] {
+        [self.horizontal, self.vertical, self.look_ahead, self.advisory_time]
+    }
+}
+
+/// Why a pair did or did not alert under `config`
+///
+/// `alerted` and the closest approach come from the same closed-form
+/// `detect_conflict_to_levels` the sweep runs against the alerting minima.
+/// Vertical distance is taken over the time the pair is horizontally within
+/// the minimum, or at closest approach if it never is. The advisory time is
+/// measured to the loss of separation, or to the horizontal loss if there is
+/// none, and only holds the alert back when the distance rule does not fire
+/// either. Conditions that cannot be measured, such as the time of a loss
+/// that never happens, read as infinite and are never reported as `furthest`.
+pub fn explain_suppression(aircraft1: &AircraftState, aircraft2: &AircraftState, config: &SafetyConfig) -> SuppressionReport {
+    let standards = config.alert_standards();
+    let look_ahead_time = config.look_ahead_time;
+    let info = detect_conflict_to_levels(
+        aircraft1,
+        None,
+        aircraft2,
+        None,
+        standards.horizontal,
+        standards.vertical,
+        look_ahead_time,
+    );
+    let window = atc_safety_core::horizontal_distance_window(
+        &(*aircraft1).into(),
+        &(*aircraft2).into(),
+        standards.horizontal,
+    );
+
+    let (from, to) = match window {
+        Some((start, end)) if start <= look_ahead_time => (start, end.min(look_ahead_time)),
+        _ => {
+            let cpa = closest_point_of_approach(aircraft1, aircraft2, look_ahead_time);
+            (cpa.time, cpa.time)
+        }
+    };
+    let vertical = minimum_vertical_distance(aircraft1, aircraft2, from, to);
+    let entry = window.map_or(f64::INFINITY, |(start, _)| start);
+    let loss = if info.time_to_conflict >= 0.0 { info.time_to_conflict } else { entry };
+
+    let horizontal = ConditionMargin::new(AlertCondition::HorizontalDistance, info.minimum_distance, standards.horizontal);
+    let vertical = ConditionMargin::new(AlertCondition::VerticalDistance, vertical, standards.vertical);
+    let look_ahead = ConditionMargin::new(AlertCondition::LookAheadTime, entry, look_ahead_time);
+    let advisory_time = ConditionMargin::new(AlertCondition::AdvisoryTime, loss, ADVISORY_TIME);
+
+    let alerted = info.severity != ConflictSeverity::None;
+    // The time rule only matters when the distance rule cannot rate the loss
+    let blocking = |m: &ConditionMargin| {
+        !m.fired && m.measured.is_finite() && !(m.condition == AlertCondition::AdvisoryTime && horizontal.fired)
+    };
+    let furthest = [horizontal, vertical, look_ahead, advisory_time]
+        .into_iter()
+        .filter(|m| !alerted && blocking(m))
+        .max_by(|a, b| a.relative_margin().total_cmp(&b.relative_margin()))
+        .map(|m| m.condition);
+
+    SuppressionReport {
+        alerted,
+        horizontal,
+        vertical,
+        look_ahead,
+        advisory_time,
+        furthest,
+    }
+}
+
+/// Smallest vertical distance between `from` and `to` seconds at constant vertical rates
+fn minimum_vertical_distance(aircraft1: &AircraftState, aircraft2: &AircraftState, from: f64, to: f64) -> f64 {
+    let gap = aircraft1.altitude - aircraft2.altitude;
+    let rate = (aircraft1.vertical_rate - aircraft2.vertical_rate) / 60.0;
+    let at = |t: f64| gap + rate * t;
+    if rate != 0.0 {
+        let crossing = -gap / rate;
+        if crossing > from && crossing < to {
+            return 0.0;
+        }
+    }
+    at(from).abs().min(at(to).abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conflict::detect_conflict;
+    use crate::traffic::TrafficSet;
+    use crate::AircraftState;
+
+    #[test]
+    fn test_explanation_names_rule_and_values() {
+        let standards = SeparationStandards::terminal();
+        // Head-on at 600 kt closure, 10 nm apart: 3 nm lost after 42 s
+        let a = AircraftState::new(0.0, 0.0, 10000.0, 90.0, 300.0);
+        let b = AircraftState::new(10.0, 0.0, 10000.0, 270.0, 300.0);
+        let info = detect_conflict(&a, &b, &standards, 120.0);
+
+        let explanation = explain_conflict(&info, &standards, TrajectoryModel::StraightLine, 120.0, 0.0);
+        assert_eq!(explanation.severity, info.severity);
+        assert_eq!(explanation.trigger, SeverityTrigger::MinimumDistance);
+        assert_eq!(explanation.rule(), "critical_minimum_distance");
+        assert_eq!(explanation.threshold, 1.5);
+        assert!(explanation.measured < 1e-6);
+        assert!(explanation.message().starts_with("Critical: minimum distance"));
+
+        let none = ConflictInfo::new(ConflictSeverity::None, -1.0, 8.0);
+        let clear = explain_conflict(&none, &standards, TrajectoryModel::StraightLine, 120.0, 0.0);
+        assert_eq!(clear.rule(), "no_alert");
+    }
+
+    #[test]
+    fn test_engine_alerts_are_explained() {
+        let mut traffic = TrafficSet::new();
+        traffic.insert(1, AircraftState::new(0.0, 0.0, 12000.0, 90.0, 250.0)).unwrap();
+        traffic.insert(2, AircraftState::new(20.0, 2.0, 12000.0, 270.0, 250.0)).unwrap();
+
+        let config = SafetyConfig::default().with_buffer(0.5);
+        let mut engine = AlertEngine::new(config.clone()).unwrap();
+        engine.update(&traffic);
+
+        let explanation = engine.explain(2, 1).unwrap();
+        assert_eq!(explanation.severity, engine.alerts()[&(1, 2)].severity);
+        assert_eq!(explanation.horizontal_separation, config.alert_distance());
+        assert_eq!(explanation.model, TrajectoryModel::StraightLine);
+        assert_eq!(explanation.time_step, 0.0);
+        assert!(engine.explain(1, 3).is_none());
+    }
+
+    #[test]
+    fn test_suppression_names_furthest_condition() {
+        let config = SafetyConfig::default();
+        // Head-on with 2000 ft between them: only the vertical minimum holds
+        let a = AircraftState::new(0.0, 0.0, 10000.0, 90.0, 300.0);
+        let b = AircraftState::new(20.0, 0.0, 12000.0, 270.0, 300.0);
+
+        let report = explain_suppression(&a, &b, &config);
+        assert!(!report.alerted);
+        assert!(report.horizontal.fired && report.look_ahead.fired && report.advisory_time.fired);
+        assert_eq!(report.vertical.measured, 2000.0);
+        assert_eq!(report.furthest, Some(AlertCondition::VerticalDistance));
+
+        // Same level but passing 3.5 nm apart: missed by half a mile
+        let offset = AircraftState::new(20.0, 3.5, 10000.0, 270.0, 300.0);
+        let report = explain_suppression(&a, &offset, &config);
+        assert_eq!(report.furthest, Some(AlertCondition::HorizontalDistance));
+        assert!((report.horizontal.measured - 3.5).abs() < 1e-9);
+        assert_eq!(report.look_ahead.measured, f64::INFINITY);
+
+        let conflict = AircraftState::new(20.0, 0.0, 10000.0, 270.0, 300.0);
+        let report = explain_suppression(&a, &conflict, &config);
+        assert!(report.alerted);
+        assert_eq!(report.furthest, None);
+    }
+
+    #[test]
+    fn test_distance_rule_alerts_beyond_advisory_time() {
+        let config = SafetyConfig::default();
+        // Head-on at the same level 40 nm apart: separation lost after 222 s, past the advisory time
+        let a = AircraftState::new(0.0, 0.0, 10000.0, 90.0, 300.0);
+        let b = AircraftState::new(40.0, 0.0, 10000.0, 270.0, 300.0);
+        let standards = config.alert_standards();
+        let info = detect_conflict(&a, &b, &standards, config.look_ahead_time);
+        assert_eq!(info.severity, ConflictSeverity::Critical);
+        assert!((info.time_to_conflict - 222.0).abs() < 1e-6);
+
+        let report = explain_suppression(&a, &b, &config);
+        assert!(report.alerted);
+        assert!(!report.advisory_time.fired);
+        assert!(report.horizontal.fired);
+        assert_eq!(report.furthest, None);
+    }
+}
@@ -0,0 +1,126 @@
+/*!
+ * FILTER MODULE
+ * Display filters applied engine-side before traffic is handed to a view
+ */
+
+use crate::aircraft_types::WakeCategory;
+use crate::traffic::TrafficSet;
+use crate::AircraftState;
+
+/// Altitude band, range ring and category filter for one view
+///
+/// Every limit is optional; an empty filter passes all traffic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayFilter {
+    pub altitude_band: Option<(f64, f64)>,
+    /// Centre x, centre y and radius (nm)
+    pub range: Option<(f64, f64, f64)>,
+    pub wake_categories: Option<Vec<WakeCategory>>,
+    /// Keep primary-only targets, whose altitude is unknown, outside the band check
+    pub show_primary: bool,
+}
+
+impl DisplayFilter {
+    pub fn new() -> Self {
+        DisplayFilter {
+            altitude_band: None,
+            range: None,
+            wake_categories: None,
+            show_primary: true,
+        }
+    }
+
+    pub fn with_altitude_band(mut self, floor: f64, ceiling: f64) -> Self {
+        self.altitude_band = Some((floor, ceiling));
+        self
+    }
+
+    pub fn with_range(mut self, x: f64, y: f64, radius: f64) -> Self {
+        self.range = Some((x, y, radius));
+        self
+    }
+
+    pub fn with_wake_categories(mut self, categories: Vec<WakeCategory>) -> Self {
+        self.wake_categories = Some(categories);
+        self
+    }
+
+    pub fn with_primary(mut self, show: bool) -> Self {
+        self.show_primary = show;
+        self
+    }
+
+    /// True if an aircraft passes every limit; an unknown category passes the category check
+    pub fn matches(&self, aircraft: &AircraftState) -> bool {
+        if let Some((floor, ceiling)) = self.altitude_band {
+            let in_band = aircraft.altitude >= floor && aircraft.altitude <= ceiling;
+            let primary = aircraft.altitude.is_nan() && self.show_primary;
+            if !in_band && !primary {
+                return false;
+            }
+        }
+
+        if let Some((x, y, radius)) = self.range {
+            if ((aircraft.x - x).powi(2) + (aircraft.y - y).powi(2)).sqrt() > radius {
+                return false;
+            }
+        }
+
+        match (&self.wake_categories, aircraft.wake) {
+            (Some(categories), Some(wake)) => categories.contains(&wake),
+            _ => true,
+        }
+    }
+
+    /// Aircraft passing the filter, in traffic storage order
+    pub fn apply(&self, traffic: &TrafficSet) -> Vec<(u64, AircraftState)> {
+        traffic.iter().filter(|(_, state)| self.matches(state)).collect()
+    }
+}
+
+impl Default for DisplayFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_band_range_and_category() {
+        let filter = DisplayFilter::new()
+            .with_altitude_band(5000.0, 15000.0)
+            .with_range(0.0, 0.0, 20.0)
+            .with_wake_categories(vec![WakeCategory::Heavy, WakeCategory::Super]);
+
+        let aircraft = AircraftState::new(3.0, 4.0, 10000.0, 0.0, 250.0);
+        assert!(filter.matches(&aircraft.with_wake(WakeCategory::Heavy)));
+        assert!(filter.matches(&aircraft));
+        assert!(!filter.matches(&aircraft.with_wake(WakeCategory::Light)));
+        assert!(!filter.matches(&AircraftState { altitude: 20000.0, ..aircraft }));
+        assert!(!filter.matches(&AircraftState { x: 30.0, ..aircraft }));
+
+        let primary = AircraftState { altitude: f64::NAN, ..aircraft };
+        assert!(filter.matches(&primary));
+        assert!(!filter.clone().with_primary(false).matches(&primary));
+    }
+
+    #[test]
+    fn test_apply_to_traffic() {
+        let mut traffic = TrafficSet::new();
+        traffic.insert(1, AircraftState::new(0.0, 0.0, 3000.0, 0.0, 180.0)).unwrap();
+        traffic.insert(2, AircraftState::new(0.0, 0.0, 9000.0, 0.0, 250.0)).unwrap();
+        traffic.insert(3, AircraftState::new(0.0, 0.0, 9000.0, 0.0, 250.0).with_wake(WakeCategory::Light)).unwrap();
+
+        let visible = DisplayFilter::new().with_altitude_band(5000.0, 15000.0).apply(&traffic);
+        assert_eq!(visible.len(), 2);
+        assert_eq!(visible[0].0, 2);
+        assert_eq!(DisplayFilter::new().apply(&traffic).len(), 3);
+
+        // The category stored with the state is filtered on
+        let heavy = DisplayFilter::new().with_wake_categories(vec![WakeCategory::Heavy]).apply(&traffic);
+        assert_eq!(heavy.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![1, 2]);
+    }
+}
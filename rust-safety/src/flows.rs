@@ -0,0 +1,146 @@
+/*!
+ * FLOWS MODULE
+ * Extraction of major traffic flows from recorded trajectories
+ */
+
+use crate::{normalize_heading, AircraftState};
+
+/// Major flow between an entry and exit point
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrafficFlow {
+    pub entry: (f64, f64),
+    pub exit: (f64, f64),
+    pub count: usize,
+    /// Median cruising level of the flow (ft, nearest 1000)
+    pub typical_level: f64,
+    /// Mean ground speed (kt)
+    pub typical_speed: f64,
+}
+
+impl TrafficFlow {
+    /// Direct heading from entry to exit
+    pub fn heading(&self) -> f64 {
+        normalize_heading((self.exit.0 - self.entry.0).atan2(self.exit.1 - self.entry.1).to_degrees())
+    }
+
+    /// State for a synthetic aircraft entering on this flow
+    pub fn spawn_state(&self) -> AircraftState {
+        AircraftState::new(self.entry.0, self.entry.1, self.typical_level, self.heading(), self.typical_speed)
+    }
+}
+
+#[derive(Debug)]
+struct Cluster {
+    entry: (f64, f64),
+    exit: (f64, f64),
+    levels: Vec<f64>,
+    speed_sum: f64,
+}
+
+fn within(a: (f64, f64), b: (f64, f64), radius: f64) -> bool {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt() <= radius
+}
+
+/// Cluster trajectories whose entry and exit points both lie within `radius` nm
+///
+/// Each flow's entry and exit are the mean of its members'. Flows with
+/// fewer than `min_count` trajectories are dropped; the rest are returned
+/// busiest first.
+pub fn extract_flows(trajectories: &[Vec<AircraftState>], radius: f64, min_count: usize) -> Vec<TrafficFlow> {
+    let mut clusters: Vec<Cluster> = Vec::new();
+
+    for track in trajectories {
+        let (first, last) = match (track.first(), track.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => continue,
+        };
+        let entry = (first.x, first.y);
+        let exit = (last.x, last.y);
+        let level = track.iter().map(|s| s.altitude).fold(f64::MIN, f64::max);
+        let speed = track.iter().map(|s| s.speed).sum::<f64>() / track.len() as f64;
+
+        match clusters
+            .iter_mut()
+            .find(|c| within(c.entry, entry, radius) && within(c.exit, exit, radius))
+        {
+            Some(cluster) => {
+                let n = cluster.levels.len() as f64;
+                cluster.entry = ((cluster.entry.0 * n + entry.0) / (n + 1.0), (cluster.entry.1 * n + entry.1) / (n + 1.0));
+                cluster.exit = ((cluster.exit.0 * n + exit.0) / (n + 1.0), (cluster.exit.1 * n + exit.1) / (n + 1.0));
+                cluster.levels.push(level);
+                cluster.speed_sum += speed;
+            }
+            None => clusters.push(Cluster {
+                entry,
+                exit,
+                levels: vec![level],
+                speed_sum: speed,
+            }),
+        }
+    }
+
+    let mut flows: Vec<TrafficFlow> = clusters
+        .into_iter()
+        .filter(|c| c.levels.len() >= min_count.max(1))
+        .map(|mut c| {
+            c.levels.sort_by(f64::total_cmp);
+            let median = c.levels[c.levels.len() / 2];
+            TrafficFlow {
+                entry: c.entry,
+                exit: c.exit,
+                count: c.levels.len(),
+                typical_level: (median / 1000.0).round() * 1000.0,
+                typical_speed: c.speed_sum / c.levels.len() as f64,
+            }
+        })
+        .collect();
+
+    flows.sort_by_key(|f| std::cmp::Reverse(f.count));
+    flows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(from: (f64, f64), to: (f64, f64), level: f64, speed: f64) -> Vec<AircraftState> {
+        (0..=10)
+            .map(|i| {
+                let f = i as f64 / 10.0;
+                let x = from.0 + (to.0 - from.0) * f;
+                let y = from.1 + (to.1 - from.1) * f;
+                AircraftState::new(x, y, level, 0.0, speed)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_clusters_by_entry_and_exit() {
+        let trajectories = vec![
+            track((-50.0, 0.0), (50.0, 0.0), 33000.0, 450.0),
+            track((-49.0, 1.0), (51.0, -1.0), 35000.0, 470.0),
+            track((-50.5, -0.5), (49.5, 0.5), 34800.0, 460.0),
+            track((0.0, -50.0), (0.0, 50.0), 12000.0, 280.0),
+            vec![],
+        ];
+
+        let flows = extract_flows(&trajectories, 3.0, 1);
+        assert_eq!(flows.len(), 2);
+        assert_eq!(flows[0].count, 3);
+        assert_eq!(flows[0].typical_level, 35000.0);
+        assert!((flows[0].typical_speed - 460.0).abs() < 1e-9);
+        assert!((flows[0].entry.0 + 49.83).abs() < 0.01);
+
+        assert_eq!(extract_flows(&trajectories, 3.0, 2).len(), 1);
+    }
+
+    #[test]
+    fn test_spawn_state_follows_flow() {
+        let flows = extract_flows(&[track((0.0, -50.0), (0.0, 50.0), 12000.0, 280.0)], 3.0, 1);
+        let spawn = flows[0].spawn_state();
+        assert_eq!((spawn.x, spawn.y), (0.0, -50.0));
+        assert_eq!(spawn.heading, 0.0);
+        assert_eq!(spawn.altitude, 12000.0);
+        assert_eq!(spawn.speed, 280.0);
+    }
+}
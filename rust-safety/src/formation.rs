@@ -0,0 +1,227 @@
+/*!
+ * FORMATION MODULE
+ * Formation flights shown as one track, with join-up and split
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::state::StateHistory;
+use crate::sweep::ConflictCache;
+use crate::traffic::{CapacityError, TrafficSet};
+use crate::AircraftState;
+
+/// Reason a join or split was refused
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormationError {
+    UnknownTrack(u64),
+    NotInFormation(u64),
+    Capacity(CapacityError),
+}
+
+impl fmt::Display for FormationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormationError::UnknownTrack(track) => write!(f, "unknown track {}", track),
+            FormationError::NotInFormation(track) => write!(f, "track {} is not in a formation", track),
+            FormationError::Capacity(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for FormationError {}
+
+/// Lead track and the elements flying with it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Formation {
+    pub lead: u64,
+    pub elements: Vec<u64>,
+}
+
+/// Formations in the traffic picture with per-track history
+///
+/// Elements are removed from the `TrafficSet` while in formation and share
+/// the lead's states in their history, so a split continues each element's
+/// history without a gap.
+#[derive(Debug, Clone)]
+pub struct FormationRegistry {
+    formations: HashMap<u64, Formation>,
+    histories: HashMap<u64, StateHistory>,
+    history_length: usize,
+}
+
+impl FormationRegistry {
+    pub fn new(history_length: usize) -> Self {
+        FormationRegistry {
+            formations: HashMap::new(),
+            histories: HashMap::new(),
+            history_length,
+        }
+    }
+
+    /// Record a track update; elements of a lead's formation share it
+    pub fn record(&mut self, track: u64, state: AircraftState) {
+        let mut tracks = vec![track];
+        if let Some(formation) = self.formations.get(&track) {
+            tracks.extend(&formation.elements);
+        }
+
+        for id in tracks {
+            self.histories
+                .entry(id)
+                .or_insert_with(|| StateHistory::new(self.history_length))
+                .add_state(state);
+        }
+    }
+
+    pub fn history(&self, track: u64) -> Option<&StateHistory> {
+        self.histories.get(&track)
+    }
+
+    pub fn formation(&self, lead: u64) -> Option<&Formation> {
+        self.formations.get(&lead)
+    }
+
+    /// Lead of the formation a track belongs to (a lead is its own lead)
+    pub fn lead_of(&self, track: u64) -> Option<u64> {
+        if self.formations.contains_key(&track) {
+            return Some(track);
+        }
+        self.formations
+            .values()
+            .find(|f| f.elements.contains(&track))
+            .map(|f| f.lead)
+    }
+
+    /// Absorb a track into a lead's formation
+    ///
+    /// The track's symbol is removed from the traffic set; a joining lead
+    /// brings its own elements along. Cached conflict results for both are
+    /// dropped so the next sweep re-evaluates them.
+    pub fn join(
+        &mut self,
+        lead: u64,
+        track: u64,
+        traffic: &mut TrafficSet,
+        cache: &mut ConflictCache,
+    ) -> Result<(), FormationError> {
+        if !traffic.contains(lead) {
+            return Err(FormationError::UnknownTrack(lead));
+        }
+        if lead == track || traffic.remove(track).is_none() {
+            return Err(FormationError::UnknownTrack(track));
+        }
+
+        let mut joining = vec![track];
+        if let Some(absorbed) = self.formations.remove(&track) {
+            joining.extend(absorbed.elements);
+        }
+
+        self.formations
+            .entry(lead)
+            .or_insert_with(|| Formation { lead, elements: Vec::new() })
+            .elements
+            .extend(joining);
+
+        cache.invalidate(lead);
+        cache.invalidate(track);
+        Ok(())
+    }
+
+    /// Split an element off a formation as its own track at `state`
+    ///
+    /// Returns the new track's state version; its conflicts and the lead's
+    /// are re-evaluated on the next sweep.
+    pub fn split(
+        &mut self,
+        lead: u64,
+        element: u64,
+        state: AircraftState,
+        traffic: &mut TrafficSet,
+        cache: &mut ConflictCache,
+    ) -> Result<u64, FormationError> {
+        let formation = self
+            .formations
+            .get_mut(&lead)
+            .ok_or(FormationError::NotInFormation(lead))?;
+        let position = formation
+            .elements
+            .iter()
+            .position(|&e| e == element)
+            .ok_or(FormationError::NotInFormation(element))?;
+
+        let version = traffic.insert(element, state).map_err(FormationError::Capacity)?;
+        formation.elements.remove(position);
+        if formation.elements.is_empty() {
+            self.formations.remove(&lead);
+        }
+
+        self.histories
+            .entry(element)
+            .or_insert_with(|| StateHistory::new(self.history_length))
+            .add_state(state);
+
+        cache.invalidate(lead);
+        cache.invalidate(element);
+        Ok(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SafetyConfig;
+
+    #[test]
+    fn test_join_and_split() {
+        let mut traffic = TrafficSet::new();
+        let mut cache = ConflictCache::new();
+        let mut formations = FormationRegistry::new(10);
+
+        let lead = AircraftState::new(0.0, 0.0, 15000.0, 90.0, 300.0);
+        traffic.insert(1, lead).unwrap();
+        traffic.insert(2, AircraftState::new(0.2, 0.0, 15000.0, 90.0, 300.0)).unwrap();
+        formations.record(2, AircraftState::new(0.1, 0.0, 15000.0, 90.0, 300.0));
+
+        formations.join(1, 2, &mut traffic, &mut cache).unwrap();
+        assert!(!traffic.contains(2));
+        assert_eq!(formations.lead_of(2), Some(1));
+
+        // Element history follows the lead while joined
+        formations.record(1, lead);
+        assert_eq!(formations.history(2).unwrap().get_latest().unwrap().x, 0.0);
+
+        let wingman = AircraftState::new(0.0, -1.0, 15000.0, 180.0, 300.0);
+        formations.split(1, 2, wingman, &mut traffic, &mut cache).unwrap();
+        assert!(traffic.contains(2));
+        assert!(formations.formation(1).is_none());
+        assert_eq!(formations.history(2).unwrap().get_previous().unwrap().x, 0.0);
+    }
+
+    #[test]
+    fn test_split_triggers_reevaluation() {
+        let mut traffic = TrafficSet::new();
+        let mut cache = ConflictCache::new();
+        let mut formations = FormationRegistry::new(10);
+        let config = SafetyConfig::default();
+
+        traffic.insert(1, AircraftState::new(0.0, 0.0, 15000.0, 0.0, 300.0)).unwrap();
+        traffic.insert(2, AircraftState::new(0.1, 0.0, 15000.0, 0.0, 300.0)).unwrap();
+        traffic.insert(3, AircraftState::new(0.0, 20.0, 15000.0, 180.0, 300.0)).unwrap();
+        formations.join(1, 2, &mut traffic, &mut cache).unwrap();
+        traffic.sweep(&config, None, &mut cache);
+
+        let element = AircraftState::new(0.5, 0.0, 15000.0, 0.0, 300.0);
+        formations.split(1, 2, element, &mut traffic, &mut cache).unwrap();
+        let report = traffic.sweep(&config, None, &mut cache);
+
+        // The lead's cached result against track 3 was dropped by the split
+        assert_eq!(report.cache_hits, 0);
+        assert!(report.conflicts.iter().any(|c| traffic.id_at(c.first) == Some(2) || traffic.id_at(c.second) == Some(2)));
+
+        assert_eq!(
+            formations.split(1, 3, element, &mut traffic, &mut cache),
+            Err(FormationError::NotInFormation(1))
+        );
+    }
+}
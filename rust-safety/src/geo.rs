@@ -0,0 +1,358 @@
+/*!
+ * GEO MODULE
+ * WGS84 positions, great-circle and ellipsoidal distances, and a local stereographic x/y frame
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::{normalize_heading, AircraftState};
+
+/// WGS84 semi-major axis (m)
+const WGS84_A: f64 = 6_378_137.0;
+
+/// WGS84 flattening
+const WGS84_F: f64 = 1.0 / 298.257_223_563;
+
+/// Mean earth radius for spherical formulas (m)
+const MEAN_RADIUS: f64 = 6_371_008.8;
+
+const METERS_PER_NM: f64 = 1852.0;
+
+/// Values per point in packed map layer buffers: latitude, longitude
+pub const GEO_POINT_STRIDE: usize = 2;
+
+/// Latitude and longitude in degrees on the WGS84 ellipsoid
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoPosition {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+#[wasm_bindgen]
+impl GeoPosition {
+    #[wasm_bindgen(constructor)]
+    pub fn new(latitude: f64, longitude: f64) -> GeoPosition {
+        GeoPosition { latitude, longitude }
+    }
+}
+
+/// Great-circle distance (nm) on a sphere of mean earth radius
+pub fn haversine_distance(from: &GeoPosition, to: &GeoPosition) -> f64 {
+    let (lat1, lat2) = (from.latitude.to_radians(), to.latitude.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (to.longitude - from.longitude).to_radians();
+    let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * MEAN_RADIUS * h.sqrt().min(1.0).asin() / METERS_PER_NM
+}
+
+/// Ellipsoidal distance (nm) by Vincenty's inverse formula
+///
+/// `None` if the iteration does not converge, which only happens for
+/// nearly antipodal points; fall back to `haversine_distance` there.
+pub fn vincenty_distance(from: &GeoPosition, to: &GeoPosition) -> Option<f64> {
+    let b = WGS84_A * (1.0 - WGS84_F);
+    let l = (to.longitude - from.longitude).to_radians();
+    let u1 = ((1.0 - WGS84_F) * from.latitude.to_radians().tan()).atan();
+    let u2 = ((1.0 - WGS84_F) * to.latitude.to_radians().tan()).atan();
+    let (sin_u1, cos_u1) = u1.sin_cos();
+    let (sin_u2, cos_u2) = u2.sin_cos();
+
+    let mut lambda = l;
+    for _ in 0..200 {
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        let sin_sigma = ((cos_u2 * sin_lambda).powi(2) + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2)).sqrt();
+        if sin_sigma == 0.0 {
+            return Some(0.0);
+        }
+        let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+        let sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+        let cos_sq_alpha = 1.0 - sin_alpha * sin_alpha;
+        // Equatorial lines have cos²α = 0
+        let cos_2sigma_m = if cos_sq_alpha == 0.0 {
+            0.0
+        } else {
+            cos_sigma - 2.0 * sin_u1 * sin_u2 / cos_sq_alpha
+        };
+        let c = WGS84_F / 16.0 * cos_sq_alpha * (4.0 + WGS84_F * (4.0 - 3.0 * cos_sq_alpha));
+        let previous = lambda;
+        lambda = l
+            + (1.0 - c)
+                * WGS84_F
+                * sin_alpha
+                * (sigma + c * sin_sigma * (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))));
+
+        if (lambda - previous).abs() < 1e-12 {
+            let u_sq = cos_sq_alpha * (WGS84_A * WGS84_A - b * b) / (b * b);
+            let a_coef = 1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+            let b_coef = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+            let delta_sigma = b_coef
+                * sin_sigma
+                * (cos_2sigma_m
+                    + b_coef / 4.0
+                        * (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))
+                            - b_coef / 6.0
+                                * cos_2sigma_m
+                                * (-3.0 + 4.0 * sin_sigma.powi(2))
+                                * (-3.0 + 4.0 * cos_2sigma_m.powi(2))));
+            return Some(b * a_coef * (sigma - delta_sigma) / METERS_PER_NM);
+        }
+    }
+    None
+}
+
+/// Initial great-circle bearing from one position to another (degrees true)
+pub fn initial_bearing(from: &GeoPosition, to: &GeoPosition) -> f64 {
+    let (lat1, lat2) = (from.latitude.to_radians(), to.latitude.to_radians());
+    let dlon = (to.longitude - from.longitude).to_radians();
+    let y = dlon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+    normalize_heading(y.atan2(x).to_degrees())
+}
+
+/// Position reached flying `distance` nm along a great circle starting on `bearing`
+pub fn destination(from: &GeoPosition, bearing: f64, distance: f64) -> GeoPosition {
+    let lat1 = from.latitude.to_radians();
+    let angle = distance * METERS_PER_NM / MEAN_RADIUS;
+    let bearing = bearing.to_radians();
+    let lat2 = (lat1.sin() * angle.cos() + lat1.cos() * angle.sin() * bearing.cos()).asin();
+    let dlon = (bearing.sin() * angle.sin() * lat1.cos()).atan2(angle.cos() - lat1.sin() * lat2.sin());
+    GeoPosition::new(lat2.to_degrees(), normalize_longitude(from.longitude + dlon.to_degrees()))
+}
+
+/// Great-circle line from one position to another in `segments` equal steps, packed latitude, longitude
+///
+/// Both ends are included, so the buffer holds `segments + 1` points.
+pub fn great_circle_points(from: &GeoPosition, to: &GeoPosition, segments: usize) -> Vec<f64> {
+    let segments = segments.max(1);
+    let (lat1, lon1) = (from.latitude.to_radians(), from.longitude.to_radians());
+    let (lat2, lon2) = (to.latitude.to_radians(), to.longitude.to_radians());
+    let angle = haversine_distance(from, to) * METERS_PER_NM / MEAN_RADIUS;
+
+    let mut points = Vec::with_capacity((segments + 1) * GEO_POINT_STRIDE);
+    for i in 0..=segments {
+        let f = i as f64 / segments as f64;
+        if angle.sin().abs() < 1e-12 {
+            // Coincident (or antipodal) ends have no unique great circle
+            let end = if f < 1.0 { from } else { to };
+            points.extend_from_slice(&[end.latitude, end.longitude]);
+            continue;
+        }
+        let a = ((1.0 - f) * angle).sin() / angle.sin();
+        let b = (f * angle).sin() / angle.sin();
+        let x = a * lat1.cos() * lon1.cos() + b * lat2.cos() * lon2.cos();
+        let y = a * lat1.cos() * lon1.sin() + b * lat2.cos() * lon2.sin();
+        let z = a * lat1.sin() + b * lat2.sin();
+        points.extend_from_slice(&[z.atan2(x.hypot(y)).to_degrees(), y.atan2(x).to_degrees()]);
+    }
+    points
+}
+
+/// Range ring of `radius` nm around a center in `points` steps, packed latitude, longitude
+///
+/// The ring starts due north, runs clockwise and is closed: the last point
+/// repeats the first.
+pub fn range_ring_points(center: &GeoPosition, radius: f64, points: usize) -> Vec<f64> {
+    arc_points(center, radius, 0.0, 360.0, points)
+}
+
+/// Clockwise arc of `radius` nm from one bearing to another in `points` steps, packed latitude, longitude
+///
+/// Equal bearings give a full circle.
+pub fn arc_points(center: &GeoPosition, radius: f64, start_bearing: f64, end_bearing: f64, points: usize) -> Vec<f64> {
+    let points = points.max(1);
+    let mut sweep = (end_bearing - start_bearing).rem_euclid(360.0);
+    if sweep == 0.0 {
+        sweep = 360.0;
+    }
+
+    let mut buffer = Vec::with_capacity((points + 1) * GEO_POINT_STRIDE);
+    for i in 0..=points {
+        let bearing = start_bearing + sweep * i as f64 / points as f64;
+        let point = destination(center, bearing, radius);
+        buffer.extend_from_slice(&[point.latitude, point.longitude]);
+    }
+    buffer
+}
+
+/// Great-circle route line through packed latitude, longitude waypoints, `segments` steps per leg
+///
+/// Shared waypoints appear once, so a route of `n` waypoints gives
+/// `(n - 1) * segments + 1` points.
+pub fn route_points(waypoints: &[f64], segments: usize) -> Result<Vec<f64>, String> {
+    if !waypoints.len().is_multiple_of(GEO_POINT_STRIDE) {
+        return Err(format!(
+            "waypoint buffer length {} is not a multiple of {}",
+            waypoints.len(),
+            GEO_POINT_STRIDE
+        ));
+    }
+    let waypoints: Vec<GeoPosition> = waypoints
+        .chunks_exact(GEO_POINT_STRIDE)
+        .map(|p| GeoPosition::new(p[0], p[1]))
+        .collect();
+
+    let mut points = Vec::new();
+    if let Some(first) = waypoints.first() {
+        points.extend_from_slice(&[first.latitude, first.longitude]);
+    }
+    for leg in waypoints.windows(2) {
+        points.extend_from_slice(&great_circle_points(&leg[0], &leg[1], segments)[GEO_POINT_STRIDE..]);
+    }
+    Ok(points)
+}
+
+fn normalize_longitude(longitude: f64) -> f64 {
+    (longitude + 540.0).rem_euclid(360.0) - 180.0
+}
+
+/// Oblique stereographic projection to the crate's x/y frame (nm, x east, y north)
+///
+/// Conformal, so angles and short distances are preserved around the
+/// origin; scale error is under 0.1% within 200 nm. The sphere has the
+/// Gaussian mean radius of the ellipsoid at the origin latitude.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StereographicProjection {
+    pub origin: GeoPosition,
+    /// Projection sphere radius (nm)
+    radius: f64,
+}
+
+#[wasm_bindgen]
+impl StereographicProjection {
+    #[wasm_bindgen(constructor)]
+    pub fn new(origin: GeoPosition) -> StereographicProjection {
+        let e2 = WGS84_F * (2.0 - WGS84_F);
+        let sin_lat = origin.latitude.to_radians().sin();
+        let w = 1.0 - e2 * sin_lat * sin_lat;
+        let radius = WGS84_A * (1.0 - e2).sqrt() / w / METERS_PER_NM;
+        StereographicProjection { origin, radius }
+    }
+
+    /// Geodetic position of a local x/y point
+    pub fn to_geo(&self, x: f64, y: f64) -> GeoPosition {
+        let rho = x.hypot(y);
+        if rho == 0.0 {
+            return self.origin;
+        }
+        let (sin_lat0, cos_lat0) = self.origin.latitude.to_radians().sin_cos();
+        let c = 2.0 * (rho / (2.0 * self.radius)).atan();
+        let (sin_c, cos_c) = c.sin_cos();
+        let latitude = (cos_c * sin_lat0 + y * sin_c * cos_lat0 / rho).asin();
+        let dlon = (x * sin_c).atan2(rho * cos_lat0 * cos_c - y * sin_lat0 * sin_c);
+        GeoPosition::new(latitude.to_degrees(), normalize_longitude(self.origin.longitude + dlon.to_degrees()))
+    }
+
+    /// Grid heading in the local frame of a true track flown at `position`
+    pub fn grid_heading(&self, position: &GeoPosition, true_track: f64) -> f64 {
+        let (x1, y1) = self.to_local(position);
+        let (x2, y2) = self.to_local(&destination(position, true_track, 0.1));
+        normalize_heading((x2 - x1).atan2(y2 - y1).to_degrees())
+    }
+
+    /// Aircraft state in the local frame from a surveillance report
+    ///
+    /// `true_track` is in degrees true and `ground_speed` in knots; the
+    /// result can go straight into `check_separation` or `detect_conflict`.
+    pub fn aircraft_state(
+        &self,
+        position: &GeoPosition,
+        altitude: f64,
+        true_track: f64,
+        ground_speed: f64,
+    ) -> AircraftState {
+        let (x, y) = self.to_local(position);
+        AircraftState::new(x, y, altitude, self.grid_heading(position, true_track), ground_speed)
+    }
+}
+
+impl StereographicProjection {
+    /// Local x/y (nm) of a geodetic position
+    pub fn to_local(&self, position: &GeoPosition) -> (f64, f64) {
+        let (sin_lat0, cos_lat0) = self.origin.latitude.to_radians().sin_cos();
+        let (sin_lat, cos_lat) = position.latitude.to_radians().sin_cos();
+        let (sin_dlon, cos_dlon) = (position.longitude - self.origin.longitude).to_radians().sin_cos();
+        let k = 2.0 * self.radius / (1.0 + sin_lat0 * sin_lat + cos_lat0 * cos_lat * cos_dlon);
+        (k * cos_lat * sin_dlon, k * (cos_lat0 * sin_lat - sin_lat0 * cos_lat * cos_dlon))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SeparationStandards;
+    use crate::separation::check_separation;
+
+    #[test]
+    fn test_distances_and_bearing() {
+        // One degree of meridian at the equator is 110574 m on WGS84
+        let equator = GeoPosition::new(0.0, 0.0);
+        let north = GeoPosition::new(1.0, 0.0);
+        assert!((vincenty_distance(&equator, &north).unwrap() - 110_574.4 / 1852.0).abs() < 0.01);
+        assert!((haversine_distance(&equator, &north) - 60.04).abs() < 0.01);
+        assert_eq!(initial_bearing(&equator, &north), 0.0);
+
+        let heathrow = GeoPosition::new(51.4700, -0.4543);
+        let kennedy = GeoPosition::new(40.6398, -73.7789);
+        let great_circle = haversine_distance(&heathrow, &kennedy);
+        let ellipsoid = vincenty_distance(&heathrow, &kennedy).unwrap();
+        assert!((great_circle - ellipsoid).abs() / ellipsoid < 0.005);
+        assert!((initial_bearing(&heathrow, &kennedy) - 288.0).abs() < 1.0);
+        assert_eq!(vincenty_distance(&kennedy, &kennedy), Some(0.0));
+    }
+
+    #[test]
+    fn test_projection_round_trip_and_separation() {
+        let projection = StereographicProjection::new(GeoPosition::new(51.47, -0.45));
+        let position = GeoPosition::new(51.9, 0.3);
+
+        let (x, y) = projection.to_local(&position);
+        let back = projection.to_geo(x, y);
+        assert!((back.latitude - position.latitude).abs() < 1e-9);
+        assert!((back.longitude - position.longitude).abs() < 1e-9);
+        assert!((x.hypot(y) - vincenty_distance(&projection.origin, &position).unwrap()).abs() < 0.05);
+
+        // Two reports 3.5 nm apart along a meridian at the same level
+        let first = projection.aircraft_state(&position, 12000.0, 90.0, 300.0);
+        let second = projection.aircraft_state(&destination(&position, 0.0, 3.5), 12000.0, 90.0, 300.0);
+        let result = check_separation(&first, &second, &SeparationStandards::terminal());
+        assert!((result.horizontal_distance - 3.5).abs() < 0.01);
+        assert!(result.is_safe);
+        assert!((first.heading - 90.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_map_layer_points() {
+        let heathrow = GeoPosition::new(51.4700, -0.4543);
+        let kennedy = GeoPosition::new(40.6398, -73.7789);
+
+        // Every interpolated point lies on the great circle
+        let line = great_circle_points(&heathrow, &kennedy, 10);
+        assert_eq!(line.len(), 11 * GEO_POINT_STRIDE);
+        assert_eq!((line[0], line[1]), (heathrow.latitude, heathrow.longitude));
+        let total = haversine_distance(&heathrow, &kennedy);
+        let point = |i: usize| GeoPosition::new(line[i * 2], line[i * 2 + 1]);
+        for i in 1..10 {
+            let via = haversine_distance(&heathrow, &point(i)) + haversine_distance(&point(i), &kennedy);
+            assert!((via - total).abs() < 1e-6);
+        }
+        assert!((point(10).latitude - kennedy.latitude).abs() < 1e-9);
+
+        let ring = range_ring_points(&heathrow, 10.0, 36);
+        assert_eq!(ring.len(), 37 * GEO_POINT_STRIDE);
+        for p in ring.chunks_exact(GEO_POINT_STRIDE) {
+            assert!((haversine_distance(&heathrow, &GeoPosition::new(p[0], p[1])) - 10.0).abs() < 1e-6);
+        }
+        assert!((ring[0] - ring[72]).abs() < 1e-9);
+
+        // Arc from 350 to 10 degrees crosses north clockwise
+        let arc = arc_points(&heathrow, 5.0, 350.0, 10.0, 2);
+        assert!((initial_bearing(&heathrow, &GeoPosition::new(arc[2], arc[3])) - 0.0).abs() < 1e-6);
+
+        let route = route_points(&[51.47, -0.4543, 52.0, 1.0, 53.0, 4.0], 4).unwrap();
+        assert_eq!(route.len(), 9 * GEO_POINT_STRIDE);
+        assert!(route_points(&[51.0], 4).is_err());
+    }
+}
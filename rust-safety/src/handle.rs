@@ -0,0 +1,149 @@
+/*!
+ * HANDLE MODULE
+ * Independent engine instances for running several exercises in one page
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::config::SafetyConfig;
+use crate::engine::AlertEngine;
+use crate::traffic::TrafficSet;
+use crate::validation::validate_state;
+use crate::AircraftState;
+
+/// One self-contained alerting engine with its own config, traffic and clock
+///
+/// The crate keeps no global state, so any number of handles can run side
+/// by side in one WASM module: each owns everything it reads and writes,
+/// and nothing one handle does is visible to another.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct EngineHandle {
+    engine: AlertEngine,
+    traffic: TrafficSet,
+    clock: f64,
+}
+
+#[wasm_bindgen]
+impl EngineHandle {
+    /// Start an engine on a copy of `config`; invalid configs are rejected
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: &SafetyConfig) -> Result<EngineHandle, String> {
+        let engine = AlertEngine::new(config.clone()).map_err(|e| e.to_string())?;
+        Ok(EngineHandle {
+            engine,
+            traffic: TrafficSet::new(),
+            clock: 0.0,
+        })
+    }
+
+    /// Add or replace a track; states failing this engine's validation are ignored
+    pub fn update_aircraft(&mut self, id: u64, state: &AircraftState) -> bool {
+        if !validate_state(state, self.engine.config().validation()) {
+            return false;
+        }
+        self.traffic.insert(id, *state).is_ok()
+    }
+
+    pub fn remove_aircraft(&mut self, id: u64) -> bool {
+        let removed = self.traffic.remove(id).is_some();
+        if removed {
+            self.engine.cache_mut().invalidate(id);
+        }
+        removed
+    }
+
+    pub fn aircraft_count(&self) -> usize {
+        self.traffic.len()
+    }
+
+    /// Exercise time of the last step (seconds)
+    pub fn clock(&self) -> f64 {
+        self.clock
+    }
+
+    /// Move this engine's clock to `time` and re-evaluate its alerts
+    ///
+    /// Returns the number of active alerts. Times earlier than the current
+    /// clock are rejected so a paused or rewound exercise cannot corrupt
+    /// another's timeline.
+    pub fn step(&mut self, time: f64) -> Result<usize, String> {
+        if !time.is_finite() || time < self.clock {
+            return Err(format!("time {} is before the engine clock {}", time, self.clock));
+        }
+        self.clock = time;
+        self.engine.update(&self.traffic);
+        Ok(self.engine.alerts().len())
+    }
+
+    /// Active alert pairs flattened as `[first, second, first, second, ...]`
+    pub fn alert_pairs(&self) -> Vec<u64> {
+        self.engine.alerts().keys().flat_map(|&(a, b)| [a, b]).collect()
+    }
+
+    /// Copy of this engine's config
+    pub fn config(&self) -> SafetyConfig {
+        self.engine.config().clone()
+    }
+
+    /// Replace this engine's config and re-evaluate its alerts; other handles are unaffected
+    pub fn reload_config(&mut self, config: &SafetyConfig) -> Result<usize, String> {
+        self.engine
+            .reload(config.clone(), &self.traffic)
+            .map_err(|e| e.to_string())?;
+        Ok(self.engine.alerts().len())
+    }
+}
+
+impl EngineHandle {
+    pub fn engine(&self) -> &AlertEngine {
+        &self.engine
+    }
+
+    pub fn traffic(&self) -> &TrafficSet {
+        &self.traffic
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Head-on pair passing 4 nm apart: an alert under 5 nm, clear under 3 nm
+    fn load(handle: &mut EngineHandle) {
+        assert!(handle.update_aircraft(1, &AircraftState::new(0.0, 0.0, 12000.0, 90.0, 250.0)));
+        assert!(handle.update_aircraft(2, &AircraftState::new(20.0, 4.0, 12000.0, 270.0, 250.0)));
+    }
+
+    #[test]
+    fn test_handles_are_independent() {
+        let mut terminal = EngineHandle::new(&SafetyConfig::default()).unwrap();
+        let mut en_route = EngineHandle::new(&SafetyConfig::new(5.0, 1000.0, 300.0, 1.0)).unwrap();
+        load(&mut terminal);
+        load(&mut en_route);
+
+        assert_eq!(terminal.step(10.0), Ok(0));
+        assert_eq!(en_route.step(500.0), Ok(1));
+        assert_eq!(en_route.alert_pairs(), vec![1, 2]);
+        assert_eq!((terminal.clock(), en_route.clock()), (10.0, 500.0));
+
+        // Reconfiguring or clearing one handle leaves the other alone
+        assert_eq!(terminal.reload_config(&SafetyConfig::new(5.0, 1000.0, 300.0, 1.0)), Ok(1));
+        assert!(en_route.remove_aircraft(2));
+        assert_eq!(en_route.step(501.0), Ok(0));
+        assert_eq!(terminal.alert_pairs(), vec![1, 2]);
+        assert_eq!(terminal.aircraft_count(), 2);
+    }
+
+    #[test]
+    fn test_rejects_invalid_input() {
+        assert!(EngineHandle::new(&SafetyConfig::new(3.0, 1000.0, 300.0, 400.0)).is_err());
+
+        let mut handle = EngineHandle::new(&SafetyConfig::default()).unwrap();
+        assert!(!handle.update_aircraft(1, &AircraftState::new(0.0, 0.0, 12000.0, 90.0, 900.0)));
+        assert_eq!(handle.aircraft_count(), 0);
+        assert!(handle.step(5.0).is_ok());
+        assert!(handle.step(4.0).is_err());
+        assert!(handle.step(f64::NAN).is_err());
+    }
+}
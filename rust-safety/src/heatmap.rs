@@ -0,0 +1,188 @@
+/*!
+ * HEATMAP MODULE
+ * Spatial statistics of conflicts and losses of separation over a session
+ */
+
+use std::fmt::Write;
+
+/// Kind of event counted in a heatmap cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeatmapEvent {
+    Conflict,
+    LossOfSeparation,
+}
+
+/// Grid of event counts per cell and altitude band
+///
+/// Cells cover `cell_size` nm from the origin; bands are `band_height` ft
+/// deep from 0 ft. Events outside the grid are counted as dropped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictHeatmap {
+    pub origin_x: f64,
+    pub origin_y: f64,
+    pub cell_size: f64,
+    pub columns: usize,
+    pub rows: usize,
+    pub band_height: f64,
+    pub bands: usize,
+    conflicts: Vec<u32>,
+    losses: Vec<u32>,
+    dropped: u32,
+}
+
+impl ConflictHeatmap {
+    pub fn new(
+        origin_x: f64,
+        origin_y: f64,
+        cell_size: f64,
+        columns: usize,
+        rows: usize,
+        band_height: f64,
+        bands: usize,
+    ) -> Self {
+        let cells = columns * rows * bands;
+        ConflictHeatmap {
+            origin_x,
+            origin_y,
+            cell_size,
+            columns,
+            rows,
+            band_height,
+            bands,
+            conflicts: vec![0; cells],
+            losses: vec![0; cells],
+            dropped: 0,
+        }
+    }
+
+    fn index(&self, x: f64, y: f64, altitude: f64) -> Option<usize> {
+        let column = ((x - self.origin_x) / self.cell_size).floor();
+        let row = ((y - self.origin_y) / self.cell_size).floor();
+        let band = (altitude / self.band_height).floor();
+        if !(column >= 0.0 && row >= 0.0 && band >= 0.0) {
+            return None;
+        }
+
+        let (column, row, band) = (column as usize, row as usize, band as usize);
+        if column >= self.columns || row >= self.rows || band >= self.bands {
+            return None;
+        }
+        Some((band * self.rows + row) * self.columns + column)
+    }
+
+    /// Count an event at a position
+    pub fn record(&mut self, event: HeatmapEvent, x: f64, y: f64, altitude: f64) {
+        match self.index(x, y, altitude) {
+            Some(i) => match event {
+                HeatmapEvent::Conflict => self.conflicts[i] += 1,
+                HeatmapEvent::LossOfSeparation => self.losses[i] += 1,
+            },
+            None => self.dropped += 1,
+        }
+    }
+
+    pub fn count(&self, event: HeatmapEvent, x: f64, y: f64, altitude: f64) -> u32 {
+        self.index(x, y, altitude).map_or(0, |i| match event {
+            HeatmapEvent::Conflict => self.conflicts[i],
+            HeatmapEvent::LossOfSeparation => self.losses[i],
+        })
+    }
+
+    /// Events that fell outside the grid
+    pub fn dropped(&self) -> u32 {
+        self.dropped
+    }
+
+    /// Counts for one altitude band as rows of columns
+    pub fn matrix(&self, event: HeatmapEvent, band: usize) -> Vec<Vec<u32>> {
+        let counts = match event {
+            HeatmapEvent::Conflict => &self.conflicts,
+            HeatmapEvent::LossOfSeparation => &self.losses,
+        };
+        if band >= self.bands {
+            return Vec::new();
+        }
+
+        let size = self.rows * self.columns;
+        counts[band * size..(band + 1) * size]
+            .chunks(self.columns.max(1))
+            .map(|row| row.to_vec())
+            .collect()
+    }
+
+    /// GeoJSON feature collection of every cell with at least one event
+    ///
+    /// Cells are polygons in the grid's nm coordinates with the band limits
+    /// and both counts as properties.
+    pub fn to_geojson(&self) -> String {
+        let mut features = Vec::new();
+        for band in 0..self.bands {
+            for row in 0..self.rows {
+                for column in 0..self.columns {
+                    let i = (band * self.rows + row) * self.columns + column;
+                    if self.conflicts[i] == 0 && self.losses[i] == 0 {
+                        continue;
+                    }
+
+                    let x0 = self.origin_x + column as f64 * self.cell_size;
+                    let y0 = self.origin_y + row as f64 * self.cell_size;
+                    let (x1, y1) = (x0 + self.cell_size, y0 + self.cell_size);
+
+                    let mut feature = String::new();
+                    let _ = write!(
+                        feature,
+                        "{{\"type\":\"Feature\",\"geometry\":{{\"type\":\"Polygon\",\"coordinates\":[[[{},{}],[{},{}],[{},{}],[{},{}],[{},{}]]]}},\
+                         \"properties\":{{\"floor\":{},\"ceiling\":{},\"conflicts\":{},\"losses\":{}}}}}",
+                        x0, y0, x1, y0, x1, y1, x0, y1, x0, y0,
+                        band as f64 * self.band_height,
+                        (band + 1) as f64 * self.band_height,
+                        self.conflicts[i],
+                        self.losses[i],
+                    );
+                    features.push(feature);
+                }
+            }
+        }
+
+        format!("{{\"type\":\"FeatureCollection\",\"features\":[{}]}}", features.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heatmap() -> ConflictHeatmap {
+        // 4x3 cells of 5 nm from (-10, -10), two 10,000 ft bands
+        let mut map = ConflictHeatmap::new(-10.0, -10.0, 5.0, 4, 3, 10000.0, 2);
+        map.record(HeatmapEvent::Conflict, 1.0, 1.0, 8000.0);
+        map.record(HeatmapEvent::Conflict, 2.0, 3.0, 9000.0);
+        map.record(HeatmapEvent::LossOfSeparation, 2.0, 3.0, 9000.0);
+        map.record(HeatmapEvent::Conflict, 1.0, 1.0, 15000.0);
+        map.record(HeatmapEvent::Conflict, 50.0, 1.0, 8000.0);
+        map
+    }
+
+    #[test]
+    fn test_counts_by_cell_and_band() {
+        let map = heatmap();
+        assert_eq!(map.count(HeatmapEvent::Conflict, 4.0, 4.0, 5000.0), 2);
+        assert_eq!(map.count(HeatmapEvent::LossOfSeparation, 4.0, 4.0, 5000.0), 1);
+        assert_eq!(map.count(HeatmapEvent::Conflict, 4.0, 4.0, 15000.0), 1);
+        assert_eq!(map.dropped(), 1);
+
+        let low = map.matrix(HeatmapEvent::Conflict, 0);
+        assert_eq!(low.len(), 3);
+        assert_eq!(low[2], vec![0, 0, 2, 0]);
+        assert!(map.matrix(HeatmapEvent::Conflict, 5).is_empty());
+    }
+
+    #[test]
+    fn test_geojson_export() {
+        let json = heatmap().to_geojson();
+        assert!(json.starts_with("{\"type\":\"FeatureCollection\""));
+        assert_eq!(json.matches("\"Feature\"").count(), 2);
+        assert!(json.contains("\"floor\":0,\"ceiling\":10000,\"conflicts\":2,\"losses\":1"));
+        assert!(json.contains("[[[0,0],[5,0],[5,5],[0,5],[0,0]]]"));
+    }
+}
@@ -0,0 +1,230 @@
+/*!
+ * HOLDING MODULE
+ * Protected holding volumes and transit conflict checks
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::separation::predict_position;
+use crate::AircraftState;
+
+/// Wind and navigation tolerance added around the nominal racetrack (nm)
+const HOLDING_BUFFER: f64 = 2.0;
+
+/// Direction of the turns in a holding pattern
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnDirection {
+    Right,
+    Left,
+}
+
+/// Maximum ICAO holding speed (kt) for an altitude (ft)
+pub fn max_holding_speed(altitude: f64) -> f64 {
+    if altitude <= 14000.0 {
+        230.0
+    } else if altitude <= 20000.0 {
+        240.0
+    } else {
+        265.0
+    }
+}
+
+/// Radius of a rate one (3 deg/s) turn at a true airspeed (nm)
+pub fn rate_one_turn_radius(speed: f64) -> f64 {
+    speed / (60.0 * std::f64::consts::PI)
+}
+
+/// Published holding pattern with the levels in use
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoldingPattern {
+    pub fix_x: f64,
+    pub fix_y: f64,
+    pub inbound_course: f64,
+    pub turn: TurnDirection,
+    pub leg_time: f64,
+    pub minimum_altitude: f64,
+    pub maximum_altitude: f64,
+}
+
+#[wasm_bindgen]
+impl HoldingPattern {
+    /// Standard one minute hold
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        fix_x: f64,
+        fix_y: f64,
+        inbound_course: f64,
+        turn: TurnDirection,
+        minimum_altitude: f64,
+        maximum_altitude: f64,
+    ) -> HoldingPattern {
+        HoldingPattern {
+            fix_x,
+            fix_y,
+            inbound_course,
+            turn,
+            leg_time: 60.0,
+            minimum_altitude,
+            maximum_altitude,
+        }
+    }
+}
+
+/// Racetrack-shaped protected volume: every point within `radius` of the
+/// segment between the two turn centres, between `floor` and `ceiling`
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoldingVolume {
+    pub inbound_center_x: f64,
+    pub inbound_center_y: f64,
+    pub outbound_center_x: f64,
+    pub outbound_center_y: f64,
+    pub radius: f64,
+    pub floor: f64,
+    pub ceiling: f64,
+}
+
+impl HoldingPattern {
+    /// Protected volume for the fastest holding speed allowed in the stack
+    pub fn protected_volume(&self) -> HoldingVolume {
+        self.protected_volume_for_speed(max_holding_speed(self.maximum_altitude))
+    }
+
+    /// Protected volume for aircraft holding at a given speed (kt)
+    pub fn protected_volume_for_speed(&self, speed: f64) -> HoldingVolume {
+        let radius = rate_one_turn_radius(speed);
+        let leg_length = speed / 3600.0 * self.leg_time;
+
+        let course = self.inbound_course.to_radians();
+        let (dir_x, dir_y) = (course.sin(), course.cos());
+        let side = match self.turn {
+            TurnDirection::Right => 1.0,
+            TurnDirection::Left => -1.0,
+        };
+        // Perpendicular towards the turn side of the inbound course
+        let (perp_x, perp_y) = (dir_y * side, -dir_x * side);
+
+        let inbound_center_x = self.fix_x + perp_x * radius;
+        let inbound_center_y = self.fix_y + perp_y * radius;
+
+        HoldingVolume {
+            inbound_center_x,
+            inbound_center_y,
+            outbound_center_x: inbound_center_x - dir_x * leg_length,
+            outbound_center_y: inbound_center_y - dir_y * leg_length,
+            radius: radius + HOLDING_BUFFER,
+            floor: self.minimum_altitude,
+            ceiling: self.maximum_altitude,
+        }
+    }
+}
+
+impl HoldingVolume {
+    /// Horizontal distance from a point to the edge of the volume (negative inside)
+    pub fn distance_to_edge(&self, x: f64, y: f64) -> f64 {
+        let (ax, ay) = (self.inbound_center_x, self.inbound_center_y);
+        let (bx, by) = (self.outbound_center_x, self.outbound_center_y);
+        let (abx, aby) = (bx - ax, by - ay);
+        let length_squared = abx * abx + aby * aby;
+
+        let t = if length_squared > 0.0 {
+            (((x - ax) * abx + (y - ay) * aby) / length_squared).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let dx = x - (ax + abx * t);
+        let dy = y - (ay + aby * t);
+        (dx * dx + dy * dy).sqrt() - self.radius
+    }
+
+    /// Vertical distance from an altitude to the volume (0 when inside its levels)
+    pub fn vertical_distance(&self, altitude: f64) -> f64 {
+        if altitude < self.floor {
+            self.floor - altitude
+        } else if altitude > self.ceiling {
+            altitude - self.ceiling
+        } else {
+            0.0
+        }
+    }
+}
+
+/// First predicted infringement of a holding volume by transiting traffic
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoldingConflict {
+    pub time: f64,
+    pub x: f64,
+    pub y: f64,
+    pub altitude: f64,
+}
+
+/// Check a transiting aircraft's predicted track against a holding stack
+///
+/// The aircraft must stay `horizontal_separation` clear of the protected
+/// area or `vertical_separation` clear of the levels in use.
+pub fn check_holding_stack(
+    aircraft: &AircraftState,
+    hold: &HoldingPattern,
+    horizontal_separation: f64,
+    vertical_separation: f64,
+    look_ahead_time: f64,
+) -> Option<HoldingConflict> {
+    let volume = hold.protected_volume();
+    let time_step = 1.0;
+    let mut time = 0.0;
+
+    while time <= look_ahead_time {
+        let state = predict_position(aircraft, time);
+        if volume.distance_to_edge(state.x, state.y) < horizontal_separation
+            && volume.vertical_distance(state.altitude) < vertical_separation
+        {
+            return Some(HoldingConflict {
+                time,
+                x: state.x,
+                y: state.y,
+                altitude: state.altitude,
+            });
+        }
+        time += time_step;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hold() -> HoldingPattern {
+        // Inbound course north to a fix at the origin, right turns, 6000-10000 ft
+        HoldingPattern::new(0.0, 0.0, 0.0, TurnDirection::Right, 6000.0, 10000.0)
+    }
+
+    #[test]
+    fn test_volume_geometry() {
+        let volume = hold().protected_volume();
+        assert_eq!(max_holding_speed(10000.0), 230.0);
+
+        // Right turns put the racetrack east of the inbound course
+        assert!(volume.inbound_center_x > 0.0);
+        assert!(volume.outbound_center_y < 0.0);
+        assert!(volume.distance_to_edge(volume.inbound_center_x, -1.0) < 0.0);
+        assert!(volume.distance_to_edge(-10.0, 0.0) > 0.0);
+    }
+
+    #[test]
+    fn test_transit_through_stack_levels() {
+        // Crossing the stack eastbound at 8000 ft
+        let aircraft = AircraftState::new(-20.0, -2.0, 8000.0, 90.0, 300.0);
+        let conflict = check_holding_stack(&aircraft, &hold(), 3.0, 1000.0, 300.0).unwrap();
+        assert!(conflict.time > 0.0 && conflict.x < 0.0);
+
+        // Same track well above the stack
+        let above = AircraftState::new(-20.0, -2.0, 12000.0, 90.0, 300.0);
+        assert!(check_holding_stack(&above, &hold(), 3.0, 1000.0, 300.0).is_none());
+    }
+}
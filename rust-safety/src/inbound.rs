@@ -0,0 +1,119 @@
+/*!
+ * INBOUND MODULE
+ * Planner list of traffic outside the operating area that is heading in
+ */
+
+use crate::area::OperatingArea;
+use crate::entry::predict_entry;
+use crate::separation::predict_position;
+use crate::AircraftState;
+
+/// Published point an entry is estimated against
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedFix {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl NamedFix {
+    pub fn new(name: &str, x: f64, y: f64) -> Self {
+        NamedFix { name: name.to_string(), x, y }
+    }
+}
+
+/// Estimated boundary entry of one inbound track
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InboundEstimate {
+    pub track: u64,
+    /// Seconds from now
+    pub entry_time: f64,
+    pub x: f64,
+    pub y: f64,
+    pub altitude: f64,
+    /// Fix closest to the entry point, if any are known
+    pub fix: Option<String>,
+    /// Distance from the entry point to `fix` (nm)
+    pub fix_distance: f64,
+}
+
+/// Fix closest to a point, with its distance
+pub fn nearest_fix(fixes: &[NamedFix], x: f64, y: f64) -> Option<(&NamedFix, f64)> {
+    fixes
+        .iter()
+        .map(|fix| (fix, ((fix.x - x).powi(2) + (fix.y - y).powi(2)).sqrt()))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Estimated entry of a track outside `area` that reaches it within `look_ahead`
+pub fn estimate_entry(
+    track: u64,
+    aircraft: &AircraftState,
+    area: &OperatingArea,
+    fixes: &[NamedFix],
+    look_ahead: f64,
+) -> Option<InboundEstimate> {
+    if area.contains(aircraft.x, aircraft.y, aircraft.altitude) {
+        return None;
+    }
+    let entry_time = predict_entry(aircraft, area, look_ahead)?;
+    let entry = predict_position(aircraft, entry_time);
+    let nearest = nearest_fix(fixes, entry.x, entry.y);
+
+    Some(InboundEstimate {
+        track,
+        entry_time,
+        x: entry.x,
+        y: entry.y,
+        altitude: entry.altitude,
+        fix: nearest.map(|(fix, _)| fix.name.clone()),
+        fix_distance: nearest.map_or(f64::INFINITY, |(_, distance)| distance),
+    })
+}
+
+/// Inbound list for a planner display, soonest entry first
+pub fn inbound_list(
+    traffic: &[(u64, AircraftState)],
+    area: &OperatingArea,
+    fixes: &[NamedFix],
+    look_ahead: f64,
+) -> Vec<InboundEstimate> {
+    let mut list: Vec<InboundEstimate> = traffic
+        .iter()
+        .filter_map(|(track, aircraft)| estimate_entry(*track, aircraft, area, fixes, look_ahead))
+        .collect();
+
+    list.sort_by(|a, b| a.entry_time.total_cmp(&b.entry_time).then(a.track.cmp(&b.track)));
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inbound_list_order_and_fixes() {
+        let area = OperatingArea::circle(50.0);
+        let fixes = vec![NamedFix::new("WEST", -50.0, 0.0), NamedFix::new("NORTH", 0.0, 50.0)];
+        let traffic = vec![
+            (1, AircraftState::new(-80.0, 0.0, 24000.0, 90.0, 360.0)),
+            (2, AircraftState::new(0.0, 60.0, 18000.0, 180.0, 360.0)),
+            // Outside and flying away
+            (3, AircraftState::new(70.0, 0.0, 30000.0, 90.0, 450.0)),
+            // Already inside
+            (4, AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0)),
+        ];
+
+        let list = inbound_list(&traffic, &area, &fixes, 900.0);
+        let tracks: Vec<u64> = list.iter().map(|e| e.track).collect();
+        assert_eq!(tracks, vec![2, 1]);
+
+        assert!((list[0].entry_time - 100.0).abs() < 1e-6);
+        assert_eq!(list[0].fix.as_deref(), Some("NORTH"));
+        assert!((list[1].entry_time - 300.0).abs() < 1e-6);
+        assert_eq!(list[1].fix.as_deref(), Some("WEST"));
+        assert!(list[1].fix_distance < 0.1);
+        assert_eq!(list[1].altitude, 24000.0);
+    }
+}
@@ -0,0 +1,220 @@
+/*!
+ * INTENT MODULE
+ * Intent inference for tracks without a flight plan
+ */
+
+use crate::config::SafetyConfig;
+use crate::separation::predict_position;
+use crate::state::StateHistory;
+use crate::{heading_difference, normalize_heading, AircraftState};
+
+/// Vertical rate below which a track counts as level (ft/min)
+const LEVEL_RATE: f64 = 300.0;
+
+/// Standard rate turn (degrees per second)
+const RATE_ONE_TURN: f64 = 3.0;
+
+/// Heading offsets flown by the maneuvering hypothesis (degrees)
+const MANEUVER_FAN: [f64; 7] = [-90.0, -60.0, -30.0, 0.0, 30.0, 60.0, 90.0];
+
+/// What an unplanned track is inferred to be doing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InferredIntent {
+    LevelCruise,
+    Climbing { target: f64 },
+    Descending { target: f64 },
+    Maneuvering,
+}
+
+/// One intent hypothesis with its probability
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntentHypothesis {
+    pub intent: InferredIntent,
+    pub probability: f64,
+    /// Observed vertical rate (ft/min)
+    pub vertical_rate: f64,
+}
+
+/// Next round thousand feet in the direction of travel, skipping one already almost reached
+fn likely_level(altitude: f64, climbing: bool) -> f64 {
+    if climbing {
+        ((altitude + LEVEL_RATE) / 1000.0).ceil() * 1000.0
+    } else {
+        ((altitude - LEVEL_RATE) / 1000.0).floor() * 1000.0
+    }
+}
+
+/// Infer an intent distribution from history sampled every `sample_interval` seconds
+///
+/// Turning shifts weight towards maneuvering; a sustained climb or descent
+/// is assumed to stop at the next round thousand feet. The probabilities
+/// sum to one; an empty history gives no hypotheses.
+pub fn infer_intent(history: &StateHistory, sample_interval: f64) -> Vec<IntentHypothesis> {
+    let states = history.states();
+    let (first, last) = match (states.first(), states.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Vec::new(),
+    };
+
+    let duration = (states.len() - 1) as f64 * sample_interval;
+    if duration <= 0.0 {
+        return vec![
+            IntentHypothesis { intent: InferredIntent::LevelCruise, probability: 0.5, vertical_rate: 0.0 },
+            IntentHypothesis { intent: InferredIntent::Maneuvering, probability: 0.5, vertical_rate: 0.0 },
+        ];
+    }
+
+    let vertical_rate = (last.altitude - first.altitude) / duration * 60.0;
+    let turning: f64 = states
+        .windows(2)
+        .map(|pair| heading_difference(pair[0].heading, pair[1].heading).abs())
+        .sum();
+    let turn_rate = turning / duration;
+
+    let maneuvering = 0.1 + 0.8 * (turn_rate / RATE_ONE_TURN).min(1.0);
+    let remaining = 1.0 - maneuvering;
+
+    let mut hypotheses = Vec::new();
+    if vertical_rate.abs() < LEVEL_RATE {
+        hypotheses.push(IntentHypothesis {
+            intent: InferredIntent::LevelCruise,
+            probability: remaining,
+            vertical_rate: 0.0,
+        });
+    } else {
+        let climbing = vertical_rate > 0.0;
+        let target = likely_level(last.altitude, climbing);
+        let intent = if climbing {
+            InferredIntent::Climbing { target }
+        } else {
+            InferredIntent::Descending { target }
+        };
+        hypotheses.push(IntentHypothesis { intent, probability: remaining * 0.8, vertical_rate });
+        hypotheses.push(IntentHypothesis {
+            intent: InferredIntent::LevelCruise,
+            probability: remaining * 0.2,
+            vertical_rate: 0.0,
+        });
+    }
+    hypotheses.push(IntentHypothesis {
+        intent: InferredIntent::Maneuvering,
+        probability: maneuvering,
+        vertical_rate: 0.0,
+    });
+
+    hypotheses
+}
+
+/// Whether an intruder following `altitude_at(time)` conflicts with `own`
+fn conflicts_along(
+    own: &AircraftState,
+    intruder: &AircraftState,
+    altitude_at: impl Fn(f64) -> f64,
+    config: &SafetyConfig,
+) -> bool {
+    let time_step = if config.time_step > 0.0 { config.time_step } else { 1.0 };
+    let mut time = 0.0;
+    while time <= config.look_ahead_time {
+        let a = predict_position(own, time);
+        let b = predict_position(intruder, time);
+        let dx = a.x - b.x;
+        let dy = a.y - b.y;
+        if (dx * dx + dy * dy).sqrt() < config.horizontal_separation
+            && (a.altitude - altitude_at(time)).abs() < config.vertical_separation
+        {
+            return true;
+        }
+        time += time_step;
+    }
+    false
+}
+
+/// Probability that an intruder conflicts with `own`, weighted over intent hypotheses
+///
+/// The maneuvering hypothesis counts the share of a fan of headings up to
+/// 90 degrees either side of the current one that lead to a conflict.
+pub fn intent_conflict_probability(
+    own: &AircraftState,
+    intruder: &AircraftState,
+    hypotheses: &[IntentHypothesis],
+    config: &SafetyConfig,
+) -> f64 {
+    hypotheses
+        .iter()
+        .map(|hypothesis| {
+            let likelihood = match hypothesis.intent {
+                InferredIntent::LevelCruise => {
+                    conflicts_along(own, intruder, |_| intruder.altitude, config).into()
+                }
+                InferredIntent::Climbing { target } => {
+                    let rate = hypothesis.vertical_rate / 60.0;
+                    let profile = |t: f64| (intruder.altitude + rate * t).min(target);
+                    conflicts_along(own, intruder, profile, config).into()
+                }
+                InferredIntent::Descending { target } => {
+                    let rate = hypothesis.vertical_rate / 60.0;
+                    let profile = |t: f64| (intruder.altitude + rate * t).max(target);
+                    conflicts_along(own, intruder, profile, config).into()
+                }
+                InferredIntent::Maneuvering => {
+                    let hits = MANEUVER_FAN
+                        .iter()
+                        .filter(|&&offset| {
+                            let turned = AircraftState {
+                                heading: normalize_heading(intruder.heading + offset),
+                                ..*intruder
+                            };
+                            conflicts_along(own, &turned, |_| intruder.altitude, config)
+                        })
+                        .count();
+                    hits as f64 / MANEUVER_FAN.len() as f64
+                }
+            };
+            hypothesis.probability * likelihood
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn climbing_history() -> StateHistory {
+        // 500 ft/min straight climb sampled every 12 s
+        let mut history = StateHistory::new(10);
+        for i in 0..5 {
+            history.add_state(AircraftState::new(0.0, i as f64 * 0.8, 10600.0 + i as f64 * 100.0, 0.0, 240.0));
+        }
+        history
+    }
+
+    #[test]
+    fn test_infers_climb_to_round_level() {
+        let hypotheses = infer_intent(&climbing_history(), 12.0);
+        assert_eq!(hypotheses[0].intent, InferredIntent::Climbing { target: 12000.0 });
+        assert!((hypotheses[0].vertical_rate - 500.0).abs() < 1e-9);
+
+        let total: f64 = hypotheses.iter().map(|h| h.probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(infer_intent(&StateHistory::new(5), 12.0).is_empty());
+    }
+
+    #[test]
+    fn test_conflict_probability_weights_hypotheses() {
+        let config = SafetyConfig::default();
+        let hypotheses = infer_intent(&climbing_history(), 12.0);
+        let intruder = *climbing_history().get_latest().unwrap();
+
+        // Head-on at 12,500 ft: only a climb to 12,000 ft (or a turn) makes it a conflict
+        let own = AircraftState::new(0.0, 20.0, 12500.0, 180.0, 240.0);
+        let probability = intent_conflict_probability(&own, &intruder, &hypotheses, &config);
+        assert!(probability > 0.5 && probability < 1.0);
+
+        let level = vec![IntentHypothesis {
+            intent: InferredIntent::LevelCruise,
+            probability: 1.0,
+            vertical_rate: 0.0,
+        }];
+        assert_eq!(intent_conflict_probability(&own, &intruder, &level, &config), 0.0);
+    }
+}
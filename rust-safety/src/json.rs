@@ -0,0 +1,243 @@
+/*!
+ * JSON MODULE
+ * Bulk JSON upload of aircraft states and download of alerts for JavaScript hosts
+ *
+ * States are an array of objects with `id`, `x`, `y`, `altitude`, `heading`
+ * and `speed`, plus optional `vertical_rate` (default 0) and `wake`
+ * (`"Light"`, `"Medium"`, `"Heavy"` or `"Super"`). A null altitude is a
+ * primary-only target. Built on `serde_json` and `serde_wasm_bindgen`, so
+ * only available with the `serde` feature.
+ */
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::aircraft_types::WakeCategory;
+use crate::conflict::{ConflictInfo, ConflictSeverity};
+use crate::handle::EngineHandle;
+use crate::AircraftState;
+
+/// Reason a JSON document could not be read
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonError {
+    /// Not valid JSON at this position (1-based)
+    Syntax { line: usize, column: usize },
+    /// Valid JSON of the wrong shape, with a description of the offending value
+    Shape(String),
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonError::Syntax { line, column } => write!(f, "invalid JSON at line {} column {}", line, column),
+            JsonError::Shape(reason) => write!(f, "unexpected value: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+impl From<serde_json::Error> for JsonError {
+    fn from(error: serde_json::Error) -> Self {
+        match error.classify() {
+            serde_json::error::Category::Data => JsonError::Shape(error.to_string()),
+            _ => JsonError::Syntax { line: error.line(), column: error.column() },
+        }
+    }
+}
+
+/// One aircraft as it appears in the JSON array
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct StateRecord {
+    /// Read as an integer so ids above 2^53 keep every digit
+    id: u64,
+    x: f64,
+    y: f64,
+    /// Null for a primary-only target
+    altitude: Option<f64>,
+    heading: f64,
+    speed: f64,
+    #[serde(default)]
+    vertical_rate: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wake: Option<WakeCategory>,
+}
+
+impl From<StateRecord> for (u64, AircraftState) {
+    fn from(record: StateRecord) -> Self {
+        let state = AircraftState {
+            wake: record.wake,
+            ..AircraftState::new(
+                record.x,
+                record.y,
+                record.altitude.unwrap_or(f64::NAN),
+                record.heading,
+                record.speed,
+            )
+            .with_vertical_rate(record.vertical_rate)
+        };
+        (record.id, state)
+    }
+}
+
+impl From<&(u64, AircraftState)> for StateRecord {
+    fn from(&(id, state): &(u64, AircraftState)) -> Self {
+        StateRecord {
+            id,
+            x: state.x,
+            y: state.y,
+            altitude: (!state.altitude.is_nan()).then_some(state.altitude),
+            heading: state.heading,
+            speed: state.speed,
+            vertical_rate: state.vertical_rate,
+            wake: state.wake,
+        }
+    }
+}
+
+/// One active alert as it appears in the JSON array; non-finite values are written as null
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+struct AlertRecord {
+    first: u64,
+    second: u64,
+    severity: ConflictSeverity,
+    time_to_conflict: f64,
+    minimum_distance: f64,
+}
+
+fn alert_records(alerts: &BTreeMap<(u64, u64), ConflictInfo>) -> Vec<AlertRecord> {
+    alerts
+        .iter()
+        .map(|(&(first, second), info)| AlertRecord {
+            first,
+            second,
+            severity: info.severity,
+            time_to_conflict: info.time_to_conflict,
+            minimum_distance: info.minimum_distance,
+        })
+        .collect()
+}
+
+/// Read an array of aircraft states keyed by track id
+pub fn states_from_json(text: &str) -> Result<Vec<(u64, AircraftState)>, JsonError> {
+    let records: Vec<StateRecord> = serde_json::from_str(text)?;
+    Ok(records.into_iter().map(Into::into).collect())
+}
+
+/// Write aircraft states in the format `states_from_json` reads
+pub fn states_to_json(states: &[(u64, AircraftState)]) -> String {
+    let records: Vec<StateRecord> = states.iter().map(StateRecord::from).collect();
+    serde_json::to_string(&records).unwrap_or_default()
+}
+
+/// Write active alerts as an array of `first`, `second`, `severity`, `time_to_conflict` and `minimum_distance`
+pub fn alerts_to_json(alerts: &BTreeMap<(u64, u64), ConflictInfo>) -> String {
+    serde_json::to_string(&alert_records(alerts)).unwrap_or_default()
+}
+
+#[wasm_bindgen]
+impl EngineHandle {
+    /// Add or replace every track in a JSON array of states; returns how many passed validation
+    pub fn load_states_json(&mut self, json: &str) -> Result<usize, String> {
+        let states = states_from_json(json).map_err(|e| e.to_string())?;
+        Ok(self.load_states(&states))
+    }
+
+    /// Active alerts as a JSON array
+    pub fn alerts_json(&self) -> String {
+        alerts_to_json(self.engine().alerts())
+    }
+
+    /// Same as `load_states_json` for an array of plain JavaScript objects
+    pub fn load_states_js(&mut self, states: JsValue) -> Result<usize, JsValue> {
+        let records: Vec<StateRecord> = serde_wasm_bindgen::from_value(states)?;
+        let states: Vec<(u64, AircraftState)> = records.into_iter().map(Into::into).collect();
+        Ok(self.load_states(&states))
+    }
+
+    /// Same as `alerts_json` as an array of plain JavaScript objects
+    pub fn alerts_js(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&alert_records(self.engine().alerts()))?)
+    }
+}
+
+impl EngineHandle {
+    fn load_states(&mut self, states: &[(u64, AircraftState)]) -> usize {
+        states
+            .iter()
+            .filter(|(id, state)| self.update_aircraft(*id, state))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SafetyConfig;
+
+    #[test]
+    fn test_states_round_trip() {
+        let text = r#"[
+            {"id": 7, "x": 1.5, "y": -2, "altitude": 12000, "heading": 90, "speed": 250, "wake": "Heavy"},
+            {"id": 8, "x": 0, "y": 0, "altitude": null, "heading": 270, "speed": 120, "vertical_rate": -500}
+        ]"#;
+        let states = states_from_json(text).unwrap();
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[0].0, 7);
+        assert_eq!(states[0].1.wake, Some(WakeCategory::Heavy));
+        assert!(states[1].1.altitude.is_nan());
+        assert_eq!(states[1].1.vertical_rate, -500.0);
+
+        let again = states_from_json(&states_to_json(&states)).unwrap();
+        assert_eq!(states_to_json(&again), states_to_json(&states));
+        assert_eq!(states_from_json("[]").unwrap().len(), 0);
+
+        // Ids beyond 2^53 are kept exactly
+        let large = r#"[{"id": 18446744073709551615, "x": 0, "y": 0, "altitude": 0, "heading": 0, "speed": 0}]"#;
+        assert_eq!(states_from_json(large).unwrap()[0].0, u64::MAX);
+        let odd = r#"[{"id": 9007199254740993, "x": 0, "y": 0, "altitude": 0, "heading": 0, "speed": 0}]"#;
+        assert_eq!(states_from_json(odd).unwrap()[0].0, 9_007_199_254_740_993);
+    }
+
+    #[test]
+    fn test_errors_and_alerts() {
+        assert_eq!(states_from_json("[{\"id\": 1,").unwrap_err(), JsonError::Syntax { line: 1, column: 10 });
+        let missing = states_from_json(r#"[{"id": 1, "x": 0, "y": 0, "altitude": 0, "heading": 0}]"#).unwrap_err();
+        assert!(matches!(missing, JsonError::Shape(reason) if reason.contains("speed")));
+        assert!(states_from_json(r#"{"id": 1}"#).is_err());
+        for id in ["-1", "1.5", "18446744073709551616"] {
+            let text = format!(r#"[{{"id": {}, "x": 0, "y": 0, "altitude": 0, "heading": 0, "speed": 0}}]"#, id);
+            assert!(matches!(states_from_json(&text), Err(JsonError::Shape(_))), "{}", id);
+        }
+        assert!(states_from_json(&"[".repeat(200_000)).is_err());
+
+        let mut alerts = BTreeMap::new();
+        alerts.insert((1, 2), ConflictInfo::new(ConflictSeverity::Warning, 45.0, f64::INFINITY));
+        assert_eq!(
+            alerts_to_json(&alerts),
+            r#"[{"first":1,"second":2,"severity":"Warning","time_to_conflict":45.0,"minimum_distance":null}]"#
+        );
+
+        // Advisory types carry the same derives for Rust hosts
+        let resolution = crate::resolution::Resolution {
+            maneuver: crate::resolution::Maneuver::Heading(30.0),
+            cost: 2.0,
+            miss_distance: 5.5,
+        };
+        let text = serde_json::to_string(&resolution).unwrap();
+        assert_eq!(serde_json::from_str::<crate::resolution::Resolution>(&text).unwrap(), resolution);
+    }
+
+    #[test]
+    fn test_handle_loads_json() {
+        let mut handle = EngineHandle::new(&SafetyConfig::default()).unwrap();
+        let json = r#"[{"id": 1, "x": 0, "y": 0, "altitude": 12000, "heading": 90, "speed": 250},
+                       {"id": 2, "x": 20, "y": 0, "altitude": 12000, "heading": 270, "speed": 900}]"#;
+        assert_eq!(handle.load_states_json(json), Ok(1));
+        assert!(handle.load_states_json("[{").is_err());
+        assert_eq!(handle.alerts_json(), "[]");
+    }
+}
@@ -0,0 +1,155 @@
+/*!
+ * LABELS MODULE
+ * Leader direction suggestions that keep radar labels apart in dense traffic
+ */
+
+use wasm_bindgen::prelude::*;
+
+/// Leader directions tried for each label, clockwise from up (degrees)
+const CANDIDATE_ANGLES: [f64; 8] = [45.0, 135.0, 315.0, 225.0, 0.0, 90.0, 180.0, 270.0];
+
+/// Screen-space size of a target symbol (pixels)
+const SYMBOL_SIZE: f64 = 6.0;
+
+/// Maps simulation nm to screen pixels (y grows downwards)
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenTransform {
+    /// Simulation x at the left edge
+    pub origin_x: f64,
+    /// Simulation y at the top edge
+    pub origin_y: f64,
+    pub pixels_per_nm: f64,
+}
+
+#[wasm_bindgen]
+impl ScreenTransform {
+    #[wasm_bindgen(constructor)]
+    pub fn new(origin_x: f64, origin_y: f64, pixels_per_nm: f64) -> ScreenTransform {
+        ScreenTransform {
+            origin_x,
+            origin_y,
+            pixels_per_nm,
+        }
+    }
+}
+
+impl ScreenTransform {
+    pub fn to_screen(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            (x - self.origin_x) * self.pixels_per_nm,
+            (self.origin_y - y) * self.pixels_per_nm,
+        )
+    }
+}
+
+/// Label box size and leader length in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabelGeometry {
+    pub width: f64,
+    pub height: f64,
+    pub leader_length: f64,
+}
+
+impl Default for LabelGeometry {
+    fn default() -> Self {
+        LabelGeometry {
+            width: 70.0,
+            height: 28.0,
+            leader_length: 30.0,
+        }
+    }
+}
+
+/// Overlap area of two centred boxes
+fn overlap(a: (f64, f64), a_size: (f64, f64), b: (f64, f64), b_size: (f64, f64)) -> f64 {
+    let w = (a_size.0 + b_size.0) / 2.0 - (a.0 - b.0).abs();
+    let h = (a_size.1 + b_size.1) / 2.0 - (a.1 - b.1).abs();
+    if w > 0.0 && h > 0.0 {
+        w * h
+    } else {
+        0.0
+    }
+}
+
+fn label_center(target: (f64, f64), angle: f64, geometry: &LabelGeometry) -> (f64, f64) {
+    let radians = angle.to_radians();
+    let reach = geometry.leader_length + geometry.width.max(geometry.height) / 2.0;
+    (target.0 + reach * radians.sin(), target.1 - reach * radians.cos())
+}
+
+/// Recommended leader angle (degrees clockwise from up) for each target
+///
+/// Labels are placed greedily, most crowded target first; each takes the
+/// candidate direction whose box overlaps the fewest target symbols and
+/// already placed labels. Ties keep the conventional upper-right leader.
+pub fn suggest_leader_angles(positions: &[(f64, f64)], transform: &ScreenTransform, geometry: &LabelGeometry) -> Vec<f64> {
+    let screen: Vec<(f64, f64)> = positions.iter().map(|&(x, y)| transform.to_screen(x, y)).collect();
+    let label_size = (geometry.width, geometry.height);
+    let symbol_size = (SYMBOL_SIZE, SYMBOL_SIZE);
+    let crowd_radius = geometry.leader_length + geometry.width;
+
+    let mut order: Vec<usize> = (0..screen.len()).collect();
+    let crowding = |i: usize| {
+        screen
+            .iter()
+            .filter(|p| ((p.0 - screen[i].0).powi(2) + (p.1 - screen[i].1).powi(2)).sqrt() < crowd_radius)
+            .count()
+    };
+    order.sort_by_key(|&i| std::cmp::Reverse(crowding(i)));
+
+    let mut angles = vec![CANDIDATE_ANGLES[0]; screen.len()];
+    let mut placed: Vec<(f64, f64)> = Vec::with_capacity(screen.len());
+
+    for i in order {
+        let mut best = (f64::INFINITY, CANDIDATE_ANGLES[0]);
+        for &angle in &CANDIDATE_ANGLES {
+            let center = label_center(screen[i], angle, geometry);
+            let symbols: f64 = screen.iter().map(|&p| overlap(center, label_size, p, symbol_size)).sum();
+            let labels: f64 = placed.iter().map(|&l| overlap(center, label_size, l, label_size)).sum();
+            let cost = symbols * 4.0 + labels;
+            if cost < best.0 {
+                best = (cost, angle);
+            }
+        }
+        angles[i] = best.1;
+        placed.push(label_center(screen[i], best.1, geometry));
+    }
+
+    angles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isolated_target_keeps_default_leader() {
+        let transform = ScreenTransform::new(-10.0, 10.0, 40.0);
+        let angles = suggest_leader_angles(&[(0.0, 0.0)], &transform, &LabelGeometry::default());
+        assert_eq!(angles, vec![45.0]);
+        assert_eq!(transform.to_screen(0.0, 0.0), (400.0, 400.0));
+    }
+
+    #[test]
+    fn test_crowded_labels_spread_out() {
+        let transform = ScreenTransform::new(-10.0, 10.0, 40.0);
+        let geometry = LabelGeometry::default();
+        // Second target sits where the first's default label would go
+        let positions = [(0.0, 0.0), (1.8, 1.0), (0.5, 0.0)];
+
+        let angles = suggest_leader_angles(&positions, &transform, &geometry);
+        let screen: Vec<_> = positions.iter().map(|&(x, y)| transform.to_screen(x, y)).collect();
+        let centers: Vec<_> = (0..3).map(|i| label_center(screen[i], angles[i], &geometry)).collect();
+
+        let size = (geometry.width, geometry.height);
+        for i in 0..3 {
+            for j in (i + 1)..3 {
+                assert_eq!(overlap(centers[i], size, centers[j], size), 0.0, "{:?}", angles);
+            }
+            for p in &screen {
+                assert_eq!(overlap(centers[i], size, *p, (SYMBOL_SIZE, SYMBOL_SIZE)), 0.0, "{:?}", angles);
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-/**
+/*!
  * RUST SAFETY MODULE
  * Memory-safe separation monitoring and conflict detection
  */
@@ -9,25 +9,173 @@ mod separation;
 mod conflict;
 mod state;
 mod validation;
+mod training;
+mod callsign;
+mod aircraft_types;
+mod atmosphere;
+mod approach;
+mod route;
+mod config;
+mod sweep;
+mod traffic;
+mod builder;
+mod schema;
+mod trend;
+mod holding;
+mod sector;
+mod comms;
+mod nordo;
+mod emergency;
+mod airport;
+mod terrain;
+mod block;
+mod formation;
+mod activity;
+mod drift;
+mod popup;
+mod intent;
+mod primary;
+mod swap;
+mod selftest;
+mod engine;
+mod region;
+mod attribution;
+mod boundary;
+mod contract;
+mod heatmap;
+mod flows;
+mod adsb;
+mod surveillance;
+mod timing;
+mod display;
+mod aural;
+mod datablock;
+mod scan;
+mod labels;
+mod filter;
+mod spatial;
+mod ownship;
+mod clearance;
+mod resolution;
+mod quarantine;
+mod wake;
+mod area;
+mod entry;
+mod inbound;
+mod explain;
+mod tracking;
+mod geo;
+mod replay;
+mod handle;
+mod stca;
+mod msaw;
+mod scheduler;
+mod sequence;
+mod airspace;
+#[cfg(feature = "serde")]
+mod json;
+mod monitor;
+mod vsd;
+mod probe;
+mod mora;
+mod wind;
+mod events;
 
 pub use separation::*;
 pub use conflict::*;
 pub use state::*;
 pub use validation::*;
+pub use training::*;
+pub use callsign::*;
+pub use aircraft_types::*;
+pub use atmosphere::*;
+pub use approach::*;
+pub use route::*;
+pub use config::*;
+pub use sweep::*;
+pub use traffic::*;
+pub use builder::*;
+pub use schema::*;
+pub use trend::*;
+pub use holding::*;
+pub use sector::*;
+pub use comms::*;
+pub use nordo::*;
+pub use emergency::*;
+pub use airport::*;
+pub use terrain::*;
+pub use block::*;
+pub use formation::*;
+pub use activity::*;
+pub use drift::*;
+pub use popup::*;
+pub use intent::*;
+pub use primary::*;
+pub use swap::*;
+pub use selftest::*;
+pub use engine::*;
+pub use region::*;
+pub use attribution::*;
+pub use boundary::*;
+pub use contract::*;
+pub use heatmap::*;
+pub use flows::*;
+pub use adsb::*;
+pub use surveillance::*;
+pub use timing::*;
+pub use display::*;
+pub use aural::*;
+pub use datablock::*;
+pub use scan::*;
+pub use labels::*;
+pub use filter::*;
+pub use spatial::*;
+pub use ownship::*;
+pub use clearance::*;
+pub use resolution::*;
+pub use quarantine::*;
+pub use wake::*;
+pub use area::*;
+pub use entry::*;
+pub use inbound::*;
+pub use explain::*;
+pub use tracking::*;
+pub use geo::*;
+pub use replay::*;
+pub use handle::*;
+pub use stca::*;
+pub use msaw::*;
+pub use scheduler::*;
+pub use sequence::*;
+pub use airspace::*;
+#[cfg(feature = "serde")]
+pub use json::*;
+pub use monitor::*;
+pub use vsd::*;
+pub use probe::*;
+pub use mora::*;
+pub use wind::*;
+pub use events::*;
 
 /// Aircraft state structure
 #[wasm_bindgen]
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AircraftState {
     pub x: f64,
     pub y: f64,
     pub altitude: f64,
     pub heading: f64,
     pub speed: f64,
+    /// Feet per minute, positive climbing
+    pub vertical_rate: f64,
+    /// Wake turbulence category, if known
+    pub wake: Option<WakeCategory>,
 }
 
 #[wasm_bindgen]
 impl AircraftState {
+    /// Level aircraft; set `vertical_rate` for a climb or descent
     #[wasm_bindgen(constructor)]
     pub fn new(x: f64, y: f64, altitude: f64, heading: f64, speed: f64) -> AircraftState {
         AircraftState {
@@ -36,30 +184,115 @@ impl AircraftState {
             altitude,
             heading,
             speed,
+            vertical_rate: 0.0,
+            wake: None,
         }
     }
 }
 
+impl AircraftState {
+    pub fn with_vertical_rate(mut self, vertical_rate: f64) -> Self {
+        self.vertical_rate = vertical_rate;
+        self
+    }
+
+    pub fn with_wake(mut self, wake: WakeCategory) -> Self {
+        self.wake = Some(wake);
+        self
+    }
+}
+
+pub use atc_safety_core::{heading_difference, interpolate_heading, normalize_heading};
+
+impl From<AircraftState> for atc_safety_core::State {
+    fn from(aircraft: AircraftState) -> Self {
+        atc_safety_core::State::new(aircraft.x, aircraft.y, aircraft.altitude, aircraft.heading, aircraft.speed)
+            .with_vertical_rate(aircraft.vertical_rate)
+    }
+}
+
+impl From<atc_safety_core::State> for AircraftState {
+    fn from(state: atc_safety_core::State) -> Self {
+        AircraftState::new(state.x, state.y, state.altitude, state.heading, state.speed)
+            .with_vertical_rate(state.vertical_rate)
+    }
+}
+
+/// Which separation standard(s) are currently met
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeparationBasis {
+    None,
+    Horizontal,
+    Vertical,
+    Both,
+}
+
 /// Separation result structure
+///
+/// Margins are the distance beyond each minimum (nm and ft); negative
+/// margins mean that minimum is infringed.
 #[wasm_bindgen]
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SeparationResult {
     pub is_safe: bool,
     pub horizontal_distance: f64,
     pub vertical_distance: f64,
+    pub horizontal_satisfied: bool,
+    pub vertical_satisfied: bool,
+    pub horizontal_margin: f64,
+    pub vertical_margin: f64,
+    /// In-trail wake turbulence distance required (nm), zero if none applies
+    pub wake_minimum: f64,
+    pub wake_satisfied: bool,
 }
 
 #[wasm_bindgen]
 impl SeparationResult {
-    pub fn new(is_safe: bool, horizontal_distance: f64, vertical_distance: f64) -> SeparationResult {
+    pub fn new(
+        horizontal_distance: f64,
+        vertical_distance: f64,
+        min_horizontal: f64,
+        min_vertical: f64,
+    ) -> SeparationResult {
+        let horizontal_satisfied = horizontal_distance >= min_horizontal;
+        let vertical_satisfied = vertical_distance >= min_vertical;
+
         SeparationResult {
-            is_safe,
+            is_safe: horizontal_satisfied || vertical_satisfied,
             horizontal_distance,
             vertical_distance,
+            horizontal_satisfied,
+            vertical_satisfied,
+            horizontal_margin: horizontal_distance - min_horizontal,
+            vertical_margin: vertical_distance - min_vertical,
+            wake_minimum: 0.0,
+            wake_satisfied: true,
+        }
+    }
+
+    /// Standard(s) providing separation
+    pub fn basis(&self) -> SeparationBasis {
+        match (self.horizontal_satisfied, self.vertical_satisfied) {
+            (true, true) => SeparationBasis::Both,
+            (true, false) => SeparationBasis::Horizontal,
+            (false, true) => SeparationBasis::Vertical,
+            (false, false) => SeparationBasis::None,
         }
     }
 }
 
+impl SeparationResult {
+    /// Apply an in-trail wake minimum (nm), which radar separation cannot replace
+    pub fn with_wake_minimum(mut self, wake_minimum: f64) -> Self {
+        self.wake_minimum = wake_minimum;
+        self.wake_satisfied = self.horizontal_distance >= wake_minimum;
+        self.is_safe = self.is_safe && self.wake_satisfied;
+        self
+    }
+}
+
 /// Initialize the WASM module
 #[wasm_bindgen(start)]
 pub fn init() {
@@ -73,16 +306,109 @@ pub fn init() {
 pub fn check_separation(
     aircraft1: &AircraftState,
     aircraft2: &AircraftState,
-    min_horizontal: f64,
-    min_vertical: f64,
+    standards: &SeparationStandards,
 ) -> SeparationResult {
-    separation::check_separation(aircraft1, aircraft2, min_horizontal, min_vertical)
+    separation::check_separation(aircraft1, aircraft2, standards)
+}
+
+/// Time to the closest point of approach, or why there is none (exported to JavaScript)
+#[wasm_bindgen]
+pub fn time_to_minimum_separation(aircraft1: &AircraftState, aircraft2: &AircraftState) -> ApproachTiming {
+    separation::time_to_minimum_separation(aircraft1, aircraft2).into()
+}
+
+/// Predicted loss-of-separation interval, if any (exported to JavaScript)
+#[wasm_bindgen]
+pub fn conflict_interval(
+    aircraft1: &AircraftState,
+    aircraft2: &AircraftState,
+    standards: &SeparationStandards,
+    look_ahead_time: f64,
+) -> Option<ConflictInterval> {
+    conflict::conflict_interval(aircraft1, aircraft2, standards, look_ahead_time)
+}
+
+/// Head-on, crossing or overtaking encounter geometry (exported to JavaScript)
+#[wasm_bindgen]
+pub fn classify_geometry(aircraft1: &AircraftState, aircraft2: &AircraftState) -> ConflictGeometry {
+    conflict::classify_geometry(aircraft1, aircraft2)
+}
+
+/// Predict a conflict and explain its severity (exported to JavaScript)
+#[wasm_bindgen]
+pub fn explain_predicted_conflict(
+    aircraft1: &AircraftState,
+    aircraft2: &AircraftState,
+    standards: &SeparationStandards,
+    look_ahead_time: f64,
+) -> AlertExplanation {
+    let info = conflict::detect_conflict(aircraft1, aircraft2, standards, look_ahead_time);
+    explain::explain_conflict(&info, standards, TrajectoryModel::StraightLine, look_ahead_time, 0.0)
+}
+
+/// Margins of a pair against each alerting condition (exported to JavaScript)
+#[wasm_bindgen]
+pub fn explain_suppressed_alert(
+    aircraft1: &AircraftState,
+    aircraft2: &AircraftState,
+    config: &SafetyConfig,
+) -> SuppressionReport {
+    explain::explain_suppression(aircraft1, aircraft2, config)
+}
+
+/// Great-circle line as packed latitude, longitude (exported to JavaScript)
+#[wasm_bindgen]
+pub fn great_circle_points(from: &GeoPosition, to: &GeoPosition, segments: usize) -> Vec<f64> {
+    geo::great_circle_points(from, to, segments)
+}
+
+/// Range ring as packed latitude, longitude (exported to JavaScript)
+#[wasm_bindgen]
+pub fn range_ring_points(center: &GeoPosition, radius: f64, points: usize) -> Vec<f64> {
+    geo::range_ring_points(center, radius, points)
+}
+
+/// Clockwise range arc as packed latitude, longitude (exported to JavaScript)
+#[wasm_bindgen]
+pub fn arc_points(center: &GeoPosition, radius: f64, start_bearing: f64, end_bearing: f64, points: usize) -> Vec<f64> {
+    geo::arc_points(center, radius, start_bearing, end_bearing, points)
+}
+
+/// Great-circle route line through packed waypoints (exported to JavaScript)
+#[wasm_bindgen]
+pub fn route_points(waypoints: &[f64], segments: usize) -> Result<Vec<f64>, String> {
+    geo::route_points(waypoints, segments)
 }
 
 /// Validate aircraft state (exported to JavaScript)
 #[wasm_bindgen]
-pub fn validate_aircraft_state(aircraft: &AircraftState) -> bool {
-    validation::validate_state(aircraft)
+pub fn validate_aircraft_state(aircraft: &AircraftState, config: &ValidationConfig) -> bool {
+    validation::validate_state(aircraft, config)
+}
+
+/// Validate a packed snapshot (exported to JavaScript)
+///
+/// Input uses the `TrafficScan` layout; the result holds an index and a
+/// `RejectReason` for every failed check.
+#[wasm_bindgen]
+pub fn validate_snapshot(states: &[f64], config: &ValidationConfig) -> Result<Vec<u32>, String> {
+    let aircraft: Vec<AircraftState> = scan::unpack_states(states)?.collect();
+    Ok(validation::validate_states(&aircraft, config)
+        .into_iter()
+        .flat_map(|r| [r.index as u32, r.error.reason() as u32])
+        .collect())
+}
+
+/// Every pair infringing the minima in a packed state buffer (exported to JavaScript)
+#[wasm_bindgen]
+pub fn check_separation_batch(states: &[f64], min_horizontal: f64, min_vertical: f64) -> Result<Vec<f64>, String> {
+    scan::check_separation_batch(states, min_horizontal, min_vertical)
+}
+
+/// Every validation rule an aircraft state fails, with the offending values (exported to JavaScript)
+#[wasm_bindgen]
+pub fn aircraft_state_report(aircraft: &AircraftState, config: &ValidationConfig) -> ValidationReport {
+    validation::check_state(aircraft, config)
 }
 
 /// Calculate horizontal distance between two aircraft
@@ -97,4 +423,105 @@ pub fn calculate_horizontal_distance(aircraft1: &AircraftState, aircraft2: &Airc
 #[wasm_bindgen]
 pub fn calculate_vertical_distance(aircraft1: &AircraftState, aircraft2: &AircraftState) -> f64 {
     (aircraft1.altitude - aircraft2.altitude).abs()
-}
\ No newline at end of file
+}
+
+/// Resolve a spoken callsign against the active traffic (exported to JavaScript)
+#[wasm_bindgen]
+pub fn resolve_spoken_callsign(phrase: &str, traffic: Vec<String>) -> CallsignResolution {
+    callsign::resolve_callsign(phrase, &traffic)
+}
+
+/// Detect confusable callsigns sharing a sector (exported to JavaScript)
+#[wasm_bindgen]
+pub fn detect_similar_callsigns(callsigns: Vec<String>, sectors: Vec<String>) -> Vec<CallsignConfusion> {
+    let active: Vec<(String, String)> = callsigns.into_iter().zip(sectors).collect();
+    callsign::detect_callsign_confusion(&active)
+}
+
+/// Look up wake and performance defaults for an ICAO type designator (exported to JavaScript)
+#[cfg(feature = "type-database")]
+#[wasm_bindgen]
+pub fn lookup_aircraft_type(designator: &str) -> Option<TypeInfo> {
+    aircraft_types::lookup_type_designator(designator)
+}
+
+/// Cold temperature correction for a minimum altitude (exported to JavaScript)
+#[wasm_bindgen]
+pub fn cold_temperature_correction(height_above_aerodrome: f64, aerodrome_temperature: f64) -> f64 {
+    atmosphere::cold_temperature_correction(height_above_aerodrome, aerodrome_temperature)
+}
+
+/// Monitor an aircraft on final against the runway glidepath (exported to JavaScript)
+#[wasm_bindgen]
+pub fn monitor_glidepath(
+    runway: &Runway,
+    previous: &TimedState,
+    current: &TimedState,
+    reference_speed: f64,
+) -> GlidepathStatus {
+    approach::monitor_glidepath(runway, previous, current, reference_speed)
+}
+
+/// Check an arrival against the approach speed gates (exported to JavaScript)
+#[wasm_bindgen]
+pub fn check_approach_gates(
+    schedule: &SpeedSchedule,
+    runway: &Runway,
+    aircraft: &AircraftState,
+) -> Vec<GateCheck> {
+    approach::check_approach_gates(schedule, runway, aircraft)
+}
+
+/// Build an aircraft from an options object (exported to JavaScript)
+#[wasm_bindgen]
+pub fn build_aircraft(options: &AircraftOptions) -> Result<AircraftDetails, String> {
+    AircraftStateBuilder::from(options).build_details().map_err(|e| e.to_string())
+}
+
+/// Serialize a config with its schema version (exported to JavaScript)
+#[wasm_bindgen]
+pub fn encode_safety_config(config: &SafetyConfig) -> String {
+    schema::encode_config(config)
+}
+
+/// Load a saved config, migrating older versions (exported to JavaScript)
+#[wasm_bindgen]
+pub fn decode_safety_config(text: &str) -> Result<SafetyConfig, String> {
+    schema::decode_config(text).map_err(|e| e.to_string())
+}
+
+/// Check a transiting aircraft against a holding stack (exported to JavaScript)
+#[wasm_bindgen]
+pub fn check_holding_stack(
+    aircraft: &AircraftState,
+    hold: &HoldingPattern,
+    horizontal_separation: f64,
+    vertical_separation: f64,
+    look_ahead_time: f64,
+) -> Option<HoldingConflict> {
+    holding::check_holding_stack(aircraft, hold, horizontal_separation, vertical_separation, look_ahead_time)
+}
+
+/// Run the built-in encounters and report whether every check passed (exported to JavaScript)
+#[wasm_bindgen]
+pub fn self_test_passes(config: &SafetyConfig) -> bool {
+    run_self_test(config).passed()
+}
+
+/// Check a config for out-of-range and inconsistent settings (exported to JavaScript)
+#[wasm_bindgen]
+pub fn validate_safety_config(config: &SafetyConfig) -> Result<(), String> {
+    config.validate().map_err(|e| e.to_string())
+}
+
+/// Leader angles for packed x, y target positions (exported to JavaScript)
+#[wasm_bindgen]
+pub fn leader_angles(positions: &[f64], transform: &ScreenTransform, label_width: f64, label_height: f64, leader_length: f64) -> Vec<f64> {
+    let positions: Vec<(f64, f64)> = positions.chunks_exact(2).map(|p| (p[0], p[1])).collect();
+    let geometry = LabelGeometry {
+        width: label_width,
+        height: label_height,
+        leader_length,
+    };
+    labels::suggest_leader_angles(&positions, transform, &geometry)
+}
@@ -0,0 +1,470 @@
+/*!
+ * MONITOR MODULE
+ * Stateful safety monitor façade: one update per report, one tick per sweep
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+
+use crate::config::SafetyConfig;
+use crate::conflict::{ConflictGeometry, ConflictSeverity};
+use crate::events::{EventBus, EventCallback, MonitorEvent, MonitorEventKind};
+use crate::airspace::Airspace;
+use crate::msaw::{check_msaw, TerrainModel};
+use crate::probe::{probe_route, route_from_buffer, RouteProbe};
+use crate::separation::check_separation;
+use crate::state::TrackManager;
+use crate::sweep::sweep_conflicts;
+use crate::validation::validate_state;
+use crate::vsd::{vertical_situation, VerticalSituation, VsdSettings};
+use crate::AircraftState;
+
+/// States kept per track
+const MONITOR_HISTORY: usize = 10;
+
+/// Conflict raised or escalated by a tick
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonitorConflict {
+    /// Callsign earlier in key order
+    pub first: String,
+    pub second: String,
+    pub severity: ConflictSeverity,
+    pub time_to_conflict: f64,
+    pub minimum_distance: f64,
+    pub geometry: ConflictGeometry,
+}
+
+/// Minimum safe altitude warning raised or escalated by a tick
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MsawHit {
+    pub callsign: String,
+    pub severity: ConflictSeverity,
+    pub time_to_violation: f64,
+}
+
+/// Everything new since the previous tick
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonitorReport {
+    /// Simulation time of the tick (seconds)
+    pub time: f64,
+    conflicts: Vec<MonitorConflict>,
+    msaw: Vec<MsawHit>,
+    rejected: Vec<String>,
+    dropped: Vec<String>,
+}
+
+#[wasm_bindgen]
+impl MonitorReport {
+    /// Conflicts raised or escalated, in callsign order
+    pub fn conflicts(&self) -> Vec<MonitorConflict> {
+        self.conflicts.clone()
+    }
+
+    /// Terrain warnings raised or escalated, in callsign order
+    pub fn msaw(&self) -> Vec<MsawHit> {
+        self.msaw.clone()
+    }
+
+    /// Callsigns whose reports failed validation since the last tick
+    pub fn rejected(&self) -> Vec<String> {
+        self.rejected.clone()
+    }
+
+    /// Callsigns dropped after coasting past the timeout
+    pub fn dropped(&self) -> Vec<String> {
+        self.dropped.clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conflicts.is_empty() && self.msaw.is_empty() && self.rejected.is_empty() && self.dropped.is_empty()
+    }
+}
+
+/// Tracks, standards and alert state behind a single JavaScript object
+///
+/// Call `update` with each radar report and `tick` once per sweep. Reports
+/// failing validation are left out of the picture and listed in the next
+/// report; a later report for the same aircraft in the same sweep replaces
+/// the earlier one. Tracks with no report during a whole sweep coast and
+/// drop as in `TrackManager`. Alerts are
+/// reported once when raised and again only if they escalate. Listeners
+/// registered with `on` are called during `tick` and must not call back
+/// into the monitor.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct SafetyMonitor {
+    config: SafetyConfig,
+    tracks: TrackManager,
+    terrain: TerrainModel,
+    airspace: Airspace,
+    clock: f64,
+    conflicts: BTreeMap<(String, String), ConflictSeverity>,
+    violations: BTreeSet<(String, String)>,
+    msaw: BTreeMap<String, ConflictSeverity>,
+    rejected: Vec<String>,
+    events: EventBus,
+}
+
+#[wasm_bindgen]
+impl SafetyMonitor {
+    /// Monitor on a copy of `config`; invalid configs are rejected
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: &SafetyConfig) -> Result<SafetyMonitor, String> {
+        config.validate().map_err(|e| e.to_string())?;
+        Ok(SafetyMonitor {
+            config: config.clone(),
+            tracks: TrackManager::new(MONITOR_HISTORY),
+            terrain: TerrainModel::new(),
+            airspace: Airspace::new(),
+            clock: 0.0,
+            conflicts: BTreeMap::new(),
+            violations: BTreeSet::new(),
+            msaw: BTreeMap::new(),
+            rejected: Vec::new(),
+            events: EventBus::new(),
+        })
+    }
+
+    /// Record a report at the current clock, replacing any made since the last tick
+    ///
+    /// False if the report failed validation; it is then listed as rejected in
+    /// the next report and the track keeps its previous state.
+    pub fn update(&mut self, callsign: &str, state: &AircraftState) -> bool {
+        if !validate_state(state, self.config.validation()) {
+            if !self.rejected.iter().any(|c| c == callsign) {
+                self.rejected.push(callsign.to_string());
+            }
+            return false;
+        }
+        self.tracks.upsert(callsign, *state, self.clock) || self.tracks.correct(callsign, *state, self.clock)
+    }
+
+    pub fn remove(&mut self, callsign: &str) -> bool {
+        self.tracks.remove(callsign)
+    }
+
+    /// Advance the clock by `dt` seconds and evaluate the picture
+    ///
+    /// Only tracks that sent no report since the previous tick are coasted.
+    pub fn tick(&mut self, dt: f64) -> MonitorReport {
+        let since = self.clock;
+        if dt.is_finite() && dt > 0.0 {
+            self.clock += dt;
+        }
+        let dropped = self.tracks.coast_since(self.clock, since);
+
+        let callsigns = self.tracks.ids();
+        let states: Vec<AircraftState> = callsigns.iter().filter_map(|c| self.tracks.latest(c)).collect();
+
+        let report = sweep_conflicts(&states, &self.config, None);
+        let standards = self.config.standards();
+        let mut conflicts = BTreeMap::new();
+        let mut violations = BTreeSet::new();
+        let mut new_conflicts = Vec::new();
+        let mut new_violations = Vec::new();
+        for conflict in &report.conflicts {
+            // Callsigns are in key order, so `first` always sorts before `second`
+            let key = (callsigns[conflict.first].clone(), callsigns[conflict.second].clone());
+            let severity = conflict.info.severity;
+            if !check_separation(&states[conflict.first], &states[conflict.second], &standards).is_safe {
+                if !self.violations.contains(&key) {
+                    new_violations.push((key.clone(), severity));
+                }
+                violations.insert(key.clone());
+            }
+            if self.conflicts.get(&key).is_none_or(|&old| severity > old) {
+                new_conflicts.push(MonitorConflict {
+                    first: key.0.clone(),
+                    second: key.1.clone(),
+                    severity,
+                    time_to_conflict: conflict.info.time_to_conflict,
+                    minimum_distance: conflict.info.minimum_distance,
+                    geometry: conflict.info.geometry,
+                });
+            }
+            conflicts.insert(key, severity);
+        }
+        let resolved: Vec<(String, String)> =
+            self.conflicts.keys().filter(|key| !conflicts.contains_key(*key)).cloned().collect();
+        self.conflicts = conflicts;
+        self.violations = violations;
+
+        let mut msaw = BTreeMap::new();
+        let mut new_msaw = Vec::new();
+        for (callsign, state) in callsigns.iter().zip(&states) {
+            let info = check_msaw(state, &self.terrain, self.config.look_ahead_time);
+            if info.severity == ConflictSeverity::None {
+                continue;
+            }
+            if self.msaw.get(callsign).is_none_or(|&old| info.severity > old) {
+                new_msaw.push(MsawHit {
+                    callsign: callsign.clone(),
+                    severity: info.severity,
+                    time_to_violation: info.time_to_violation,
+                });
+            }
+            msaw.insert(callsign.clone(), info.severity);
+        }
+        self.msaw = msaw;
+
+        if !self.events.is_empty() {
+            let event = |kind, (first, second): (String, String), severity| MonitorEvent {
+                kind,
+                time: self.clock,
+                first,
+                second,
+                severity,
+            };
+            for conflict in &new_conflicts {
+                let pair = (conflict.first.clone(), conflict.second.clone());
+                self.events.emit(&event(MonitorEventKind::ConflictDetected, pair, conflict.severity));
+            }
+            for (pair, severity) in new_violations {
+                self.events.emit(&event(MonitorEventKind::SeparationViolation, pair, severity));
+            }
+            for pair in resolved {
+                self.events.emit(&event(MonitorEventKind::ConflictResolved, pair, ConflictSeverity::None));
+            }
+            for callsign in &dropped {
+                let track = (callsign.clone(), String::new());
+                self.events.emit(&event(MonitorEventKind::TrackDropped, track, ConflictSeverity::None));
+            }
+        }
+
+        MonitorReport {
+            time: self.clock,
+            conflicts: new_conflicts,
+            msaw: new_msaw,
+            rejected: std::mem::take(&mut self.rejected),
+            dropped,
+        }
+    }
+
+    /// Call `callback` with each `MonitorEvent` of a category during `tick`
+    ///
+    /// Categories are `conflict_detected`, `conflict_resolved`,
+    /// `separation_violation` and `track_dropped`. Returns the listener ID
+    /// for `off`.
+    pub fn on(&mut self, category: &str, callback: EventCallback) -> Result<u32, String> {
+        let kind = MonitorEventKind::from_name(category).ok_or_else(|| format!("unknown event category {}", category))?;
+        Ok(self.events.subscribe(kind, Rc::new(move |event| callback.invoke(event))))
+    }
+
+    /// Remove a listener; false if the ID is unknown
+    pub fn off(&mut self, id: u32) -> bool {
+        self.events.unsubscribe(id)
+    }
+
+    /// Correct terrain warnings and probes for the temperature at an aerodrome (ft, deg C)
+    pub fn set_aerodrome_temperature(&mut self, elevation: f64, temperature: f64) {
+        self.terrain.aerodrome = Some((elevation, temperature));
+    }
+
+    /// Simulation time (seconds)
+    pub fn clock(&self) -> f64 {
+        self.clock
+    }
+
+    pub fn track_count(&self) -> usize {
+        self.tracks.len()
+    }
+
+    /// Conflicts active after the last tick
+    pub fn active_conflict_count(&self) -> usize {
+        self.conflicts.len()
+    }
+
+    /// Vertical situation display data for one track, or `None` if it is not tracked
+    pub fn vertical_situation(&self, callsign: &str, settings: &VsdSettings) -> Option<VerticalSituation> {
+        let aircraft = self.tracks.latest(callsign)?;
+        Some(vertical_situation(
+            &aircraft,
+            &self.traffic_except(callsign),
+            &self.terrain,
+            settings,
+            self.config.vertical_separation,
+        ))
+    }
+
+    /// Probe a track flying a drawn route of flat (x, y, altitude, speed) values
+    ///
+    /// NaN altitudes and speeds leave a waypoint unconstrained. Fails if the
+    /// track is unknown or the route buffer is malformed.
+    pub fn probe_route(&self, callsign: &str, route: &[f64]) -> Result<RouteProbe, String> {
+        let aircraft = self
+            .tracks
+            .latest(callsign)
+            .ok_or_else(|| format!("unknown track {}", callsign))?;
+        let waypoints = route_from_buffer(route)?;
+        Ok(probe_route(
+            &aircraft,
+            &waypoints,
+            &self.traffic_except(callsign),
+            &self.terrain,
+            &self.airspace,
+            &self.config,
+            self.clock,
+        ))
+    }
+}
+
+impl SafetyMonitor {
+    /// Check terrain and obstacles for MSAW; without one only the sea surface counts
+    pub fn with_terrain(mut self, terrain: TerrainModel) -> Self {
+        self.terrain = terrain;
+        self
+    }
+
+    /// Register a Rust listener for one category of event
+    pub fn on_event(&mut self, kind: MonitorEventKind, listener: impl Fn(&MonitorEvent) + 'static) -> u32 {
+        self.events.subscribe(kind, Rc::new(listener))
+    }
+
+    /// Special use areas checked by route probes
+    pub fn with_airspace(mut self, airspace: Airspace) -> Self {
+        self.airspace = airspace;
+        self
+    }
+
+    pub fn tracks(&self) -> &TrackManager {
+        &self.tracks
+    }
+
+    fn traffic_except(&self, callsign: &str) -> Vec<(String, AircraftState)> {
+        self.tracks
+            .ids()
+            .into_iter()
+            .filter(|id| id != callsign)
+            .filter_map(|id| self.tracks.latest(&id).map(|state| (id, state)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain::TerrainGrid;
+
+    #[test]
+    fn test_reports_new_conflicts_once() {
+        let mut monitor = SafetyMonitor::new(&SafetyConfig::default()).unwrap();
+        assert!(monitor.update("BAW1", &AircraftState::new(0.0, 0.0, 12000.0, 90.0, 300.0)));
+        assert!(monitor.update("EZY2", &AircraftState::new(20.0, 0.0, 12000.0, 270.0, 300.0)));
+        assert!(!monitor.update("RYR3", &AircraftState::new(5.0, 5.0, 12000.0, 90.0, 2000.0)));
+
+        let report = monitor.tick(4.0);
+        assert_eq!(report.time, 4.0);
+        assert_eq!(report.rejected(), vec!["RYR3".to_string()]);
+        let conflicts = report.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!((conflicts[0].first.as_str(), conflicts[0].second.as_str()), ("BAW1", "EZY2"));
+        assert_eq!(conflicts[0].geometry, ConflictGeometry::HeadOn);
+
+        // Tracks that reported during the tick are not coasted
+        assert!(!monitor.tracks().is_coasted("BAW1"));
+
+        // Still in conflict but nothing new to report
+        let report = monitor.tick(4.0);
+        assert!(monitor.tracks().is_coasted("BAW1"));
+        assert!(report.is_empty());
+        assert_eq!(monitor.active_conflict_count(), 1);
+        assert_eq!(monitor.track_count(), 2);
+
+        let vsd = monitor.vertical_situation("BAW1", &VsdSettings::default()).unwrap();
+        assert_eq!(vsd.traffic()[0].callsign, "EZY2");
+        assert!(monitor.vertical_situation("RYR3", &VsdSettings::default()).is_none());
+
+        // Turning BAW1 away to the north clears the conflict
+        let probe = monitor.probe_route("BAW1", &[0.0, 30.0, f64::NAN, f64::NAN]).unwrap();
+        assert!(probe.is_clear());
+        assert!(monitor.probe_route("RYR3", &[0.0, 30.0, f64::NAN, f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn test_later_report_replaces_earlier_in_same_tick() {
+        let mut monitor = SafetyMonitor::new(&SafetyConfig::default()).unwrap();
+        monitor.tick(4.0);
+        assert!(monitor.update("BAW1", &AircraftState::new(0.0, 0.0, 12000.0, 90.0, 300.0)));
+        assert!(monitor.update("EZY2", &AircraftState::new(20.0, 0.0, 12000.0, 270.0, 300.0)));
+        // Corrected report before the next tick: EZY2 is 4000 ft higher
+        assert!(monitor.update("EZY2", &AircraftState::new(20.0, 0.0, 16000.0, 270.0, 300.0)));
+
+        assert!(monitor.tick(4.0).conflicts().is_empty());
+        assert_eq!(monitor.tracks().latest("EZY2").unwrap().altitude, 16000.0);
+        assert_eq!(monitor.tracks().history("EZY2").unwrap().states().len(), 1);
+    }
+
+    #[test]
+    fn test_msaw_and_dropped_tracks() {
+        let grid = TerrainGrid::new(0.0, -5.0, 1.0, 20, 10, vec![4000.0; 200]).unwrap();
+        let mut monitor = SafetyMonitor::new(&SafetyConfig::default())
+            .unwrap()
+            .with_terrain(TerrainModel::new().with_grid(grid));
+        monitor.update("DLH4", &AircraftState::new(5.0, 0.0, 3000.0, 90.0, 200.0));
+        monitor.update("KLM5", &AircraftState::new(5.0, 3.0, 5100.0, 90.0, 200.0));
+
+        let hits = monitor.tick(1.0).msaw();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].severity, ConflictSeverity::Critical);
+        assert!(monitor.tick(1.0).msaw().is_empty());
+
+        // 100 ft above the minimum on a standard day, below it at -30 C
+        monitor.set_aerodrome_temperature(0.0, -30.0);
+        let hits = monitor.tick(1.0).msaw();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].callsign, "KLM5");
+
+        // Silent past the coast timeout
+        let report = monitor.tick(120.0);
+        assert_eq!(report.dropped(), vec!["DLH4".to_string(), "KLM5".to_string()]);
+        assert_eq!(monitor.track_count(), 0);
+    }
+
+    #[test]
+    fn test_listeners_called_during_tick() {
+        use std::cell::RefCell;
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let mut monitor = SafetyMonitor::new(&SafetyConfig::default()).unwrap();
+        for kind in [
+            MonitorEventKind::ConflictDetected,
+            MonitorEventKind::SeparationViolation,
+            MonitorEventKind::ConflictResolved,
+            MonitorEventKind::TrackDropped,
+        ] {
+            let log = Rc::clone(&seen);
+            monitor.on_event(kind, move |event| log.borrow_mut().push((event.kind, event.first.clone())));
+        }
+
+        monitor.update("AFR5", &AircraftState::new(0.0, 0.0, 8000.0, 90.0, 250.0));
+        monitor.update("KLM6", &AircraftState::new(1.0, 0.0, 8000.0, 90.0, 250.0));
+        monitor.tick(1.0);
+        assert_eq!(
+            *seen.borrow(),
+            vec![
+                (MonitorEventKind::ConflictDetected, "AFR5".to_string()),
+                (MonitorEventKind::SeparationViolation, "AFR5".to_string()),
+            ]
+        );
+
+        seen.borrow_mut().clear();
+        monitor.remove("KLM6");
+        monitor.tick(1.0);
+        monitor.tick(120.0);
+        assert_eq!(
+            *seen.borrow(),
+            vec![
+                (MonitorEventKind::ConflictResolved, "AFR5".to_string()),
+                (MonitorEventKind::TrackDropped, "AFR5".to_string()),
+            ]
+        );
+    }
+}
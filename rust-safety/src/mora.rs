@@ -0,0 +1,137 @@
+/*!
+ * MORA MODULE
+ * Minimum off-route altitude grid and controlling altitude along ad-hoc routes
+ */
+
+use crate::msaw::Obstacle;
+use crate::sector::point_near_polygon;
+use crate::terrain::TerrainGrid;
+
+/// Clearance above the highest terrain or obstacle in a cell (ft)
+const MORA_CLEARANCE: f64 = 1000.0;
+
+/// Clearance where the highest point of a cell is above `MORA_HIGH_TERRAIN` (ft)
+const MORA_HIGH_CLEARANCE: f64 = 2000.0;
+
+/// Elevation above which the larger clearance applies (ft)
+const MORA_HIGH_TERRAIN: f64 = 5000.0;
+
+/// Grid of minimum off-route altitudes (ft), one per cell
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoraGrid {
+    altitudes: TerrainGrid,
+}
+
+impl MoraGrid {
+    /// Grid of published altitudes, laid out as in `TerrainGrid`
+    pub fn new(
+        origin_x: f64,
+        origin_y: f64,
+        cell_size: f64,
+        columns: usize,
+        rows: usize,
+        altitudes: Vec<f64>,
+    ) -> Option<Self> {
+        TerrainGrid::new(origin_x, origin_y, cell_size, columns, rows, altitudes).map(|altitudes| MoraGrid { altitudes })
+    }
+
+    /// Derive altitudes over the terrain grid's extent in cells of `cell_size` nm
+    ///
+    /// Each cell clears its highest terrain by 1000 ft, or by 2000 ft where
+    /// that is above 5000 ft. Obstacles count in every cell they come
+    /// within half a cell diagonal of the cell's center.
+    pub fn from_terrain(terrain: &TerrainGrid, obstacles: &[Obstacle], cell_size: f64) -> Option<Self> {
+        if cell_size.is_nan() || cell_size <= 0.0 {
+            return None;
+        }
+        let columns = (terrain.columns as f64 * terrain.cell_size / cell_size).ceil() as usize;
+        let rows = (terrain.rows as f64 * terrain.cell_size / cell_size).ceil() as usize;
+        let reach = cell_size * std::f64::consts::FRAC_1_SQRT_2;
+
+        let mut altitudes = Vec::with_capacity(columns * rows);
+        for row in 0..rows {
+            for column in 0..columns {
+                let min_x = terrain.origin_x + column as f64 * cell_size;
+                let min_y = terrain.origin_y + row as f64 * cell_size;
+                let (center_x, center_y) = (min_x + cell_size / 2.0, min_y + cell_size / 2.0);
+                let ground = terrain.max_elevation_in(min_x, min_y, min_x + cell_size, min_y + cell_size);
+                let highest = obstacles
+                    .iter()
+                    .filter(|obstacle| point_near_polygon(center_x, center_y, &obstacle.boundary, reach))
+                    .fold(ground, |highest, obstacle| highest.max(obstacle.top));
+                let clearance = if highest > MORA_HIGH_TERRAIN { MORA_HIGH_CLEARANCE } else { MORA_CLEARANCE };
+                altitudes.push(highest + clearance);
+            }
+        }
+        MoraGrid::new(terrain.origin_x, terrain.origin_y, cell_size, columns, rows, altitudes)
+    }
+
+    /// Altitude of the cell containing a point, or `None` outside the grid
+    pub fn altitude_at(&self, x: f64, y: f64) -> Option<f64> {
+        self.altitudes.elevation_at(x, y)
+    }
+
+    /// Controlling (highest) altitude of the cells a straight leg passes through
+    ///
+    /// `None` when the leg never enters the grid.
+    pub fn controlling_altitude(&self, from: (f64, f64), to: (f64, f64)) -> Option<f64> {
+        let length = (to.0 - from.0).hypot(to.1 - from.1);
+        // Sampled every quarter cell, so only a clipped cell corner can be missed
+        let samples = (length / (self.altitudes.cell_size / 4.0)).ceil().max(1.0) as usize;
+        (0..=samples)
+            .filter_map(|i| {
+                let t = i as f64 / samples as f64;
+                self.altitude_at(from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t)
+            })
+            .reduce(f64::max)
+    }
+}
+
+/// Controlling altitude for each leg of a route from `start` through `waypoints`
+///
+/// Entry `i` is the leg flown to waypoint `i`; legs outside the grid are `None`.
+pub fn mora_along_route(grid: &MoraGrid, start: (f64, f64), waypoints: &[(f64, f64)]) -> Vec<Option<f64>> {
+    let mut from = start;
+    waypoints
+        .iter()
+        .map(|&to| {
+            let altitude = grid.controlling_altitude(from, to);
+            from = to;
+            altitude
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_from_terrain() {
+        // 4x2 cells of 1 nm: low on the left, 6000 ft peaks on the right
+        let terrain = TerrainGrid::new(
+            0.0,
+            0.0,
+            1.0,
+            4,
+            2,
+            vec![300.0, 800.0, 4500.0, 6000.0, 200.0, 400.0, 1000.0, 5500.0],
+        )
+        .unwrap();
+        let mast = Obstacle::new(vec![(0.4, 0.4), (0.6, 0.4), (0.6, 0.6), (0.4, 0.6)], 2400.0);
+
+        let grid = MoraGrid::from_terrain(&terrain, &[mast], 2.0).unwrap();
+        assert_eq!(grid.altitude_at(1.0, 1.0), Some(3400.0));
+        assert_eq!(grid.altitude_at(3.0, 1.0), Some(8000.0));
+        assert_eq!(grid.altitude_at(5.0, 1.0), None);
+        assert!(MoraGrid::from_terrain(&terrain, &[], 0.0).is_none());
+    }
+
+    #[test]
+    fn test_controlling_altitude_along_route() {
+        let grid = MoraGrid::new(0.0, 0.0, 10.0, 3, 1, vec![2000.0, 5000.0, 3000.0]).unwrap();
+        let legs = mora_along_route(&grid, (5.0, 5.0), &[(8.0, 5.0), (25.0, 5.0), (25.0, 40.0)]);
+        assert_eq!(legs, vec![Some(2000.0), Some(5000.0), Some(3000.0)]);
+        assert_eq!(grid.controlling_altitude((0.0, 50.0), (30.0, 50.0)), None);
+    }
+}
@@ -0,0 +1,231 @@
+/*!
+ * MSAW MODULE
+ * Minimum Safe Altitude Warning: predicted descent into terrain and obstacles
+ */
+
+use crate::atmosphere::corrected_minimum_altitude;
+use crate::conflict::{classify_severity, ConflictSeverity};
+use crate::mora::MoraGrid;
+use crate::sector::point_near_polygon;
+use crate::separation::predict_position;
+use crate::terrain::TerrainGrid;
+use crate::AircraftState;
+
+/// Area with everything inside it up to a top elevation (ft), such as a mast or a city block
+#[derive(Debug, Clone, PartialEq)]
+pub struct Obstacle {
+    pub boundary: Vec<(f64, f64)>,
+    pub top: f64,
+}
+
+impl Obstacle {
+    pub fn new(boundary: Vec<(f64, f64)>, top: f64) -> Self {
+        Obstacle { boundary, top }
+    }
+}
+
+/// Terrain and obstacles an aircraft must stay clear of
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerrainModel {
+    pub grid: Option<TerrainGrid>,
+    pub obstacles: Vec<Obstacle>,
+    /// Required height above the highest terrain or obstacle (ft)
+    pub clearance: f64,
+    /// Terrain within this distance (nm) of the predicted position counts
+    pub lateral_buffer: f64,
+    /// Off-route altitudes that reroutes are checked against
+    pub mora: Option<MoraGrid>,
+    /// Aerodrome elevation (ft) and temperature (deg C) for the cold temperature correction
+    pub aerodrome: Option<(f64, f64)>,
+}
+
+impl TerrainModel {
+    /// No terrain, 1000 ft clearance within 1 nm of the track
+    pub fn new() -> Self {
+        TerrainModel {
+            grid: None,
+            obstacles: Vec::new(),
+            clearance: 1000.0,
+            lateral_buffer: 1.0,
+            mora: None,
+            aerodrome: None,
+        }
+    }
+
+    pub fn with_grid(mut self, grid: TerrainGrid) -> Self {
+        self.grid = Some(grid);
+        self
+    }
+
+    pub fn with_obstacle(mut self, obstacle: Obstacle) -> Self {
+        self.obstacles.push(obstacle);
+        self
+    }
+
+    pub fn with_mora(mut self, mora: MoraGrid) -> Self {
+        self.mora = Some(mora);
+        self
+    }
+
+    /// Correct minimum safe altitudes for the temperature reported at an aerodrome
+    pub fn with_temperature(mut self, aerodrome_elevation: f64, aerodrome_temperature: f64) -> Self {
+        self.aerodrome = Some((aerodrome_elevation, aerodrome_temperature));
+        self
+    }
+
+    pub fn with_clearance(mut self, clearance: f64, lateral_buffer: f64) -> Self {
+        self.clearance = clearance;
+        self.lateral_buffer = lateral_buffer;
+        self
+    }
+
+    /// Highest terrain or obstacle top within the lateral buffer of a point (ft, 0 when none)
+    pub fn highest_near(&self, x: f64, y: f64) -> f64 {
+        let terrain = self
+            .grid
+            .as_ref()
+            .map_or(0.0, |grid| grid.max_elevation_near(x, y, self.lateral_buffer));
+        self.obstacles
+            .iter()
+            .filter(|obstacle| point_near_polygon(x, y, &obstacle.boundary, self.lateral_buffer))
+            .fold(terrain, |highest, obstacle| highest.max(obstacle.top))
+    }
+
+    /// Minimum safe altitude at a point: highest terrain plus clearance (ft),
+    /// raised by the cold temperature correction when a temperature is set
+    pub fn minimum_safe_altitude(&self, x: f64, y: f64) -> f64 {
+        let minimum = self.highest_near(x, y) + self.clearance;
+        match self.aerodrome {
+            Some((elevation, temperature)) => corrected_minimum_altitude(minimum, elevation, temperature),
+            None => minimum,
+        }
+    }
+}
+
+impl Default for TerrainModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Result of a minimum safe altitude check
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MsawInfo {
+    pub severity: ConflictSeverity,
+    /// Seconds until the aircraft is predicted below the minimum safe
+    /// altitude, zero if it already is and infinite if it never is
+    pub time_to_violation: f64,
+    /// Lowest predicted height above the minimum safe altitude (ft), negative when below
+    pub minimum_margin: f64,
+    /// Minimum safe altitude where the margin is lowest (ft)
+    pub minimum_safe_altitude: f64,
+}
+
+/// Predict the aircraft along its current track and vertical rate and warn
+/// if it will descend below the minimum safe altitude within `look_ahead` seconds
+///
+/// Severity uses the conflict bands: critical within 30 s, warning within
+/// 60 s and advisory within 2 minutes, or sooner if the predicted infringement
+/// is deep relative to the clearance. Primary-only targets without an
+/// altitude are never warned.
+pub fn check_msaw(aircraft: &AircraftState, terrain: &TerrainModel, look_ahead: f64) -> MsawInfo {
+    let mut info = MsawInfo {
+        severity: ConflictSeverity::None,
+        time_to_violation: f64::INFINITY,
+        minimum_margin: f64::INFINITY,
+        minimum_safe_altitude: 0.0,
+    };
+    if !aircraft.altitude.is_finite() {
+        return info;
+    }
+
+    let mut time = 0.0;
+    while time <= look_ahead {
+        let state = predict_position(aircraft, time);
+        let msa = terrain.minimum_safe_altitude(state.x, state.y);
+        let margin = state.altitude - msa;
+        if margin < info.minimum_margin {
+            info.minimum_margin = margin;
+            info.minimum_safe_altitude = msa;
+        }
+        if margin < 0.0 && info.time_to_violation.is_infinite() {
+            info.time_to_violation = time;
+        }
+        time += 1.0;
+    }
+
+    if info.time_to_violation.is_finite() {
+        // Lowest height above terrain takes the place of miss distance
+        let height = (terrain.clearance + info.minimum_margin).max(0.0);
+        info.severity = classify_severity(info.time_to_violation, height, terrain.clearance).severity;
+        if info.severity == ConflictSeverity::None {
+            info.severity = ConflictSeverity::Advisory;
+        }
+    }
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 3000 ft ridge 10 nm east of the origin and a 1500 ft mast 5 nm north
+    fn terrain() -> TerrainModel {
+        let mut elevations = vec![200.0; 20 * 4];
+        for row in 0..4 {
+            elevations[row * 20 + 10] = 3000.0;
+        }
+        let grid = TerrainGrid::new(0.0, -2.0, 1.0, 20, 4, elevations).unwrap();
+        TerrainModel::new()
+            .with_grid(grid)
+            .with_obstacle(Obstacle::new(vec![(-0.1, 4.9), (0.1, 4.9), (0.1, 5.1), (-0.1, 5.1)], 1500.0))
+    }
+
+    #[test]
+    fn test_descent_into_ridge() {
+        let terrain = terrain();
+        // 360 kt towards the ridge, reaching it after 90 s
+        let level = AircraftState::new(0.5, 0.0, 5000.0, 90.0, 360.0);
+        assert_eq!(check_msaw(&level, &terrain, 120.0).severity, ConflictSeverity::None);
+
+        let descending = AircraftState::new(0.5, 0.0, 5000.0, 90.0, 360.0).with_vertical_rate(-600.0);
+        let info = check_msaw(&descending, &terrain, 120.0);
+        assert!(info.time_to_violation > 60.0 && info.time_to_violation < 120.0);
+        assert_eq!(info.severity, ConflictSeverity::Advisory);
+        assert_eq!(info.minimum_safe_altitude, 4000.0);
+
+        // A steeper descent would fly into the ridge itself
+        let steep = descending.with_vertical_rate(-1500.0);
+        assert_eq!(check_msaw(&steep, &terrain, 120.0).severity, ConflictSeverity::Critical);
+
+        let low = AircraftState::new(8.0, 0.0, 3500.0, 90.0, 360.0);
+        assert_eq!(check_msaw(&low, &terrain, 120.0).severity, ConflictSeverity::Critical);
+    }
+
+    #[test]
+    fn test_cold_temperature_raises_minimum() {
+        // Level 100 ft above the ridge's minimum safe altitude
+        let aircraft = AircraftState::new(0.5, 0.0, 4100.0, 90.0, 360.0);
+        assert_eq!(check_msaw(&aircraft, &terrain(), 120.0).severity, ConflictSeverity::None);
+
+        // At -30 C the 4000 ft minimum needs 760 ft more
+        let cold = terrain().with_temperature(0.0, -30.0);
+        let info = check_msaw(&aircraft, &cold, 120.0);
+        assert_eq!(info.minimum_safe_altitude, 4760.0);
+        assert_ne!(info.severity, ConflictSeverity::None);
+    }
+
+    #[test]
+    fn test_obstacles_and_primary_targets() {
+        let terrain = terrain();
+        let towards_mast = AircraftState::new(0.0, 0.0, 2000.0, 0.0, 240.0);
+        let info = check_msaw(&towards_mast, &terrain, 120.0);
+        assert_eq!(info.minimum_safe_altitude, 2500.0);
+        assert!(info.time_to_violation > 40.0 && info.time_to_violation < 60.0);
+        assert_eq!(info.severity, ConflictSeverity::Warning);
+
+        let primary = AircraftState::new(0.0, 0.0, f64::NAN, 0.0, 240.0);
+        assert_eq!(check_msaw(&primary, &terrain, 120.0).severity, ConflictSeverity::None);
+    }
+}
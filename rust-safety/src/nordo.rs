@@ -0,0 +1,166 @@
+/*!
+ * NORDO MODULE
+ * Lost-communication (squawk 7600) procedure prediction
+ */
+
+use crate::comms::CommTracker;
+use crate::route::{predict_along_route, AltitudeRestriction, RoutePoint, RouteWaypoint};
+use crate::separation::predict_position;
+use crate::AircraftState;
+
+/// Time a NORDO aircraft keeps its last assigned level and speed (7 minutes)
+pub const LOST_COMM_HOLD_TIME: f64 = 420.0;
+
+/// Remaining flight plan a NORDO aircraft will rejoin
+#[derive(Debug, Clone, PartialEq)]
+pub struct LostCommProcedure {
+    /// Remaining flight plan, ending at the expected approach fix
+    pub flight_plan: Vec<RouteWaypoint>,
+    /// Filed cruising level the aircraft climbs to after the hold time
+    pub filed_altitude: f64,
+}
+
+impl LostCommProcedure {
+    pub fn new(flight_plan: Vec<RouteWaypoint>, filed_altitude: f64) -> Self {
+        LostCommProcedure {
+            flight_plan,
+            filed_altitude,
+        }
+    }
+}
+
+/// Predict a NORDO aircraft following lost-communication procedures
+///
+/// The aircraft holds its last assigned heading, speed and level until
+/// `LOST_COMM_HOLD_TIME` after the failure, then proceeds along its flight
+/// plan at the higher of its current and filed level, meeting any published
+/// restrictions on the way to the expected approach fix.
+pub fn predict_lost_comm(
+    aircraft: &AircraftState,
+    time_since_failure: f64,
+    procedure: &LostCommProcedure,
+    duration: f64,
+    time_step: f64,
+) -> Vec<RoutePoint> {
+    let mut points = Vec::new();
+    if time_step <= 0.0 {
+        return points;
+    }
+
+    let hold_remaining = (LOST_COMM_HOLD_TIME - time_since_failure.max(0.0)).clamp(0.0, duration);
+
+    let mut time = 0.0;
+    let mut state = *aircraft;
+    points.push(RoutePoint { time, state, waypoint_index: 0 });
+    while time + time_step <= hold_remaining {
+        time += time_step;
+        state = predict_position(aircraft, time);
+        points.push(RoutePoint { time, state, waypoint_index: 0 });
+    }
+
+    // Unrestricted waypoints are flown at or above the filed level
+    let route: Vec<RouteWaypoint> = procedure
+        .flight_plan
+        .iter()
+        .map(|wp| match wp.altitude {
+            AltitudeRestriction::None => RouteWaypoint {
+                altitude: AltitudeRestriction::AtOrAbove(procedure.filed_altitude),
+                ..wp.clone()
+            },
+            _ => wp.clone(),
+        })
+        .collect();
+
+    let start = time;
+    points.extend(
+        predict_along_route(&state, &route, duration - start, time_step)
+            .into_iter()
+            .skip(1)
+            .map(|point| RoutePoint {
+                time: start + point.time,
+                ..point
+            }),
+    );
+
+    points
+}
+
+/// Time until an aircraft on its current track loses separation with a NORDO aircraft
+pub fn probe_against_nordo(
+    aircraft: &AircraftState,
+    nordo: &AircraftState,
+    time_since_failure: f64,
+    procedure: &LostCommProcedure,
+    horizontal_separation: f64,
+    vertical_separation: f64,
+    look_ahead_time: f64,
+) -> Option<f64> {
+    predict_lost_comm(nordo, time_since_failure, procedure, look_ahead_time, 1.0)
+        .iter()
+        .find(|point| {
+            let own = predict_position(aircraft, point.time);
+            let dx = own.x - point.state.x;
+            let dy = own.y - point.state.y;
+            (dx * dx + dy * dy).sqrt() < horizontal_separation
+                && (own.altitude - point.state.altitude).abs() < vertical_separation
+        })
+        .map(|point| point.time)
+}
+
+impl CommTracker {
+    /// Seconds since a track's radio failed, if it is NORDO
+    pub fn time_since_radio_failure(&self, track: u64, now: f64) -> Option<f64> {
+        if self.is_radio_failed(track) {
+            self.since(track).map(|since| now - since)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn procedure() -> LostCommProcedure {
+        LostCommProcedure::new(
+            vec![
+                RouteWaypoint::new("ENRTE", 0.0, -40.0, AltitudeRestriction::None, None),
+                RouteWaypoint::new("IAF", 20.0, -40.0, AltitudeRestriction::At(5000.0), Some(210.0)),
+            ],
+            12000.0,
+        )
+    }
+
+    #[test]
+    fn test_holds_then_rejoins_flight_plan() {
+        // Vectored east at 8000 ft when the radio failed two minutes ago
+        let nordo = AircraftState::new(0.0, 0.0, 8000.0, 90.0, 300.0);
+        let path = predict_lost_comm(&nordo, 120.0, &procedure(), 900.0, 1.0);
+
+        // Still on the vector five minutes later
+        let at_hold_end = path.iter().find(|p| p.time == 300.0).unwrap();
+        assert_eq!(at_hold_end.state.heading, 90.0);
+        assert_eq!(at_hold_end.state.altitude, 8000.0);
+
+        // Then turns towards the flight plan and climbs to the filed level
+        let later = path.iter().find(|p| p.time == 600.0).unwrap();
+        assert!(later.state.altitude > 8000.0);
+        assert!(later.state.heading > 180.0);
+    }
+
+    #[test]
+    fn test_probe_uses_lost_comm_intent() {
+        let nordo = AircraftState::new(0.0, 0.0, 8000.0, 90.0, 300.0);
+
+        // Traffic level at 8000 ft sitting on the NORDO aircraft's vector
+        let traffic = AircraftState::new(20.0, 0.0, 8000.0, 0.0, 0.0);
+        assert!(probe_against_nordo(&traffic, &nordo, 0.0, &procedure(), 3.0, 1000.0, 300.0).is_some());
+
+        // Elapsed failure time comes from the comm tracker
+        let mut comms = CommTracker::new();
+        comms.check_in(1, "WEST", 0.0);
+        comms.fail_radio(1, 100.0);
+        assert_eq!(comms.time_since_radio_failure(1, 160.0), Some(60.0));
+    }
+}
@@ -0,0 +1,134 @@
+/*!
+ * OWN-SHIP MODULE
+ * Traffic relative to one reference track, for cockpit-style traffic displays
+ */
+
+use atc_safety_core::velocity;
+
+use crate::config::SafetyConfig;
+use crate::conflict::{detect_conflict, ConflictInfo};
+use crate::traffic::TrafficSet;
+use crate::{normalize_heading, AircraftState};
+
+/// One intruder as seen from own ship
+#[derive(Debug, Clone, Copy)]
+pub struct RelativeTraffic {
+    pub track: u64,
+    /// Horizontal distance (nm)
+    pub range: f64,
+    /// Bearing from own nose, clockwise (degrees)
+    pub relative_bearing: f64,
+    /// Position in the heading-up frame: x to the right, y ahead (nm)
+    pub relative_x: f64,
+    pub relative_y: f64,
+    /// Intruder altitude above own ship (ft)
+    pub relative_altitude: f64,
+    /// Rate the range is shrinking (kt); negative when opening
+    pub closure_rate: f64,
+    pub converging: bool,
+    pub conflict: ConflictInfo,
+}
+
+impl RelativeTraffic {
+    /// Signed relative altitude in hundreds of feet, as shown beside a traffic symbol
+    pub fn altitude_tag(&self) -> String {
+        if self.relative_altitude.is_nan() {
+            return String::new();
+        }
+        let hundreds = (self.relative_altitude / 100.0).round() as i64;
+        if hundreds == 0 {
+            "00".to_string()
+        } else {
+            format!("{:+03}", hundreds)
+        }
+    }
+}
+
+/// Relative picture of one intruder from own ship
+pub fn relative_to(own: &AircraftState, track: u64, intruder: &AircraftState, config: &SafetyConfig) -> RelativeTraffic {
+    let dx = intruder.x - own.x;
+    let dy = intruder.y - own.y;
+    let range = (dx * dx + dy * dy).sqrt();
+
+    let (own_vx, own_vy) = velocity(&(*own).into());
+    let (vx, vy) = velocity(&(*intruder).into());
+    let closure_rate = if range > 0.0 {
+        -(dx * (vx - own_vx) + dy * (vy - own_vy)) / range * 3600.0
+    } else {
+        0.0
+    };
+
+    let bearing = dx.atan2(dy).to_degrees();
+    let heading = own.heading.to_radians();
+    RelativeTraffic {
+        track,
+        range,
+        relative_bearing: normalize_heading(bearing - own.heading),
+        relative_x: dx * heading.cos() - dy * heading.sin(),
+        relative_y: dx * heading.sin() + dy * heading.cos(),
+        relative_altitude: intruder.altitude - own.altitude,
+        closure_rate,
+        converging: closure_rate > 0.0,
+        conflict: detect_conflict(
+            own,
+            intruder,
+            &config.alert_standards(),
+            config.look_ahead_time,
+        ),
+    }
+}
+
+/// Every other track relative to `own`, nearest first
+///
+/// Returns `None` if own ship is not in the traffic set.
+pub fn own_ship_picture(traffic: &TrafficSet, own: u64, config: &SafetyConfig) -> Option<Vec<RelativeTraffic>> {
+    let own_state = traffic.get(own)?;
+    let mut picture: Vec<RelativeTraffic> = traffic
+        .iter()
+        .filter(|(id, _)| *id != own)
+        .map(|(id, state)| relative_to(&own_state, id, &state, config))
+        .collect();
+    picture.sort_by(|a, b| a.range.total_cmp(&b.range));
+    Some(picture)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conflict::ConflictSeverity;
+
+    #[test]
+    fn test_heading_up_geometry() {
+        let config = SafetyConfig::default();
+        // Own ship heading east; intruder 10 nm north is on the left
+        let own = AircraftState::new(0.0, 0.0, 10000.0, 90.0, 300.0);
+        let intruder = AircraftState::new(0.0, 10.0, 10700.0, 90.0, 300.0);
+
+        let relative = relative_to(&own, 2, &intruder, &config);
+        assert!((relative.range - 10.0).abs() < 1e-9);
+        assert!((relative.relative_bearing - 270.0).abs() < 1e-9);
+        assert!((relative.relative_x + 10.0).abs() < 1e-9);
+        assert!(relative.relative_y.abs() < 1e-9);
+        assert_eq!(relative.altitude_tag(), "+07");
+        assert!(relative.closure_rate.abs() < 1e-9);
+        assert!(!relative.converging);
+    }
+
+    #[test]
+    fn test_picture_sorted_with_alerts() {
+        let mut traffic = TrafficSet::new();
+        traffic.insert(1, AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0)).unwrap();
+        traffic.insert(2, AircraftState::new(0.0, 20.0, 10000.0, 90.0, 250.0)).unwrap();
+        traffic.insert(3, AircraftState::new(0.0, 8.0, 9500.0, 180.0, 250.0)).unwrap();
+
+        let picture = own_ship_picture(&traffic, 1, &SafetyConfig::default()).unwrap();
+        assert_eq!(picture.len(), 2);
+        assert_eq!(picture[0].track, 3);
+        assert!((picture[0].closure_rate - 500.0).abs() < 1e-6);
+        assert!(picture[0].converging);
+        assert_eq!(picture[0].altitude_tag(), "-05");
+        assert_ne!(picture[0].conflict.severity, ConflictSeverity::None);
+
+        assert!(own_ship_picture(&traffic, 9, &SafetyConfig::default()).is_none());
+    }
+}
@@ -0,0 +1,121 @@
+/*!
+ * POP-UP MODULE
+ * Immediate conflict check for tracks appearing between sweeps
+ */
+
+use crate::config::SafetyConfig;
+use crate::conflict::{scan_conflict, ConflictSeverity};
+use crate::traffic::{CapacityError, TrafficSet};
+use crate::AircraftState;
+
+/// Look-ahead of the pop-up check (seconds)
+const POP_UP_HORIZON: f64 = 60.0;
+
+/// Horizontal separation multiplier covering the uncertainty of a new track
+const POP_UP_BUFFER: f64 = 1.5;
+
+/// Existing track in short-term conflict with a pop-up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PopUpConflict {
+    pub track: u64,
+    pub severity: ConflictSeverity,
+    pub time_to_conflict: f64,
+    pub minimum_distance: f64,
+}
+
+/// Short-horizon conflict check of a new track against the traffic set
+///
+/// The horizontal minimum is widened by half and, for primary-only targets
+/// without a reported altitude, every track counts as vertically co-altitude.
+/// Results are soonest first; the next full sweep refines them.
+pub fn check_pop_up(
+    traffic: &TrafficSet,
+    id: u64,
+    state: &AircraftState,
+    altitude_known: bool,
+    config: &SafetyConfig,
+) -> Vec<PopUpConflict> {
+    let horizontal_separation = config.horizontal_separation * POP_UP_BUFFER;
+    let fastest = traffic.speeds().iter().copied().fold(0.0, f64::max);
+    let range = horizontal_separation + (state.speed + fastest) * POP_UP_HORIZON / 3600.0;
+
+    let mut conflicts: Vec<PopUpConflict> = traffic
+        .within_range(state.x, state.y, range)
+        .into_iter()
+        .filter_map(|slot| {
+            let track = traffic.id_at(slot).filter(|&track| track != id)?;
+            let other = traffic.state_at(slot);
+            let info = scan_conflict(
+                state,
+                &other,
+                horizontal_separation,
+                config.vertical_separation,
+                POP_UP_HORIZON,
+                config.time_step,
+                |a, b| if altitude_known { (a.altitude - b.altitude).abs() } else { 0.0 },
+            );
+            (info.time_to_conflict >= 0.0).then_some(PopUpConflict {
+                track,
+                severity: info.severity,
+                time_to_conflict: info.time_to_conflict,
+                minimum_distance: info.minimum_distance,
+            })
+        })
+        .collect();
+
+    conflicts.sort_by(|a, b| {
+        a.time_to_conflict
+            .total_cmp(&b.time_to_conflict)
+            .then(a.track.cmp(&b.track))
+    });
+    conflicts
+}
+
+impl TrafficSet {
+    /// Insert a brand-new track and check it immediately, without waiting for a sweep
+    pub fn insert_pop_up(
+        &mut self,
+        id: u64,
+        state: AircraftState,
+        altitude_known: bool,
+        config: &SafetyConfig,
+    ) -> Result<Vec<PopUpConflict>, CapacityError> {
+        self.insert(id, state)?;
+        Ok(check_pop_up(self, id, &state, altitude_known, config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn traffic() -> TrafficSet {
+        let mut traffic = TrafficSet::new();
+        traffic.insert(1, AircraftState::new(0.0, 0.0, 10000.0, 90.0, 250.0)).unwrap();
+        traffic.insert(2, AircraftState::new(40.0, 40.0, 10000.0, 90.0, 250.0)).unwrap();
+        traffic
+    }
+
+    #[test]
+    fn test_pop_up_flags_nearby_traffic() {
+        let mut traffic = traffic();
+        let config = SafetyConfig::default();
+
+        let conflicts = traffic
+            .insert_pop_up(7, AircraftState::new(4.0, 3.0, 10200.0, 270.0, 120.0), true, &config)
+            .unwrap();
+        assert!(traffic.contains(7));
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].track, 1);
+    }
+
+    #[test]
+    fn test_unknown_altitude_is_conservative() {
+        let traffic = traffic();
+        let config = SafetyConfig::default();
+        let primary = AircraftState::new(4.0, 3.0, 0.0, 270.0, 120.0);
+
+        assert!(check_pop_up(&traffic, 7, &primary, true, &config).is_empty());
+        assert_eq!(check_pop_up(&traffic, 7, &primary, false, &config).len(), 1);
+    }
+}
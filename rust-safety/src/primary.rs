@@ -0,0 +1,95 @@
+/*!
+ * PRIMARY TARGET MODULE
+ * Degraded tracking of primary radar returns without altitude
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::config::SafetyConfig;
+use crate::conflict::{scan_conflict, ConflictInfo};
+use crate::validation::is_primary_only;
+use crate::AircraftState;
+
+/// How an unknown altitude is treated in vertical checks
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnknownAltitudePolicy {
+    /// Assume the target may be at any altitude
+    #[default]
+    ConflictsWithAll,
+    /// Skip vertical checks against it entirely
+    Ignore,
+}
+
+/// Primary-only target at a position with a tracked heading and ground speed
+pub fn primary_target(x: f64, y: f64, heading: f64, speed: f64) -> AircraftState {
+    AircraftState::new(x, y, f64::NAN, heading, speed)
+}
+
+/// Vertical distance honouring the unknown altitude policy
+pub fn degraded_vertical_distance(
+    aircraft1: &AircraftState,
+    aircraft2: &AircraftState,
+    policy: UnknownAltitudePolicy,
+) -> f64 {
+    if is_primary_only(aircraft1) || is_primary_only(aircraft2) {
+        match policy {
+            UnknownAltitudePolicy::ConflictsWithAll => 0.0,
+            UnknownAltitudePolicy::Ignore => f64::INFINITY,
+        }
+    } else {
+        (aircraft1.altitude - aircraft2.altitude).abs()
+    }
+}
+
+/// Conflict detection that still produces advisories for primary-only targets
+pub fn detect_conflict_degraded(
+    aircraft1: &AircraftState,
+    aircraft2: &AircraftState,
+    config: &SafetyConfig,
+    policy: UnknownAltitudePolicy,
+) -> ConflictInfo {
+    scan_conflict(
+        aircraft1,
+        aircraft2,
+        config.horizontal_separation,
+        config.vertical_separation,
+        config.look_ahead_time,
+        config.time_step,
+        |a, b| degraded_vertical_distance(a, b, policy),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SeparationStandards;
+    use crate::conflict::{detect_conflict, ConflictSeverity};
+
+    #[test]
+    fn test_primary_target_conflicts_with_all_altitudes() {
+        let config = SafetyConfig::default();
+        let own = AircraftState::new(0.0, 0.0, 35000.0, 90.0, 450.0);
+        let primary = primary_target(10.0, 0.0, 270.0, 120.0);
+
+        // The plain probe never sees a NaN altitude as co-altitude
+        assert_eq!(detect_conflict(&own, &primary, &SeparationStandards::terminal(), 300.0).severity, ConflictSeverity::None);
+
+        let info = detect_conflict_degraded(&own, &primary, &config, UnknownAltitudePolicy::default());
+        assert!(info.time_to_conflict >= 0.0);
+        assert_ne!(info.severity, ConflictSeverity::None);
+    }
+
+    #[test]
+    fn test_ignore_policy_and_known_altitudes() {
+        let config = SafetyConfig::default();
+        let own = AircraftState::new(0.0, 0.0, 35000.0, 90.0, 450.0);
+        let primary = primary_target(10.0, 0.0, 270.0, 120.0);
+
+        let info = detect_conflict_degraded(&own, &primary, &config, UnknownAltitudePolicy::Ignore);
+        assert_eq!(info.time_to_conflict, -1.0);
+
+        let other = AircraftState::new(10.0, 0.0, 30000.0, 270.0, 450.0);
+        assert_eq!(degraded_vertical_distance(&own, &other, UnknownAltitudePolicy::ConflictsWithAll), 5000.0);
+    }
+}
@@ -0,0 +1,325 @@
+/*!
+ * PROBE MODULE
+ * What-if conflict, airspace and terrain probe along an ad-hoc route
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::airspace::{Airspace, RestrictionKind};
+use crate::config::SafetyConfig;
+use crate::conflict::{classify_severity, ConflictSeverity};
+use crate::mora::mora_along_route;
+use crate::msaw::TerrainModel;
+use crate::route::{predict_along_route, AltitudeRestriction, RouteWaypoint};
+use crate::separation::predict_position;
+use crate::AircraftState;
+
+/// Values per waypoint in a probe route buffer: x, y, altitude, speed
+pub const PROBE_WAYPOINT_STRIDE: usize = 4;
+
+/// Loss of separation predicted along the probed route
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProbeConflict {
+    pub callsign: String,
+    /// Seconds until separation is first lost
+    pub time: f64,
+    /// Seconds until the last predicted point still without separation
+    pub end_time: f64,
+    pub duration: f64,
+    /// Waypoint being flown to when separation is lost
+    pub waypoint_index: usize,
+    /// Closest horizontal approach while vertically unseparated (nm)
+    pub minimum_distance: f64,
+    pub severity: ConflictSeverity,
+    /// Conflict segment of the probed route then of the traffic, each as start x, y, end x, y
+    pub segments: Vec<f64>,
+}
+
+/// Active special use area the probed route penetrates
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProbeAreaHit {
+    pub area: String,
+    pub kind: RestrictionKind,
+    pub time: f64,
+    pub waypoint_index: usize,
+}
+
+/// Leg cleared below the minimum off-route altitude
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProbeMoraHit {
+    /// Waypoint the leg is flown to
+    pub waypoint_index: usize,
+    /// Altitude the leg is flown to (ft)
+    pub cleared_altitude: f64,
+    /// Controlling off-route altitude along the leg (ft)
+    pub mora: f64,
+}
+
+/// Everything wrong with a route, for redrawing while it is dragged
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RouteProbe {
+    conflicts: Vec<ProbeConflict>,
+    areas: Vec<ProbeAreaHit>,
+    mora: Vec<ProbeMoraHit>,
+    /// Seconds until the route first goes below the minimum safe altitude, infinite if never
+    pub terrain_time: f64,
+    /// Lowest height above the minimum safe altitude along the route (ft)
+    pub terrain_margin: f64,
+}
+
+#[wasm_bindgen]
+impl RouteProbe {
+    /// Traffic conflicts ordered by time
+    pub fn conflicts(&self) -> Vec<ProbeConflict> {
+        self.conflicts.clone()
+    }
+
+    /// Area penetrations ordered by time
+    pub fn areas(&self) -> Vec<ProbeAreaHit> {
+        self.areas.clone()
+    }
+
+    /// Legs whose cleared altitude is below the controlling MORA, in route order
+    pub fn mora(&self) -> Vec<ProbeMoraHit> {
+        self.mora.clone()
+    }
+
+    pub fn is_clear(&self) -> bool {
+        self.conflicts.is_empty() && self.areas.is_empty() && self.mora.is_empty() && self.terrain_time.is_infinite()
+    }
+}
+
+/// Read a route drawn in JavaScript as flat (x, y, altitude, speed) values
+///
+/// A NaN altitude or speed leaves that waypoint unconstrained, so the
+/// aircraft keeps its current altitude or speed.
+pub fn route_from_buffer(route: &[f64]) -> Result<Vec<RouteWaypoint>, String> {
+    if route.is_empty() || !route.len().is_multiple_of(PROBE_WAYPOINT_STRIDE) {
+        return Err(format!(
+            "route needs a non-empty multiple of {} values, got {}",
+            PROBE_WAYPOINT_STRIDE,
+            route.len()
+        ));
+    }
+    Ok(route
+        .chunks_exact(PROBE_WAYPOINT_STRIDE)
+        .enumerate()
+        .map(|(i, p)| {
+            let altitude = if p[2].is_nan() { AltitudeRestriction::None } else { AltitudeRestriction::At(p[2]) };
+            let speed = if p[3].is_nan() { None } else { Some(p[3]) };
+            RouteWaypoint::new(&(i + 1).to_string(), p[0], p[1], altitude, speed)
+        })
+        .collect())
+}
+
+/// Probe an aircraft flying `waypoints` against traffic, airspace and terrain
+///
+/// The route is predicted as in `predict_along_route` at the config's time
+/// step over its look-ahead, with traffic dead-reckoned alongside and judged
+/// against the same alert distance as the sweep, buffer included. Areas are
+/// checked against their schedule from `now` using the airspace buffers, and
+/// terrain against the model's minimum safe altitude. With a MORA grid in
+/// the terrain model, each leg's cleared altitude (the waypoint's, or the
+/// one carried from before when unconstrained) is checked against the
+/// highest grid altitude it crosses. Traffic without an altitude is never
+/// taken as vertically separated.
+pub fn probe_route(
+    aircraft: &AircraftState,
+    waypoints: &[RouteWaypoint],
+    traffic: &[(String, AircraftState)],
+    terrain: &TerrainModel,
+    airspace: &Airspace,
+    config: &SafetyConfig,
+    now: f64,
+) -> RouteProbe {
+    let profile = predict_along_route(aircraft, waypoints, config.look_ahead_time, config.time_step);
+    let mut probe = RouteProbe {
+        conflicts: Vec::new(),
+        areas: Vec::new(),
+        mora: Vec::new(),
+        terrain_time: f64::INFINITY,
+        terrain_margin: f64::INFINITY,
+    };
+
+    for (callsign, other) in traffic {
+        let mut entry: Option<(f64, usize, AircraftState, AircraftState)> = None;
+        let mut exit: Option<(f64, AircraftState, AircraftState)> = None;
+        let mut separated_again = false;
+        let mut minimum_distance = f64::INFINITY;
+        for point in &profile {
+            let predicted = predict_position(other, point.time);
+            let vertical = (point.state.altitude - predicted.altitude).abs();
+            if vertical >= config.vertical_separation {
+                separated_again |= entry.is_some();
+                continue;
+            }
+            let horizontal = (point.state.x - predicted.x).hypot(point.state.y - predicted.y);
+            minimum_distance = minimum_distance.min(horizontal);
+            if horizontal >= config.alert_distance() {
+                separated_again |= entry.is_some();
+            } else if !separated_again {
+                // Only the first interval is reported
+                entry.get_or_insert((point.time, point.waypoint_index, point.state, predicted));
+                exit = Some((point.time, point.state, predicted));
+            }
+        }
+        if let (Some((time, waypoint_index, own_entry, traffic_entry)), Some((end_time, own_exit, traffic_exit))) =
+            (entry, exit)
+        {
+            probe.conflicts.push(ProbeConflict {
+                callsign: callsign.clone(),
+                time,
+                end_time,
+                duration: end_time - time,
+                waypoint_index,
+                minimum_distance,
+                severity: classify_severity(time, minimum_distance, config.alert_distance()).severity,
+                segments: vec![
+                    own_entry.x,
+                    own_entry.y,
+                    own_exit.x,
+                    own_exit.y,
+                    traffic_entry.x,
+                    traffic_entry.y,
+                    traffic_exit.x,
+                    traffic_exit.y,
+                ],
+            });
+        }
+    }
+
+    for area in airspace.areas() {
+        let hit = profile.iter().find(|point| {
+            area.is_active(now + point.time)
+                && area.contains(
+                    point.state.x,
+                    point.state.y,
+                    point.state.altitude,
+                    airspace.lateral_buffer,
+                    airspace.vertical_buffer,
+                )
+        });
+        if let Some(point) = hit {
+            probe.areas.push(ProbeAreaHit {
+                area: area.name.clone(),
+                kind: area.kind,
+                time: point.time,
+                waypoint_index: point.waypoint_index,
+            });
+        }
+    }
+
+    for point in &profile {
+        let margin = point.state.altitude - terrain.minimum_safe_altitude(point.state.x, point.state.y);
+        probe.terrain_margin = probe.terrain_margin.min(margin);
+        if margin < 0.0 && probe.terrain_time.is_infinite() {
+            probe.terrain_time = point.time;
+        }
+    }
+
+    if let Some(grid) = &terrain.mora {
+        let fixes: Vec<(f64, f64)> = waypoints.iter().map(|waypoint| (waypoint.x, waypoint.y)).collect();
+        let mut cleared_altitude = aircraft.altitude;
+        for (waypoint_index, (waypoint, mora)) in waypoints
+            .iter()
+            .zip(mora_along_route(grid, (aircraft.x, aircraft.y), &fixes))
+            .enumerate()
+        {
+            cleared_altitude = waypoint.altitude.clamp(cleared_altitude);
+            if let Some(mora) = mora.filter(|&mora| cleared_altitude < mora) {
+                probe.mora.push(ProbeMoraHit { waypoint_index, cleared_altitude, mora });
+            }
+        }
+    }
+
+    probe.conflicts.sort_by(|a, b| a.time.total_cmp(&b.time));
+    probe.areas.sort_by(|a, b| a.time.total_cmp(&b.time));
+    probe
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::airspace::RestrictedArea;
+
+    #[test]
+    fn test_route_buffer() {
+        let waypoints = route_from_buffer(&[0.0, 10.0, 8000.0, f64::NAN, 5.0, 10.0, f64::NAN, 220.0]).unwrap();
+        assert_eq!(waypoints.len(), 2);
+        assert_eq!(waypoints[0].altitude, AltitudeRestriction::At(8000.0));
+        assert_eq!(waypoints[0].speed, None);
+        assert_eq!(waypoints[1].altitude, AltitudeRestriction::None);
+        assert_eq!(waypoints[1].speed, Some(220.0));
+        assert!(route_from_buffer(&[]).is_err());
+        assert!(route_from_buffer(&[0.0, 1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn test_probe_finds_traffic_area_and_terrain() {
+        let config = SafetyConfig::default();
+        let aircraft = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 360.0);
+        // North 6 nm then east, descending to 2000 ft at the turn
+        let route = route_from_buffer(&[0.0, 6.0, 2000.0, f64::NAN, 30.0, 6.0, f64::NAN, f64::NAN]).unwrap();
+
+        let traffic = vec![
+            ("LOW".to_string(), AircraftState::new(12.0, 6.0, 2000.0, 0.0, 0.0)),
+            ("HIGH".to_string(), AircraftState::new(12.0, 6.0, 9000.0, 0.0, 0.0)),
+        ];
+        let mut airspace = Airspace::new();
+        airspace.register(RestrictedArea::new(
+            "R1",
+            RestrictionKind::Restricted,
+            vec![(14.0, 4.0), (18.0, 4.0), (18.0, 8.0), (14.0, 8.0)],
+            0.0,
+            5000.0,
+        ));
+        let terrain = TerrainModel::new().with_obstacle(crate::msaw::Obstacle::new(
+            vec![(21.0, 5.0), (22.0, 5.0), (22.0, 7.0), (21.0, 7.0)],
+            1500.0,
+        ));
+
+        let probe = probe_route(&aircraft, &route, &traffic, &terrain, &airspace, &config, 0.0);
+        let conflicts = probe.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].callsign, "LOW");
+        assert_eq!(conflicts[0].waypoint_index, 1);
+        assert!(conflicts[0].minimum_distance < 0.1);
+        // Flying through the stationary traffic: 3 nm either side at 0.1 nm/s
+        assert!((conflicts[0].duration - 59.0).abs() <= 1.0);
+
+        // A 2 nm buffer widens the conflict to 5 nm either side
+        let buffered = config.clone().with_buffer(2.0);
+        let probe = probe_route(&aircraft, &route, &traffic, &terrain, &airspace, &buffered, 0.0);
+        assert!((probe.conflicts()[0].duration - 99.0).abs() <= 1.0);
+        assert_eq!(conflicts[0].end_time, conflicts[0].time + conflicts[0].duration);
+        let segments = &conflicts[0].segments;
+        assert!((segments[0] - 9.0).abs() < 0.2 && (segments[2] - 15.0).abs() < 0.2);
+        assert_eq!(&segments[4..], &[12.0, 6.0, 12.0, 6.0]);
+        assert_eq!(probe.areas()[0].area, "R1");
+        // 19 nm and 26 nm along the route, less about 0.8 nm cut by the fly-by turn
+        assert!((180.0..=185.0).contains(&probe.areas()[0].time));
+        assert!((250.0..=255.0).contains(&probe.terrain_time));
+        assert!(!probe.is_clear());
+
+        // A MORA grid of 3000 ft flags the leg cleared to 2000 ft
+        let mora = crate::mora::MoraGrid::new(0.0, 0.0, 10.0, 4, 2, vec![3000.0; 8]).unwrap();
+        let gridded = TerrainModel::new().with_mora(mora);
+        let hits = probe_route(&aircraft, &route, &[], &gridded, &Airspace::new(), &config, 0.0).mora();
+        assert_eq!(hits.len(), 2);
+        assert_eq!((hits[0].waypoint_index, hits[0].cleared_altitude, hits[0].mora), (0, 2000.0, 3000.0));
+
+        // The same route kept at 10000 ft is clear of all three
+        let high = route_from_buffer(&[0.0, 6.0, f64::NAN, f64::NAN, 30.0, 6.0, f64::NAN, f64::NAN]).unwrap();
+        let probe = probe_route(&aircraft, &high, &traffic, &terrain, &airspace, &config, 0.0);
+        assert!(probe.is_clear());
+        assert_eq!(probe.terrain_margin, 10000.0 - 2500.0);
+    }
+}
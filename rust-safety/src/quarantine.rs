@@ -0,0 +1,187 @@
+/*!
+ * QUARANTINE MODULE
+ * Suppression of feeds that keep sending invalid states for the same ICAO address
+ */
+
+use std::collections::HashMap;
+
+use crate::validation::{validate_state, ValidationConfig};
+use crate::AircraftState;
+
+/// What happened to one incoming state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// Valid and passed on for processing
+    Accepted,
+    /// Invalid; counts towards quarantine
+    Rejected,
+    /// Address is quarantined, so the state was not examined
+    Suppressed,
+}
+
+/// Strike and quarantine record for one ICAO address
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuarantineEntry {
+    pub icao: u32,
+    /// Invalid states since the last valid one or release
+    pub strikes: u32,
+    /// Times the address has been quarantined without an intervening valid state
+    pub level: u32,
+    /// End of the current quarantine (seconds), if any
+    pub until: Option<f64>,
+    /// States dropped while quarantined
+    pub suppressed: u64,
+}
+
+impl QuarantineEntry {
+    fn new(icao: u32) -> Self {
+        QuarantineEntry {
+            icao,
+            strikes: 0,
+            level: 0,
+            until: None,
+            suppressed: 0,
+        }
+    }
+
+    pub fn is_active(&self, time: f64) -> bool {
+        self.until.is_some_and(|until| time < until)
+    }
+}
+
+/// Automatic quarantine with exponential backoff
+///
+/// After `threshold` invalid states in a row an address is quarantined
+/// for `base_backoff` seconds, doubling each time it reoffends up to
+/// `max_backoff`. A valid state resets the backoff.
+#[derive(Debug, Clone)]
+pub struct InputQuarantine {
+    pub threshold: u32,
+    pub base_backoff: f64,
+    pub max_backoff: f64,
+    /// Limits states are validated against
+    pub validation: ValidationConfig,
+    entries: HashMap<u32, QuarantineEntry>,
+}
+
+impl InputQuarantine {
+    pub fn new(threshold: u32, base_backoff: f64, max_backoff: f64) -> Self {
+        InputQuarantine {
+            threshold: threshold.max(1),
+            base_backoff,
+            max_backoff,
+            validation: ValidationConfig::default(),
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn with_validation(mut self, validation: ValidationConfig) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Validate one state from `icao`, unless the address is quarantined
+    pub fn admit(&mut self, icao: u32, state: &AircraftState, time: f64) -> Admission {
+        let entry = self.entries.entry(icao).or_insert_with(|| QuarantineEntry::new(icao));
+        if entry.is_active(time) {
+            entry.suppressed += 1;
+            return Admission::Suppressed;
+        }
+        entry.until = None;
+
+        if validate_state(state, &self.validation) {
+            entry.strikes = 0;
+            entry.level = 0;
+            return Admission::Accepted;
+        }
+
+        entry.strikes += 1;
+        if entry.strikes >= self.threshold {
+            let backoff = self.base_backoff * 2f64.powi(entry.level.min(30) as i32);
+            entry.until = Some(time + backoff.min(self.max_backoff));
+            entry.level += 1;
+            entry.strikes = 0;
+        }
+        Admission::Rejected
+    }
+
+    /// Addresses quarantined at `time`, lowest address first
+    pub fn quarantined(&self, time: f64) -> Vec<QuarantineEntry> {
+        let mut active: Vec<QuarantineEntry> = self.entries.values().filter(|e| e.is_active(time)).copied().collect();
+        active.sort_by_key(|e| e.icao);
+        active
+    }
+
+    pub fn entry(&self, icao: u32) -> Option<&QuarantineEntry> {
+        self.entries.get(&icao)
+    }
+
+    /// Release an address and forget its history; false if it was unknown
+    pub fn clear(&mut self, icao: u32) -> bool {
+        self.entries.remove(&icao).is_some()
+    }
+
+    pub fn clear_all(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl Default for InputQuarantine {
+    /// Three strikes, 10 s first quarantine, capped at 10 minutes
+    fn default() -> Self {
+        InputQuarantine::new(3, 10.0, 600.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bad() -> AircraftState {
+        AircraftState::new(0.0, 0.0, -5000.0, 90.0, 250.0)
+    }
+
+    #[test]
+    fn test_backoff_doubles_for_repeat_offenders() {
+        let mut quarantine = InputQuarantine::default();
+        let icao = 0x4CA1B2;
+
+        for t in 0..3 {
+            assert_eq!(quarantine.admit(icao, &bad(), t as f64), Admission::Rejected);
+        }
+        assert_eq!(quarantine.entry(icao).unwrap().until, Some(12.0));
+        assert_eq!(quarantine.admit(icao, &bad(), 5.0), Admission::Suppressed);
+        assert_eq!(quarantine.admit(icao, &AircraftState::new(0.0, 0.0, 5000.0, 90.0, 250.0), 6.0), Admission::Suppressed);
+        assert_eq!(quarantine.entry(icao).unwrap().suppressed, 2);
+
+        for t in 12..15 {
+            quarantine.admit(icao, &bad(), t as f64);
+        }
+        assert_eq!(quarantine.entry(icao).unwrap().until, Some(34.0));
+        assert_eq!(quarantine.quarantined(20.0).len(), 1);
+        assert!(quarantine.quarantined(34.0).is_empty());
+    }
+
+    #[test]
+    fn test_valid_state_resets_and_clear_releases() {
+        let mut quarantine = InputQuarantine::new(2, 10.0, 15.0);
+        let good = AircraftState::new(0.0, 0.0, 5000.0, 90.0, 250.0);
+
+        quarantine.admit(1, &bad(), 0.0);
+        assert_eq!(quarantine.admit(1, &good, 1.0), Admission::Accepted);
+        quarantine.admit(1, &bad(), 2.0);
+        assert!(quarantine.quarantined(2.0).is_empty());
+
+        quarantine.admit(1, &bad(), 3.0);
+        quarantine.admit(2, &bad(), 3.0);
+        quarantine.admit(2, &bad(), 3.0);
+        let active: Vec<u32> = quarantine.quarantined(4.0).iter().map(|e| e.icao).collect();
+        assert_eq!(active, vec![1, 2]);
+
+        assert!(quarantine.clear(1));
+        assert!(!quarantine.clear(1));
+        assert_eq!(quarantine.admit(1, &good, 5.0), Admission::Accepted);
+        quarantine.clear_all();
+        assert!(quarantine.quarantined(5.0).is_empty());
+    }
+}
@@ -0,0 +1,224 @@
+/*!
+ * REGION MODULE
+ * Region-scoped overrides of separation minima and alerting settings
+ */
+
+use crate::config::SafetyConfig;
+use crate::conflict::{calculate_severity, classify_geometry, ConflictInfo};
+use crate::sector::point_in_polygon;
+use crate::separation::predict_position;
+use crate::traffic::TrafficSet;
+use crate::AircraftState;
+
+/// Settings that replace the base config inside a polygon and altitude band
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionOverride {
+    pub name: String,
+    pub boundary: Vec<(f64, f64)>,
+    pub floor: f64,
+    pub ceiling: f64,
+    pub horizontal_separation: Option<f64>,
+    pub vertical_separation: Option<f64>,
+    pub buffer: Option<f64>,
+}
+
+impl RegionOverride {
+    pub fn new(name: &str, boundary: Vec<(f64, f64)>, floor: f64, ceiling: f64) -> Self {
+        RegionOverride {
+            name: name.to_string(),
+            boundary,
+            floor,
+            ceiling,
+            horizontal_separation: None,
+            vertical_separation: None,
+            buffer: None,
+        }
+    }
+
+    pub fn with_minima(mut self, horizontal_separation: f64, vertical_separation: f64) -> Self {
+        self.horizontal_separation = Some(horizontal_separation);
+        self.vertical_separation = Some(vertical_separation);
+        self
+    }
+
+    pub fn with_buffer(mut self, buffer: f64) -> Self {
+        self.buffer = Some(buffer);
+        self
+    }
+
+    pub fn contains(&self, x: f64, y: f64, altitude: f64) -> bool {
+        altitude >= self.floor && altitude <= self.ceiling && point_in_polygon(x, y, &self.boundary)
+    }
+
+    fn apply(&self, config: &SafetyConfig) -> SafetyConfig {
+        let mut applied = config.clone();
+        applied.horizontal_separation = self.horizontal_separation.unwrap_or(config.horizontal_separation);
+        applied.vertical_separation = self.vertical_separation.unwrap_or(config.vertical_separation);
+        applied.buffer = self.buffer.unwrap_or(config.buffer);
+        applied
+    }
+}
+
+/// Base config with region overrides; the first matching region wins
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigOverlay {
+    pub base: SafetyConfig,
+    pub regions: Vec<RegionOverride>,
+}
+
+impl ConfigOverlay {
+    pub fn new(base: SafetyConfig) -> Self {
+        ConfigOverlay {
+            base,
+            regions: Vec::new(),
+        }
+    }
+
+    pub fn with_region(mut self, region: RegionOverride) -> Self {
+        self.regions.push(region);
+        self
+    }
+
+    /// Region whose overrides apply at a position
+    pub fn region_at(&self, x: f64, y: f64, altitude: f64) -> Option<&RegionOverride> {
+        self.regions.iter().find(|r| r.contains(x, y, altitude))
+    }
+
+    /// Config in force at a position
+    pub fn config_at(&self, x: f64, y: f64, altitude: f64) -> SafetyConfig {
+        self.region_at(x, y, altitude)
+            .map_or_else(|| self.base.clone(), |r| r.apply(&self.base))
+    }
+
+    /// Largest alerting distance and vertical minimum in any region
+    fn widest(&self) -> (f64, f64) {
+        self.regions
+            .iter()
+            .map(|r| r.apply(&self.base))
+            .fold((self.base.alert_distance(), self.base.vertical_separation), |(h, v), c| {
+                (h.max(c.alert_distance()), v.max(c.vertical_separation))
+            })
+    }
+}
+
+/// Conflict probe using the minima in force where the pair is predicted to be
+///
+/// Each step resolves the config at the midpoint of the two predicted
+/// positions, so a conflict is judged by the rules of the region it occurs in.
+pub fn detect_conflict_regional(
+    aircraft1: &AircraftState,
+    aircraft2: &AircraftState,
+    overlay: &ConfigOverlay,
+) -> ConflictInfo {
+    let base = &overlay.base;
+    let time_step = if base.time_step > 0.0 { base.time_step } else { 1.0 };
+
+    let mut min_distance = f64::INFINITY;
+    let mut conflict: Option<(f64, f64)> = None;
+    let mut time = 0.0;
+
+    while time <= base.look_ahead_time {
+        let a = predict_position(aircraft1, time);
+        let b = predict_position(aircraft2, time);
+        let dx = a.x - b.x;
+        let dy = a.y - b.y;
+        let horizontal = (dx * dx + dy * dy).sqrt();
+        min_distance = min_distance.min(horizontal);
+
+        if conflict.is_none() {
+            let config = overlay.config_at((a.x + b.x) / 2.0, (a.y + b.y) / 2.0, (a.altitude + b.altitude) / 2.0);
+            if horizontal < config.alert_distance() && (a.altitude - b.altitude).abs() < config.vertical_separation {
+                conflict = Some((time, config.horizontal_separation));
+            }
+        }
+        time += time_step;
+    }
+
+    let info = match conflict {
+        Some((time, minimum)) => ConflictInfo::new(calculate_severity(time, min_distance, minimum), time, min_distance),
+        None => ConflictInfo::new(calculate_severity(-1.0, min_distance, base.horizontal_separation), -1.0, min_distance),
+    };
+    info.with_geometry(classify_geometry(aircraft1, aircraft2))
+}
+
+/// Conflict between two tracks found by a regional sweep
+#[derive(Debug, Clone, Copy)]
+pub struct RegionalConflict {
+    pub first: u64,
+    pub second: u64,
+    pub info: ConflictInfo,
+}
+
+/// Probe every pair that could come within the widest regional minimum
+pub fn regional_conflicts(traffic: &TrafficSet, overlay: &ConfigOverlay) -> Vec<RegionalConflict> {
+    let (widest_h, widest_v) = overlay.widest();
+    let fastest = traffic.speeds().iter().copied().fold(0.0, f64::max);
+    let reach = widest_h + 2.0 * fastest * overlay.base.look_ahead_time / 3600.0;
+
+    let mut conflicts = Vec::new();
+    for (i, (first, a)) in traffic.iter().enumerate() {
+        for slot in traffic.within_range(a.x, a.y, reach) {
+            if slot <= i {
+                continue;
+            }
+            let b = traffic.state_at(slot);
+            if (a.altitude - b.altitude).abs() >= widest_v {
+                continue;
+            }
+
+            let info = detect_conflict_regional(&a, &b, overlay);
+            if info.time_to_conflict >= 0.0 {
+                if let Some(second) = traffic.id_at(slot) {
+                    conflicts.push(RegionalConflict { first, second, info });
+                }
+            }
+        }
+    }
+
+    conflicts.sort_by(|x, y| x.info.time_to_conflict.total_cmp(&y.info.time_to_conflict));
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overlay() -> ConfigOverlay {
+        // 3 nm inside the TMA square around the origin, 5 nm elsewhere
+        let tma = RegionOverride::new("TMA", vec![(-20.0, -20.0), (20.0, -20.0), (20.0, 20.0), (-20.0, 20.0)], 0.0, 24500.0)
+            .with_minima(3.0, 1000.0);
+        ConfigOverlay::new(SafetyConfig::new(5.0, 1000.0, 300.0, 1.0)).with_region(tma)
+    }
+
+    #[test]
+    fn test_config_resolved_by_position() {
+        let overlay = overlay();
+        assert_eq!(overlay.config_at(0.0, 0.0, 10000.0).horizontal_separation, 3.0);
+        assert_eq!(overlay.config_at(50.0, 0.0, 10000.0).horizontal_separation, 5.0);
+        assert_eq!(overlay.config_at(0.0, 0.0, 30000.0).horizontal_separation, 5.0);
+    }
+
+    #[test]
+    fn test_conflict_judged_where_it_occurs() {
+        let overlay = overlay();
+
+        // Passing 4 nm apart: fine inside the TMA, a conflict outside it
+        let a = AircraftState::new(-10.0, 0.0, 10000.0, 90.0, 250.0);
+        let b = AircraftState::new(10.0, 4.0, 10000.0, 270.0, 250.0);
+        assert_eq!(detect_conflict_regional(&a, &b, &overlay).time_to_conflict, -1.0);
+
+        let c = AircraftState::new(50.0, 0.0, 10000.0, 90.0, 250.0);
+        let d = AircraftState::new(70.0, 4.0, 10000.0, 270.0, 250.0);
+        assert!(detect_conflict_regional(&c, &d, &overlay).time_to_conflict >= 0.0);
+
+        let mut traffic = TrafficSet::new();
+        for (id, state) in [(1, a), (2, b), (3, c), (4, d)] {
+            traffic.insert(id, state).unwrap();
+        }
+        let found: Vec<(u64, u64)> = regional_conflicts(&traffic, &overlay)
+            .iter()
+            .map(|c| (c.first, c.second))
+            .collect();
+        assert_eq!(found, vec![(3, 4)]);
+    }
+}
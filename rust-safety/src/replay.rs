@@ -0,0 +1,197 @@
+/*!
+ * REPLAY MODULE
+ * Alert timeline fingerprints of recorded sessions for comparing crate versions
+ */
+
+use std::fmt::Write;
+
+use crate::config::{ConfigError, SafetyConfig};
+use crate::conflict::ConflictSeverity;
+use crate::engine::AlertEngine;
+use crate::traffic::TrafficSet;
+use crate::AircraftState;
+
+/// Onset times are rounded to this resolution in the fingerprint (seconds)
+const ONSET_RESOLUTION: f64 = 0.1;
+
+/// Every track's state at one instant of a recorded session
+#[derive(Debug, Clone)]
+pub struct RecordedFrame {
+    pub time: f64,
+    pub tracks: Vec<(u64, AircraftState)>,
+}
+
+impl RecordedFrame {
+    pub fn new(time: f64, tracks: Vec<(u64, AircraftState)>) -> Self {
+        RecordedFrame { time, tracks }
+    }
+}
+
+/// A pair first reaching a severity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlertOnset {
+    /// Lower track id
+    pub first: u64,
+    pub second: u64,
+    pub severity: ConflictSeverity,
+    /// Seconds after the first frame, rounded to 0.1 s
+    pub onset: f64,
+}
+
+/// How one onset differs from a baseline timeline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimelineChange {
+    /// In the baseline but not raised now
+    Missing(AlertOnset),
+    /// Raised now but not in the baseline
+    Added(AlertOnset),
+    /// Raised at a different time; `delta` is positive when later
+    Shifted { onset: AlertOnset, delta: f64 },
+}
+
+/// Normalized alert onsets of one replayed session, in time then pair order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AlertTimeline {
+    pub onsets: Vec<AlertOnset>,
+}
+
+impl AlertTimeline {
+    /// Canonical text form, one `first-second severity onset` line per onset
+    pub fn fingerprint(&self) -> String {
+        let mut text = String::new();
+        for onset in &self.onsets {
+            let _ = writeln!(text, "{}-{} {:?} {:.1}", onset.first, onset.second, onset.severity, onset.onset);
+        }
+        text
+    }
+
+    /// 64-bit FNV-1a digest of the fingerprint, stable across platforms and compilers
+    pub fn digest(&self) -> u64 {
+        self.fingerprint().bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    }
+
+    /// Differences from `baseline`, ignoring onset shifts up to `tolerance` seconds
+    pub fn compare(&self, baseline: &AlertTimeline, tolerance: f64) -> Vec<TimelineChange> {
+        let key = |o: &AlertOnset| (o.first, o.second, o.severity as u8);
+        let mut changes = Vec::new();
+
+        for old in &baseline.onsets {
+            match self.onsets.iter().find(|new| key(new) == key(old)) {
+                None => changes.push(TimelineChange::Missing(*old)),
+                Some(new) if (new.onset - old.onset).abs() > tolerance => changes.push(TimelineChange::Shifted {
+                    onset: *new,
+                    delta: new.onset - old.onset,
+                }),
+                Some(_) => {}
+            }
+        }
+        for new in &self.onsets {
+            if !baseline.onsets.iter().any(|old| key(old) == key(new)) {
+                changes.push(TimelineChange::Added(*new));
+            }
+        }
+        changes
+    }
+}
+
+/// Replay `frames` through an `AlertEngine` and record when each pair reaches each severity
+///
+/// Tracks missing from a frame are removed until they reappear. Only the
+/// first onset of each pair and severity is kept, so alerts that clear and
+/// re-raise do not change the fingerprint.
+pub fn replay_session(frames: &[RecordedFrame], config: &SafetyConfig) -> Result<AlertTimeline, ConfigError> {
+    let mut engine = AlertEngine::new(config.clone())?;
+    let mut traffic = TrafficSet::new();
+    let mut onsets: Vec<AlertOnset> = Vec::new();
+    let start = frames.first().map_or(0.0, |frame| frame.time);
+
+    for frame in frames {
+        let gone: Vec<u64> = traffic
+            .ids()
+            .iter()
+            .copied()
+            .filter(|id| !frame.tracks.iter().any(|(track, _)| track == id))
+            .collect();
+        for id in gone {
+            traffic.remove(id);
+        }
+        for &(id, state) in &frame.tracks {
+            // Unbounded sets never reject an insert
+            let _ = traffic.insert(id, state);
+        }
+
+        let change = engine.update(&traffic);
+        let onset = ((frame.time - start) / ONSET_RESOLUTION).round() * ONSET_RESOLUTION;
+        for (first, second) in change.added.iter().chain(&change.escalated) {
+            let severity = engine.alerts()[&(*first, *second)].severity;
+            let seen = onsets
+                .iter()
+                .any(|o| o.first == *first && o.second == *second && o.severity == severity);
+            if !seen {
+                onsets.push(AlertOnset { first: *first, second: *second, severity, onset });
+            }
+        }
+    }
+
+    onsets.sort_by(|a, b| {
+        a.onset
+            .total_cmp(&b.onset)
+            .then((a.first, a.second).cmp(&(b.first, b.second)))
+            .then((a.severity as u8).cmp(&(b.severity as u8)))
+    });
+    Ok(AlertTimeline { onsets })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Head-on pair closing at 600 kt from 30 nm, sampled every 5 s
+    fn session(offset: f64) -> Vec<RecordedFrame> {
+        (0..24)
+            .map(|i| {
+                let t = i as f64 * 5.0;
+                let closed = t / 360.0 * 300.0;
+                RecordedFrame::new(
+                    1000.0 + t,
+                    vec![
+                        (7, AircraftState::new(closed, offset, 12000.0, 90.0, 300.0)),
+                        (3, AircraftState::new(30.0 - closed, 0.0, 12000.0, 270.0, 300.0)),
+                    ],
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_timeline_is_normalized_and_stable() {
+        let config = SafetyConfig::default();
+        let timeline = replay_session(&session(0.0), &config).unwrap();
+
+        assert!(!timeline.onsets.is_empty());
+        assert!(timeline.onsets.iter().all(|o| o.first == 3 && o.second == 7));
+        assert!(timeline.onsets.windows(2).all(|w| w[0].onset <= w[1].onset));
+        assert_eq!(timeline.onsets.last().unwrap().severity, ConflictSeverity::Critical);
+        assert!(timeline.fingerprint().starts_with("3-7 "));
+
+        let again = replay_session(&session(0.0), &config).unwrap();
+        assert_eq!(again.digest(), timeline.digest());
+        assert!(again.compare(&timeline, 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_compare_reports_changes() {
+        let config = SafetyConfig::default();
+        let baseline = replay_session(&session(0.0), &config).unwrap();
+        // Passing 10 nm apart never alerts, so every baseline onset goes missing
+        let changed = replay_session(&session(10.0), &config).unwrap();
+
+        let changes = changed.compare(&baseline, 0.0);
+        assert_eq!(changes.len(), baseline.onsets.len());
+        assert!(changes.iter().all(|c| matches!(c, TimelineChange::Missing(_))));
+        assert_ne!(changed.digest(), baseline.digest());
+        assert!(baseline.compare(&AlertTimeline::default(), 0.0).iter().all(|c| matches!(c, TimelineChange::Added(_))));
+    }
+}
@@ -0,0 +1,270 @@
+/*!
+ * RESOLUTION MODULE
+ * Ranked heading, level and speed maneuvers that resolve a predicted conflict
+ */
+
+use crate::clearance::Intent;
+use crate::config::{SafetyConfig, SeparationStandards};
+use crate::conflict::{
+    closest_point_of_approach, detect_conflict, detect_conflict_with_intent, is_resolution_effective,
+    ConflictSeverity, RESOLUTION_LOOK_AHEAD,
+};
+use crate::{normalize_heading, AircraftState};
+
+/// Heading offsets tried either side of the current heading (degrees)
+const HEADING_OFFSETS: [f64; 9] = [10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 90.0];
+
+/// Level changes tried above and below the current level (ft)
+const LEVEL_CHANGES: [f64; 2] = [1000.0, 2000.0];
+
+/// Speed changes tried faster and slower (kt)
+const SPEED_CHANGES: [f64; 4] = [10.0, 20.0, 30.0, 40.0];
+
+/// Slowest speed a resolution may assign (kt)
+const MIN_RESOLUTION_SPEED: f64 = 160.0;
+
+/// Lowest level a resolution may assign (ft)
+const MIN_RESOLUTION_ALTITUDE: f64 = 1000.0;
+
+/// One instruction to the maneuvering aircraft
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Maneuver {
+    /// New heading (degrees)
+    Heading(f64),
+    /// New cleared level (ft)
+    Altitude(f64),
+    /// New speed (kt)
+    Speed(f64),
+}
+
+/// Weights an operator puts on each consequence of a resolution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostModel {
+    /// Per extra nm flown
+    pub track_miles: f64,
+    /// Per 1000 ft of level change
+    pub altitude_change: f64,
+    /// Per 10 kt of speed change
+    pub speed_change: f64,
+    /// Per instruction the controller has to issue
+    pub instruction: f64,
+}
+
+impl CostModel {
+    pub fn new(track_miles: f64, altitude_change: f64, speed_change: f64, instruction: f64) -> Self {
+        CostModel {
+            track_miles,
+            altitude_change,
+            speed_change,
+            instruction,
+        }
+    }
+
+    /// Cost of `maneuver` by `aircraft`
+    ///
+    /// A vector is flown for half the look-ahead and then back to the point
+    /// the aircraft would have reached on its own; vectors and speed changes
+    /// take two instructions (assign and resume), level changes one.
+    pub fn cost(&self, aircraft: &AircraftState, maneuver: &Maneuver) -> f64 {
+        match *maneuver {
+            Maneuver::Heading(heading) => {
+                let offset = crate::heading_difference(aircraft.heading, heading).abs().to_radians();
+                let distance = aircraft.speed * RESOLUTION_LOOK_AHEAD / 3600.0;
+                let out = distance / 2.0;
+                let back = ((distance - out * offset.cos()).powi(2) + (out * offset.sin()).powi(2)).sqrt();
+                self.track_miles * (out + back - distance) + 2.0 * self.instruction
+            }
+            Maneuver::Altitude(altitude) => {
+                self.altitude_change * (altitude - aircraft.altitude).abs() / 1000.0 + self.instruction
+            }
+            Maneuver::Speed(speed) => self.speed_change * (speed - aircraft.speed).abs() / 10.0 + 2.0 * self.instruction,
+        }
+    }
+}
+
+impl Default for CostModel {
+    /// A nm of track, 1000 ft and 20 kt cost alike; each instruction half as much
+    fn default() -> Self {
+        CostModel::new(1.0, 1.0, 0.5, 0.5)
+    }
+}
+
+/// A maneuver that clears the conflict, with its predicted outcome
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Resolution {
+    pub maneuver: Maneuver,
+    /// Weighted cost from the `CostModel`
+    pub cost: f64,
+    /// Predicted horizontal miss distance after the maneuver (nm)
+    pub miss_distance: f64,
+}
+
+impl Maneuver {
+    /// The aircraft state as if the maneuver were flown immediately
+    fn applied(&self, aircraft: &AircraftState) -> AircraftState {
+        match *self {
+            Maneuver::Heading(heading) => AircraftState { heading, ..*aircraft },
+            Maneuver::Altitude(_) => *aircraft,
+            Maneuver::Speed(speed) => AircraftState { speed, ..*aircraft },
+        }
+    }
+}
+
+/// True if `maneuver` by `aircraft` leaves no conflict with `intruder`
+///
+/// Headings use `is_resolution_effective`; level changes are flown at the
+/// default climb or descent rate and speed changes take effect at once.
+pub fn is_maneuver_effective(
+    aircraft: &AircraftState,
+    intruder: &AircraftState,
+    maneuver: &Maneuver,
+    horizontal_separation: f64,
+    vertical_separation: f64,
+) -> bool {
+    match *maneuver {
+        Maneuver::Heading(heading) => {
+            is_resolution_effective(aircraft, intruder, heading, horizontal_separation, vertical_separation)
+        }
+        Maneuver::Altitude(altitude) => {
+            let info = detect_conflict_with_intent(
+                aircraft,
+                &Intent::new().with_altitude(altitude),
+                intruder,
+                &Intent::new(),
+                horizontal_separation,
+                vertical_separation,
+                RESOLUTION_LOOK_AHEAD,
+            );
+            info.severity == ConflictSeverity::None
+        }
+        Maneuver::Speed(_) => {
+            let info = detect_conflict(
+                &maneuver.applied(aircraft),
+                intruder,
+                &SeparationStandards::new(horizontal_separation, vertical_separation),
+                RESOLUTION_LOOK_AHEAD,
+            );
+            info.severity == ConflictSeverity::None
+        }
+    }
+}
+
+/// Every candidate maneuver for `aircraft`, before validation
+fn candidates(aircraft: &AircraftState) -> Vec<Maneuver> {
+    let mut maneuvers = Vec::new();
+    for offset in HEADING_OFFSETS {
+        maneuvers.push(Maneuver::Heading(normalize_heading(aircraft.heading + offset)));
+        maneuvers.push(Maneuver::Heading(normalize_heading(aircraft.heading - offset)));
+    }
+    for change in LEVEL_CHANGES {
+        maneuvers.push(Maneuver::Altitude(aircraft.altitude + change));
+        if aircraft.altitude - change >= MIN_RESOLUTION_ALTITUDE {
+            maneuvers.push(Maneuver::Altitude(aircraft.altitude - change));
+        }
+    }
+    for change in SPEED_CHANGES {
+        maneuvers.push(Maneuver::Speed(aircraft.speed + change));
+        if aircraft.speed - change >= MIN_RESOLUTION_SPEED {
+            maneuvers.push(Maneuver::Speed(aircraft.speed - change));
+        }
+    }
+    maneuvers
+}
+
+/// Maneuvers for `aircraft` that resolve its conflict with `intruder`
+///
+/// Only candidates that pass `is_maneuver_effective` against the same alert
+/// distance the sweep uses are returned, so a resolution never leaves the pair
+/// inside the buffer; cheapest under `costs` first, equal costs prefer the
+/// larger miss distance.
+pub fn resolve_conflict(
+    aircraft: &AircraftState,
+    intruder: &AircraftState,
+    config: &SafetyConfig,
+    costs: &CostModel,
+) -> Vec<Resolution> {
+    let mut resolutions: Vec<Resolution> = candidates(aircraft)
+        .into_iter()
+        .filter(|maneuver| {
+            is_maneuver_effective(
+                aircraft,
+                intruder,
+                maneuver,
+                config.alert_distance(),
+                config.vertical_separation,
+            )
+        })
+        .map(|maneuver| Resolution {
+            maneuver,
+            cost: costs.cost(aircraft, &maneuver),
+            miss_distance: closest_point_of_approach(&maneuver.applied(aircraft), intruder, RESOLUTION_LOOK_AHEAD)
+                .distance,
+        })
+        .collect();
+
+    resolutions.sort_by(|a, b| {
+        a.cost
+            .total_cmp(&b.cost)
+            .then(b.miss_distance.total_cmp(&a.miss_distance))
+    });
+    resolutions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_head_on_resolutions_are_ranked() {
+        let config = SafetyConfig::default();
+        let aircraft = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 300.0);
+        let intruder = AircraftState::new(0.0, 20.0, 10000.0, 180.0, 300.0);
+
+        let resolutions = resolve_conflict(&aircraft, &intruder, &config, &CostModel::default());
+        assert!(!resolutions.is_empty());
+        assert!(resolutions.windows(2).all(|w| w[0].cost <= w[1].cost));
+
+        // A level change clears a head-on conflict more cheaply than a turn
+        let level = resolutions[0];
+        assert_eq!(level.maneuver, Maneuver::Altitude(11000.0));
+        assert!((level.cost - 1.5).abs() < 1e-9);
+        assert!(level.miss_distance < 1e-6);
+
+        // An operator that avoids level changes gets a vector instead
+        let lateral = resolve_conflict(&aircraft, &intruder, &config, &CostModel::new(1.0, 10.0, 0.5, 0.5));
+        assert!(matches!(lateral[0].maneuver, Maneuver::Heading(_)));
+
+        // Speed alone cannot separate opposite direction traffic
+        assert!(!resolutions.iter().any(|r| matches!(r.maneuver, Maneuver::Speed(_))));
+        for resolution in &resolutions {
+            if let Maneuver::Heading(_) = resolution.maneuver {
+                assert!(resolution.miss_distance >= config.alert_distance());
+            }
+        }
+    }
+
+    #[test]
+    fn test_overtake_resolved_by_slowing() {
+        let config = SafetyConfig::default();
+        // Catching up 30 kt on the same track and level
+        let aircraft = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 280.0);
+        let intruder = AircraftState::new(0.0, 4.0, 10000.0, 0.0, 250.0);
+
+        let resolutions = resolve_conflict(&aircraft, &intruder, &config, &CostModel::default());
+        assert!(resolutions.contains(&Resolution {
+            maneuver: Maneuver::Speed(240.0),
+            cost: 3.0,
+            miss_distance: 4.0,
+        }));
+
+        // With a 2 nm buffer a 4 nm miss still alerts, so slowing down is no longer offered
+        let buffered = resolve_conflict(&aircraft, &intruder, &config.clone().with_buffer(2.0), &CostModel::default());
+        assert!(!buffered.iter().any(|r| r.maneuver == Maneuver::Speed(240.0)));
+        assert!(candidates(&AircraftState::new(0.0, 0.0, 1500.0, 0.0, 170.0))
+            .iter()
+            .all(|m| !matches!(m, Maneuver::Speed(s) if *s < MIN_RESOLUTION_SPEED)
+                && !matches!(m, Maneuver::Altitude(a) if *a < MIN_RESOLUTION_ALTITUDE)));
+    }
+}
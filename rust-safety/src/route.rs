@@ -0,0 +1,389 @@
+/*!
+ * ROUTE MODULE
+ * Published procedure profiles and route-following prediction
+ */
+
+use crate::holding::rate_one_turn_radius;
+use crate::separation::predict_position;
+use crate::{heading_difference, normalize_heading, AircraftState};
+
+/// Altitude constraint published at a waypoint
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AltitudeRestriction {
+    None,
+    At(f64),
+    AtOrAbove(f64),
+    AtOrBelow(f64),
+    Between(f64, f64),
+}
+
+impl AltitudeRestriction {
+    /// Closest altitude to `altitude` that satisfies the restriction
+    pub fn clamp(&self, altitude: f64) -> f64 {
+        match *self {
+            AltitudeRestriction::None => altitude,
+            AltitudeRestriction::At(value) => value,
+            AltitudeRestriction::AtOrAbove(value) => altitude.max(value),
+            AltitudeRestriction::AtOrBelow(value) => altitude.min(value),
+            AltitudeRestriction::Between(low, high) => altitude.clamp(low.min(high), high.max(low)),
+        }
+    }
+}
+
+/// How the aircraft turns onto the next leg at a waypoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaypointTurn {
+    /// Turn started early so the aircraft rolls out on the next leg
+    FlyBy,
+    /// Waypoint overflown, then a turn direct to the next one
+    FlyOver,
+}
+
+/// Waypoint on a published route with its restrictions
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteWaypoint {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+    pub altitude: AltitudeRestriction,
+    pub speed: Option<f64>,
+    pub turn: WaypointTurn,
+    /// Center of the constant radius arc (RF leg) flown to this waypoint
+    pub arc_center: Option<(f64, f64)>,
+}
+
+impl RouteWaypoint {
+    /// Fly-by waypoint reached on a straight leg
+    pub fn new(name: &str, x: f64, y: f64, altitude: AltitudeRestriction, speed: Option<f64>) -> Self {
+        RouteWaypoint {
+            name: name.to_string(),
+            x,
+            y,
+            altitude,
+            speed,
+            turn: WaypointTurn::FlyBy,
+            arc_center: None,
+        }
+    }
+
+    pub fn fly_over(mut self) -> Self {
+        self.turn = WaypointTurn::FlyOver;
+        self
+    }
+
+    /// Reach this waypoint on an arc about a center, the shorter way round
+    pub fn with_arc(mut self, center_x: f64, center_y: f64) -> Self {
+        self.arc_center = Some((center_x, center_y));
+        self
+    }
+}
+
+/// Predicted point along a route
+#[derive(Debug, Clone, Copy)]
+pub struct RoutePoint {
+    pub time: f64,
+    pub state: AircraftState,
+    pub waypoint_index: usize,
+}
+
+/// Turns are flown at rate one (degrees per second)
+const ROUTE_TURN_RATE: f64 = 3.0;
+
+/// Track change beyond which fly-by anticipation stops growing (degrees)
+const MAX_ANTICIPATED_TURN: f64 = 120.0;
+
+/// Compass bearing between two points
+fn bearing(from: (f64, f64), to: (f64, f64)) -> f64 {
+    normalize_heading((to.0 - from.0).atan2(to.1 - from.1).to_degrees())
+}
+
+/// Distance before a fly-by waypoint at which the turn onto the next leg starts (nm)
+fn turn_anticipation(waypoints: &[RouteWaypoint], index: usize, leg_from: (f64, f64), speed: f64) -> f64 {
+    let target = &waypoints[index];
+    let Some(next) = waypoints.get(index + 1) else {
+        return 0.0;
+    };
+    if target.turn == WaypointTurn::FlyOver || target.arc_center.is_some() || next.arc_center.is_some() {
+        return 0.0;
+    }
+
+    let inbound = bearing(leg_from, (target.x, target.y));
+    let outbound = bearing((target.x, target.y), (next.x, next.y));
+    let change = heading_difference(inbound, outbound).abs().min(MAX_ANTICIPATED_TURN);
+    rate_one_turn_radius(speed) * (change / 2.0).to_radians().tan()
+}
+
+/// Turn towards a point by at most a rate one turn over `time_step`, then fly `distance` on the new heading
+fn steer_towards(state: &mut AircraftState, point: (f64, f64), distance: f64, time_step: f64) {
+    let limit = ROUTE_TURN_RATE * time_step;
+    let turn = heading_difference(state.heading, bearing((state.x, state.y), point));
+    state.heading = normalize_heading(state.heading + turn.clamp(-limit, limit));
+    let (sin, cos) = state.heading.to_radians().sin_cos();
+    state.x += sin * distance;
+    state.y += cos * distance;
+}
+
+/// Predict an aircraft flying a route with its published restrictions
+///
+/// The aircraft stays as high as the restrictions allow, meeting each
+/// altitude and speed constraint at its waypoint by a constant gradient.
+/// Straight legs are flown direct to the waypoint with rate one turns.
+/// Fly-by waypoints are sequenced a turn radius' anticipation early so the
+/// turn cuts the corner; fly-over waypoints are crossed before turning.
+/// RF legs follow their arc. Points are produced every `time_step`
+/// seconds; `waypoint_index` is the waypoint being flown to.
+pub fn predict_along_route(
+    aircraft: &AircraftState,
+    waypoints: &[RouteWaypoint],
+    duration: f64,
+    time_step: f64,
+) -> Vec<RoutePoint> {
+    let mut points = Vec::new();
+    if time_step <= 0.0 {
+        return points;
+    }
+
+    let mut state = *aircraft;
+    let mut index = 0;
+    let mut leg_start = *aircraft;
+    let mut leg_from = (aircraft.x, aircraft.y);
+    let mut time = 0.0;
+
+    points.push(RoutePoint { time, state, waypoint_index: index });
+
+    while time < duration {
+        time += time_step;
+
+        // Time still to fly this step; what is left on reaching a waypoint carries onto the next leg
+        let mut left = time_step;
+        while left > 0.0 {
+            if index >= waypoints.len() {
+                // Past the last waypoint: continue on the final track
+                state = predict_position(&state, left);
+                break;
+            }
+
+            let target = &waypoints[index];
+            let target_point = (target.x, target.y);
+            let step_distance = state.speed / 3600.0 * left;
+            let target_altitude = target.altitude.clamp(leg_start.altitude);
+            let target_speed = target.speed.unwrap_or(leg_start.speed);
+
+            // Arc radius and degrees still to turn about the center, positive clockwise
+            let arc = target.arc_center.map(|center| {
+                let radius = (target.x - center.0).hypot(target.y - center.1);
+                let end = bearing(center, target_point);
+                let to_go = heading_difference(bearing(center, (state.x, state.y)), end);
+                let total = heading_difference(bearing(center, leg_from), end);
+                (center, radius, to_go, total)
+            });
+            let (remaining, leg_length) = match arc {
+                Some((_, radius, to_go, total)) => (to_go.abs().to_radians() * radius, total.abs().to_radians() * radius),
+                None => (
+                    (target.x - state.x).hypot(target.y - state.y),
+                    (target.x - leg_from.0).hypot(target.y - leg_from.1),
+                ),
+            };
+            let anticipation = turn_anticipation(waypoints, index, leg_from, state.speed);
+
+            if remaining <= step_distance || remaining <= anticipation {
+                if remaining <= step_distance {
+                    state.x = target.x;
+                    state.y = target.y;
+                    left -= if state.speed > 0.0 { remaining / state.speed * 3600.0 } else { left };
+                } else {
+                    // Fly-by: start the turn onto the next leg now
+                    let next = &waypoints[index + 1];
+                    steer_towards(&mut state, (next.x, next.y), step_distance, left);
+                    left = 0.0;
+                }
+                state.altitude = target_altitude;
+                state.speed = target_speed;
+                leg_start = state;
+                leg_from = target_point;
+                index += 1;
+                continue;
+            }
+
+            match arc {
+                Some((center, radius, to_go, _)) => {
+                    let direction = to_go.signum();
+                    let position = bearing(center, (state.x, state.y)) + direction * (step_distance / radius).to_degrees();
+                    let (sin, cos) = position.to_radians().sin_cos();
+                    state.x = center.0 + radius * sin;
+                    state.y = center.1 + radius * cos;
+                    state.heading = normalize_heading(position + direction * 90.0);
+                }
+                None => steer_towards(&mut state, target_point, step_distance, left),
+            }
+
+            let progress = (1.0 - (remaining - step_distance) / leg_length.max(1e-9)).clamp(0.0, 1.0);
+            state.altitude = leg_start.altitude + (target_altitude - leg_start.altitude) * progress;
+            state.speed = leg_start.speed + (target_speed - leg_start.speed) * progress;
+            break;
+        }
+
+        points.push(RoutePoint { time, state, waypoint_index: index });
+    }
+
+    points
+}
+
+/// Predicted loss of separation against traffic while flying a procedure
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcedureConflict {
+    pub traffic_index: usize,
+    pub time: f64,
+    pub waypoint: String,
+    pub restriction: AltitudeRestriction,
+}
+
+/// Validate a "descend via" (or "climb via") clearance against other cleared traffic
+///
+/// Returns every traffic conflict with the restriction being flown towards
+/// when separation is first lost. An empty result means the published
+/// profile is conflict free over the look-ahead.
+pub fn validate_procedure_clearance(
+    aircraft: &AircraftState,
+    waypoints: &[RouteWaypoint],
+    traffic: &[AircraftState],
+    horizontal_separation: f64,
+    vertical_separation: f64,
+    look_ahead_time: f64,
+) -> Vec<ProcedureConflict> {
+    let time_step = 1.0;
+    let profile = predict_along_route(aircraft, waypoints, look_ahead_time, time_step);
+    let mut conflicts = Vec::new();
+
+    for (traffic_index, other) in traffic.iter().enumerate() {
+        let mut other_state = *other;
+
+        for (i, point) in profile.iter().enumerate() {
+            if i > 0 {
+                other_state = predict_position(&other_state, time_step);
+            }
+
+            let dx = point.state.x - other_state.x;
+            let dy = point.state.y - other_state.y;
+            let horizontal = (dx * dx + dy * dy).sqrt();
+            let vertical = (point.state.altitude - other_state.altitude).abs();
+
+            if horizontal < horizontal_separation && vertical < vertical_separation {
+                let waypoint_index = point.waypoint_index.min(waypoints.len().saturating_sub(1));
+                let (waypoint, restriction) = match waypoints.get(waypoint_index) {
+                    Some(wp) => (wp.name.clone(), wp.altitude),
+                    None => (String::new(), AltitudeRestriction::None),
+                };
+
+                conflicts.push(ProcedureConflict {
+                    traffic_index,
+                    time: point.time,
+                    waypoint,
+                    restriction,
+                });
+                break;
+            }
+        }
+    }
+
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn star() -> Vec<RouteWaypoint> {
+        vec![
+            RouteWaypoint::new("ALPHA", 0.0, 10.0, AltitudeRestriction::AtOrBelow(11000.0), Some(250.0)),
+            RouteWaypoint::new("BRAVO", 0.0, 0.0, AltitudeRestriction::At(6000.0), Some(210.0)),
+        ]
+    }
+
+    #[test]
+    fn test_restriction_clamp() {
+        assert_eq!(AltitudeRestriction::AtOrBelow(11000.0).clamp(15000.0), 11000.0);
+        assert_eq!(AltitudeRestriction::AtOrAbove(8000.0).clamp(15000.0), 15000.0);
+        assert_eq!(AltitudeRestriction::Between(7000.0, 9000.0).clamp(5000.0), 7000.0);
+    }
+
+    #[test]
+    fn test_profile_meets_restrictions() {
+        let aircraft = AircraftState::new(0.0, 20.0, 15000.0, 180.0, 280.0);
+        let profile = predict_along_route(&aircraft, &star(), 600.0, 1.0);
+
+        // The first point past ALPHA has flown on from the restriction for part of a step
+        let at_alpha = profile.iter().find(|p| p.waypoint_index == 1).unwrap();
+        assert!((10990.0..=11000.0).contains(&at_alpha.state.altitude));
+        assert!((249.0..=250.0).contains(&at_alpha.state.speed));
+
+        let last = profile.last().unwrap();
+        assert_eq!(last.state.altitude, 6000.0);
+    }
+
+    #[test]
+    fn test_waypoints_do_not_lose_time() {
+        // 360 kt is 0.1 nm a second; no waypoint falls on a step boundary
+        let aircraft = AircraftState::new(0.0, 0.0, 5000.0, 0.0, 360.0);
+        let waypoints: Vec<RouteWaypoint> = (1..=4)
+            .map(|i| RouteWaypoint::new("WPT", 0.0, i as f64 * 1.05, AltitudeRestriction::None, None))
+            .collect();
+        let profile = predict_along_route(&aircraft, &waypoints, 50.0, 1.0);
+
+        let passed_third = profile.iter().find(|p| p.waypoint_index == 3).unwrap();
+        assert!((passed_third.time - 32.0).abs() < 1e-9);
+        assert!((passed_third.state.y - 3.2).abs() < 1e-9);
+        assert!((profile.last().unwrap().state.y - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_procedure_conflict_reports_restriction() {
+        let aircraft = AircraftState::new(0.0, 20.0, 15000.0, 180.0, 280.0);
+
+        // Traffic holding level at 6000 near BRAVO
+        let traffic = vec![AircraftState::new(0.5, 0.0, 6000.0, 0.0, 0.0)];
+        let conflicts = validate_procedure_clearance(&aircraft, &star(), &traffic, 3.0, 1000.0, 600.0);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].waypoint, "BRAVO");
+        assert_eq!(conflicts[0].restriction, AltitudeRestriction::At(6000.0));
+
+        // Traffic well above the profile
+        let traffic = vec![AircraftState::new(0.5, 0.0, 20000.0, 0.0, 0.0)];
+        assert!(validate_procedure_clearance(&aircraft, &star(), &traffic, 3.0, 1000.0, 600.0).is_empty());
+    }
+
+    #[test]
+    fn test_fly_by_cuts_corner_and_fly_over_overshoots() {
+        let aircraft = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 360.0);
+        let corner = RouteWaypoint::new("CORNER", 0.0, 10.0, AltitudeRestriction::None, None);
+        let exit = RouteWaypoint::new("EXIT", 10.0, 10.0, AltitudeRestriction::None, None);
+        let arrival = |profile: &[RoutePoint]| profile.iter().find(|p| p.waypoint_index == 2).unwrap().time;
+        let furthest_north = |profile: &[RoutePoint]| profile.iter().map(|p| p.state.y).fold(f64::MIN, f64::max);
+
+        let fly_by = predict_along_route(&aircraft, &[corner.clone(), exit.clone()], 300.0, 1.0);
+        let fly_over = predict_along_route(&aircraft, &[corner.fly_over(), exit], 300.0, 1.0);
+
+        // Rate one at 360 kt is a 1.9 nm radius: the fly-by turn starts that far
+        // before the corner, within a 0.1 nm step, and barely passes the outbound leg
+        let turn_start = fly_by.iter().find(|p| p.waypoint_index == 1).unwrap();
+        assert!((turn_start.state.y - (10.0 - rate_one_turn_radius(360.0))).abs() < 0.15);
+        assert!(furthest_north(&fly_by) < 10.1);
+        assert!(furthest_north(&fly_over) > 11.0);
+        assert!(arrival(&fly_by) < arrival(&fly_over));
+    }
+
+    #[test]
+    fn test_rf_leg_follows_arc() {
+        // Quarter circle of 10 nm clockwise about (10, 0)
+        let aircraft = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 360.0);
+        let arc = RouteWaypoint::new("ARC", 10.0, 10.0, AltitudeRestriction::None, None).with_arc(10.0, 0.0);
+        let profile = predict_along_route(&aircraft, &[arc], 200.0, 1.0);
+
+        for point in profile.iter().filter(|p| p.waypoint_index == 0) {
+            assert!(((point.state.x - 10.0).hypot(point.state.y) - 10.0).abs() < 1e-9);
+        }
+        let end = profile.iter().find(|p| p.waypoint_index == 1).unwrap();
+        assert!((end.time - 157.0).abs() <= 1.0);
+        assert!((end.state.heading - 90.0).abs() < 1.0);
+    }
+}
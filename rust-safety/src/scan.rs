@@ -0,0 +1,170 @@
+/*!
+ * SCAN MODULE
+ * All-pairs conflict screening of a flat state buffer in one JavaScript call
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::config::{SafetyConfig, SeparationStandards};
+use crate::separation::check_separation;
+use crate::sweep::sweep_conflicts;
+use crate::AircraftState;
+
+/// Values per aircraft in the input buffer: x, y, altitude, heading, speed,
+/// vertical rate (ft/min, positive climbing)
+pub const SCAN_INPUT_STRIDE: usize = 6;
+
+/// Values per conflict in the result buffer: first index, second index,
+/// severity (0 none to 3 critical), time to conflict, minimum distance
+pub const SCAN_RESULT_STRIDE: usize = 5;
+
+/// Values per violation in the batch separation result: first index,
+/// second index, horizontal distance (nm), vertical distance (ft)
+pub const SEPARATION_RESULT_STRIDE: usize = 4;
+
+/// Aircraft from a packed buffer of `SCAN_INPUT_STRIDE` values each
+pub(crate) fn unpack_states(states: &[f64]) -> Result<impl Iterator<Item = AircraftState> + '_, String> {
+    if !states.len().is_multiple_of(SCAN_INPUT_STRIDE) {
+        return Err(format!(
+            "state buffer length {} is not a multiple of {}",
+            states.len(),
+            SCAN_INPUT_STRIDE
+        ));
+    }
+    Ok(states
+        .chunks_exact(SCAN_INPUT_STRIDE)
+        .map(|s| AircraftState::new(s[0], s[1], s[2], s[3], s[4]).with_vertical_rate(s[5])))
+}
+
+/// Reusable all-pairs screen over packed aircraft states
+///
+/// JavaScript passes every aircraft in one `Float64Array` and gets every
+/// conflicting pair back in another, instead of crossing the boundary once
+/// per pair. Indices in the result refer to aircraft order in the input.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct TrafficScan {
+    config: SafetyConfig,
+    states: Vec<AircraftState>,
+    conflicts: usize,
+}
+
+#[wasm_bindgen]
+impl TrafficScan {
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: &SafetyConfig) -> TrafficScan {
+        TrafficScan {
+            config: config.clone(),
+            states: Vec::new(),
+            conflicts: 0,
+        }
+    }
+
+    /// Screen `states` (x, y, altitude, heading, speed, vertical rate per aircraft) for conflicts
+    pub fn scan(&mut self, states: &[f64]) -> Result<Vec<f64>, String> {
+        self.states.clear();
+        self.states.extend(unpack_states(states)?);
+
+        let report = sweep_conflicts(&self.states, &self.config, None);
+        self.conflicts = report.conflicts.len();
+
+        let mut results = Vec::with_capacity(report.conflicts.len() * SCAN_RESULT_STRIDE);
+        for conflict in &report.conflicts {
+            results.extend_from_slice(&[
+                conflict.first as f64,
+                conflict.second as f64,
+                conflict.info.severity as u8 as f64,
+                conflict.info.time_to_conflict,
+                conflict.info.minimum_distance,
+            ]);
+        }
+        Ok(results)
+    }
+
+    /// Aircraft in the last scan
+    pub fn aircraft_count(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Conflicting pairs found by the last scan
+    pub fn conflict_count(&self) -> usize {
+        self.conflicts
+    }
+}
+
+/// Current separation of every pair in a packed state buffer, in one call
+///
+/// Input uses the `TrafficScan` layout. Only pairs infringing both
+/// `min_horizontal` (nm) and `min_vertical` (ft) are returned, as
+/// `SEPARATION_RESULT_STRIDE` values each in index order.
+pub fn check_separation_batch(states: &[f64], min_horizontal: f64, min_vertical: f64) -> Result<Vec<f64>, String> {
+    let aircraft: Vec<AircraftState> = unpack_states(states)?.collect();
+    let standards = SeparationStandards::new(min_horizontal, min_vertical);
+
+    let mut results = Vec::new();
+    for (i, first) in aircraft.iter().enumerate() {
+        for (j, second) in aircraft.iter().enumerate().skip(i + 1) {
+            let result = check_separation(first, second, &standards);
+            if !result.is_safe {
+                results.extend_from_slice(&[
+                    i as f64,
+                    j as f64,
+                    result.horizontal_distance,
+                    result.vertical_distance,
+                ]);
+            }
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conflict::ConflictSeverity;
+
+    #[test]
+    fn test_scan_packed_states() {
+        let mut scan = TrafficScan::new(&SafetyConfig::default());
+        let states = [
+            0.0, 0.0, 10000.0, 90.0, 250.0, 0.0, // head-on with the second
+            10.0, 0.0, 10000.0, 270.0, 250.0, 0.0,
+            0.0, 50.0, 10000.0, 0.0, 250.0, 0.0, // far away
+            30.0, 0.0, 8000.0, 0.0, 250.0, 1000.0, // climbing into the next
+            30.0, 20.0, 10000.0, 180.0, 250.0, 0.0,
+        ];
+
+        let results = scan.scan(&states).unwrap();
+        assert_eq!(scan.aircraft_count(), 5);
+        assert_eq!(scan.conflict_count(), 2);
+        assert_eq!(results.len(), 2 * SCAN_RESULT_STRIDE);
+        assert_eq!((results[0], results[1]), (0.0, 1.0));
+        assert_eq!(results[2], ConflictSeverity::Critical as u8 as f64);
+        assert!(results[3] >= 0.0 && results[3] < 60.0);
+        assert_eq!((results[5], results[6]), (3.0, 4.0));
+    }
+
+    #[test]
+    fn test_separation_batch() {
+        let states = [
+            0.0, 0.0, 10000.0, 90.0, 250.0, 0.0,
+            2.0, 0.0, 10500.0, 270.0, 250.0, 0.0, // 2 nm and 500 ft from the first
+            0.0, 2.0, 12000.0, 0.0, 250.0, 0.0,   // vertically separated from both
+            1.0, 1.0, 10000.0, 0.0, 250.0, 0.0,
+        ];
+
+        let results = check_separation_batch(&states, 3.0, 1000.0).unwrap();
+        assert_eq!(results.len(), 3 * SEPARATION_RESULT_STRIDE);
+        assert_eq!(&results[..SEPARATION_RESULT_STRIDE], &[0.0, 1.0, 2.0, 500.0]);
+        assert_eq!((results[4], results[5]), (0.0, 3.0));
+        assert_eq!((results[8], results[9]), (1.0, 3.0));
+        assert!(check_separation_batch(&states[..8], 3.0, 1000.0).is_err());
+    }
+
+    #[test]
+    fn test_rejects_partial_state() {
+        let mut scan = TrafficScan::new(&SafetyConfig::default());
+        assert!(scan.scan(&[0.0, 0.0, 10000.0]).is_err());
+        assert_eq!(scan.scan(&[]).unwrap(), Vec::<f64>::new());
+    }
+}
@@ -0,0 +1,204 @@
+/*!
+ * SCHEDULER MODULE
+ * Future events keyed to simulation time, safe under pause, acceleration and rewind
+ */
+
+use wasm_bindgen::prelude::*;
+
+/// What a scheduled event does when it comes due
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    /// Expect-further-clearance time for a holding aircraft
+    ExpectFurtherClearance,
+    /// Temporary flight restriction or activity area becoming active
+    RestrictionStart,
+    RestrictionEnd,
+    /// Scripted aircraft spawn
+    Spawn,
+    /// Any other exercise script step
+    Scripted,
+}
+
+/// One event due at a simulation time
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledEvent {
+    /// Unique within one scheduler, in scheduling order
+    pub id: u64,
+    /// Simulation time the event is due (seconds)
+    pub time: f64,
+    pub kind: EventKind,
+    /// Callsign, restriction name or script step the event applies to
+    pub target: String,
+}
+
+/// Queue of future events driven by the simulation clock, not wall time
+///
+/// Events fire only when `advance` moves the clock past them, so pausing
+/// (not advancing) holds them and time acceleration (large steps) fires
+/// every event passed, in time order. Moving the clock backwards re-arms
+/// events due after the new time, so a rewound exercise replays them.
+/// Events due at the same time fire in scheduling order.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct EventScheduler {
+    /// Sorted by (time, id)
+    pending: Vec<ScheduledEvent>,
+    /// Fired events in firing order, kept for rewinds
+    fired: Vec<ScheduledEvent>,
+    clock: f64,
+    next_id: u64,
+}
+
+#[wasm_bindgen]
+impl EventScheduler {
+    /// Empty scheduler with the clock at zero
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> EventScheduler {
+        EventScheduler {
+            next_id: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Simulation time of the last `advance` (seconds)
+    pub fn clock(&self) -> f64 {
+        self.clock
+    }
+
+    /// Schedule an event and return its ID
+    ///
+    /// Events due at or before the clock fire on the next `advance`;
+    /// non-finite times are rejected with ID 0.
+    pub fn schedule(&mut self, time: f64, kind: EventKind, target: &str) -> u64 {
+        if !time.is_finite() {
+            return 0;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.insert(ScheduledEvent {
+            id,
+            time,
+            kind,
+            target: target.to_string(),
+        });
+        id
+    }
+
+    /// Drop a pending event; false if it has fired or does not exist
+    pub fn cancel(&mut self, id: u64) -> bool {
+        let before = self.pending.len();
+        self.pending.retain(|event| event.id != id);
+        self.pending.len() != before
+    }
+
+    /// Move a pending event to a new time, keeping its ID
+    pub fn reschedule(&mut self, id: u64, time: f64) -> bool {
+        let Some(index) = self.pending.iter().position(|event| event.id == id) else {
+            return false;
+        };
+        if !time.is_finite() {
+            return false;
+        }
+        let mut event = self.pending.remove(index);
+        event.time = time;
+        self.insert(event);
+        true
+    }
+
+    /// Move the clock to `time` and return the events that came due, in order
+    ///
+    /// Moving backwards fires nothing and re-arms every event due after
+    /// `time`.
+    pub fn advance(&mut self, time: f64) -> Vec<ScheduledEvent> {
+        if !time.is_finite() {
+            return Vec::new();
+        }
+        if time < self.clock {
+            let (rearmed, kept): (Vec<_>, Vec<_>) = self.fired.drain(..).partition(|event| event.time > time);
+            self.fired = kept;
+            for event in rearmed {
+                self.insert(event);
+            }
+            self.clock = time;
+            return Vec::new();
+        }
+
+        let due = self.pending.partition_point(|event| event.time <= time);
+        let fired: Vec<ScheduledEvent> = self.pending.drain(..due).collect();
+        self.fired.extend(fired.iter().cloned());
+        self.clock = time;
+        fired
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Time of the next pending event, for sizing an accelerated step
+    pub fn next_time(&self) -> Option<f64> {
+        self.pending.first().map(|event| event.time)
+    }
+}
+
+impl EventScheduler {
+    /// Pending events in firing order
+    pub fn pending(&self) -> &[ScheduledEvent] {
+        &self.pending
+    }
+
+    fn insert(&mut self, event: ScheduledEvent) {
+        let index = self
+            .pending
+            .partition_point(|other| (other.time, other.id) < (event.time, event.id));
+        self.pending.insert(index, event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets(events: &[ScheduledEvent]) -> Vec<&str> {
+        events.iter().map(|event| event.target.as_str()).collect()
+    }
+
+    #[test]
+    fn test_fires_in_order_under_acceleration() {
+        let mut scheduler = EventScheduler::new();
+        scheduler.schedule(300.0, EventKind::ExpectFurtherClearance, "BAW219");
+        scheduler.schedule(120.0, EventKind::RestrictionStart, "TFR1");
+        scheduler.schedule(120.0, EventKind::Spawn, "EZY12");
+        let cancelled = scheduler.schedule(200.0, EventKind::Spawn, "RYR3");
+
+        // Paused: advancing to the same time fires nothing
+        assert!(scheduler.advance(0.0).is_empty());
+        assert!(scheduler.cancel(cancelled));
+        assert_eq!(scheduler.next_time(), Some(120.0));
+
+        // A 10x step passes two events at once, ties in scheduling order
+        assert_eq!(targets(&scheduler.advance(150.0)), vec!["TFR1", "EZY12"]);
+        assert_eq!(targets(&scheduler.advance(400.0)), vec!["BAW219"]);
+        assert_eq!(scheduler.pending_count(), 0);
+        assert!(!scheduler.cancel(cancelled));
+    }
+
+    #[test]
+    fn test_rewind_rearms_events() {
+        let mut scheduler = EventScheduler::new();
+        let efc = scheduler.schedule(60.0, EventKind::ExpectFurtherClearance, "DLH4");
+        scheduler.schedule(30.0, EventKind::RestrictionEnd, "TFR1");
+        scheduler.advance(90.0);
+
+        assert!(scheduler.advance(45.0).is_empty());
+        assert_eq!(scheduler.clock(), 45.0);
+        assert_eq!(targets(scheduler.pending()), vec!["DLH4"]);
+
+        assert!(scheduler.reschedule(efc, 80.0));
+        assert!(scheduler.advance(70.0).is_empty());
+        let fired = scheduler.advance(80.0);
+        assert_eq!((fired[0].id, fired[0].time), (efc, 80.0));
+        assert_eq!(scheduler.schedule(f64::NAN, EventKind::Scripted, "x"), 0);
+    }
+}
@@ -0,0 +1,315 @@
+/*!
+ * SCHEMA MODULE
+ * Versioned text formats for saved configs and traffic snapshots
+ *
+ * Each document starts with a header line naming the format and its schema
+ * version, followed by one `key value...` record per line. Older versions
+ * are migrated to the current data model on load.
+ */
+
+use std::fmt;
+
+use crate::config::SafetyConfig;
+use crate::traffic::{CapacityError, TrafficSet};
+use crate::AircraftState;
+
+/// Current schema version written by `encode_config`
+pub const CONFIG_SCHEMA_VERSION: u32 = 3;
+
+/// Current schema version written by `encode_snapshot`
+pub const SNAPSHOT_SCHEMA_VERSION: u32 = 2;
+
+const CONFIG_HEADER: &str = "atc-safety-config";
+const SNAPSHOT_HEADER: &str = "atc-safety-snapshot";
+
+/// Reason a saved document could not be loaded
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaError {
+    MissingHeader,
+    UnsupportedVersion(u32),
+    MissingField(&'static str),
+    Malformed { line: usize },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::MissingHeader => write!(f, "missing format header"),
+            SchemaError::UnsupportedVersion(version) => {
+                write!(f, "unsupported schema version {}", version)
+            }
+            SchemaError::MissingField(field) => write!(f, "missing field '{}'", field),
+            SchemaError::Malformed { line } => write!(f, "malformed record on line {}", line),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// Serialize a config at the current schema version
+pub fn encode_config(config: &SafetyConfig) -> String {
+    format!(
+        "{} {}\nhorizontal_separation {}\nvertical_separation {}\nlook_ahead_time {}\ntime_step {}\nbuffer {}\nalert_hysteresis {} {}\n",
+        CONFIG_HEADER,
+        CONFIG_SCHEMA_VERSION,
+        config.horizontal_separation,
+        config.vertical_separation,
+        config.look_ahead_time,
+        config.time_step,
+        config.buffer,
+        config.alert_on_probability,
+        config.alert_off_probability,
+    )
+}
+
+/// Load a config, migrating older schema versions
+///
+/// Version 1 predates the configurable time step, which loads as the
+/// 1 second default. Versions before 3 load without an alerting buffer and
+/// with the default alert hysteresis.
+pub fn decode_config(text: &str) -> Result<SafetyConfig, SchemaError> {
+    let (version, records) = parse_document(text, CONFIG_HEADER, CONFIG_SCHEMA_VERSION)?;
+
+    let mut config = SafetyConfig::default();
+    let mut seen = [false; 6];
+
+    for (line, key, values) in records {
+        if key == "alert_hysteresis" && version >= 3 {
+            match *parse_numbers(line, &values)?.as_slice() {
+                [on, off] => {
+                    config.alert_on_probability = on;
+                    config.alert_off_probability = off;
+                    seen[5] = true;
+                    continue;
+                }
+                _ => return Err(SchemaError::Malformed { line }),
+            }
+        }
+
+        let value = single_value(line, &values)?;
+        let slot = match key {
+            "horizontal_separation" => {
+                config.horizontal_separation = value;
+                0
+            }
+            "vertical_separation" => {
+                config.vertical_separation = value;
+                1
+            }
+            "look_ahead_time" => {
+                config.look_ahead_time = value;
+                2
+            }
+            "time_step" if version >= 2 => {
+                config.time_step = value;
+                3
+            }
+            "buffer" if version >= 3 => {
+                config.buffer = value;
+                4
+            }
+            // Unknown keys from newer minor additions are ignored
+            _ => continue,
+        };
+        seen[slot] = true;
+    }
+
+    let required: &[(usize, &'static str)] = if version >= 3 {
+        &[
+            (0, "horizontal_separation"),
+            (1, "vertical_separation"),
+            (2, "look_ahead_time"),
+            (3, "time_step"),
+            (4, "buffer"),
+            (5, "alert_hysteresis"),
+        ]
+    } else if version >= 2 {
+        &[(0, "horizontal_separation"), (1, "vertical_separation"), (2, "look_ahead_time"), (3, "time_step")]
+    } else {
+        &[(0, "horizontal_separation"), (1, "vertical_separation"), (2, "look_ahead_time")]
+    };
+    if let Some(&(_, field)) = required.iter().find(|(slot, _)| !seen[*slot]) {
+        return Err(SchemaError::MissingField(field));
+    }
+
+    Ok(config)
+}
+
+/// Traffic picture at one instant, keyed by aircraft identifier
+#[derive(Debug, Clone, Default)]
+pub struct TrafficSnapshot {
+    pub time: f64,
+    pub aircraft: Vec<(u64, AircraftState)>,
+}
+
+impl TrafficSnapshot {
+    pub fn new(time: f64) -> Self {
+        TrafficSnapshot {
+            time,
+            aircraft: Vec::new(),
+        }
+    }
+
+    /// Capture every aircraft in a traffic set
+    pub fn from_traffic(traffic: &TrafficSet, time: f64) -> Self {
+        TrafficSnapshot {
+            time,
+            aircraft: traffic.iter().collect(),
+        }
+    }
+
+    /// Rebuild a traffic set from the snapshot
+    pub fn to_traffic(&self) -> Result<TrafficSet, CapacityError> {
+        let mut traffic = TrafficSet::with_capacity(self.aircraft.len());
+        for &(id, state) in &self.aircraft {
+            traffic.insert(id, state)?;
+        }
+        Ok(traffic)
+    }
+}
+
+/// Serialize a snapshot at the current schema version
+pub fn encode_snapshot(snapshot: &TrafficSnapshot) -> String {
+    let mut text = format!("{} {}\ntime {}\n", SNAPSHOT_HEADER, SNAPSHOT_SCHEMA_VERSION, snapshot.time);
+    for (id, state) in &snapshot.aircraft {
+        text.push_str(&format!(
+            "aircraft {} {} {} {} {} {}\n",
+            id, state.x, state.y, state.altitude, state.heading, state.speed
+        ));
+    }
+    text
+}
+
+/// Load a snapshot, migrating older schema versions
+///
+/// Version 1 had no snapshot time and stored aircraft without identifiers;
+/// they load at time 0 with identifiers assigned in order from 1.
+pub fn decode_snapshot(text: &str) -> Result<TrafficSnapshot, SchemaError> {
+    let (version, records) = parse_document(text, SNAPSHOT_HEADER, SNAPSHOT_SCHEMA_VERSION)?;
+    let mut snapshot = TrafficSnapshot::new(0.0);
+
+    for (line, key, values) in records {
+        match key {
+            "time" if version >= 2 => snapshot.time = single_value(line, &values)?,
+            "aircraft" => {
+                let (id, fields) = if version >= 2 {
+                    let id = values.first().and_then(|v| v.parse::<u64>().ok());
+                    (id.ok_or(SchemaError::Malformed { line })?, &values[1..])
+                } else {
+                    (snapshot.aircraft.len() as u64 + 1, &values[..])
+                };
+
+                let numbers = parse_numbers(line, fields)?;
+                if numbers.len() != 5 {
+                    return Err(SchemaError::Malformed { line });
+                }
+                let state = AircraftState::new(numbers[0], numbers[1], numbers[2], numbers[3], numbers[4]);
+                snapshot.aircraft.push((id, state));
+            }
+            _ => continue,
+        }
+    }
+
+    Ok(snapshot)
+}
+
+type Record<'a> = (usize, &'a str, Vec<&'a str>);
+
+/// Split a document into its schema version and (line, key, values) records
+fn parse_document<'a>(
+    text: &'a str,
+    header: &str,
+    current_version: u32,
+) -> Result<(u32, Vec<Record<'a>>), SchemaError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let (header_line, first) = lines.next().ok_or(SchemaError::MissingHeader)?;
+    let mut parts = first.split_whitespace();
+    if parts.next() != Some(header) {
+        return Err(SchemaError::MissingHeader);
+    }
+    let version = parts
+        .next()
+        .and_then(|v| v.parse::<u32>().ok())
+        .ok_or(SchemaError::Malformed { line: header_line })?;
+    if version == 0 || version > current_version {
+        return Err(SchemaError::UnsupportedVersion(version));
+    }
+
+    let records = lines
+        .map(|(line, text)| {
+            let mut parts = text.split_whitespace();
+            let key = parts.next().unwrap_or_default();
+            (line, key, parts.collect())
+        })
+        .collect();
+
+    Ok((version, records))
+}
+
+fn parse_numbers(line: usize, values: &[&str]) -> Result<Vec<f64>, SchemaError> {
+    values
+        .iter()
+        .map(|v| v.parse::<f64>().map_err(|_| SchemaError::Malformed { line }))
+        .collect()
+}
+
+fn single_value(line: usize, values: &[&str]) -> Result<f64, SchemaError> {
+    match parse_numbers(line, values)?.as_slice() {
+        [value] => Ok(*value),
+        _ => Err(SchemaError::Malformed { line }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_round_trip_and_migration() {
+        let config = SafetyConfig::new(5.0, 1000.0, 240.0, 2.0)
+            .with_buffer(0.5)
+            .with_alert_hysteresis(0.7, 0.4);
+        assert_eq!(decode_config(&encode_config(&config)), Ok(config));
+
+        let v2 = "atc-safety-config 2\nhorizontal_separation 5\nvertical_separation 1000\nlook_ahead_time 240\ntime_step 2\n";
+        assert_eq!(decode_config(v2), Ok(SafetyConfig::new(5.0, 1000.0, 240.0, 2.0)));
+
+        let v1 = "atc-safety-config 1\nhorizontal_separation 5\nvertical_separation 2000\nlook_ahead_time 120\n";
+        let migrated = decode_config(v1).unwrap();
+        assert_eq!(migrated, SafetyConfig::new(5.0, 2000.0, 120.0, 1.0));
+    }
+
+    #[test]
+    fn test_config_errors() {
+        assert_eq!(decode_config("horizontal_separation 3"), Err(SchemaError::MissingHeader));
+        assert_eq!(decode_config("atc-safety-config 9\n"), Err(SchemaError::UnsupportedVersion(9)));
+        assert_eq!(
+            decode_config("atc-safety-config 2\nhorizontal_separation 3\n"),
+            Err(SchemaError::MissingField("vertical_separation"))
+        );
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_and_migration() {
+        let mut traffic = TrafficSet::new();
+        traffic.insert(42, AircraftState::new(1.5, -2.0, 9000.0, 90.0, 250.0)).unwrap();
+        traffic.insert(7, AircraftState::new(0.0, 3.0, 11000.0, 270.0, 300.0)).unwrap();
+
+        let decoded = decode_snapshot(&encode_snapshot(&TrafficSnapshot::from_traffic(&traffic, 60.0))).unwrap();
+        assert_eq!(decoded.time, 60.0);
+        let restored = decoded.to_traffic().unwrap();
+        assert_eq!(restored.get(42).unwrap().x, 1.5);
+        assert_eq!(restored.get(7).unwrap().speed, 300.0);
+
+        let v1 = "atc-safety-snapshot 1\naircraft 0 0 10000 0 250\naircraft 5 5 12000 180 280\n";
+        let migrated = decode_snapshot(v1).unwrap();
+        assert_eq!(migrated.time, 0.0);
+        assert_eq!(migrated.aircraft[1].0, 2);
+        assert_eq!(migrated.aircraft[1].1.altitude, 12000.0);
+    }
+}
@@ -0,0 +1,361 @@
+/*!
+ * SECTOR MODULE
+ * Sector boundaries, track ownership, handoffs and runtime re-sectorization
+ */
+
+use std::collections::HashMap;
+
+use crate::AircraftState;
+
+/// Volume of airspace controlled as one unit
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sector {
+    pub name: String,
+    pub boundary: Vec<(f64, f64)>,
+    pub floor: f64,
+    pub ceiling: f64,
+}
+
+impl Sector {
+    pub fn new(name: &str, boundary: Vec<(f64, f64)>, floor: f64, ceiling: f64) -> Self {
+        Sector {
+            name: name.to_string(),
+            boundary,
+            floor,
+            ceiling,
+        }
+    }
+
+    /// Check whether a position lies inside the sector
+    pub fn contains(&self, x: f64, y: f64, altitude: f64) -> bool {
+        altitude >= self.floor && altitude < self.ceiling && point_in_polygon(x, y, &self.boundary)
+    }
+
+    /// Average of the boundary vertices
+    pub fn centroid(&self) -> (f64, f64) {
+        let n = self.boundary.len().max(1) as f64;
+        let (sx, sy) = self
+            .boundary
+            .iter()
+            .fold((0.0, 0.0), |(sx, sy), &(x, y)| (sx + x, sy + y));
+        (sx / n, sy / n)
+    }
+}
+
+/// Ray-casting point in polygon test
+pub fn point_in_polygon(x: f64, y: f64, polygon: &[(f64, f64)]) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len().wrapping_sub(1);
+
+    for i in 0..polygon.len() {
+        let (xi, yi) = polygon[i];
+        let (xj, yj) = polygon[j];
+        if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+
+    inside
+}
+
+/// True if a point is inside a polygon or within `radius` nm of its edge
+pub fn point_near_polygon(x: f64, y: f64, polygon: &[(f64, f64)], radius: f64) -> bool {
+    if point_in_polygon(x, y, polygon) {
+        return true;
+    }
+    let n = polygon.len();
+    (0..n).any(|i| segment_distance(x, y, polygon[i], polygon[(i + 1) % n]) <= radius)
+}
+
+fn segment_distance(x: f64, y: f64, (ax, ay): (f64, f64), (bx, by): (f64, f64)) -> f64 {
+    let (dx, dy) = (bx - ax, by - ay);
+    let length_sq = dx * dx + dy * dy;
+    let t = if length_sq > 0.0 {
+        (((x - ax) * dx + (y - ay) * dy) / length_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (x - ax - t * dx).hypot(y - ay - t * dy)
+}
+
+/// Transfer of control pending acceptance by the receiving sector
+#[derive(Debug, Clone, PartialEq)]
+pub struct Handoff {
+    pub track: u64,
+    pub from: String,
+    pub to: String,
+}
+
+/// Change produced by ownership updates, handoffs or re-sectorization
+#[derive(Debug, Clone, PartialEq)]
+pub enum SectorEvent {
+    SectorAdded(String),
+    SectorRemoved(String),
+    OwnershipChanged {
+        track: u64,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    HandoffRerouted {
+        track: u64,
+        previous_target: String,
+        new_target: String,
+    },
+    HandoffCancelled {
+        track: u64,
+        target: String,
+    },
+}
+
+/// Current sector layout with track ownership and pending handoffs
+#[derive(Debug, Clone, Default)]
+pub struct SectorMap {
+    sectors: Vec<Sector>,
+    owners: HashMap<u64, String>,
+    handoffs: HashMap<u64, Handoff>,
+}
+
+impl SectorMap {
+    pub fn new(sectors: Vec<Sector>) -> Self {
+        SectorMap {
+            sectors,
+            ..SectorMap::default()
+        }
+    }
+
+    pub fn sectors(&self) -> &[Sector] {
+        &self.sectors
+    }
+
+    pub fn sector(&self, name: &str) -> Option<&Sector> {
+        self.sectors.iter().find(|s| s.name == name)
+    }
+
+    /// Name of the first sector containing an aircraft
+    pub fn sector_at(&self, aircraft: &AircraftState) -> Option<&str> {
+        self.sectors
+            .iter()
+            .find(|s| s.contains(aircraft.x, aircraft.y, aircraft.altitude))
+            .map(|s| s.name.as_str())
+    }
+
+    pub fn owner(&self, track: u64) -> Option<&str> {
+        self.owners.get(&track).map(String::as_str)
+    }
+
+    /// Tracks owned by a sector
+    pub fn tracks_owned_by(&self, sector: &str) -> Vec<u64> {
+        let mut tracks: Vec<u64> = self
+            .owners
+            .iter()
+            .filter(|(_, owner)| owner.as_str() == sector)
+            .map(|(&track, _)| track)
+            .collect();
+        tracks.sort_unstable();
+        tracks
+    }
+
+    /// Give an unowned track to the sector it is in
+    pub fn update_track(&mut self, track: u64, aircraft: &AircraftState) -> Option<SectorEvent> {
+        if self.owners.contains_key(&track) {
+            return None;
+        }
+
+        let sector = self.sector_at(aircraft)?.to_string();
+        self.owners.insert(track, sector.clone());
+        Some(SectorEvent::OwnershipChanged {
+            track,
+            from: None,
+            to: Some(sector),
+        })
+    }
+
+    pub fn remove_track(&mut self, track: u64) {
+        self.owners.remove(&track);
+        self.handoffs.remove(&track);
+    }
+
+    /// Offer a track to another sector; fails for unowned tracks or unknown sectors
+    pub fn initiate_handoff(&mut self, track: u64, to: &str) -> bool {
+        let from = match self.owners.get(&track) {
+            Some(owner) if owner != to && self.sector(to).is_some() => owner.clone(),
+            _ => return false,
+        };
+
+        self.handoffs.insert(track, Handoff { track, from, to: to.to_string() });
+        true
+    }
+
+    pub fn pending_handoff(&self, track: u64) -> Option<&Handoff> {
+        self.handoffs.get(&track)
+    }
+
+    /// Complete a pending handoff, transferring ownership
+    pub fn accept_handoff(&mut self, track: u64) -> Option<SectorEvent> {
+        let handoff = self.handoffs.remove(&track)?;
+        let previous = self.owners.insert(track, handoff.to.clone());
+
+        Some(SectorEvent::OwnershipChanged {
+            track,
+            from: previous,
+            to: Some(handoff.to),
+        })
+    }
+
+    /// Replace the sector layout at runtime
+    ///
+    /// Tracks whose owning sector is gone, or which are no longer inside it,
+    /// move to the new sector containing them. Handoffs to a removed sector
+    /// are rerouted to the sector now covering the old target's centre, and
+    /// cancelled when that is the new owner or nothing covers it.
+    pub fn resectorize(
+        &mut self,
+        sectors: Vec<Sector>,
+        positions: &[(u64, AircraftState)],
+    ) -> Vec<SectorEvent> {
+        let mut events = Vec::new();
+
+        for old in &self.sectors {
+            if !sectors.iter().any(|s| s.name == old.name) {
+                events.push(SectorEvent::SectorRemoved(old.name.clone()));
+            }
+        }
+        for new in &sectors {
+            if self.sector(&new.name).is_none() {
+                events.push(SectorEvent::SectorAdded(new.name.clone()));
+            }
+        }
+
+        // Where each removed sector's traffic flow now lands
+        let successors: HashMap<String, Option<String>> = self
+            .sectors
+            .iter()
+            .filter(|old| !sectors.iter().any(|s| s.name == old.name))
+            .map(|old| {
+                let (cx, cy) = old.centroid();
+                let mid = (old.floor + old.ceiling) / 2.0;
+                let successor = sectors.iter().find(|s| s.contains(cx, cy, mid)).map(|s| s.name.clone());
+                (old.name.clone(), successor)
+            })
+            .collect();
+
+        self.sectors = sectors;
+
+        let mut tracks: Vec<u64> = self.owners.keys().copied().collect();
+        tracks.sort_unstable();
+        for track in tracks {
+            let owner = self.owners[&track].clone();
+            let position = positions.iter().find(|(id, _)| *id == track).map(|(_, s)| s);
+
+            let still_valid = match (self.sector(&owner), position) {
+                (Some(sector), Some(a)) => sector.contains(a.x, a.y, a.altitude),
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+            if still_valid {
+                continue;
+            }
+
+            let new_owner = match position {
+                Some(a) => self.sector_at(a).map(str::to_string),
+                None => successors.get(&owner).cloned().flatten(),
+            };
+            match &new_owner {
+                Some(name) => self.owners.insert(track, name.clone()),
+                None => self.owners.remove(&track),
+            };
+            events.push(SectorEvent::OwnershipChanged {
+                track,
+                from: Some(owner),
+                to: new_owner,
+            });
+        }
+
+        let mut pending: Vec<u64> = self.handoffs.keys().copied().collect();
+        pending.sort_unstable();
+        for track in pending {
+            let Some(mut handoff) = self.handoffs.remove(&track) else {
+                continue;
+            };
+            let target = if self.sector(&handoff.to).is_some() {
+                Some(handoff.to.clone())
+            } else {
+                successors.get(&handoff.to).cloned().flatten()
+            };
+
+            match (target, self.owners.get(&track)) {
+                (Some(target), Some(owner)) if &target != owner => {
+                    if target != handoff.to {
+                        events.push(SectorEvent::HandoffRerouted {
+                            track,
+                            previous_target: handoff.to.clone(),
+                            new_target: target.clone(),
+                        });
+                    }
+                    handoff.from = owner.clone();
+                    handoff.to = target;
+                    self.handoffs.insert(track, handoff);
+                }
+                _ => events.push(SectorEvent::HandoffCancelled {
+                    track,
+                    target: handoff.to,
+                }),
+            }
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(name: &str, x0: f64, x1: f64) -> Sector {
+        Sector::new(name, vec![(x0, -20.0), (x1, -20.0), (x1, 20.0), (x0, 20.0)], 0.0, 40000.0)
+    }
+
+    #[test]
+    fn test_ownership_and_handoff() {
+        let mut map = SectorMap::new(vec![square("WEST", -20.0, 0.0), square("EAST", 0.0, 20.0)]);
+        let aircraft = AircraftState::new(-5.0, 0.0, 10000.0, 90.0, 250.0);
+
+        assert!(map.update_track(1, &aircraft).is_some());
+        assert_eq!(map.owner(1), Some("WEST"));
+
+        assert!(map.initiate_handoff(1, "EAST"));
+        assert!(!map.initiate_handoff(1, "NORTH"));
+        map.accept_handoff(1);
+        assert_eq!(map.owner(1), Some("EAST"));
+    }
+
+    #[test]
+    fn test_combining_sectors_reassigns_tracks_and_handoffs() {
+        let mut map = SectorMap::new(vec![
+            square("WEST", -20.0, 0.0),
+            square("EAST", 0.0, 20.0),
+            square("FAR", 20.0, 40.0),
+        ]);
+        let west = AircraftState::new(-5.0, 0.0, 10000.0, 90.0, 250.0);
+        let east = AircraftState::new(5.0, 0.0, 10000.0, 270.0, 250.0);
+        map.update_track(1, &west);
+        map.update_track(2, &east);
+        map.initiate_handoff(1, "EAST");
+
+        // Night configuration: WEST and EAST combined into CENTRAL
+        let events = map.resectorize(
+            vec![square("CENTRAL", -20.0, 20.0), square("FAR", 20.0, 40.0)],
+            &[(1, west), (2, east)],
+        );
+
+        assert!(events.contains(&SectorEvent::SectorAdded("CENTRAL".to_string())));
+        assert!(events.contains(&SectorEvent::SectorRemoved("EAST".to_string())));
+        assert_eq!(map.owner(1), Some("CENTRAL"));
+        assert_eq!(map.owner(2), Some("CENTRAL"));
+
+        // The handoff is now internal to CENTRAL and is dropped
+        assert!(map.pending_handoff(1).is_none());
+        assert!(events.contains(&SectorEvent::HandoffCancelled { track: 1, target: "EAST".to_string() }));
+    }
+}
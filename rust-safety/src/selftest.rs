@@ -0,0 +1,135 @@
+/*!
+ * SELF-TEST MODULE
+ * Canonical encounters run through the configured engine to verify alerting
+ */
+
+use crate::config::SafetyConfig;
+use crate::sweep::sweep_conflicts;
+use crate::AircraftState;
+
+/// Lead time every expected alert must give (seconds)
+const MIN_LEAD_TIME: f64 = 60.0;
+
+/// Built-in encounter with the expected outcome
+#[derive(Debug, Clone, Copy)]
+struct Encounter {
+    name: &'static str,
+    first: AircraftState,
+    second: AircraftState,
+    expect_alert: bool,
+}
+
+fn encounters() -> [Encounter; 5] {
+    [
+        Encounter {
+            name: "head-on",
+            first: AircraftState::new(0.0, 0.0, 15000.0, 90.0, 240.0),
+            second: AircraftState::new(20.0, 0.0, 15000.0, 270.0, 240.0),
+            expect_alert: true,
+        },
+        Encounter {
+            name: "crossing",
+            first: AircraftState::new(-12.0, 0.0, 15000.0, 90.0, 300.0),
+            second: AircraftState::new(0.0, -12.0, 15000.0, 0.0, 300.0),
+            expect_alert: true,
+        },
+        Encounter {
+            name: "climb-through",
+            first: AircraftState::new(0.0, 0.0, 14500.0, 90.0, 240.0),
+            second: AircraftState::new(20.0, 0.0, 15000.0, 270.0, 240.0),
+            expect_alert: true,
+        },
+        Encounter {
+            name: "vertically separated",
+            first: AircraftState::new(0.0, 0.0, 15000.0, 90.0, 240.0),
+            second: AircraftState::new(20.0, 0.0, 17000.0, 270.0, 240.0),
+            expect_alert: false,
+        },
+        Encounter {
+            name: "diverging",
+            first: AircraftState::new(0.0, 0.0, 15000.0, 270.0, 240.0),
+            second: AircraftState::new(20.0, 0.0, 15000.0, 90.0, 240.0),
+            expect_alert: false,
+        },
+    ]
+}
+
+/// Outcome of one built-in encounter
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfTestResult {
+    pub name: &'static str,
+    pub expected_alert: bool,
+    /// Warning time given when an alert fired
+    pub lead_time: Option<f64>,
+    pub passed: bool,
+}
+
+/// Pass/fail report of a self-test run
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfTestReport {
+    pub results: Vec<SelfTestResult>,
+}
+
+impl SelfTestReport {
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|r| r.passed)
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &SelfTestResult> {
+        self.results.iter().filter(|r| !r.passed)
+    }
+}
+
+/// Run the built-in encounters through the sweep with `config`
+///
+/// Expected alerts must fire at least a minute before loss of separation;
+/// separated and diverging encounters must stay quiet.
+pub fn run_self_test(config: &SafetyConfig) -> SelfTestReport {
+    let results = encounters()
+        .iter()
+        .map(|encounter| {
+            let report = sweep_conflicts(&[encounter.first, encounter.second], config, None);
+            let lead_time = report.conflicts.first().map(|c| c.info.time_to_conflict);
+
+            let passed = match (encounter.expect_alert, lead_time) {
+                (true, Some(lead)) => lead >= MIN_LEAD_TIME,
+                (false, None) => true,
+                _ => false,
+            };
+
+            SelfTestResult {
+                name: encounter.name,
+                expected_alert: encounter.expect_alert,
+                lead_time,
+                passed,
+            }
+        })
+        .collect();
+
+    SelfTestReport { results }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_passes() {
+        let report = run_self_test(&SafetyConfig::default());
+        assert_eq!(report.results.len(), 5);
+        assert!(report.passed(), "{:?}", report.failures().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_detects_disabled_protection() {
+        // Look-ahead too short to give a minute of warning
+        let short = SafetyConfig::new(3.0, 1000.0, 30.0, 1.0);
+        assert!(!run_self_test(&short).passed());
+
+        // A zero vertical minimum never alerts
+        let disabled = SafetyConfig::new(3.0, 0.0, 300.0, 1.0);
+        let report = run_self_test(&disabled);
+        let failed: Vec<&str> = report.failures().map(|r| r.name).collect();
+        assert_eq!(failed, vec!["head-on", "crossing", "climb-through"]);
+    }
+}
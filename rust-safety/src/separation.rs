@@ -1,35 +1,92 @@
-/**
+/*!
  * SEPARATION MONITORING MODULE
  * Memory-safe separation distance calculations
  */
 
+use atc_safety_core as core_math;
+use wasm_bindgen::prelude::*;
+
+use crate::config::SeparationStandards;
+use crate::wake::required_wake_distance;
 use crate::{AircraftState, SeparationResult};
 
+/// Relative speed below which two aircraft count as moving together (nm/s, squared)
+const PARALLEL_SPEED_SQUARED: f64 = 1e-10;
+
+/// How the horizontal distance between two aircraft is changing
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ApproachStatus {
+    /// Closest point of approach is `time` seconds ahead
+    Approaching { time: f64 },
+    /// Closest point of approach is now or already behind them
+    Diverging,
+    /// No relative motion, so the distance stays constant
+    Parallel,
+}
+
+/// `ApproachStatus` kind for JavaScript
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ApproachKind {
+    Approaching,
+    Diverging,
+    Parallel,
+}
+
+/// `ApproachStatus` for JavaScript; `time` is zero unless approaching
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApproachTiming {
+    pub kind: ApproachKind,
+    pub time: f64,
+}
+
+impl From<ApproachStatus> for ApproachTiming {
+    fn from(status: ApproachStatus) -> Self {
+        match status {
+            ApproachStatus::Approaching { time } => ApproachTiming {
+                kind: ApproachKind::Approaching,
+                time,
+            },
+            ApproachStatus::Diverging => ApproachTiming {
+                kind: ApproachKind::Diverging,
+                time: 0.0,
+            },
+            ApproachStatus::Parallel => ApproachTiming {
+                kind: ApproachKind::Parallel,
+                time: 0.0,
+            },
+        }
+    }
+}
+
 /// Check if separation standards are met between two aircraft
 pub fn check_separation(
     aircraft1: &AircraftState,
     aircraft2: &AircraftState,
-    min_horizontal: f64,
-    min_vertical: f64,
+    standards: &SeparationStandards,
 ) -> SeparationResult {
     let horizontal_distance = calculate_horizontal_distance(aircraft1, aircraft2);
     let vertical_distance = calculate_vertical_distance(aircraft1, aircraft2);
     
-    let is_safe = horizontal_distance >= min_horizontal || vertical_distance >= min_vertical;
-    
-    SeparationResult::new(is_safe, horizontal_distance, vertical_distance)
+    let result = SeparationResult::new(horizontal_distance, vertical_distance, standards.horizontal, standards.vertical);
+    match required_wake_distance(aircraft1, aircraft2) {
+        Some(wake_minimum) => result.with_wake_minimum(wake_minimum),
+        None => result,
+    }
 }
 
 /// Calculate horizontal distance between two aircraft
 fn calculate_horizontal_distance(aircraft1: &AircraftState, aircraft2: &AircraftState) -> f64 {
-    let dx = aircraft1.x - aircraft2.x;
-    let dy = aircraft1.y - aircraft2.y;
-    (dx * dx + dy * dy).sqrt()
+    core_math::horizontal_distance(&(*aircraft1).into(), &(*aircraft2).into())
 }
 
 /// Calculate vertical distance between two aircraft
 fn calculate_vertical_distance(aircraft1: &AircraftState, aircraft2: &AircraftState) -> f64 {
-    (aircraft1.altitude - aircraft2.altitude).abs()
+    core_math::vertical_distance(&(*aircraft1).into(), &(*aircraft2).into())
 }
 
 /// Check if aircraft are converging
@@ -45,20 +102,19 @@ pub fn are_converging(aircraft1: &AircraftState, aircraft2: &AircraftState) -> b
     future_distance < current_distance
 }
 
-/// Predict aircraft position after given time
-fn predict_position(aircraft: &AircraftState, time_seconds: f64) -> AircraftState {
-    let speed_nm_per_sec = aircraft.speed / 3600.0;
-    let heading_rad = aircraft.heading.to_radians();
-    
-    let dx = heading_rad.sin() * speed_nm_per_sec * time_seconds;
-    let dy = heading_rad.cos() * speed_nm_per_sec * time_seconds;
-    
+/// Predict aircraft position after given time, climbing or descending at its vertical rate
+pub(crate) fn predict_position(aircraft: &AircraftState, time_seconds: f64) -> AircraftState {
     AircraftState {
-        x: aircraft.x + dx,
-        y: aircraft.y + dy,
-        altitude: aircraft.altitude,
-        heading: aircraft.heading,
-        speed: aircraft.speed,
+        wake: aircraft.wake,
+        ..core_math::predict(&(*aircraft).into(), time_seconds).into()
+    }
+}
+
+/// Predict aircraft position, levelling off once the target altitude is reached
+pub fn predict_position_to_level(aircraft: &AircraftState, time_seconds: f64, target_altitude: f64) -> AircraftState {
+    AircraftState {
+        wake: aircraft.wake,
+        ..core_math::predict_to_level(&(*aircraft).into(), time_seconds, target_altitude).into()
     }
 }
 
@@ -66,33 +122,17 @@ fn predict_position(aircraft: &AircraftState, time_seconds: f64) -> AircraftStat
 pub fn time_to_minimum_separation(
     aircraft1: &AircraftState,
     aircraft2: &AircraftState,
-) -> Option<f64> {
-    let dx = aircraft2.x - aircraft1.x;
-    let dy = aircraft2.y - aircraft1.y;
-    
-    let hdg1_rad = aircraft1.heading.to_radians();
-    let hdg2_rad = aircraft2.heading.to_radians();
-    
-    let v1x = hdg1_rad.sin() * aircraft1.speed / 3600.0;
-    let v1y = hdg1_rad.cos() * aircraft1.speed / 3600.0;
-    let v2x = hdg2_rad.sin() * aircraft2.speed / 3600.0;
-    let v2y = hdg2_rad.cos() * aircraft2.speed / 3600.0;
-    
-    let dvx = v2x - v1x;
-    let dvy = v2y - v1y;
-    
-    let relative_speed_squared = dvx * dvx + dvy * dvy;
-    
-    if relative_speed_squared < 1e-10 {
-        return None; // No relative motion
+) -> ApproachStatus {
+    let (state1, state2) = ((*aircraft1).into(), (*aircraft2).into());
+    let (v1x, v1y) = core_math::velocity(&state1);
+    let (v2x, v2y) = core_math::velocity(&state2);
+    if (v2x - v1x).powi(2) + (v2y - v1y).powi(2) < PARALLEL_SPEED_SQUARED {
+        return ApproachStatus::Parallel;
     }
-    
-    let time = -(dx * dvx + dy * dvy) / relative_speed_squared;
-    
-    if time > 0.0 {
-        Some(time)
-    } else {
-        None
+
+    match core_math::time_to_closest_approach(&state1, &state2) {
+        Some(time) => ApproachStatus::Approaching { time },
+        None => ApproachStatus::Diverging,
     }
 }
 
@@ -127,13 +167,14 @@ pub fn minimum_separation_over_time(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::SeparationBasis;
 
     #[test]
     fn test_separation_check() {
         let aircraft1 = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0);
         let aircraft2 = AircraftState::new(5.0, 0.0, 10000.0, 180.0, 250.0);
         
-        let result = check_separation(&aircraft1, &aircraft2, 3.0, 1000.0);
+        let result = check_separation(&aircraft1, &aircraft2, &SeparationStandards::terminal());
         assert!(result.is_safe);
     }
 
@@ -142,7 +183,48 @@ mod tests {
         let aircraft1 = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0);
         let aircraft2 = AircraftState::new(2.0, 0.0, 10500.0, 180.0, 250.0);
         
-        let result = check_separation(&aircraft1, &aircraft2, 3.0, 1000.0);
+        let result = check_separation(&aircraft1, &aircraft2, &SeparationStandards::terminal());
         assert!(!result.is_safe);
     }
+
+    #[test]
+    fn test_governing_minimum_and_margins() {
+        let aircraft1 = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0);
+        let aircraft2 = AircraftState::new(1.0, 0.0, 11200.0, 180.0, 250.0);
+
+        let result = check_separation(&aircraft1, &aircraft2, &SeparationStandards::terminal());
+        assert!(result.is_safe);
+        assert_eq!(result.basis(), SeparationBasis::Vertical);
+        assert_eq!(result.vertical_margin, 200.0);
+        assert_eq!(result.horizontal_margin, -2.0);
+    }
+
+    #[test]
+    fn test_prediction_follows_vertical_rate() {
+        let descending = AircraftState::new(0.0, 0.0, 12000.0, 0.0, 240.0).with_vertical_rate(-1500.0);
+        assert_eq!(predict_position(&descending, 60.0).altitude, 10500.0);
+
+        let levelled = predict_position_to_level(&descending, 120.0, 11000.0);
+        assert_eq!(levelled.altitude, 11000.0);
+        assert_eq!(levelled.vertical_rate, 0.0);
+        assert!((levelled.y - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_approach_status() {
+        let aircraft1 = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 300.0);
+        let head_on = AircraftState::new(0.0, 10.0, 10000.0, 180.0, 300.0);
+        assert_eq!(
+            time_to_minimum_separation(&aircraft1, &head_on),
+            ApproachStatus::Approaching { time: 60.0 }
+        );
+
+        let passed = AircraftState::new(0.0, -10.0, 10000.0, 180.0, 300.0);
+        assert_eq!(time_to_minimum_separation(&aircraft1, &passed), ApproachStatus::Diverging);
+
+        let in_trail = AircraftState::new(0.0, 5.0, 12000.0, 0.0, 300.0);
+        let status = time_to_minimum_separation(&aircraft1, &in_trail);
+        assert_eq!(status, ApproachStatus::Parallel);
+        assert_eq!(ApproachTiming::from(status).kind, ApproachKind::Parallel);
+    }
 }
\ No newline at end of file
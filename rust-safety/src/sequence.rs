@@ -0,0 +1,151 @@
+/*!
+ * SEQUENCE MODULE
+ * Arrival sequencing and landing gap finding with deterministic ordering
+ */
+
+use std::cmp::Ordering;
+
+use crate::approach::{Runway, SpeedSchedule};
+use crate::AircraftState;
+
+/// One arrival's place in the landing sequence
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequencedArrival {
+    /// 1 for the first to land
+    pub position: usize,
+    pub callsign: String,
+    /// Unconstrained time to the threshold following the speed schedule (seconds)
+    pub eta: f64,
+    /// Time the arrival is sequenced to land, at least `eta`
+    pub landing_time: f64,
+    /// Delay to absorb before landing (seconds)
+    pub delay: f64,
+}
+
+/// Sequencing order of two arrivals
+///
+/// Earlier ETA first; equal ETAs are broken by callsign in byte order
+/// (so `"BAW1" < "BAW12" < "EZY1"`). ETAs are compared with
+/// `f64::total_cmp`, so an unknown (NaN) or unreachable (infinite) ETA
+/// sorts after every finite one and never makes the order depend on input
+/// order.
+pub fn compare_arrivals(eta1: f64, callsign1: &str, eta2: f64, callsign2: &str) -> Ordering {
+    eta1.total_cmp(&eta2).then_with(|| callsign1.cmp(callsign2))
+}
+
+/// Sequence arrivals to `runway` by ETA with at least `spacing` seconds between landings
+///
+/// ETAs are flown direct to the threshold following `schedule`. The order
+/// follows `compare_arrivals`, so the same arrivals give the same sequence
+/// in whatever order they are passed; arrivals sharing a callsign keep
+/// their input order.
+pub fn sequence_arrivals(
+    arrivals: &[(String, AircraftState)],
+    runway: &Runway,
+    schedule: &SpeedSchedule,
+    spacing: f64,
+) -> Vec<SequencedArrival> {
+    let mut etas: Vec<(f64, &str)> = arrivals
+        .iter()
+        .map(|(callsign, state)| {
+            let distance = (state.x - runway.threshold_x).hypot(state.y - runway.threshold_y);
+            (schedule.time_to_threshold(distance, state.speed), callsign.as_str())
+        })
+        .collect();
+    etas.sort_by(|a, b| compare_arrivals(a.0, a.1, b.0, b.1));
+
+    let mut sequence: Vec<SequencedArrival> = Vec::with_capacity(etas.len());
+    for (eta, callsign) in etas {
+        let earliest = sequence.last().map_or(eta, |previous| previous.landing_time + spacing);
+        // Arrivals that will never reach the threshold are listed but not slotted
+        let landing_time = if eta.is_finite() { eta.max(earliest) } else { eta };
+        sequence.push(SequencedArrival {
+            position: sequence.len() + 1,
+            callsign: callsign.to_string(),
+            eta,
+            landing_time,
+            delay: if eta.is_finite() { landing_time - eta } else { 0.0 },
+        });
+    }
+    sequence
+}
+
+/// Earliest landing time at or after `eta` at least `spacing` seconds from every sequenced landing
+///
+/// Candidates are tried in time order (the ETA itself, then just behind
+/// each landing), so the first gap that fits is always the one returned.
+pub fn find_landing_gap(sequence: &[SequencedArrival], eta: f64, spacing: f64) -> f64 {
+    let fits = |time: f64| {
+        sequence
+            .iter()
+            .all(|arrival| (arrival.landing_time - time).abs() >= spacing)
+    };
+
+    let mut candidates: Vec<f64> = sequence
+        .iter()
+        .map(|arrival| arrival.landing_time + spacing)
+        .filter(|&time| time > eta)
+        .collect();
+    candidates.sort_by(f64::total_cmp);
+
+    std::iter::once(eta)
+        .chain(candidates)
+        .find(|&time| fits(time))
+        .unwrap_or(eta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arrivals() -> Vec<(String, AircraftState)> {
+        // EZY1 and BAW12 are equidistant and equally fast, so their ETAs tie
+        vec![
+            ("EZY1".to_string(), AircraftState::new(0.0, -20.0, 6000.0, 0.0, 250.0)),
+            ("BAW12".to_string(), AircraftState::new(20.0, 0.0, 6000.0, 270.0, 250.0)),
+            ("DLH4".to_string(), AircraftState::new(0.0, 12.0, 5000.0, 180.0, 220.0)),
+            ("BAW1".to_string(), AircraftState::new(-30.0, 0.0, 8000.0, 90.0, 280.0)),
+        ]
+    }
+
+    #[test]
+    fn test_sequence_is_reproducible() {
+        let runway = Runway::new(0.0, 0.0, 0.0, 90.0);
+        let schedule = SpeedSchedule::standard();
+        let sequence = sequence_arrivals(&arrivals(), &runway, &schedule, 90.0);
+
+        let order: Vec<&str> = sequence.iter().map(|a| a.callsign.as_str()).collect();
+        assert_eq!(order, vec!["DLH4", "BAW12", "EZY1", "BAW1"]);
+        assert_eq!(sequence[1].eta, sequence[2].eta);
+        assert_eq!(sequence[2].landing_time, sequence[1].landing_time + 90.0);
+        assert!(sequence.windows(2).all(|w| w[1].landing_time - w[0].landing_time >= 90.0));
+
+        // Byte-identical output for every input order
+        let expected = format!("{:?}", sequence);
+        let mut input = arrivals();
+        for _ in 0..input.len() {
+            input.rotate_left(1);
+            assert_eq!(format!("{:?}", sequence_arrivals(&input, &runway, &schedule, 90.0)), expected);
+            input.reverse();
+            assert_eq!(format!("{:?}", sequence_arrivals(&input, &runway, &schedule, 90.0)), expected);
+        }
+    }
+
+    #[test]
+    fn test_gap_and_unknown_eta() {
+        let runway = Runway::new(0.0, 0.0, 0.0, 90.0);
+        let sequence = sequence_arrivals(&arrivals(), &runway, &SpeedSchedule::standard(), 90.0);
+
+        let gap = find_landing_gap(&sequence, sequence[0].landing_time + 30.0, 90.0);
+        assert!(sequence.iter().all(|a| (a.landing_time - gap).abs() >= 90.0));
+        assert_eq!(find_landing_gap(&[], 100.0, 90.0), 100.0);
+
+        // A stationary target has no ETA and goes to the back
+        let mut input = arrivals();
+        input.insert(0, ("AAA1".to_string(), AircraftState::new(5.0, 5.0, 3000.0, 0.0, 0.0)));
+        let sequence = sequence_arrivals(&input, &runway, &SpeedSchedule::standard(), 90.0);
+        let last = sequence.last().unwrap();
+        assert_eq!((last.callsign.as_str(), last.delay), ("AAA1", 0.0));
+        assert_eq!(compare_arrivals(f64::NAN, "A", 10.0, "B"), Ordering::Greater);
+    }
+}
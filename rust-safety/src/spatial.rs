@@ -0,0 +1,160 @@
+/*!
+ * SPATIAL MODULE
+ * Uniform grid pruning of aircraft pairs before conflict detection
+ */
+
+use std::collections::HashMap;
+
+use crate::config::SafetyConfig;
+use crate::conflict::{detect_conflict, ConflictSeverity};
+use crate::sweep::PairConflict;
+use crate::AircraftState;
+
+/// Aircraft indices bucketed into square cells
+#[derive(Debug, Clone)]
+pub struct SpatialGrid {
+    cell_size: f64,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl SpatialGrid {
+    /// Bucket aircraft into cells of `cell_size` nm
+    pub fn build(aircraft: &[AircraftState], cell_size: f64) -> Self {
+        let cell_size = if cell_size > 0.0 { cell_size } else { 1.0 };
+        let mut grid = SpatialGrid {
+            cell_size,
+            cells: HashMap::new(),
+        };
+        for (i, a) in aircraft.iter().enumerate() {
+            grid.cells.entry(grid.cell(a.x, a.y)).or_default().push(i);
+        }
+        grid
+    }
+
+    fn cell(&self, x: f64, y: f64) -> (i64, i64) {
+        ((x / self.cell_size).floor() as i64, (y / self.cell_size).floor() as i64)
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Index pairs (lower first) within `range` nm horizontally, sorted
+    ///
+    /// Only neighbouring cells are searched, so `range` must not exceed
+    /// the cell size.
+    pub fn pairs_within(&self, aircraft: &[AircraftState], range: f64) -> Vec<(usize, usize)> {
+        let range = range.min(self.cell_size);
+        let mut pairs = Vec::new();
+
+        for (i, a) in aircraft.iter().enumerate() {
+            let (cx, cy) = self.cell(a.x, a.y);
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    let Some(members) = self.cells.get(&(cx + dx, cy + dy)) else {
+                        continue;
+                    };
+                    for &j in members.iter().filter(|&&j| j > i) {
+                        let b = &aircraft[j];
+                        if (a.x - b.x).powi(2) + (a.y - b.y).powi(2) <= range * range {
+                            pairs.push((i, j));
+                        }
+                    }
+                }
+            }
+        }
+
+        pairs.sort_unstable();
+        pairs
+    }
+}
+
+/// Widest horizontal distance (nm) at which two aircraft no faster than
+/// `max_speed` kt can still conflict within the look-ahead
+pub fn screening_range(config: &SafetyConfig, max_speed: f64) -> f64 {
+    config.alert_distance() + 2.0 * max_speed * config.look_ahead_time / 3600.0
+}
+
+/// Conflicts among pairs closer than `range` nm, as with an all-pairs check
+///
+/// Pairs further apart are never evaluated. Use `screening_range` for a
+/// range that cannot miss a conflict.
+pub fn detect_conflicts_spatial(aircraft: &[AircraftState], config: &SafetyConfig, range: f64) -> Vec<PairConflict> {
+    let grid = SpatialGrid::build(aircraft, range);
+    grid.pairs_within(aircraft, range)
+        .into_iter()
+        .filter_map(|(first, second)| {
+            let info = detect_conflict(
+                &aircraft[first],
+                &aircraft[second],
+                &config.alert_standards(),
+                config.look_ahead_time,
+            );
+            (info.severity != ConflictSeverity::None).then_some(PairConflict { first, second, info })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SeparationStandards;
+
+    fn scattered(n: usize) -> Vec<AircraftState> {
+        let mut seed = 12345u64;
+        let mut next = move || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 11) as f64 / (1u64 << 53) as f64
+        };
+        (0..n)
+            .map(|_| {
+                AircraftState::new(
+                    next() * 400.0 - 200.0,
+                    next() * 400.0 - 200.0,
+                    (next() * 10.0).floor() * 1000.0 + 20000.0,
+                    next() * 360.0,
+                    250.0 + next() * 250.0,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_grid_pairs_match_brute_force() {
+        let aircraft = scattered(200);
+        let grid = SpatialGrid::build(&aircraft, 30.0);
+        let pairs = grid.pairs_within(&aircraft, 30.0);
+
+        let mut expected = Vec::new();
+        for i in 0..aircraft.len() {
+            for j in (i + 1)..aircraft.len() {
+                let d = ((aircraft[i].x - aircraft[j].x).powi(2) + (aircraft[i].y - aircraft[j].y).powi(2)).sqrt();
+                if d <= 30.0 {
+                    expected.push((i, j));
+                }
+            }
+        }
+        assert_eq!(pairs, expected);
+        assert!(grid.cell_count() > 1);
+    }
+
+    #[test]
+    fn test_spatial_detection_finds_every_conflict() {
+        let config = SafetyConfig::new(5.0, 1000.0, 120.0, 1.0);
+        let aircraft = scattered(150);
+        let found = detect_conflicts_spatial(&aircraft, &config, screening_range(&config, 500.0));
+
+        let mut expected = Vec::new();
+        for i in 0..aircraft.len() {
+            for j in (i + 1)..aircraft.len() {
+                let info = detect_conflict(&aircraft[i], &aircraft[j], &SeparationStandards::en_route(), 120.0);
+                if info.severity != ConflictSeverity::None {
+                    expected.push((i, j));
+                }
+            }
+        }
+        let found: Vec<_> = found.iter().map(|c| (c.first, c.second)).collect();
+        assert!(!expected.is_empty());
+        assert_eq!(found, expected);
+    }
+}
@@ -1,9 +1,15 @@
-/**
+/*!
  * STATE MANAGEMENT MODULE
  * Aircraft state tracking and validation
  */
 
-use crate::AircraftState;
+use std::collections::BTreeMap;
+
+use wasm_bindgen::prelude::*;
+
+use crate::separation::predict_position;
+use crate::validation::ValidationConfig;
+use crate::{heading_difference, AircraftState};
 
 /// State change tracking
 #[derive(Debug, Clone, Copy)]
@@ -24,7 +30,7 @@ impl StateChange {
     
     pub fn calculate(previous: &AircraftState, current: &AircraftState) -> Self {
         StateChange {
-            heading_change: normalize_heading_diff(current.heading - previous.heading),
+            heading_change: heading_difference(previous.heading, current.heading),
             speed_change: current.speed - previous.speed,
             altitude_change: current.altitude - previous.altitude,
         }
@@ -37,45 +43,98 @@ impl StateChange {
     }
 }
 
-/// Normalize heading difference to -180 to 180 range
-fn normalize_heading_diff(diff: f64) -> f64 {
-    let mut result = diff;
-    while result > 180.0 {
-        result -= 360.0;
+impl Default for StateChange {
+    fn default() -> Self {
+        Self::new()
     }
-    while result < -180.0 {
-        result += 360.0;
-    }
-    result
 }
 
 /// Track aircraft state history
+///
+/// States added without a time carry a NaN timestamp; they yield no rates
+/// and are only evicted by count.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StateHistory {
     states: Vec<AircraftState>,
+    timestamps: Vec<f64>,
     max_history: usize,
+    /// Oldest state kept, in seconds before the latest
+    max_age: Option<f64>,
 }
 
 impl StateHistory {
     pub fn new(max_history: usize) -> Self {
         StateHistory {
             states: Vec::with_capacity(max_history),
+            timestamps: Vec::with_capacity(max_history),
             max_history,
+            max_age: None,
         }
     }
     
+    /// Also evict states more than `max_age` seconds older than the latest
+    pub fn with_max_age(mut self, max_age: f64) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+    
     pub fn add_state(&mut self, state: AircraftState) {
-        self.states.push(state);
+        self.add_timed_state(TimedState::new(state, f64::NAN));
+    }
+    
+    pub fn add_timed_state(&mut self, observation: TimedState) {
+        self.states.push(observation.state);
+        self.timestamps.push(observation.timestamp);
         
         // Keep only recent history
         if self.states.len() > self.max_history {
             self.states.remove(0);
+            self.timestamps.remove(0);
+        }
+        if let Some(max_age) = self.max_age {
+            let cutoff = observation.timestamp - max_age;
+            let expired = self.timestamps.iter().take_while(|&&t| t < cutoff).count();
+            self.states.drain(..expired);
+            self.timestamps.drain(..expired);
         }
     }
     
     pub fn get_latest(&self) -> Option<&AircraftState> {
         self.states.last()
     }
+
+    /// Overwrite the latest state, keeping its time; false if the history is empty
+    pub fn replace_latest(&mut self, state: AircraftState) -> bool {
+        match self.states.last_mut() {
+            Some(latest) => {
+                *latest = state;
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Recorded states, oldest first
+    pub fn states(&self) -> &[AircraftState] {
+        &self.states
+    }
+    
+    /// Observation times matching `states()`
+    pub fn timestamps(&self) -> &[f64] {
+        &self.timestamps
+    }
+    
+    /// The `index`th state with its time, oldest first
+    pub fn timed_state(&self, index: usize) -> Option<TimedState> {
+        Some(TimedState::new(*self.states.get(index)?, self.timestamps[index]))
+    }
+    
+    /// Rates between the two latest states, as `calculate_rate_of_change`
+    pub fn rate_of_change(&self) -> Option<(f64, f64, f64)> {
+        let len = self.states.len();
+        calculate_rate_of_change(&self.timed_state(len.checked_sub(2)?)?, &self.timed_state(len - 1)?)
+    }
     
     pub fn get_previous(&self) -> Option<&AircraftState> {
         if self.states.len() >= 2 {
@@ -122,44 +181,281 @@ pub fn is_state_normal(aircraft: &AircraftState) -> bool {
         return false;
     }
     
-    // Check heading bounds
-    if aircraft.heading < 0.0 || aircraft.heading >= 360.0 {
+    // Check heading bounds (360 is accepted as north)
+    if !(0.0..=360.0).contains(&aircraft.heading) {
         return false;
     }
     
     true
 }
 
-/// Calculate rate of change
-pub fn calculate_rate_of_change(
-    previous: &AircraftState,
-    current: &AircraftState,
-    time_delta: f64,
-) -> (f64, f64, f64) {
-    let heading_rate = normalize_heading_diff(current.heading - previous.heading) / time_delta;
+/// Aircraft state with the time it was observed (seconds)
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimedState {
+    pub state: AircraftState,
+    pub timestamp: f64,
+}
+
+#[wasm_bindgen]
+impl TimedState {
+    #[wasm_bindgen(constructor)]
+    pub fn new(state: AircraftState, timestamp: f64) -> TimedState {
+        TimedState { state, timestamp }
+    }
+}
+
+/// Default time a silent track is dead-reckoned for (seconds)
+const DEFAULT_COAST_WINDOW: f64 = 10.0;
+
+/// Default silence after which a track is dropped (seconds)
+const DEFAULT_COAST_TIMEOUT: f64 = 60.0;
+
+/// Dead-reckoned estimate of a track that has stopped reporting
+#[derive(Debug, Clone, Copy)]
+pub struct Coast {
+    /// Extrapolated state at the last `coast` call
+    pub estimate: TimedState,
+    /// Coast cycles since the last report
+    pub count: u32,
+}
+
+/// One tracked aircraft: its latest observation and recent history
+#[derive(Debug, Clone)]
+pub struct Track {
+    pub latest: TimedState,
+    pub history: StateHistory,
+    /// Set while the track is coasting on missed updates
+    pub coast: Option<Coast>,
+}
+
+impl Track {
+    /// Seconds since the last report as of the latest coast, zero when reporting
+    pub fn coast_age(&self) -> f64 {
+        self.coast.map_or(0.0, |c| c.estimate.timestamp - self.latest.timestamp)
+    }
+}
+
+/// Registry of every tracked aircraft, keyed by callsign or track ID
+///
+/// Iteration is in key order so every host sees the same sequence.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct TrackManager {
+    tracks: BTreeMap<String, Track>,
+    history_length: usize,
+    /// Longest extrapolation of a silent track (seconds)
+    pub coast_window: f64,
+    /// Silence after which `coast` drops a track (seconds)
+    pub coast_timeout: f64,
+}
+
+#[wasm_bindgen]
+impl TrackManager {
+    /// Registry keeping `history_length` states per track
+    #[wasm_bindgen(constructor)]
+    pub fn new(history_length: usize) -> TrackManager {
+        TrackManager {
+            tracks: BTreeMap::new(),
+            history_length: history_length.max(1),
+            coast_window: DEFAULT_COAST_WINDOW,
+            coast_timeout: DEFAULT_COAST_TIMEOUT,
+        }
+    }
+
+    /// Dead-reckon silent tracks for up to `window` seconds, dropping them after `timeout`
+    pub fn with_coasting(mut self, window: f64, timeout: f64) -> TrackManager {
+        self.coast_window = window;
+        self.coast_timeout = timeout;
+        self
+    }
+
+    /// Start tracking `id`; false if it is already tracked
+    pub fn add(&mut self, id: &str, state: AircraftState, timestamp: f64) -> bool {
+        if self.tracks.contains_key(id) {
+            return false;
+        }
+        let mut history = StateHistory::new(self.history_length);
+        let latest = TimedState::new(state, timestamp);
+        history.add_timed_state(latest);
+        self.tracks.insert(id.to_string(), Track { latest, history, coast: None });
+        true
+    }
+
+    /// Record a new state for `id`; false if unknown or not newer than the last
+    pub fn update(&mut self, id: &str, state: AircraftState, timestamp: f64) -> bool {
+        let Some(track) = self.tracks.get_mut(id) else {
+            return false;
+        };
+        let observation = TimedState::new(state, timestamp);
+        if elapsed(&track.latest, &observation).is_none() {
+            return false;
+        }
+        track.latest = observation;
+        track.history.add_timed_state(observation);
+        track.coast = None;
+        true
+    }
+
+    /// Add `id` if new, otherwise update it
+    pub fn upsert(&mut self, id: &str, state: AircraftState, timestamp: f64) -> bool {
+        self.add(id, state, timestamp) || self.update(id, state, timestamp)
+    }
+
+    /// Replace the latest report for `id` with a correction made at the same time
+    ///
+    /// False if `id` is unknown or its latest report has a different time stamp.
+    pub fn correct(&mut self, id: &str, state: AircraftState, timestamp: f64) -> bool {
+        let Some(track) = self.tracks.get_mut(id) else {
+            return false;
+        };
+        if track.latest.timestamp != timestamp {
+            return false;
+        }
+        track.latest.state = state;
+        track.history.replace_latest(state);
+        track.coast = None;
+        true
+    }
+
+    pub fn remove(&mut self, id: &str) -> bool {
+        self.tracks.remove(id).is_some()
+    }
+
+    /// Drop tracks not updated within `max_age` seconds of `now`, returning their IDs
+    pub fn prune_stale(&mut self, now: f64, max_age: f64) -> Vec<String> {
+        let stale: Vec<String> = self
+            .tracks
+            .iter()
+            .filter(|(_, track)| now - track.latest.timestamp > max_age)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &stale {
+            self.tracks.remove(id);
+        }
+        stale
+    }
+
+    /// Coast every track without a report at `now`, returning the IDs dropped
+    ///
+    /// Positions are extrapolated from the last reported heading and speed,
+    /// holding still once the silence exceeds the coast window. Tracks silent
+    /// for longer than the timeout are removed.
+    pub fn coast(&mut self, now: f64) -> Vec<String> {
+        self.coast_since(now, now)
+    }
+
+    /// As `coast`, but only tracks with no report since `since` are coasted
+    ///
+    /// Lets a periodic caller leave alone tracks that reported during the
+    /// cycle just ended, even though their reports are older than `now`.
+    pub fn coast_since(&mut self, now: f64, since: f64) -> Vec<String> {
+        let dropped = self.prune_stale(now, self.coast_timeout);
+        for track in self.tracks.values_mut() {
+            let age = now - track.latest.timestamp;
+            if track.latest.timestamp >= since || !(age.is_finite() && age > 0.0) {
+                continue;
+            }
+            let estimate = TimedState::new(predict_position(&track.latest.state, age.min(self.coast_window)), now);
+            let count = track.coast.map_or(0, |c| c.count) + 1;
+            track.coast = Some(Coast { estimate, count });
+        }
+        dropped
+    }
+
+    pub fn is_coasted(&self, id: &str) -> bool {
+        self.tracks.get(id).is_some_and(|track| track.coast.is_some())
+    }
+
+    /// Coast cycles since the last report for `id`
+    pub fn coast_count(&self, id: &str) -> u32 {
+        self.tracks.get(id).and_then(|track| track.coast).map_or(0, |c| c.count)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.tracks.contains_key(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tracks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tracks.is_empty()
+    }
+
+    /// Tracked IDs in order
+    pub fn ids(&self) -> Vec<String> {
+        self.tracks.keys().cloned().collect()
+    }
+
+    /// Best current state: the dead-reckoned estimate while coasting, else the last report
+    pub fn latest(&self, id: &str) -> Option<AircraftState> {
+        self.tracks
+            .get(id)
+            .map(|track| track.coast.map_or(track.latest.state, |c| c.estimate.state))
+    }
+
+    /// Time of the last report for `id`
+    pub fn timestamp(&self, id: &str) -> Option<f64> {
+        self.tracks.get(id).map(|track| track.latest.timestamp)
+    }
+}
+
+impl TrackManager {
+    pub fn track(&self, id: &str) -> Option<&Track> {
+        self.tracks.get(id)
+    }
+
+    pub fn history(&self, id: &str) -> Option<&StateHistory> {
+        self.tracks.get(id).map(|track| &track.history)
+    }
+
+    /// Every track in ID order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Track)> {
+        self.tracks.iter().map(|(id, track)| (id.as_str(), track))
+    }
+}
+
+impl Default for TrackManager {
+    fn default() -> Self {
+        TrackManager::new(10)
+    }
+}
+
+/// Seconds from one observation to the next, if the timestamps advance
+pub fn elapsed(previous: &TimedState, current: &TimedState) -> Option<f64> {
+    let delta = current.timestamp - previous.timestamp;
+    (delta.is_finite() && delta > 0.0).then_some(delta)
+}
+
+/// Heading (deg/s), speed (kt/s) and altitude (ft/s) rates between two observations
+///
+/// `None` when the timestamps do not advance.
+pub fn calculate_rate_of_change(previous: &TimedState, current: &TimedState) -> Option<(f64, f64, f64)> {
+    let time_delta = elapsed(previous, current)?;
+    let (previous, current) = (&previous.state, &current.state);
+
+    let heading_rate = heading_difference(previous.heading, current.heading) / time_delta;
     let speed_rate = (current.speed - previous.speed) / time_delta;
     let altitude_rate = (current.altitude - previous.altitude) / time_delta;
-    
-    (heading_rate, speed_rate, altitude_rate)
+
+    Some((heading_rate, speed_rate, altitude_rate))
 }
 
 /// Detect unusual state changes
-pub fn detect_unusual_changes(
-    previous: &AircraftState,
-    current: &AircraftState,
-    time_delta: f64,
-) -> bool {
-    let (heading_rate, speed_rate, altitude_rate) = 
-        calculate_rate_of_change(previous, current, time_delta);
-    
-    // Check for unrealistic rates of change
-    let max_turn_rate = 5.0; // degrees per second
-    let max_accel_rate = 20.0; // knots per second
-    let max_climb_rate = 3000.0; // feet per minute (converted to per second)
-    
-    heading_rate.abs() > max_turn_rate
-        || speed_rate.abs() > max_accel_rate
-        || altitude_rate.abs() > (max_climb_rate / 60.0)
+///
+/// `None` when the timestamps do not advance, so no rate can be derived.
+pub fn detect_unusual_changes(previous: &TimedState, current: &TimedState, config: &ValidationConfig) -> Option<bool> {
+    let (heading_rate, speed_rate, altitude_rate) = calculate_rate_of_change(previous, current)?;
+
+    // Check for unrealistic rates of change; climb rate is per minute
+    Some(
+        heading_rate.abs() > config.max_turn_rate
+            || speed_rate.abs() > config.max_acceleration
+            || altitude_rate.abs() > (config.max_climb_rate / 60.0),
+    )
 }
 
 #[cfg(test)]
@@ -204,4 +500,98 @@ mod tests {
         let abnormal = AircraftState::new(0.0, 0.0, 70000.0, 180.0, 250.0);
         assert!(!is_state_normal(&abnormal));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_rates_need_advancing_timestamps() {
+        let previous = TimedState::new(AircraftState::new(0.0, 0.0, 10000.0, 90.0, 250.0), 10.0);
+        let current = TimedState::new(AircraftState::new(0.0, 1.0, 10200.0, 100.0, 250.0), 12.0);
+
+        assert_eq!(calculate_rate_of_change(&previous, &current), Some((5.0, 0.0, 100.0)));
+        let config = ValidationConfig::default();
+        assert_eq!(detect_unusual_changes(&previous, &current, &config), Some(true));
+        // A fighter turning and zooming at these rates is unremarkable
+        let military = ValidationConfig::new().with_max_rates(10.0, 30.0, 30000.0);
+        assert_eq!(detect_unusual_changes(&previous, &current, &military), Some(false));
+
+        assert_eq!(elapsed(&current, &previous), None);
+        assert_eq!(calculate_rate_of_change(&previous, &previous), None);
+        let undated = TimedState::new(current.state, f64::NAN);
+        assert_eq!(detect_unusual_changes(&previous, &undated, &config), None);
+    }
+
+    #[test]
+    fn test_track_manager_lifecycle() {
+        let mut tracks = TrackManager::new(3);
+        let state = AircraftState::new(0.0, 0.0, 10000.0, 90.0, 250.0);
+
+        assert!(tracks.add("BAW123", state, 0.0));
+        assert!(!tracks.add("BAW123", state, 1.0));
+        assert!(tracks.upsert("DLH4", state, 0.0));
+        assert!(!tracks.update("UNKNOWN", state, 1.0));
+
+        for t in 1..5 {
+            assert!(tracks.update("BAW123", AircraftState { x: t as f64, ..state }, t as f64));
+        }
+        // Out-of-order reports are ignored
+        assert!(!tracks.update("BAW123", state, 2.0));
+        assert_eq!(tracks.latest("BAW123").unwrap().x, 4.0);
+        assert_eq!(tracks.history("BAW123").unwrap().states().len(), 3);
+
+        let ids: Vec<&str> = tracks.iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, vec!["BAW123", "DLH4"]);
+
+        assert_eq!(tracks.prune_stale(10.0, 8.0), vec!["DLH4".to_string()]);
+        assert_eq!(tracks.ids(), vec!["BAW123".to_string()]);
+        assert!(tracks.remove("BAW123"));
+        assert!(tracks.is_empty());
+    }
+
+    #[test]
+    fn test_history_rates_and_age_eviction() {
+        let mut history = StateHistory::new(10).with_max_age(30.0);
+        for t in 0..5 {
+            let state = AircraftState::new(0.0, t as f64, 10000.0 + 100.0 * t as f64, 90.0, 250.0);
+            history.add_timed_state(TimedState::new(state, 10.0 * t as f64));
+        }
+
+        // Only the states from 10 s to 40 s are within 30 s of the latest
+        assert_eq!(history.timestamps(), &[10.0, 20.0, 30.0, 40.0]);
+        assert_eq!(history.rate_of_change(), Some((0.0, 0.0, 10.0)));
+
+        let mut untimed = StateHistory::new(2);
+        untimed.add_state(AircraftState::new(0.0, 0.0, 10000.0, 90.0, 250.0));
+        untimed.add_state(AircraftState::new(0.0, 1.0, 10000.0, 90.0, 250.0));
+        assert_eq!(untimed.rate_of_change(), None);
+    }
+
+    #[test]
+    fn test_silent_tracks_coast_then_drop() {
+        let mut tracks = TrackManager::new(5).with_coasting(10.0, 30.0);
+        // Eastbound at 360 kt: 0.1 nm per second
+        tracks.add("EZY12", AircraftState::new(0.0, 0.0, 10000.0, 90.0, 360.0), 0.0);
+
+        assert!(tracks.coast(4.0).is_empty());
+        assert!(tracks.is_coasted("EZY12"));
+        assert!((tracks.latest("EZY12").unwrap().x - 0.4).abs() < 1e-9);
+
+        // Extrapolation stops at the coast window
+        tracks.coast(20.0);
+        assert!((tracks.latest("EZY12").unwrap().x - 1.0).abs() < 1e-9);
+        assert_eq!(tracks.coast_count("EZY12"), 2);
+        assert_eq!(tracks.track("EZY12").unwrap().coast_age(), 20.0);
+
+        // A report ends coasting
+        tracks.update("EZY12", AircraftState::new(2.1, 0.0, 10000.0, 90.0, 360.0), 21.0);
+        assert!(!tracks.is_coasted("EZY12"));
+        assert_eq!(tracks.coast_count("EZY12"), 0);
+
+        // Reports since the cycle began are not coasted
+        assert!(tracks.coast_since(25.0, 21.0).is_empty());
+        assert!(!tracks.is_coasted("EZY12"));
+        assert!(tracks.coast_since(25.0, 22.0).is_empty());
+        assert!(tracks.is_coasted("EZY12"));
+
+        assert_eq!(tracks.coast(52.0), vec!["EZY12".to_string()]);
+        assert!(tracks.is_empty());
+    }
+}
@@ -0,0 +1,330 @@
+/*!
+ * STCA MODULE
+ * Short Term Conflict Alert with confirmation, hysteresis and stable alert IDs
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use wasm_bindgen::prelude::*;
+
+use crate::config::{ConfigError, SafetyConfig};
+use crate::conflict::{detect_conflict_to_levels, ConflictInfo, ConflictSeverity};
+use crate::sweep::sweep_conflicts;
+use crate::AircraftState;
+
+/// How readily STCA raises and clears alerts
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StcaSettings {
+    /// Consecutive conflicting cycles before a pending alert is raised
+    pub confirm_cycles: u32,
+    /// Consecutive clear cycles before an active alert is cleared
+    pub clear_cycles: u32,
+    /// Extra distance (nm) beyond the alert distance a raised alert is held
+    pub off_margin: f64,
+}
+
+#[wasm_bindgen]
+impl StcaSettings {
+    /// Raise on the second conflicting cycle, hold out to 0.5 nm past the
+    /// alert distance and clear after three clear cycles
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> StcaSettings {
+        StcaSettings {
+            confirm_cycles: 2,
+            clear_cycles: 3,
+            off_margin: 0.5,
+        }
+    }
+}
+
+impl Default for StcaSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Where an STCA alert is in its lifecycle
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StcaPhase {
+    /// Detected but not yet confirmed; never shown to the controller
+    Pending,
+    Active,
+    Cleared,
+}
+
+/// One pair's alert with the ID it keeps from raising to clearing
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StcaAlert {
+    /// Unique within one `Stca`; a pair alerting again gets a new ID
+    pub id: u64,
+    /// Lower track id
+    pub first: u64,
+    pub second: u64,
+    pub phase: StcaPhase,
+    /// Highest severity reached while active
+    pub severity: ConflictSeverity,
+    pub time_to_conflict: f64,
+    pub minimum_distance: f64,
+    /// Time of the first conflicting cycle
+    pub detected: f64,
+    /// Time of the latest cycle inside the off threshold
+    pub updated: f64,
+    hits: u32,
+    misses: u32,
+}
+
+/// Change to the alert list from one STCA cycle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StcaEvent {
+    Raised(StcaAlert),
+    /// Active alert reaching a higher severity
+    Escalated(StcaAlert),
+    Cleared(StcaAlert),
+}
+
+impl StcaEvent {
+    pub fn alert(&self) -> &StcaAlert {
+        match self {
+            StcaEvent::Raised(alert) | StcaEvent::Escalated(alert) | StcaEvent::Cleared(alert) => alert,
+        }
+    }
+}
+
+/// Pair evaluation for one cycle
+enum Measurement {
+    /// Inside the alert distance
+    Conflict(ConflictInfo),
+    /// Outside the alert distance but inside the off threshold
+    Held(ConflictInfo),
+    Clear,
+}
+
+/// Alert state per aircraft pair across surveillance cycles
+///
+/// A pair must conflict for `confirm_cycles` cycles in a row before it is
+/// raised, stays raised while its predicted miss distance is within
+/// `off_margin` of the alert distance, and clears only after
+/// `clear_cycles` cycles beyond that. Pairs losing a track clear at once.
+#[derive(Debug, Clone)]
+pub struct Stca {
+    config: SafetyConfig,
+    pub settings: StcaSettings,
+    alerts: BTreeMap<(u64, u64), StcaAlert>,
+    next_id: u64,
+}
+
+impl Stca {
+    pub fn new(config: SafetyConfig, settings: StcaSettings) -> Result<Self, ConfigError> {
+        config.validate()?;
+        Ok(Stca {
+            config,
+            settings,
+            alerts: BTreeMap::new(),
+            next_id: 1,
+        })
+    }
+
+    pub fn config(&self) -> &SafetyConfig {
+        &self.config
+    }
+
+    /// Evaluate one surveillance cycle at `time` and return the alert list changes
+    pub fn update(&mut self, traffic: &[(u64, AircraftState)], time: f64) -> Vec<StcaEvent> {
+        let measured = self.measure(traffic);
+        let mut events = Vec::new();
+
+        let keys: BTreeSet<(u64, u64)> = self.alerts.keys().chain(measured.keys()).copied().collect();
+        for key in keys {
+            let measurement = match measured.get(&key) {
+                Some(Measurement::Conflict(info)) => Measurement::Conflict(*info),
+                Some(Measurement::Held(info)) => Measurement::Held(*info),
+                _ => Measurement::Clear,
+            };
+            let present = traffic.iter().any(|(id, _)| *id == key.0) && traffic.iter().any(|(id, _)| *id == key.1);
+            if let Some(event) = self.advance(key, measurement, present, time) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    /// Pending and active alerts, keyed by (lower, higher) track id
+    pub fn alerts(&self) -> impl Iterator<Item = &StcaAlert> {
+        self.alerts.values()
+    }
+
+    /// Active alerts in the order they were raised, for an alert list
+    pub fn active(&self) -> Vec<StcaAlert> {
+        let mut active: Vec<StcaAlert> =
+            self.alerts.values().filter(|a| a.phase == StcaPhase::Active).copied().collect();
+        active.sort_by_key(|a| a.id);
+        active
+    }
+
+    pub fn alert(&self, id: u64) -> Option<&StcaAlert> {
+        self.alerts.values().find(|a| a.id == id)
+    }
+
+    /// Measure every pair within the off threshold
+    fn measure(&self, traffic: &[(u64, AircraftState)]) -> BTreeMap<(u64, u64), Measurement> {
+        let states: Vec<AircraftState> = traffic.iter().map(|(_, state)| *state).collect();
+        let off = self.config.clone().with_buffer(self.config.buffer + self.settings.off_margin);
+        let report = sweep_conflicts(&states, &off, None);
+
+        let mut measured = BTreeMap::new();
+        for conflict in &report.conflicts {
+            let (a, b) = (traffic[conflict.first].0, traffic[conflict.second].0);
+            let info = detect_conflict_to_levels(
+                &states[conflict.first],
+                None,
+                &states[conflict.second],
+                None,
+                self.config.alert_distance(),
+                self.config.vertical_separation,
+                self.config.look_ahead_time,
+            );
+            let measurement = if info.severity == ConflictSeverity::None {
+                Measurement::Held(conflict.info)
+            } else {
+                Measurement::Conflict(info)
+            };
+            measured.insert((a.min(b), a.max(b)), measurement);
+        }
+        measured
+    }
+
+    fn advance(&mut self, key: (u64, u64), measurement: Measurement, present: bool, time: f64) -> Option<StcaEvent> {
+        let Some(alert) = self.alerts.get_mut(&key) else {
+            let Measurement::Conflict(info) = measurement else {
+                return None;
+            };
+            let alert = StcaAlert {
+                id: self.next_id,
+                first: key.0,
+                second: key.1,
+                phase: StcaPhase::Pending,
+                severity: info.severity,
+                time_to_conflict: info.time_to_conflict,
+                minimum_distance: info.minimum_distance,
+                detected: time,
+                updated: time,
+                hits: 0,
+                misses: 0,
+            };
+            self.next_id += 1;
+            self.alerts.insert(key, alert);
+            return self.advance(key, measurement, present, time);
+        };
+
+        match (alert.phase, measurement) {
+            (StcaPhase::Pending, Measurement::Conflict(info)) => {
+                alert.hits += 1;
+                alert.severity = info.severity;
+                alert.time_to_conflict = info.time_to_conflict;
+                alert.minimum_distance = info.minimum_distance;
+                alert.updated = time;
+                if alert.hits >= self.settings.confirm_cycles {
+                    alert.phase = StcaPhase::Active;
+                    return Some(StcaEvent::Raised(*alert));
+                }
+                None
+            }
+            // Unconfirmed detections are dropped without ever being shown
+            (StcaPhase::Pending, _) => {
+                self.alerts.remove(&key);
+                None
+            }
+            (_, Measurement::Conflict(info)) if present => {
+                let escalated = info.severity > alert.severity;
+                alert.misses = 0;
+                alert.severity = if escalated { info.severity } else { alert.severity };
+                alert.time_to_conflict = info.time_to_conflict;
+                alert.minimum_distance = info.minimum_distance;
+                alert.updated = time;
+                escalated.then_some(StcaEvent::Escalated(*alert))
+            }
+            (_, Measurement::Held(info)) if present => {
+                alert.misses = 0;
+                alert.time_to_conflict = info.time_to_conflict;
+                alert.minimum_distance = info.minimum_distance;
+                alert.updated = time;
+                None
+            }
+            _ => {
+                alert.misses += 1;
+                if present && alert.misses < self.settings.clear_cycles {
+                    return None;
+                }
+                let mut cleared = self.alerts.remove(&key)?;
+                cleared.phase = StcaPhase::Cleared;
+                Some(StcaEvent::Cleared(cleared))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pair on parallel eastbound tracks `offset` nm apart at the same level
+    fn pair(offset: f64) -> Vec<(u64, AircraftState)> {
+        vec![
+            (4, AircraftState::new(0.0, 0.0, 12000.0, 90.0, 300.0)),
+            (9, AircraftState::new(0.0, offset, 12000.0, 90.0, 300.0)),
+        ]
+    }
+
+    #[test]
+    fn test_lifecycle_with_hysteresis() {
+        let mut stca = Stca::new(SafetyConfig::default(), StcaSettings::new()).unwrap();
+
+        // Pending on the first conflicting cycle, raised on the second
+        assert!(stca.update(&pair(2.0), 0.0).is_empty());
+        assert_eq!(stca.alerts().next().unwrap().phase, StcaPhase::Pending);
+        let raised = stca.update(&pair(2.0), 4.0);
+        assert!(matches!(raised.as_slice(), [StcaEvent::Raised(a)] if a.first == 4 && a.second == 9 && a.detected == 0.0));
+        let id = raised[0].alert().id;
+
+        // Drifting just past the alert distance holds the alert
+        for t in 2..6 {
+            assert!(stca.update(&pair(3.3), t as f64 * 4.0).is_empty());
+        }
+        assert_eq!(stca.active().len(), 1);
+        assert_eq!(stca.alert(id).unwrap().phase, StcaPhase::Active);
+
+        // Beyond the off threshold it clears after three cycles
+        assert!(stca.update(&pair(4.0), 24.0).is_empty());
+        assert!(stca.update(&pair(4.0), 28.0).is_empty());
+        let cleared = stca.update(&pair(4.0), 32.0);
+        assert!(matches!(cleared.as_slice(), [StcaEvent::Cleared(a)] if a.id == id && a.phase == StcaPhase::Cleared));
+        assert!(stca.active().is_empty());
+
+        // Alerting again is a new alert
+        stca.update(&pair(2.0), 36.0);
+        let again = stca.update(&pair(2.0), 40.0);
+        assert!(again[0].alert().id > id);
+    }
+
+    #[test]
+    fn test_flicker_and_lost_tracks() {
+        let mut stca = Stca::new(SafetyConfig::default(), StcaSettings::new()).unwrap();
+
+        // A single-cycle detection is never shown
+        assert!(stca.update(&pair(2.0), 0.0).is_empty());
+        assert!(stca.update(&pair(5.0), 4.0).is_empty());
+        assert_eq!(stca.alerts().count(), 0);
+
+        stca.update(&pair(2.0), 8.0);
+        stca.update(&pair(2.0), 12.0);
+        assert_eq!(stca.active().len(), 1);
+
+        // Losing a track clears its alerts immediately
+        let events = stca.update(&pair(2.0)[..1], 16.0);
+        assert!(matches!(events.as_slice(), [StcaEvent::Cleared(_)]));
+    }
+}
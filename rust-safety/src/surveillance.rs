@@ -0,0 +1,229 @@
+/*!
+ * SURVEILLANCE MODULE
+ * Simulated sensor imperfections: update period, noise, missed updates and latency
+ */
+
+use crate::{AircraftState, TrafficSet};
+
+/// Error characteristics of one surveillance source
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensorModel {
+    pub name: String,
+    /// Time between scans (seconds)
+    pub update_period: f64,
+    /// Standard deviation of horizontal position error (nm)
+    pub position_noise: f64,
+    /// Standard deviation of altitude error (ft)
+    pub altitude_noise: f64,
+    /// Probability that a target is not reported on a scan
+    pub miss_probability: f64,
+    /// Delay between measurement and delivery (seconds)
+    pub latency: f64,
+}
+
+impl SensorModel {
+    /// Perfect sensor scanning every `update_period` seconds
+    pub fn new(name: &str, update_period: f64) -> Self {
+        SensorModel {
+            name: name.to_string(),
+            update_period,
+            position_noise: 0.0,
+            altitude_noise: 0.0,
+            miss_probability: 0.0,
+            latency: 0.0,
+        }
+    }
+
+    /// 1 Hz ADS-B with small position error
+    pub fn adsb() -> Self {
+        SensorModel::new("ADS-B", 1.0)
+            .with_noise(0.01, 25.0)
+            .with_misses(0.02)
+            .with_latency(0.5)
+    }
+
+    /// Terminal radar with a 4.8 s rotation
+    pub fn terminal_radar() -> Self {
+        SensorModel::new("TRACON", 4.8)
+            .with_noise(0.08, 50.0)
+            .with_misses(0.05)
+            .with_latency(1.5)
+    }
+
+    pub fn with_noise(mut self, position_noise: f64, altitude_noise: f64) -> Self {
+        self.position_noise = position_noise.max(0.0);
+        self.altitude_noise = altitude_noise.max(0.0);
+        self
+    }
+
+    pub fn with_misses(mut self, probability: f64) -> Self {
+        self.miss_probability = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_latency(mut self, latency: f64) -> Self {
+        self.latency = latency.max(0.0);
+        self
+    }
+}
+
+/// Measurement of one aircraft by one sensor
+#[derive(Debug, Clone, Copy)]
+pub struct SurveillanceReport {
+    /// Index of the sensor in the simulator
+    pub sensor: usize,
+    pub track: u64,
+    /// When the position was measured
+    pub measured_at: f64,
+    /// When the report reaches the tracker
+    pub delivered_at: f64,
+    pub state: AircraftState,
+}
+
+/// Turns true simulation states into imperfect sensor reports
+///
+/// Noise and misses come from a seeded generator, so a run is reproducible.
+#[derive(Debug, Clone)]
+pub struct SurveillanceSimulator {
+    sensors: Vec<SensorModel>,
+    next_scan: Vec<f64>,
+    pending: Vec<SurveillanceReport>,
+    rng: u64,
+}
+
+impl SurveillanceSimulator {
+    pub fn new(sensors: Vec<SensorModel>, seed: u64) -> Self {
+        SurveillanceSimulator {
+            next_scan: vec![f64::NEG_INFINITY; sensors.len()],
+            sensors,
+            pending: Vec::new(),
+            rng: seed | 1,
+        }
+    }
+
+    pub fn sensors(&self) -> &[SensorModel] {
+        &self.sensors
+    }
+
+    /// Reports measured but not yet delivered
+    pub fn in_flight(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Uniform value in [0, 1) from a xorshift generator
+    fn next_random(&mut self) -> f64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal value (Box-Muller)
+    fn next_gaussian(&mut self) -> f64 {
+        let u = self.next_random().max(f64::MIN_POSITIVE);
+        let v = self.next_random();
+        (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+    }
+
+    /// Scan the traffic at `time` and return every report delivered by then
+    ///
+    /// Each sensor scans when its update period has elapsed. Delivered
+    /// reports come back in delivery order.
+    pub fn step(&mut self, time: f64, traffic: &TrafficSet) -> Vec<SurveillanceReport> {
+        for sensor in 0..self.sensors.len() {
+            if time < self.next_scan[sensor] {
+                continue;
+            }
+            let period = self.sensors[sensor].update_period;
+            self.next_scan[sensor] = if self.next_scan[sensor].is_finite() {
+                self.next_scan[sensor] + period
+            } else {
+                time + period
+            };
+
+            for (track, truth) in traffic.iter() {
+                if let Some(report) = self.measure(sensor, track, &truth, time) {
+                    self.pending.push(report);
+                }
+            }
+        }
+
+        let (mut delivered, pending): (Vec<_>, Vec<_>) =
+            self.pending.drain(..).partition(|r| r.delivered_at <= time);
+        self.pending = pending;
+        delivered.sort_by(|a, b| a.delivered_at.total_cmp(&b.delivered_at));
+        delivered
+    }
+
+    fn measure(&mut self, sensor: usize, track: u64, truth: &AircraftState, time: f64) -> Option<SurveillanceReport> {
+        let model = self.sensors[sensor].clone();
+        if self.next_random() < model.miss_probability {
+            return None;
+        }
+
+        let mut state = *truth;
+        state.x += self.next_gaussian() * model.position_noise;
+        state.y += self.next_gaussian() * model.position_noise;
+        state.altitude += self.next_gaussian() * model.altitude_noise;
+
+        Some(SurveillanceReport {
+            sensor,
+            track,
+            measured_at: time,
+            delivered_at: time + model.latency,
+            state,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn traffic() -> TrafficSet {
+        let mut traffic = TrafficSet::new();
+        traffic.insert(1, AircraftState::new(0.0, 0.0, 10000.0, 90.0, 250.0)).unwrap();
+        traffic
+    }
+
+    #[test]
+    fn test_update_period_and_latency() {
+        let radar = SensorModel::new("radar", 4.0).with_latency(1.5);
+        let mut sim = SurveillanceSimulator::new(vec![radar], 3);
+        let traffic = traffic();
+
+        assert!(sim.step(0.0, &traffic).is_empty());
+        assert_eq!(sim.in_flight(), 1);
+        assert!(sim.step(1.0, &traffic).is_empty());
+
+        let reports = sim.step(2.0, &traffic);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].measured_at, 0.0);
+        assert_eq!(reports[0].delivered_at, 1.5);
+        assert_eq!(reports[0].state.x, 0.0);
+
+        // Next scan at 4 s, none in between
+        assert!(sim.step(3.0, &traffic).is_empty());
+        assert_eq!(sim.in_flight(), 0);
+        sim.step(4.0, &traffic);
+        assert_eq!(sim.in_flight(), 1);
+    }
+
+    #[test]
+    fn test_noise_and_missed_updates() {
+        let sensor = SensorModel::new("noisy", 1.0).with_noise(0.1, 100.0).with_misses(0.25);
+        let mut sim = SurveillanceSimulator::new(vec![sensor], 11);
+        let traffic = traffic();
+
+        let reports: Vec<_> = (0..2000).flat_map(|t| sim.step(t as f64, &traffic)).collect();
+        assert!(reports.len() > 1400 && reports.len() < 1600, "{}", reports.len());
+
+        let n = reports.len() as f64;
+        let mean_x = reports.iter().map(|r| r.state.x).sum::<f64>() / n;
+        let sd_x = (reports.iter().map(|r| (r.state.x - mean_x).powi(2)).sum::<f64>() / n).sqrt();
+        let sd_alt = (reports.iter().map(|r| (r.state.altitude - 10000.0).powi(2)).sum::<f64>() / n).sqrt();
+        assert!(mean_x.abs() < 0.01);
+        assert!((sd_x - 0.1).abs() < 0.01, "{}", sd_x);
+        assert!((sd_alt - 100.0).abs() < 10.0, "{}", sd_alt);
+    }
+}
@@ -0,0 +1,135 @@
+/*!
+ * TRACK SWAP MODULE
+ * Detection of surveillance tracks exchanged between crossing aircraft
+ */
+
+use crate::aircraft_types::TypeInfo;
+use crate::state::StateHistory;
+use crate::{heading_difference, AircraftState};
+
+/// Mismatch above which a track update is considered implausible
+const MISMATCH_THRESHOLD: f64 = 1.0;
+
+/// Track taking part in a close crossing
+#[derive(Debug, Clone, Copy)]
+pub struct CrossingTrack<'a> {
+    pub id: u64,
+    /// History including the latest update
+    pub history: &'a StateHistory,
+    /// Performance of the aircraft type filed for the callsign, if known
+    pub performance: Option<TypeInfo>,
+}
+
+/// Event suggesting two tracks may have swapped after crossing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrackSwap {
+    pub first: u64,
+    pub second: u64,
+    /// Combined mismatch of the latest updates against their own tracks
+    pub own_mismatch: f64,
+    /// Combined mismatch if the latest updates were exchanged
+    pub swapped_mismatch: f64,
+}
+
+/// How poorly an update continues a motion history and fits a type's performance
+fn mismatch(update: &AircraftState, before: &AircraftState, performance: Option<TypeInfo>) -> f64 {
+    let turn = heading_difference(before.heading, update.heading).abs() / 30.0;
+    let acceleration = (update.speed - before.speed).abs() / 50.0;
+    let performance = performance.map_or(0.0, |p| (update.speed - p.cruise_speed).abs() / 100.0);
+    turn + acceleration + performance
+}
+
+/// Closest horizontal approach over the aligned histories
+fn closest_approach(first: &StateHistory, second: &StateHistory) -> f64 {
+    let a = first.states();
+    let b = second.states();
+    a.iter()
+        .rev()
+        .zip(b.iter().rev())
+        .map(|(p, q)| ((p.x - q.x).powi(2) + (p.y - q.y).powi(2)).sqrt())
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Check whether two tracks that passed within `crossing_distance` have swapped
+///
+/// Each latest update is scored against the state before it and against the
+/// other track's; a swap is suggested when the updates are implausible as
+/// received but at most half as implausible exchanged.
+pub fn detect_track_swap(
+    first: &CrossingTrack,
+    second: &CrossingTrack,
+    crossing_distance: f64,
+) -> Option<TrackSwap> {
+    let (a_latest, a_before) = (first.history.get_latest()?, first.history.get_previous()?);
+    let (b_latest, b_before) = (second.history.get_latest()?, second.history.get_previous()?);
+
+    if closest_approach(first.history, second.history) > crossing_distance {
+        return None;
+    }
+
+    let own_mismatch = mismatch(a_latest, a_before, first.performance)
+        + mismatch(b_latest, b_before, second.performance);
+    let swapped_mismatch = mismatch(b_latest, a_before, first.performance)
+        + mismatch(a_latest, b_before, second.performance);
+
+    (own_mismatch > MISMATCH_THRESHOLD && swapped_mismatch <= own_mismatch * 0.5).then_some(TrackSwap {
+        first: first.id,
+        second: second.id,
+        own_mismatch,
+        swapped_mismatch,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crossing(swap: bool) -> (StateHistory, StateHistory) {
+        // Eastbound 250 kt and northbound 450 kt meeting at (5, 0), 12 s updates
+        let mut east = StateHistory::new(10);
+        let mut north = StateHistory::new(10);
+        for i in 0..4 {
+            let remaining = (3 - i) as f64 * 12.0 / 3600.0;
+            east.add_state(AircraftState::new(5.0 - 250.0 * remaining, 0.0, 10000.0, 90.0, 250.0));
+            north.add_state(AircraftState::new(5.0, -450.0 * remaining, 11000.0, 0.0, 450.0));
+        }
+
+        let east_next = AircraftState::new(5.0 + 250.0 / 300.0, 0.0, 10000.0, 90.0, 250.0);
+        let north_next = AircraftState::new(5.0, 1.5, 11000.0, 0.0, 450.0);
+        if swap {
+            east.add_state(north_next);
+            north.add_state(east_next);
+        } else {
+            east.add_state(east_next);
+            north.add_state(north_next);
+        }
+        (east, north)
+    }
+
+    #[test]
+    fn test_detects_swap_after_crossing() {
+        let (east, north) = crossing(true);
+        let first = CrossingTrack { id: 1, history: &east, performance: None };
+        let second = CrossingTrack { id: 2, history: &north, performance: None };
+
+        let swap = detect_track_swap(&first, &second, 1.0).unwrap();
+        assert_eq!((swap.first, swap.second), (1, 2));
+        assert!(swap.swapped_mismatch < swap.own_mismatch);
+
+        // Tracks that never came close are not flagged
+        let mut distant = StateHistory::new(10);
+        for state in north.states() {
+            distant.add_state(AircraftState { x: state.x + 20.0, ..*state });
+        }
+        let second = CrossingTrack { id: 2, history: &distant, performance: None };
+        assert!(detect_track_swap(&first, &second, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_consistent_tracks_not_flagged() {
+        let (east, north) = crossing(false);
+        let first = CrossingTrack { id: 1, history: &east, performance: None };
+        let second = CrossingTrack { id: 2, history: &north, performance: None };
+        assert!(detect_track_swap(&first, &second, 1.0).is_none());
+    }
+}
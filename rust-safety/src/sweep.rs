@@ -0,0 +1,680 @@
+/*!
+ * SWEEP MODULE
+ * Batch conflict detection over the whole traffic picture
+ */
+
+use std::collections::HashMap;
+
+use crate::config::SafetyConfig;
+use crate::conflict::{detect_conflict_to_levels, ConflictInfo, ConflictSeverity};
+use crate::traffic::CapacityError;
+use crate::AircraftState;
+
+/// Conflict found between two aircraft of the swept set
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairConflict {
+    pub first: usize,
+    pub second: usize,
+    pub info: ConflictInfo,
+}
+
+/// Result of one sweep with the degradations that were needed to stay in budget
+#[derive(Debug, Clone, Default)]
+pub struct SweepReport {
+    pub conflicts: Vec<PairConflict>,
+    /// Pairs with a result this tick, solved or served from the cache
+    pub pairs_evaluated: usize,
+    /// Low-priority pairs left unsolved because the budget ran out
+    pub pairs_skipped: usize,
+    /// Pairs actually solved this tick; this is what the budget counts
+    pub pairs_solved: usize,
+    pub cache_hits: usize,
+    /// Common time every state was extrapolated to, if the sweep was time-aligned
+    pub epoch: Option<f64>,
+}
+
+impl SweepReport {
+    pub fn is_degraded(&self) -> bool {
+        self.pairs_skipped > 0
+    }
+
+    /// Reset for reuse without releasing the conflict buffer
+    fn reset(&mut self) {
+        self.conflicts.clear();
+        self.pairs_evaluated = 0;
+        self.pairs_skipped = 0;
+        self.pairs_solved = 0;
+        self.cache_hits = 0;
+        self.epoch = None;
+    }
+}
+
+/// Stable identity and state version of an aircraft in the swept set
+///
+/// The cache takes an (id, version) pair to name exactly one state, so a
+/// version must never be reused for an id with a different state, even after
+/// the id is removed and added again. `TrafficSet` versions meet this; other
+/// callers that restart versions must `ConflictCache::invalidate` the id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StateKey {
+    pub id: u64,
+    pub version: u64,
+}
+
+impl StateKey {
+    pub fn new(id: u64, version: u64) -> Self {
+        StateKey { id, version }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CacheEntry {
+    version1: u64,
+    version2: u64,
+    generation: u64,
+    info: ConflictInfo,
+}
+
+/// Memoized pairwise conflict results keyed by state versions and config hash
+///
+/// An entry is reused only while both aircraft keep the same state version.
+/// Changing the config clears the cache; pairs not seen during a sweep are
+/// evicted. Entries are trusted as long as the `StateKey` contract holds.
+/// Pairs skipped for lack of budget are remembered and solved first next sweep.
+#[derive(Debug, Clone, Default)]
+pub struct ConflictCache {
+    entries: HashMap<(u64, u64), CacheEntry>,
+    /// Pairs skipped for lack of budget, with the generation that skipped them
+    deferred: HashMap<(u64, u64), u64>,
+    config_hash: Option<u64>,
+    generation: u64,
+}
+
+impl ConflictCache {
+    pub fn new() -> Self {
+        ConflictCache::default()
+    }
+
+    /// Cache pre-sized for every pair of `max_aircraft` aircraft
+    pub fn with_capacity(max_aircraft: usize) -> Self {
+        ConflictCache {
+            entries: HashMap::with_capacity(pair_count(max_aircraft)),
+            ..ConflictCache::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.deferred.clear();
+    }
+
+    /// Drop every entry involving an aircraft, e.g. when its track is removed
+    pub fn invalidate(&mut self, id: u64) {
+        self.entries.retain(|(a, b), _| *a != id && *b != id);
+        self.deferred.retain(|(a, b), _| *a != id && *b != id);
+    }
+
+    /// Whether a pair was skipped by the last sweep and is still waiting to be solved
+    pub fn is_deferred(&self, key1: StateKey, key2: StateKey) -> bool {
+        self.deferred.contains_key(&(key1.id, key2.id))
+    }
+
+    fn begin_sweep(&mut self, config: &SafetyConfig) {
+        let hash = config.config_hash();
+        if self.config_hash != Some(hash) {
+            self.entries.clear();
+            self.deferred.clear();
+            self.config_hash = Some(hash);
+        }
+        self.generation += 1;
+    }
+
+    fn end_sweep(&mut self) {
+        let generation = self.generation;
+        self.entries
+            .retain(|_, entry| entry.generation == generation);
+        self.deferred.retain(|_, deferred| *deferred == generation);
+    }
+
+    fn lookup(&mut self, key1: StateKey, key2: StateKey) -> Option<ConflictInfo> {
+        let generation = self.generation;
+        let entry = self.entries.get_mut(&(key1.id, key2.id))?;
+        entry.generation = generation;
+
+        let valid = entry.version1 == key1.version && entry.version2 == key2.version;
+        valid.then_some(entry.info)
+    }
+
+    fn store(&mut self, key1: StateKey, key2: StateKey, info: ConflictInfo) {
+        self.entries.insert(
+            (key1.id, key2.id),
+            CacheEntry {
+                version1: key1.version,
+                version2: key2.version,
+                generation: self.generation,
+                info,
+            },
+        );
+    }
+
+    fn defer(&mut self, key1: StateKey, key2: StateKey) {
+        self.deferred.insert((key1.id, key2.id), self.generation);
+    }
+}
+
+/// Pre-allocated buffers for sweeps that never allocate during a tick
+#[derive(Debug, Clone)]
+pub struct SweepWorkspace {
+    max_aircraft: usize,
+    candidates: Vec<Candidate>,
+    report: SweepReport,
+}
+
+impl SweepWorkspace {
+    /// Allocate every buffer needed to sweep up to `max_aircraft` aircraft
+    pub fn with_capacity(max_aircraft: usize) -> Self {
+        let pairs = pair_count(max_aircraft);
+        SweepWorkspace {
+            max_aircraft,
+            candidates: Vec::with_capacity(pairs),
+            report: SweepReport {
+                conflicts: Vec::with_capacity(pairs),
+                ..SweepReport::default()
+            },
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.max_aircraft
+    }
+
+    /// Report of the last sweep run in this workspace
+    pub fn report(&self) -> &SweepReport {
+        &self.report
+    }
+}
+
+/// Aircraft read by storage index during a sweep
+///
+/// Lets the sweep run straight over a `TrafficSet`'s field arrays as well as
+/// over a slice of states, without copying either into a new buffer.
+pub(crate) trait SweepSource {
+    fn count(&self) -> usize;
+
+    fn state(&self, index: usize) -> AircraftState;
+
+    /// Cache key of an aircraft; only asked for when the sweep is cached
+    fn key(&self, index: usize) -> StateKey;
+}
+
+/// Slice of states with their keys, empty when the sweep is not cached
+struct SliceSource<'a> {
+    aircraft: &'a [AircraftState],
+    keys: &'a [StateKey],
+}
+
+impl SweepSource for SliceSource<'_> {
+    fn count(&self) -> usize {
+        self.aircraft.len()
+    }
+
+    fn state(&self, index: usize) -> AircraftState {
+        self.aircraft[index]
+    }
+
+    fn key(&self, index: usize) -> StateKey {
+        self.keys[index]
+    }
+}
+
+/// Number of distinct pairs among `n` aircraft
+fn pair_count(n: usize) -> usize {
+    n * n.saturating_sub(1) / 2
+}
+
+/// Candidate pair after cheap pre-screening
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    first: usize,
+    second: usize,
+    distance: f64,
+    urgency: f64,
+    deferred: bool,
+}
+
+/// Cheap risk estimate for a pair: current distance and seconds until horizontal
+/// separation is lost at the current closure rate (0 if already inside, infinite
+/// if opening). Returns `None` when the pair cannot conflict within the look-ahead.
+fn score_pair(
+    aircraft1: &AircraftState,
+    aircraft2: &AircraftState,
+    config: &SafetyConfig,
+) -> Option<(f64, f64)> {
+    let dx = aircraft2.x - aircraft1.x;
+    let dy = aircraft2.y - aircraft1.y;
+    let distance = (dx * dx + dy * dy).sqrt();
+
+    // Vertically separated even if the climb or descent rates close the gap for the whole look-ahead
+    let vertical = (aircraft1.altitude - aircraft2.altitude).abs();
+    let max_vertical_closure = (aircraft1.vertical_rate - aircraft2.vertical_rate).abs() / 60.0 * config.look_ahead_time;
+    if vertical - max_vertical_closure >= config.vertical_separation {
+        return None;
+    }
+
+    // Even at worst-case closure the pair cannot meet within the look-ahead
+    let max_closure = (aircraft1.speed + aircraft2.speed) / 3600.0 * config.look_ahead_time;
+    if distance - max_closure >= config.alert_distance() {
+        return None;
+    }
+
+    if distance < config.alert_distance() {
+        return Some((distance, 0.0));
+    }
+
+    let (v1x, v1y) = velocity(aircraft1);
+    let (v2x, v2y) = velocity(aircraft2);
+    let closure = -(dx * (v2x - v1x) + dy * (v2y - v1y)) / distance;
+
+    let urgency = if closure > 1e-9 {
+        (distance - config.alert_distance()) / closure
+    } else {
+        f64::INFINITY
+    };
+
+    Some((distance, urgency))
+}
+
+/// Velocity in nm per second
+fn velocity(aircraft: &AircraftState) -> (f64, f64) {
+    atc_safety_core::velocity(&(*aircraft).into())
+}
+
+/// Run conflict detection on all pairs, skipping low-risk pairs once the budget is spent
+///
+/// Pairs are scored cheaply from current distance and closure rate and evaluated
+/// most urgent first. Each pair is solved in closed form like `detect_conflict`,
+/// so its cost does not depend on the time step or horizon and the only way to
+/// save work is to solve fewer pairs. `budget` is the maximum number of pairs
+/// solved in the tick; `None` means unlimited. Pairs predicted to lose
+/// separation within half the look-ahead at the current closure rate are high
+/// priority and always solved; low-risk pairs past the budget are skipped.
+pub fn sweep_conflicts(
+    aircraft: &[AircraftState],
+    config: &SafetyConfig,
+    budget: Option<usize>,
+) -> SweepReport {
+    run_sweep(&SliceSource { aircraft, keys: &[] }, config, budget, None)
+}
+
+/// Sweep reusing cached results for pairs whose states have not changed
+///
+/// `keys` gives the identity and state version of each aircraft, in the same
+/// order as `aircraft`. Cache hits cost nothing against the budget, and pairs
+/// skipped for lack of budget are solved ahead of other low-risk pairs next time.
+pub fn sweep_conflicts_cached(
+    aircraft: &[AircraftState],
+    keys: &[StateKey],
+    config: &SafetyConfig,
+    budget: Option<usize>,
+    cache: &mut ConflictCache,
+) -> SweepReport {
+    if keys.len() != aircraft.len() {
+        return sweep_conflicts(aircraft, config, budget);
+    }
+
+    sweep_source_cached(&SliceSource { aircraft, keys }, config, budget, cache)
+}
+
+/// Cached sweep over any source; used by `TrafficSet` to sweep its columns in place
+pub(crate) fn sweep_source_cached(
+    source: &impl SweepSource,
+    config: &SafetyConfig,
+    budget: Option<usize>,
+    cache: &mut ConflictCache,
+) -> SweepReport {
+    cache.begin_sweep(config);
+    let report = run_sweep(source, config, budget, Some(&mut *cache));
+    cache.end_sweep();
+    report
+}
+
+/// Cached sweep into pre-allocated buffers, for fixed-capacity real-time use
+///
+/// Never allocates as long as the aircraft count stays within the workspace
+/// capacity and the cache was created with at least the same capacity;
+/// larger sets are rejected with a `CapacityError`.
+pub fn sweep_conflicts_into<'a>(
+    aircraft: &[AircraftState],
+    keys: &[StateKey],
+    config: &SafetyConfig,
+    budget: Option<usize>,
+    cache: &mut ConflictCache,
+    workspace: &'a mut SweepWorkspace,
+) -> Result<&'a SweepReport, CapacityError> {
+    if keys.len() != aircraft.len() {
+        return Err(CapacityError::new(workspace.max_aircraft));
+    }
+
+    sweep_source_into(&SliceSource { aircraft, keys }, config, budget, cache, workspace)
+}
+
+/// Allocation-free cached sweep over any source
+pub(crate) fn sweep_source_into<'a>(
+    source: &impl SweepSource,
+    config: &SafetyConfig,
+    budget: Option<usize>,
+    cache: &mut ConflictCache,
+    workspace: &'a mut SweepWorkspace,
+) -> Result<&'a SweepReport, CapacityError> {
+    if source.count() > workspace.max_aircraft {
+        return Err(CapacityError::new(workspace.max_aircraft));
+    }
+
+    cache.begin_sweep(config);
+    sweep_into_buffers(
+        source,
+        config,
+        budget,
+        Some(&mut *cache),
+        &mut workspace.candidates,
+        &mut workspace.report,
+    );
+    cache.end_sweep();
+    Ok(&workspace.report)
+}
+
+fn run_sweep(
+    source: &impl SweepSource,
+    config: &SafetyConfig,
+    budget: Option<usize>,
+    cache: Option<&mut ConflictCache>,
+) -> SweepReport {
+    let mut candidates = Vec::new();
+    let mut report = SweepReport::default();
+    sweep_into_buffers(source, config, budget, cache, &mut candidates, &mut report);
+    report
+}
+
+fn sweep_into_buffers(
+    source: &impl SweepSource,
+    config: &SafetyConfig,
+    budget: Option<usize>,
+    mut cache: Option<&mut ConflictCache>,
+    candidates: &mut Vec<Candidate>,
+    report: &mut SweepReport,
+) {
+    candidates.clear();
+    report.reset();
+
+    let high_priority_window = config.look_ahead_time / 2.0;
+
+    for i in 0..source.count() {
+        let first = source.state(i);
+        for j in (i + 1)..source.count() {
+            if let Some((distance, urgency)) = score_pair(&first, &source.state(j), config) {
+                let deferred = cache.as_ref().is_some_and(|cache| cache.is_deferred(source.key(i), source.key(j)));
+                candidates.push(Candidate {
+                    first: i,
+                    second: j,
+                    distance,
+                    urgency,
+                    deferred: deferred && urgency > high_priority_window,
+                });
+            }
+        }
+    }
+
+    // Most urgent first, except that low-risk pairs deferred last sweep go ahead
+    // of the other low-risk pairs; then closest, then by index so ordering is stable
+    candidates.sort_unstable_by(|a, b| {
+        (a.urgency > high_priority_window, !a.deferred)
+            .cmp(&(b.urgency > high_priority_window, !b.deferred))
+            .then(a.urgency.total_cmp(&b.urgency))
+            .then(a.distance.total_cmp(&b.distance))
+            .then((a.first, a.second).cmp(&(b.first, b.second)))
+    });
+
+    // Evaluate in priority order so a budget overrun only drops the least urgent pairs
+    for &candidate in candidates.iter() {
+        let cached = cache
+            .as_mut()
+            .and_then(|cache| cache.lookup(source.key(candidate.first), source.key(candidate.second)));
+        if let Some(info) = cached {
+            report.cache_hits += 1;
+            report.pairs_evaluated += 1;
+            if info.severity != ConflictSeverity::None {
+                report.conflicts.push(PairConflict {
+                    first: candidate.first,
+                    second: candidate.second,
+                    info,
+                });
+            }
+            continue;
+        }
+
+        let high_priority = candidate.urgency <= high_priority_window;
+        if !high_priority && budget.is_some_and(|budget| report.pairs_solved >= budget) {
+            report.pairs_skipped += 1;
+            if let Some(cache) = cache.as_mut() {
+                cache.defer(source.key(candidate.first), source.key(candidate.second));
+            }
+            continue;
+        }
+
+        let info = detect_conflict_to_levels(
+            &source.state(candidate.first),
+            None,
+            &source.state(candidate.second),
+            None,
+            config.alert_distance(),
+            config.vertical_separation,
+            config.look_ahead_time,
+        );
+        report.pairs_solved += 1;
+        report.pairs_evaluated += 1;
+
+        if let Some(cache) = cache.as_mut() {
+            cache.store(source.key(candidate.first), source.key(candidate.second), info);
+        }
+
+        if info.severity != ConflictSeverity::None {
+            report.conflicts.push(PairConflict {
+                first: candidate.first,
+                second: candidate.second,
+                info,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SeparationStandards;
+    use crate::conflict::detect_conflict;
+
+    fn traffic() -> Vec<AircraftState> {
+        vec![
+            // Head-on pair, 8 nm apart
+            AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0),
+            AircraftState::new(0.0, 8.0, 10000.0, 180.0, 250.0),
+            // Distant converging pair, low risk
+            AircraftState::new(40.0, 0.0, 20000.0, 0.0, 250.0),
+            AircraftState::new(40.0, 30.0, 20000.0, 180.0, 250.0),
+            // Vertically separated, pruned
+            AircraftState::new(-20.0, 0.0, 30000.0, 90.0, 250.0),
+        ]
+    }
+
+    #[test]
+    fn test_unlimited_sweep() {
+        let report = sweep_conflicts(&traffic(), &SafetyConfig::default(), None);
+
+        assert!(!report.is_degraded());
+        assert_eq!(report.conflicts.len(), 2);
+        assert_eq!(report.pairs_evaluated, 2);
+    }
+
+    #[test]
+    fn test_degraded_sweep_keeps_high_priority() {
+        let config = SafetyConfig::default();
+        let report = sweep_conflicts(&traffic(), &config, Some(0));
+
+        // The urgent pair is solved past the budget; the low-risk one is skipped
+        assert!(report.is_degraded());
+        assert_eq!(report.pairs_solved, 1);
+        assert_eq!(report.pairs_skipped, 1);
+        assert!(report
+            .conflicts
+            .iter()
+            .any(|c| c.first == 0 && c.second == 1));
+
+        let report = sweep_conflicts(&traffic(), &config, Some(2));
+        assert!(!report.is_degraded());
+        assert_eq!(report.pairs_solved, 2);
+    }
+
+    #[test]
+    fn test_climbing_pair_is_not_pruned() {
+        // 2000 ft below and climbing 1000 ft/min towards a head-on aircraft 20 nm away
+        let fleet = vec![
+            AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0),
+            AircraftState::new(0.0, 20.0, 8000.0, 180.0, 250.0).with_vertical_rate(1000.0),
+        ];
+        let report = sweep_conflicts(&fleet, &SafetyConfig::default(), None);
+        assert_eq!(report.pairs_evaluated, 1);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].info.severity, ConflictSeverity::Critical);
+
+        // Levelled off the same pair stays separated and is pruned
+        let level = vec![fleet[0], AircraftState::new(0.0, 20.0, 8000.0, 180.0, 250.0)];
+        assert_eq!(sweep_conflicts(&level, &SafetyConfig::default(), None).pairs_evaluated, 0);
+    }
+
+    #[test]
+    fn test_sweep_matches_detect_conflict() {
+        let config = SafetyConfig::default();
+        let fleet = traffic();
+        let report = sweep_conflicts(&fleet, &config, None);
+
+        let standards = SeparationStandards::new(config.alert_distance(), config.vertical_separation);
+        for conflict in &report.conflicts {
+            let expected =
+                detect_conflict(&fleet[conflict.first], &fleet[conflict.second], &standards, config.look_ahead_time);
+            assert_eq!(conflict.info.time_to_conflict, expected.time_to_conflict);
+            assert_eq!(conflict.info.severity, expected.severity);
+        }
+    }
+
+    #[test]
+    fn test_skipped_pairs_are_deferred() {
+        let config = SafetyConfig::default();
+        let mut fleet = traffic();
+        // Second low-risk pair, closer than the first
+        fleet.push(AircraftState::new(200.0, 0.0, 20000.0, 0.0, 250.0));
+        fleet.push(AircraftState::new(200.0, 28.0, 20000.0, 180.0, 250.0));
+        let mut keys: Vec<StateKey> = (0..fleet.len() as u64).map(|id| StateKey::new(id, 1)).collect();
+        let mut cache = ConflictCache::new();
+
+        let first = sweep_conflicts_cached(&fleet, &keys, &config, Some(2), &mut cache);
+        assert_eq!(first.pairs_skipped, 1);
+        assert!(cache.is_deferred(keys[2], keys[3]));
+
+        // With every state changed the deferred pair is solved before the more urgent low-risk one
+        keys.iter_mut().for_each(|key| key.version = 2);
+        let second = sweep_conflicts_cached(&fleet, &keys, &config, Some(2), &mut cache);
+        assert_eq!(second.pairs_solved, 2);
+        assert!(!cache.is_deferred(keys[2], keys[3]));
+        assert!(cache.is_deferred(keys[5], keys[6]));
+
+        // Cache hits do not count against the budget
+        let third = sweep_conflicts_cached(&fleet, &keys, &config, Some(0), &mut cache);
+        assert_eq!(third.cache_hits, 2);
+        assert_eq!(third.pairs_solved, 0);
+        assert_eq!(third.pairs_skipped, 1);
+    }
+
+    #[test]
+    fn test_pair_scoring_orders_by_urgency() {
+        let config = SafetyConfig::default();
+        let fleet = traffic();
+
+        let (_, close) = score_pair(&fleet[0], &fleet[1], &config).unwrap();
+        let (_, far) = score_pair(&fleet[2], &fleet[3], &config).unwrap();
+        assert!(close < far);
+        assert!(score_pair(&fleet[0], &fleet[4], &config).is_none());
+
+        // Diverging pair within reach is never urgent
+        let diverging = AircraftState::new(0.0, 8.0, 10000.0, 0.0, 250.0);
+        let (_, urgency) = score_pair(&fleet[0], &diverging, &config).unwrap();
+        assert!(urgency.is_infinite());
+    }
+
+    #[test]
+    fn test_cache_reuses_unchanged_pairs() {
+        let fleet = traffic();
+        let config = SafetyConfig::default();
+        let mut keys: Vec<StateKey> = (0..fleet.len() as u64)
+            .map(|id| StateKey::new(id, 1))
+            .collect();
+        let mut cache = ConflictCache::new();
+
+        let first = sweep_conflicts_cached(&fleet, &keys, &config, None, &mut cache);
+        assert_eq!(first.cache_hits, 0);
+        assert_eq!(cache.len(), 2);
+
+        let second = sweep_conflicts_cached(&fleet, &keys, &config, None, &mut cache);
+        assert_eq!(second.cache_hits, 2);
+        assert_eq!(second.pairs_solved, 0);
+        assert_eq!(second.conflicts.len(), first.conflicts.len());
+
+        // A new state version for one aircraft only recomputes its pairs
+        keys[0].version = 2;
+        let third = sweep_conflicts_cached(&fleet, &keys, &config, None, &mut cache);
+        assert_eq!(third.cache_hits, 1);
+
+        // Invalidating an aircraft drops its pairs even if its version repeats
+        cache.invalidate(0);
+        let fourth = sweep_conflicts_cached(&fleet, &keys, &config, None, &mut cache);
+        assert_eq!(fourth.cache_hits, 1);
+    }
+
+    #[test]
+    fn test_cache_invalidated_by_config_change() {
+        let fleet = traffic();
+        let keys: Vec<StateKey> = (0..fleet.len() as u64)
+            .map(|id| StateKey::new(id, 1))
+            .collect();
+        let mut cache = ConflictCache::new();
+
+        sweep_conflicts_cached(&fleet, &keys, &SafetyConfig::default(), None, &mut cache);
+        let config = SafetyConfig::new(5.0, 1000.0, 300.0, 1.0);
+        let report = sweep_conflicts_cached(&fleet, &keys, &config, None, &mut cache);
+        assert_eq!(report.cache_hits, 0);
+    }
+
+    #[test]
+    fn test_workspace_sweep_respects_capacity() {
+        let fleet = traffic();
+        let keys: Vec<StateKey> = (0..fleet.len() as u64).map(|id| StateKey::new(id, 1)).collect();
+        let config = SafetyConfig::default();
+
+        let mut cache = ConflictCache::with_capacity(8);
+        let mut workspace = SweepWorkspace::with_capacity(8);
+        let report = sweep_conflicts_into(&fleet, &keys, &config, None, &mut cache, &mut workspace).unwrap();
+        assert_eq!(report.conflicts.len(), 2);
+
+        let mut small = SweepWorkspace::with_capacity(2);
+        let result = sweep_conflicts_into(&fleet, &keys, &config, None, &mut cache, &mut small);
+        assert_eq!(result.unwrap_err().capacity, 2);
+    }
+}
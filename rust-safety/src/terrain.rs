@@ -0,0 +1,139 @@
+/*!
+ * TERRAIN MODULE
+ * Elevation grid lookups for terrain clearance checks
+ */
+
+/// Regular grid of terrain and obstacle elevations (ft)
+///
+/// Cell `(column, row)` covers `origin + (column, row) * cell_size` to the
+/// next cell; elevations are stored row by row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerrainGrid {
+    pub origin_x: f64,
+    pub origin_y: f64,
+    pub cell_size: f64,
+    pub columns: usize,
+    pub rows: usize,
+    elevations: Vec<f64>,
+}
+
+impl TerrainGrid {
+    /// Build a grid; returns `None` if the elevation count does not match the dimensions
+    pub fn new(
+        origin_x: f64,
+        origin_y: f64,
+        cell_size: f64,
+        columns: usize,
+        rows: usize,
+        elevations: Vec<f64>,
+    ) -> Option<Self> {
+        if cell_size <= 0.0 || elevations.len() != columns * rows {
+            return None;
+        }
+
+        Some(TerrainGrid {
+            origin_x,
+            origin_y,
+            cell_size,
+            columns,
+            rows,
+            elevations,
+        })
+    }
+
+    /// Elevation of the cell containing a point, or `None` outside the grid
+    pub fn elevation_at(&self, x: f64, y: f64) -> Option<f64> {
+        let column = ((x - self.origin_x) / self.cell_size).floor();
+        let row = ((y - self.origin_y) / self.cell_size).floor();
+        if column < 0.0 || row < 0.0 || !column.is_finite() || !row.is_finite() {
+            return None;
+        }
+
+        let (column, row) = (column as usize, row as usize);
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+        Some(self.elevations[row * self.columns + column])
+    }
+
+    /// Highest elevation of any cell within `radius` of a point (0 when none)
+    pub fn max_elevation_near(&self, x: f64, y: f64, radius: f64) -> f64 {
+        let radius = radius.max(0.0);
+        let first_column = ((x - radius - self.origin_x) / self.cell_size).floor().max(0.0) as usize;
+        let first_row = ((y - radius - self.origin_y) / self.cell_size).floor().max(0.0) as usize;
+        let last_column = ((x + radius - self.origin_x) / self.cell_size).floor();
+        let last_row = ((y + radius - self.origin_y) / self.cell_size).floor();
+        if last_column < 0.0 || last_row < 0.0 {
+            return 0.0;
+        }
+        let last_column = (last_column as usize).min(self.columns.saturating_sub(1));
+        let last_row = (last_row as usize).min(self.rows.saturating_sub(1));
+
+        let mut highest: f64 = 0.0;
+        for row in first_row..=last_row {
+            for column in first_column..=last_column {
+                // Closest point of the cell to the query point
+                let cell_x = self.origin_x + column as f64 * self.cell_size;
+                let cell_y = self.origin_y + row as f64 * self.cell_size;
+                let dx = x - x.clamp(cell_x, cell_x + self.cell_size);
+                let dy = y - y.clamp(cell_y, cell_y + self.cell_size);
+                if dx * dx + dy * dy <= radius * radius {
+                    highest = highest.max(self.elevations[row * self.columns + column]);
+                }
+            }
+        }
+        highest
+    }
+
+    /// Highest elevation of any cell overlapping a rectangle (0 when none)
+    pub fn max_elevation_in(&self, min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> f64 {
+        let first_column = ((min_x - self.origin_x) / self.cell_size).floor().max(0.0) as usize;
+        let first_row = ((min_y - self.origin_y) / self.cell_size).floor().max(0.0) as usize;
+        // Cells that only touch the far edge do not overlap
+        let last_column = ((max_x - self.origin_x) / self.cell_size).ceil() - 1.0;
+        let last_row = ((max_y - self.origin_y) / self.cell_size).ceil() - 1.0;
+        if last_column < 0.0 || last_row < 0.0 || !last_column.is_finite() || !last_row.is_finite() {
+            return 0.0;
+        }
+        let last_column = (last_column as usize).min(self.columns.saturating_sub(1));
+        let last_row = (last_row as usize).min(self.rows.saturating_sub(1));
+
+        let mut highest: f64 = 0.0;
+        for row in first_row..=last_row {
+            for column in first_column..=last_column {
+                highest = highest.max(self.elevations[row * self.columns + column]);
+            }
+        }
+        highest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> TerrainGrid {
+        // 3x2 cells of 1 nm with a 4000 ft ridge in the middle column
+        TerrainGrid::new(0.0, 0.0, 1.0, 3, 2, vec![500.0, 4000.0, 800.0, 600.0, 4000.0, 900.0]).unwrap()
+    }
+
+    #[test]
+    fn test_elevation_lookup() {
+        let grid = grid();
+        assert_eq!(grid.elevation_at(0.5, 0.5), Some(500.0));
+        assert_eq!(grid.elevation_at(2.5, 1.5), Some(900.0));
+        assert_eq!(grid.elevation_at(3.5, 0.5), None);
+        assert!(TerrainGrid::new(0.0, 0.0, 1.0, 2, 2, vec![0.0]).is_none());
+    }
+
+    #[test]
+    fn test_max_elevation_near() {
+        let grid = grid();
+        assert_eq!(grid.max_elevation_near(0.2, 0.5, 0.4), 500.0);
+        assert_eq!(grid.max_elevation_near(0.2, 0.5, 1.0), 4000.0);
+        assert_eq!(grid.max_elevation_near(-10.0, -10.0, 1.0), 0.0);
+
+        assert_eq!(grid.max_elevation_in(2.0, 0.0, 3.0, 2.0), 900.0);
+        assert_eq!(grid.max_elevation_in(0.0, 0.0, 1.5, 1.0), 4000.0);
+    }
+}
@@ -0,0 +1,200 @@
+/*!
+ * TIMING MODULE
+ * Multi-rate track updates with per-source timestamps
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::config::SafetyConfig;
+use crate::separation::predict_position;
+use crate::surveillance::SurveillanceReport;
+use crate::sweep::{sweep_conflicts, SweepReport};
+use crate::traffic::{CapacityError, TrafficSet};
+use crate::AircraftState;
+
+/// Why a timestamped report was not applied
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportError {
+    Capacity(CapacityError),
+    /// The track already holds a newer measurement
+    OutOfOrder { track: u64, latest: f64, received: f64 },
+}
+
+impl fmt::Display for ReportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportError::Capacity(e) => e.fmt(f),
+            ReportError::OutOfOrder { track, latest, received } => write!(
+                f,
+                "report for track {} measured at {} is older than its latest at {}",
+                track, received, latest
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReportError {}
+
+impl From<CapacityError> for ReportError {
+    fn from(e: CapacityError) -> Self {
+        ReportError::Capacity(e)
+    }
+}
+
+/// Measurement time and source of a track's current state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrackTime {
+    pub time: f64,
+    pub source: usize,
+}
+
+/// Traffic picture fed by sources reporting at different rates
+///
+/// Each track keeps the measurement time of its state. Reports older than
+/// that are rejected, so a slow radar return arriving after a newer ADS-B
+/// position cannot pull the track back. `aligned` dead-reckons every track
+/// to one epoch for comparison.
+#[derive(Debug, Clone, Default)]
+pub struct TimedTraffic {
+    traffic: TrafficSet,
+    times: HashMap<u64, TrackTime>,
+}
+
+impl TimedTraffic {
+    pub fn new() -> Self {
+        TimedTraffic::default()
+    }
+
+    /// Pre-allocated picture that never grows past `capacity` aircraft
+    pub fn fixed(capacity: usize) -> Self {
+        TimedTraffic {
+            traffic: TrafficSet::fixed(capacity),
+            times: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Latest states as measured, not time-aligned
+    pub fn traffic(&self) -> &TrafficSet {
+        &self.traffic
+    }
+
+    pub fn track_time(&self, id: u64) -> Option<TrackTime> {
+        self.times.get(&id).copied()
+    }
+
+    /// Newest measurement time across all tracks
+    pub fn latest_time(&self) -> Option<f64> {
+        self.times.values().map(|t| t.time).reduce(f64::max)
+    }
+
+    /// Apply a state measured at `time` by `source`, returning the new state version
+    ///
+    /// A report with the same time as the track's replaces it; an older one
+    /// is rejected as out of order.
+    pub fn update(&mut self, id: u64, state: AircraftState, time: f64, source: usize) -> Result<u64, ReportError> {
+        if let Some(latest) = self.times.get(&id) {
+            if time < latest.time {
+                return Err(ReportError::OutOfOrder {
+                    track: id,
+                    latest: latest.time,
+                    received: time,
+                });
+            }
+        }
+
+        let version = self.traffic.insert(id, state)?;
+        self.times.insert(id, TrackTime { time, source });
+        Ok(version)
+    }
+
+    /// Apply a simulated sensor report at its measurement time
+    pub fn apply(&mut self, report: &SurveillanceReport) -> Result<u64, ReportError> {
+        self.update(report.track, report.state, report.measured_at, report.sensor)
+    }
+
+    pub fn remove(&mut self, id: u64) -> Option<AircraftState> {
+        self.times.remove(&id);
+        self.traffic.remove(id)
+    }
+
+    /// Every track extrapolated to `epoch`, in storage order
+    pub fn aligned(&self, epoch: f64) -> Vec<AircraftState> {
+        self.traffic
+            .iter()
+            .map(|(id, state)| match self.times.get(&id) {
+                Some(t) => predict_position(&state, epoch - t.time),
+                None => state,
+            })
+            .collect()
+    }
+
+    /// Sweep with every track extrapolated to one epoch
+    ///
+    /// `epoch` defaults to the newest measurement time, so no track is
+    /// compared using a state tens of seconds older than another's. The
+    /// epoch used is recorded in the report.
+    pub fn sweep_at(&self, epoch: Option<f64>, config: &SafetyConfig, budget: Option<usize>) -> SweepReport {
+        let epoch = epoch.or_else(|| self.latest_time()).unwrap_or(0.0);
+        let mut report = sweep_conflicts(&self.aligned(epoch), config, budget);
+        report.epoch = Some(epoch);
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_stale_out_of_order_reports() {
+        let mut traffic = TimedTraffic::new();
+        let adsb = AircraftState::new(10.0, 0.0, 10000.0, 90.0, 360.0);
+        let radar = AircraftState::new(9.9, 0.0, 10000.0, 90.0, 360.0);
+
+        assert_eq!(traffic.update(1, adsb, 12.0, 0), Ok(1));
+        let stale = traffic.update(1, radar, 11.0, 1);
+        assert_eq!(stale, Err(ReportError::OutOfOrder { track: 1, latest: 12.0, received: 11.0 }));
+        assert_eq!(traffic.traffic().get(1).unwrap().x, 10.0);
+
+        assert_eq!(traffic.update(1, radar, 12.0, 1), Ok(2));
+        assert_eq!(traffic.track_time(1), Some(TrackTime { time: 12.0, source: 1 }));
+
+        let mut full = TimedTraffic::fixed(1);
+        full.update(1, adsb, 0.0, 0).unwrap();
+        assert!(matches!(full.update(2, adsb, 0.0, 0), Err(ReportError::Capacity(_))));
+    }
+
+    #[test]
+    fn test_aligns_tracks_to_common_epoch() {
+        let mut traffic = TimedTraffic::new();
+        // 360 kt east is 0.1 nm/s
+        traffic.update(1, AircraftState::new(0.0, 0.0, 10000.0, 90.0, 360.0), 10.0, 0).unwrap();
+        traffic.update(2, AircraftState::new(0.0, 5.0, 10000.0, 90.0, 360.0), 4.0, 1).unwrap();
+
+        assert_eq!(traffic.latest_time(), Some(10.0));
+        let aligned = traffic.aligned(10.0);
+        assert!(aligned[0].x.abs() < 1e-9);
+        assert!((aligned[1].x - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sweep_uses_common_epoch() {
+        let config = SafetyConfig::default();
+        let mut traffic = TimedTraffic::new();
+        // Head-on pair; the second report is 40 s old and would look 8 nm further away
+        traffic.update(1, AircraftState::new(-4.0, 0.0, 10000.0, 90.0, 360.0), 100.0, 0).unwrap();
+        traffic.update(2, AircraftState::new(8.0, 0.0, 10000.0, 270.0, 360.0), 60.0, 1).unwrap();
+
+        let report = traffic.sweep_at(None, &config, None);
+        assert_eq!(report.epoch, Some(100.0));
+        assert_eq!(report.conflicts.len(), 1);
+        let aligned_time = report.conflicts[0].info.time_to_conflict;
+        // 8 nm apart closing at 0.2 nm/s reaches 3 nm after 25 s
+        assert!((aligned_time - 25.0).abs() <= config.time_step, "{}", aligned_time);
+
+        let unaligned = sweep_conflicts(&traffic.traffic().states(), &config, None);
+        assert_eq!(unaligned.epoch, None);
+        assert!(unaligned.conflicts[0].info.time_to_conflict > aligned_time + 15.0);
+    }
+}
@@ -0,0 +1,276 @@
+/*!
+ * TRACKING MODULE
+ * Constant-velocity Kalman filtering of noisy surveillance positions
+ */
+
+use std::collections::HashMap;
+
+use crate::state::TimedState;
+use crate::{normalize_heading, AircraftState};
+
+type Matrix = [[f64; 4]; 4];
+
+/// Standard deviation of the initial velocity estimate (kt)
+const INITIAL_VELOCITY_SIGMA: f64 = 50.0;
+
+/// Horizontal track filter with state `[x, y, vx, vy]` in nm and nm/s
+///
+/// Velocity is modelled as constant with white-noise acceleration, so turns
+/// and speed changes are followed with a lag set by `process_noise`.
+/// Altitude, vertical rate and wake category are taken from the latest
+/// measurement unfiltered.
+#[derive(Debug, Clone)]
+pub struct KalmanFilter {
+    state: [f64; 4],
+    covariance: Matrix,
+    /// Acceleration noise spectral density (nm²/s³)
+    pub process_noise: f64,
+    /// Standard deviation of measured positions (nm)
+    pub measurement_noise: f64,
+    time: f64,
+    last: AircraftState,
+}
+
+impl KalmanFilter {
+    /// Start a filter from a first measurement, using its heading and speed as the initial velocity
+    pub fn new(measurement: &TimedState, measurement_noise: f64, process_noise: f64) -> Self {
+        let aircraft = measurement.state;
+        let speed = aircraft.speed / 3600.0;
+        let heading = aircraft.heading.to_radians();
+        let position_variance = measurement_noise * measurement_noise;
+        let velocity_variance = (INITIAL_VELOCITY_SIGMA / 3600.0).powi(2);
+
+        let mut covariance = [[0.0; 4]; 4];
+        covariance[0][0] = position_variance;
+        covariance[1][1] = position_variance;
+        covariance[2][2] = velocity_variance;
+        covariance[3][3] = velocity_variance;
+
+        KalmanFilter {
+            state: [aircraft.x, aircraft.y, speed * heading.sin(), speed * heading.cos()],
+            covariance,
+            process_noise,
+            measurement_noise,
+            time: measurement.timestamp,
+            last: aircraft,
+        }
+    }
+
+    /// Time of the current estimate (seconds)
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Advance the estimate to `time`; earlier times are ignored
+    pub fn predict(&mut self, time: f64) {
+        let dt = time - self.time;
+        if !(dt.is_finite() && dt > 0.0) {
+            return;
+        }
+
+        let [x, y, vx, vy] = self.state;
+        self.state = [x + vx * dt, y + vy * dt, vx, vy];
+
+        let mut transition = identity();
+        transition[0][2] = dt;
+        transition[1][3] = dt;
+        let mut covariance = multiply(&multiply(&transition, &self.covariance), &transpose(&transition));
+
+        let q = self.process_noise;
+        for axis in 0..2 {
+            let (p, v) = (axis, axis + 2);
+            covariance[p][p] += q * dt.powi(3) / 3.0;
+            covariance[p][v] += q * dt.powi(2) / 2.0;
+            covariance[v][p] += q * dt.powi(2) / 2.0;
+            covariance[v][v] += q * dt;
+        }
+        self.covariance = covariance;
+        self.time = time;
+    }
+
+    /// Fold in a measured position, predicting forward to its timestamp first
+    pub fn update(&mut self, measurement: &TimedState) {
+        self.predict(measurement.timestamp);
+        let z = [measurement.state.x, measurement.state.y];
+        if !(z[0].is_finite() && z[1].is_finite()) {
+            return;
+        }
+
+        let p = &self.covariance;
+        let r = self.measurement_noise * self.measurement_noise;
+        let s = [[p[0][0] + r, p[0][1]], [p[1][0], p[1][1] + r]];
+        let det = s[0][0] * s[1][1] - s[0][1] * s[1][0];
+        if det.abs() < f64::EPSILON {
+            return;
+        }
+        let s_inv = [[s[1][1] / det, -s[0][1] / det], [-s[1][0] / det, s[0][0] / det]];
+
+        // Gain K = P Hᵀ S⁻¹, where P Hᵀ is the first two columns of P
+        let mut gain = [[0.0; 2]; 4];
+        for (i, row) in gain.iter_mut().enumerate() {
+            for (j, k) in row.iter_mut().enumerate() {
+                *k = p[i][0] * s_inv[0][j] + p[i][1] * s_inv[1][j];
+            }
+        }
+
+        let innovation = [z[0] - self.state[0], z[1] - self.state[1]];
+        for (i, row) in gain.iter().enumerate() {
+            self.state[i] += row[0] * innovation[0] + row[1] * innovation[1];
+        }
+
+        // P = (I - K H) P
+        let mut covariance = [[0.0; 4]; 4];
+        for (i, row) in covariance.iter_mut().enumerate() {
+            for (j, c) in row.iter_mut().enumerate() {
+                *c = p[i][j] - gain[i][0] * p[0][j] - gain[i][1] * p[1][j];
+            }
+        }
+        self.covariance = covariance;
+        self.last = measurement.state;
+    }
+
+    /// Filtered aircraft state, with heading and speed from the filtered velocity
+    pub fn state(&self) -> AircraftState {
+        let [x, y, vx, vy] = self.state;
+        AircraftState {
+            x,
+            y,
+            heading: normalize_heading(vx.atan2(vy).to_degrees()),
+            speed: vx.hypot(vy) * 3600.0,
+            ..self.last
+        }
+    }
+
+    /// Filtered velocity (nm/s east, north)
+    pub fn velocity(&self) -> (f64, f64) {
+        (self.state[2], self.state[3])
+    }
+
+    /// Full state covariance in `[x, y, vx, vy]` order
+    pub fn covariance(&self) -> &Matrix {
+        &self.covariance
+    }
+
+    /// Position covariance (nm²)
+    pub fn position_covariance(&self) -> [[f64; 2]; 2] {
+        let p = &self.covariance;
+        [[p[0][0], p[0][1]], [p[1][0], p[1][1]]]
+    }
+
+    /// Root-mean-square position error per axis (nm), for conflict probability estimates
+    pub fn position_sigma(&self) -> f64 {
+        ((self.covariance[0][0] + self.covariance[1][1]) / 2.0).sqrt()
+    }
+}
+
+/// One Kalman filter per track
+#[derive(Debug, Clone)]
+pub struct TrackFilters {
+    pub measurement_noise: f64,
+    pub process_noise: f64,
+    filters: HashMap<u64, KalmanFilter>,
+}
+
+impl TrackFilters {
+    pub fn new(measurement_noise: f64, process_noise: f64) -> Self {
+        TrackFilters {
+            measurement_noise,
+            process_noise,
+            filters: HashMap::new(),
+        }
+    }
+
+    /// Filter a measurement for `track`, starting a filter if it is new
+    pub fn update(&mut self, track: u64, measurement: &TimedState) -> AircraftState {
+        match self.filters.get_mut(&track) {
+            Some(filter) => {
+                filter.update(measurement);
+                filter.state()
+            }
+            None => {
+                let filter = KalmanFilter::new(measurement, self.measurement_noise, self.process_noise);
+                let state = filter.state();
+                self.filters.insert(track, filter);
+                state
+            }
+        }
+    }
+
+    pub fn filter(&self, track: u64) -> Option<&KalmanFilter> {
+        self.filters.get(&track)
+    }
+
+    pub fn remove(&mut self, track: u64) -> bool {
+        self.filters.remove(&track).is_some()
+    }
+}
+
+fn identity() -> Matrix {
+    let mut m = [[0.0; 4]; 4];
+    for (i, row) in m.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+    m
+}
+
+fn multiply(a: &Matrix, b: &Matrix) -> Matrix {
+    let mut m = [[0.0; 4]; 4];
+    for (i, row) in m.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..4).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    m
+}
+
+fn transpose(a: &Matrix) -> Matrix {
+    let mut m = [[0.0; 4]; 4];
+    for (i, row) in m.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = a[j][i];
+        }
+    }
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noisy_track_is_smoothed() {
+        let mut filters = TrackFilters::new(0.05, 1e-8);
+        // Eastbound at 360 kt with 0.05 nm cross-track jitter and a wrong initial heading
+        let mut filtered = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 0.0);
+        for t in 0..60 {
+            let jitter = if t % 2 == 0 { 0.05 } else { -0.05 };
+            let heading = if t == 0 { 60.0 } else { 90.0 };
+            let measured = AircraftState::new(0.1 * t as f64, jitter, 10000.0, heading, 360.0);
+            filtered = filters.update(1, &TimedState::new(measured, t as f64));
+        }
+
+        assert!(filtered.y.abs() < 0.02);
+        assert!((filtered.x - 5.9).abs() < 0.02);
+        assert!((filtered.heading - 90.0).abs() < 1.0);
+        assert!((filtered.speed - 360.0).abs() < 5.0);
+
+        let filter = filters.filter(1).unwrap();
+        assert!(filter.position_sigma() < 0.05);
+        assert!(filters.remove(1));
+    }
+
+    #[test]
+    fn test_prediction_grows_covariance() {
+        let start = TimedState::new(AircraftState::new(0.0, 0.0, 10000.0, 90.0, 360.0), 0.0);
+        let mut filter = KalmanFilter::new(&start, 0.1, 1e-6);
+        let initial = filter.position_sigma();
+
+        filter.predict(10.0);
+        assert!(filter.position_sigma() > initial);
+        assert!((filter.state().x - 1.0).abs() < 1e-9);
+
+        // Stale measurements do not move the clock backwards
+        filter.predict(5.0);
+        assert_eq!(filter.time(), 10.0);
+    }
+}
@@ -0,0 +1,379 @@
+/*!
+ * TRAFFIC MODULE
+ * Struct-of-arrays storage for the full traffic picture
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::aircraft_types::WakeCategory;
+use crate::config::SafetyConfig;
+use crate::sweep::{sweep_source_cached, sweep_source_into, ConflictCache, StateKey, SweepReport, SweepSource, SweepWorkspace};
+use crate::AircraftState;
+
+/// Returned when a fixed-capacity structure is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError {
+    pub capacity: usize,
+}
+
+impl CapacityError {
+    pub fn new(capacity: usize) -> Self {
+        CapacityError { capacity }
+    }
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "capacity of {} aircraft exceeded", self.capacity)
+    }
+}
+
+impl std::error::Error for CapacityError {}
+
+/// Traffic set stored as contiguous per-field arrays
+///
+/// Batch kernels read the field slices directly; `get`/`insert` provide the
+/// usual per-aircraft `AircraftState` view on top. Every insert or update
+/// gives the aircraft a new state version so cached results can be reused.
+/// Versions come from one counter for the whole set, so an aircraft removed
+/// and added again never repeats a version it had before.
+///
+/// A set created with `fixed` allocates all storage up front and rejects
+/// new aircraft beyond its capacity instead of growing.
+#[derive(Debug, Clone, Default)]
+pub struct TrafficSet {
+    capacity_limit: Option<usize>,
+    ids: Vec<u64>,
+    versions: Vec<u64>,
+    last_version: u64,
+    x: Vec<f64>,
+    y: Vec<f64>,
+    altitude: Vec<f64>,
+    heading: Vec<f64>,
+    speed: Vec<f64>,
+    vertical_rate: Vec<f64>,
+    wake: Vec<Option<WakeCategory>>,
+    index: HashMap<u64, usize>,
+}
+
+impl TrafficSet {
+    pub fn new() -> Self {
+        TrafficSet::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        TrafficSet {
+            capacity_limit: None,
+            ids: Vec::with_capacity(capacity),
+            versions: Vec::with_capacity(capacity),
+            last_version: 0,
+            x: Vec::with_capacity(capacity),
+            y: Vec::with_capacity(capacity),
+            altitude: Vec::with_capacity(capacity),
+            heading: Vec::with_capacity(capacity),
+            speed: Vec::with_capacity(capacity),
+            vertical_rate: Vec::with_capacity(capacity),
+            wake: Vec::with_capacity(capacity),
+            index: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Pre-allocated set that never grows past `capacity` aircraft
+    pub fn fixed(capacity: usize) -> Self {
+        TrafficSet {
+            capacity_limit: Some(capacity),
+            ..TrafficSet::with_capacity(capacity)
+        }
+    }
+
+    pub fn capacity_limit(&self) -> Option<usize> {
+        self.capacity_limit
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn contains(&self, id: u64) -> bool {
+        self.index.contains_key(&id)
+    }
+
+    /// Add an aircraft or replace its state, returning the new state version
+    pub fn insert(&mut self, id: u64, state: AircraftState) -> Result<u64, CapacityError> {
+        if let Some(&i) = self.index.get(&id) {
+            self.write(i, &state);
+            self.last_version += 1;
+            self.versions[i] = self.last_version;
+            return Ok(self.last_version);
+        }
+
+        if let Some(limit) = self.capacity_limit {
+            if self.ids.len() >= limit {
+                return Err(CapacityError::new(limit));
+            }
+        }
+
+        self.last_version += 1;
+        self.index.insert(id, self.ids.len());
+        self.ids.push(id);
+        self.versions.push(self.last_version);
+        self.x.push(state.x);
+        self.y.push(state.y);
+        self.altitude.push(state.altitude);
+        self.heading.push(state.heading);
+        self.speed.push(state.speed);
+        self.vertical_rate.push(state.vertical_rate);
+        self.wake.push(state.wake);
+        Ok(self.last_version)
+    }
+
+    /// Remove an aircraft, returning its last state
+    pub fn remove(&mut self, id: u64) -> Option<AircraftState> {
+        let i = self.index.remove(&id)?;
+        let state = self.state_at(i);
+
+        self.ids.swap_remove(i);
+        self.versions.swap_remove(i);
+        self.x.swap_remove(i);
+        self.y.swap_remove(i);
+        self.altitude.swap_remove(i);
+        self.heading.swap_remove(i);
+        self.speed.swap_remove(i);
+        self.vertical_rate.swap_remove(i);
+        self.wake.swap_remove(i);
+
+        // The last aircraft moved into the freed slot
+        if i < self.ids.len() {
+            self.index.insert(self.ids[i], i);
+        }
+
+        Some(state)
+    }
+
+    pub fn get(&self, id: u64) -> Option<AircraftState> {
+        self.index.get(&id).map(|&i| self.state_at(i))
+    }
+
+    pub fn version(&self, id: u64) -> Option<u64> {
+        self.index.get(&id).map(|&i| self.versions[i])
+    }
+
+    /// Identifier of the aircraft stored at a slot
+    pub fn id_at(&self, index: usize) -> Option<u64> {
+        self.ids.get(index).copied()
+    }
+
+    pub fn state_at(&self, index: usize) -> AircraftState {
+        AircraftState {
+            x: self.x[index],
+            y: self.y[index],
+            altitude: self.altitude[index],
+            heading: self.heading[index],
+            speed: self.speed[index],
+            vertical_rate: self.vertical_rate[index],
+            wake: self.wake[index],
+        }
+    }
+
+    fn write(&mut self, index: usize, state: &AircraftState) {
+        self.x[index] = state.x;
+        self.y[index] = state.y;
+        self.altitude[index] = state.altitude;
+        self.heading[index] = state.heading;
+        self.speed[index] = state.speed;
+        self.vertical_rate[index] = state.vertical_rate;
+        self.wake[index] = state.wake;
+    }
+
+    pub fn ids(&self) -> &[u64] {
+        &self.ids
+    }
+
+    pub fn xs(&self) -> &[f64] {
+        &self.x
+    }
+
+    pub fn ys(&self) -> &[f64] {
+        &self.y
+    }
+
+    pub fn altitudes(&self) -> &[f64] {
+        &self.altitude
+    }
+
+    pub fn headings(&self) -> &[f64] {
+        &self.heading
+    }
+
+    pub fn speeds(&self) -> &[f64] {
+        &self.speed
+    }
+
+    pub fn vertical_rates(&self) -> &[f64] {
+        &self.vertical_rate
+    }
+
+    /// Iterate over (id, state) pairs in storage order
+    pub fn iter(&self) -> impl Iterator<Item = (u64, AircraftState)> + '_ {
+        (0..self.len()).map(move |i| (self.ids[i], self.state_at(i)))
+    }
+
+    /// Per-aircraft states in storage order
+    pub fn states(&self) -> Vec<AircraftState> {
+        (0..self.len()).map(|i| self.state_at(i)).collect()
+    }
+
+    /// Identity and version keys in storage order
+    pub fn keys(&self) -> Vec<StateKey> {
+        self.ids
+            .iter()
+            .zip(&self.versions)
+            .map(|(&id, &version)| StateKey::new(id, version))
+            .collect()
+    }
+
+    /// Squared horizontal distance from a point to every aircraft (batch kernel)
+    pub fn distances_squared_from(&self, x: f64, y: f64, out: &mut Vec<f64>) {
+        out.clear();
+        out.extend(self.x.iter().zip(&self.y).map(|(&ax, &ay)| {
+            let dx = ax - x;
+            let dy = ay - y;
+            dx * dx + dy * dy
+        }));
+    }
+
+    /// Slots of aircraft within a horizontal range of a point
+    pub fn within_range(&self, x: f64, y: f64, range: f64) -> Vec<usize> {
+        let mut distances = Vec::with_capacity(self.len());
+        self.distances_squared_from(x, y, &mut distances);
+
+        let range_squared = range * range;
+        distances
+            .iter()
+            .enumerate()
+            .filter(|(_, &d)| d <= range_squared)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Run a cached conflict sweep over the whole set; pair indices are storage slots
+    ///
+    /// The sweep reads the field arrays in place rather than building states first.
+    pub fn sweep(
+        &self,
+        config: &SafetyConfig,
+        budget: Option<usize>,
+        cache: &mut ConflictCache,
+    ) -> SweepReport {
+        sweep_source_cached(self, config, budget, cache)
+    }
+
+    /// Allocation-free sweep using pre-allocated buffers
+    pub fn sweep_into<'a>(
+        &self,
+        config: &SafetyConfig,
+        budget: Option<usize>,
+        cache: &mut ConflictCache,
+        workspace: &'a mut SweepWorkspace,
+    ) -> Result<&'a SweepReport, CapacityError> {
+        sweep_source_into(self, config, budget, cache, workspace)
+    }
+}
+
+impl SweepSource for TrafficSet {
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn state(&self, index: usize) -> AircraftState {
+        self.state_at(index)
+    }
+
+    fn key(&self, index: usize) -> StateKey {
+        StateKey::new(self.ids[index], self.versions[index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_update_remove() {
+        let mut traffic = TrafficSet::new();
+        assert_eq!(traffic.insert(10, AircraftState::new(0.0, 0.0, 10000.0, 90.0, 250.0)), Ok(1));
+        assert_eq!(traffic.insert(20, AircraftState::new(5.0, 0.0, 11000.0, 270.0, 250.0)), Ok(2));
+        assert_eq!(traffic.insert(30, AircraftState::new(9.0, 0.0, 12000.0, 0.0, 250.0)), Ok(3));
+
+        // Updating gives a new version
+        assert_eq!(traffic.insert(10, AircraftState::new(1.0, 0.0, 10000.0, 90.0, 250.0)), Ok(4));
+        assert_eq!(traffic.version(10), Some(4));
+        assert_eq!(traffic.get(10).unwrap().x, 1.0);
+
+        // Removing keeps the remaining slots consistent
+        assert_eq!(traffic.remove(10).unwrap().x, 1.0);
+        assert_eq!(traffic.len(), 2);
+        assert_eq!(traffic.get(30).unwrap().altitude, 12000.0);
+        assert!(traffic.get(10).is_none());
+    }
+
+    #[test]
+    fn test_within_range() {
+        let mut traffic = TrafficSet::new();
+        traffic.insert(1, AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0)).unwrap();
+        traffic.insert(2, AircraftState::new(3.0, 4.0, 10000.0, 0.0, 250.0)).unwrap();
+        traffic.insert(3, AircraftState::new(30.0, 40.0, 10000.0, 0.0, 250.0)).unwrap();
+
+        let nearby = traffic.within_range(0.0, 0.0, 5.0);
+        assert_eq!(nearby, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_sweep_over_traffic_set() {
+        let mut traffic = TrafficSet::new();
+        traffic.insert(1, AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0)).unwrap();
+        traffic.insert(2, AircraftState::new(0.0, 8.0, 10000.0, 180.0, 250.0)).unwrap();
+
+        let mut cache = ConflictCache::new();
+        let report = traffic.sweep(&SafetyConfig::default(), None, &mut cache);
+        assert_eq!(report.conflicts.len(), 1);
+
+        // Sweeping the columns in place matches sweeping the per-aircraft states
+        let states = crate::sweep::sweep_conflicts(&traffic.states(), &SafetyConfig::default(), None);
+        assert_eq!(report.conflicts[0].info.time_to_conflict, states.conflicts[0].info.time_to_conflict);
+
+        // Unchanged traffic is served from the cache
+        let report = traffic.sweep(&SafetyConfig::default(), None, &mut cache);
+        assert_eq!(report.cache_hits, 1);
+
+        // Re-added on a diverging heading, the aircraft is not matched to its old result
+        traffic.remove(2);
+        traffic.insert(2, AircraftState::new(0.0, 8.0, 10000.0, 0.0, 250.0)).unwrap();
+        let report = traffic.sweep(&SafetyConfig::default(), None, &mut cache);
+        assert_eq!(report.cache_hits, 0);
+        assert!(report.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_fixed_capacity_mode() {
+        let mut traffic = TrafficSet::fixed(2);
+        traffic.insert(1, AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0)).unwrap();
+        traffic.insert(2, AircraftState::new(0.0, 8.0, 10000.0, 180.0, 250.0)).unwrap();
+
+        let third = traffic.insert(3, AircraftState::new(9.0, 9.0, 10000.0, 0.0, 250.0));
+        assert_eq!(third, Err(CapacityError::new(2)));
+
+        // Updates to existing aircraft are still accepted
+        assert!(traffic.insert(1, AircraftState::new(0.1, 0.0, 10000.0, 0.0, 250.0)).is_ok());
+
+        let mut cache = ConflictCache::with_capacity(2);
+        let mut workspace = SweepWorkspace::with_capacity(2);
+        let report = traffic.sweep_into(&SafetyConfig::default(), None, &mut cache, &mut workspace).unwrap();
+        assert_eq!(report.conflicts.len(), 1);
+    }
+}
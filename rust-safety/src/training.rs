@@ -0,0 +1,390 @@
+/*!
+ * TRAINING MODULE
+ * Adaptive difficulty scaling and trainee performance scoring
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::validation::{validate_command, ValidationConfig};
+
+/// Scenario generation parameters for a difficulty level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultySettings {
+    pub level: f64,
+    pub spawn_interval: f64,
+    pub conflict_geometry_rate: f64,
+    pub pilot_error_probability: f64,
+}
+
+impl DifficultySettings {
+    /// Derive settings from a level between 0.0 (easiest) and 1.0 (hardest)
+    pub fn for_level(level: f64) -> Self {
+        let level = if level.is_finite() { level.clamp(0.0, 1.0) } else { 0.0 };
+
+        DifficultySettings {
+            level,
+            spawn_interval: lerp(180.0, 45.0, level), // seconds between new aircraft
+            conflict_geometry_rate: lerp(0.05, 0.5, level), // share of spawns on a conflicting path
+            pilot_error_probability: lerp(0.0, 0.1, level), // chance an instruction is misread
+        }
+    }
+}
+
+/// Linear interpolation between two values
+fn lerp(from: f64, to: f64, t: f64) -> f64 {
+    from + (to - from) * t
+}
+
+/// Trainee performance measured over one session or scoring window
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PerformanceSample {
+    pub alerts_raised: u32,
+    pub alerts_missed: u32,
+    pub resolutions_issued: u32,
+    pub late_resolutions: u32,
+}
+
+impl PerformanceSample {
+    pub fn new(
+        alerts_raised: u32,
+        alerts_missed: u32,
+        resolutions_issued: u32,
+        late_resolutions: u32,
+    ) -> Self {
+        PerformanceSample {
+            alerts_raised,
+            alerts_missed,
+            resolutions_issued,
+            late_resolutions,
+        }
+    }
+
+    /// Performance score from 0.0 (poor) to 1.0 (perfect)
+    pub fn score(&self) -> f64 {
+        let miss_rate = ratio(self.alerts_missed, self.alerts_raised);
+        let late_rate = ratio(self.late_resolutions, self.resolutions_issued);
+
+        // Missed alerts weigh more than slow reactions
+        (1.0 - (miss_rate * 0.6 + late_rate * 0.4)).clamp(0.0, 1.0)
+    }
+}
+
+/// Fraction of a total, treating an empty total as zero
+fn ratio(count: u32, total: u32) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (count.min(total) as f64) / (total as f64)
+}
+
+/// How the controller chooses the next difficulty level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DifficultyMode {
+    Fixed,
+    Adaptive,
+}
+
+/// Adjusts scenario difficulty between sessions
+#[derive(Debug, Clone, Copy)]
+pub struct DifficultyController {
+    level: f64,
+    mode: DifficultyMode,
+    step: f64,
+    raise_threshold: f64,
+    lower_threshold: f64,
+}
+
+impl DifficultyController {
+    /// Always produce the given target difficulty
+    pub fn fixed(level: f64) -> Self {
+        DifficultyController {
+            level: DifficultySettings::for_level(level).level,
+            mode: DifficultyMode::Fixed,
+            step: 0.1,
+            raise_threshold: 0.85,
+            lower_threshold: 0.6,
+        }
+    }
+
+    /// Start at the given level and adapt to measured performance
+    pub fn adaptive(initial_level: f64) -> Self {
+        DifficultyController {
+            mode: DifficultyMode::Adaptive,
+            ..Self::fixed(initial_level)
+        }
+    }
+
+    pub fn level(&self) -> f64 {
+        self.level
+    }
+
+    pub fn mode(&self) -> DifficultyMode {
+        self.mode
+    }
+
+    pub fn settings(&self) -> DifficultySettings {
+        DifficultySettings::for_level(self.level)
+    }
+
+    /// Feed a session's performance and get the settings for the next one
+    pub fn record_performance(&mut self, sample: &PerformanceSample) -> DifficultySettings {
+        if self.mode == DifficultyMode::Adaptive {
+            let score = sample.score();
+
+            // Back off faster than we ramp up so a struggling trainee recovers quickly
+            if score >= self.raise_threshold {
+                self.level += self.step;
+            } else if score < self.lower_threshold {
+                self.level -= self.step * 2.0;
+            }
+            self.level = self.level.clamp(0.0, 1.0);
+        }
+
+        self.settings()
+    }
+}
+
+/// Alert responses slower than this count as late resolutions (seconds)
+const LATE_RESPONSE_THRESHOLD: f64 = 30.0;
+
+/// Running record of a training session used to build the debrief scorecard
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct SessionLog {
+    separation_losses: u32,
+    separation_loss_time: f64,
+    response_times: Vec<f64>,
+    alerts_missed: u32,
+    vectors_issued: u32,
+    unnecessary_vectors: u32,
+    arrivals: u32,
+    arrival_delay: f64,
+    commands_issued: u32,
+    nonconforming_commands: u32,
+}
+
+#[wasm_bindgen]
+impl SessionLog {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> SessionLog {
+        SessionLog::default()
+    }
+
+    /// Record a loss of separation lasting the given number of seconds
+    pub fn record_separation_loss(&mut self, duration: f64) {
+        self.separation_losses += 1;
+        self.separation_loss_time += duration.max(0.0);
+    }
+
+    /// Record how long the trainee took to act on an alert, or None if it was never acted on
+    pub fn record_alert_response(&mut self, response_time: Option<f64>) {
+        match response_time {
+            Some(time) if time.is_finite() => self.response_times.push(time.max(0.0)),
+            _ => self.alerts_missed += 1,
+        }
+    }
+
+    /// Record a vector, flagging whether it was needed to keep separation or sequence
+    pub fn record_vector(&mut self, necessary: bool) {
+        self.vectors_issued += 1;
+        if !necessary {
+            self.unnecessary_vectors += 1;
+        }
+    }
+
+    /// Record an arrival and the delay imposed on it (seconds)
+    pub fn record_arrival(&mut self, delay: f64) {
+        self.arrivals += 1;
+        self.arrival_delay += delay.max(0.0);
+    }
+
+    /// Record an issued command and check it against standard phraseology values
+    pub fn record_command(&mut self, command_type: &str, value: f64) {
+        self.commands_issued += 1;
+        if !is_command_conforming(command_type, value) {
+            self.nonconforming_commands += 1;
+        }
+    }
+
+    /// Build the debrief scorecard for everything recorded so far
+    pub fn scorecard(&self) -> Scorecard {
+        let alerts_answered = self.response_times.len() as u32;
+        let mean_alert_response = if alerts_answered > 0 {
+            self.response_times.iter().sum::<f64>() / alerts_answered as f64
+        } else {
+            0.0
+        };
+        let worst_alert_response = self.response_times.iter().cloned().fold(0.0, f64::max);
+        let mean_arrival_delay = if self.arrivals > 0 {
+            self.arrival_delay / self.arrivals as f64
+        } else {
+            0.0
+        };
+
+        let mut card = Scorecard {
+            separation_losses: self.separation_losses,
+            separation_loss_time: self.separation_loss_time,
+            alerts_answered,
+            alerts_missed: self.alerts_missed,
+            mean_alert_response,
+            worst_alert_response,
+            vectors_issued: self.vectors_issued,
+            unnecessary_vectors: self.unnecessary_vectors,
+            arrivals: self.arrivals,
+            mean_arrival_delay,
+            commands_issued: self.commands_issued,
+            nonconforming_commands: self.nonconforming_commands,
+            overall: 0.0,
+        };
+        card.overall = card.compute_overall();
+        card
+    }
+}
+
+impl SessionLog {
+    /// Summarize the session for the difficulty controller
+    pub fn performance_sample(&self) -> PerformanceSample {
+        let late = self
+            .response_times
+            .iter()
+            .filter(|t| **t > LATE_RESPONSE_THRESHOLD)
+            .count() as u32;
+
+        PerformanceSample::new(
+            self.response_times.len() as u32 + self.alerts_missed,
+            self.alerts_missed,
+            self.response_times.len() as u32,
+            late,
+        )
+    }
+}
+
+/// Check a command value is valid and uses standard increments
+fn is_command_conforming(command_type: &str, value: f64) -> bool {
+    if !validate_command(command_type, value, &ValidationConfig::default()) {
+        return false;
+    }
+
+    let increment = match command_type {
+        "heading" => 5.0,
+        "altitude" => 100.0,
+        "speed" => 10.0,
+        _ => return false,
+    };
+    (value / increment).fract() == 0.0
+}
+
+/// Structured session scorecard for the debrief UI
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scorecard {
+    pub separation_losses: u32,
+    pub separation_loss_time: f64,
+    pub alerts_answered: u32,
+    pub alerts_missed: u32,
+    pub mean_alert_response: f64,
+    pub worst_alert_response: f64,
+    pub vectors_issued: u32,
+    pub unnecessary_vectors: u32,
+    pub arrivals: u32,
+    pub mean_arrival_delay: f64,
+    pub commands_issued: u32,
+    pub nonconforming_commands: u32,
+    pub overall: f64,
+}
+
+impl Scorecard {
+    /// Overall score out of 100
+    fn compute_overall(&self) -> f64 {
+        let mut score = 100.0;
+
+        score -= self.separation_losses as f64 * 20.0;
+        score -= self.alerts_missed as f64 * 10.0;
+        score -= (self.mean_alert_response - 10.0).max(0.0) * 0.5;
+        score -= self.unnecessary_vectors as f64 * 2.0;
+        score -= (self.mean_arrival_delay / 60.0) * 2.0;
+        score -= self.nonconforming_commands as f64 * 1.0;
+
+        score.clamp(0.0, 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_scale_with_level() {
+        let easy = DifficultySettings::for_level(0.0);
+        let hard = DifficultySettings::for_level(1.0);
+
+        assert!(hard.spawn_interval < easy.spawn_interval);
+        assert!(hard.conflict_geometry_rate > easy.conflict_geometry_rate);
+        assert!(hard.pilot_error_probability > easy.pilot_error_probability);
+        assert_eq!(DifficultySettings::for_level(5.0).level, 1.0);
+    }
+
+    #[test]
+    fn test_adaptive_controller() {
+        let mut controller = DifficultyController::adaptive(0.5);
+
+        let perfect = PerformanceSample::new(4, 0, 4, 0);
+        controller.record_performance(&perfect);
+        assert!(controller.level() > 0.5);
+
+        let poor = PerformanceSample::new(4, 3, 4, 2);
+        controller.record_performance(&poor);
+        assert!(controller.level() < 0.5);
+    }
+
+    #[test]
+    fn test_fixed_controller_ignores_performance() {
+        let mut controller = DifficultyController::fixed(0.3);
+        let settings = controller.record_performance(&PerformanceSample::new(2, 2, 2, 2));
+
+        assert_eq!(settings.level, 0.3);
+    }
+
+    #[test]
+    fn test_clean_session_scores_full_marks() {
+        let mut log = SessionLog::new();
+        log.record_alert_response(Some(8.0));
+        log.record_vector(true);
+        log.record_arrival(0.0);
+        log.record_command("heading", 270.0);
+
+        let card = log.scorecard();
+        assert_eq!(card.overall, 100.0);
+        assert_eq!(card.alerts_answered, 1);
+    }
+
+    #[test]
+    fn test_scorecard_penalties() {
+        let mut log = SessionLog::new();
+        log.record_separation_loss(25.0);
+        log.record_alert_response(None);
+        log.record_vector(false);
+        log.record_command("heading", 273.0);
+        log.record_command("speed", 900.0);
+
+        let card = log.scorecard();
+        assert_eq!(card.separation_losses, 1);
+        assert_eq!(card.alerts_missed, 1);
+        assert_eq!(card.unnecessary_vectors, 1);
+        assert_eq!(card.nonconforming_commands, 2);
+        assert!(card.overall < 70.0);
+    }
+
+    #[test]
+    fn test_performance_sample_from_log() {
+        let mut log = SessionLog::new();
+        log.record_alert_response(Some(5.0));
+        log.record_alert_response(Some(45.0));
+        log.record_alert_response(None);
+
+        let sample = log.performance_sample();
+        assert_eq!(sample.alerts_raised, 3);
+        assert_eq!(sample.alerts_missed, 1);
+        assert_eq!(sample.late_resolutions, 1);
+    }
+}
@@ -0,0 +1,222 @@
+/*!
+ * TREND MODULE
+ * Separation margin trending per aircraft pair
+ */
+
+use std::collections::{HashMap, VecDeque};
+
+use wasm_bindgen::prelude::*;
+
+use crate::config::SafetyConfig;
+use crate::SeparationResult;
+
+/// Default number of updates kept per pair
+const DEFAULT_WINDOW: usize = 10;
+
+/// Normalized margin change per second below which a pair is considered stable
+const DEFAULT_STABLE_RATE: f64 = 0.001;
+
+/// Direction the separation margin is moving
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarginTrend {
+    Eroding,
+    Stable,
+    Improving,
+}
+
+/// Trend of one pair's margin over recent updates
+///
+/// `margin` is normalized to the minima: 0 means exactly at the limit of
+/// whichever standard is providing separation, 1 means twice the minimum.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarginTrendInfo {
+    pub trend: MarginTrend,
+    pub margin: f64,
+    pub rate: f64,
+    pub time_to_zero: Option<f64>,
+}
+
+/// Normalized margin of a separation result: the better of the two standards
+pub fn normalized_margin(result: &SeparationResult, config: &SafetyConfig) -> f64 {
+    let horizontal = result.horizontal_margin / config.horizontal_separation;
+    let vertical = result.vertical_margin / config.vertical_separation;
+    horizontal.max(vertical)
+}
+
+/// Tracks the separation margin of aircraft pairs across updates
+#[derive(Debug, Clone)]
+pub struct MarginTracker {
+    window: usize,
+    stable_rate: f64,
+    pairs: HashMap<(u64, u64), VecDeque<(f64, f64)>>,
+}
+
+impl MarginTracker {
+    pub fn new() -> Self {
+        MarginTracker::with_window(DEFAULT_WINDOW, DEFAULT_STABLE_RATE)
+    }
+
+    pub fn with_window(window: usize, stable_rate: f64) -> Self {
+        MarginTracker {
+            window: window.max(2),
+            stable_rate: stable_rate.abs(),
+            pairs: HashMap::new(),
+        }
+    }
+
+    /// Record the latest separation for a pair and return its trend
+    pub fn record(
+        &mut self,
+        first: u64,
+        second: u64,
+        time: f64,
+        result: &SeparationResult,
+        config: &SafetyConfig,
+    ) -> MarginTrendInfo {
+        let margin = normalized_margin(result, config);
+        let samples = self.pairs.entry(pair_key(first, second)).or_default();
+
+        // Out-of-order updates restart the history
+        if samples.back().is_some_and(|&(last, _)| time <= last) {
+            samples.clear();
+        }
+        samples.push_back((time, margin));
+        while samples.len() > self.window {
+            samples.pop_front();
+        }
+
+        evaluate(samples, self.stable_rate)
+    }
+
+    /// Current trend for a pair, if it has been recorded
+    pub fn trend(&self, first: u64, second: u64) -> Option<MarginTrendInfo> {
+        self.pairs
+            .get(&pair_key(first, second))
+            .map(|samples| evaluate(samples, self.stable_rate))
+    }
+
+    pub fn remove_pair(&mut self, first: u64, second: u64) {
+        self.pairs.remove(&pair_key(first, second));
+    }
+
+    /// Forget every pair involving an aircraft
+    pub fn remove_aircraft(&mut self, id: u64) {
+        self.pairs.retain(|&(a, b), _| a != id && b != id);
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+impl Default for MarginTracker {
+    fn default() -> Self {
+        MarginTracker::new()
+    }
+}
+
+fn pair_key(first: u64, second: u64) -> (u64, u64) {
+    (first.min(second), first.max(second))
+}
+
+/// Least-squares slope of the margin history and its extrapolation to zero
+fn evaluate(samples: &VecDeque<(f64, f64)>, stable_rate: f64) -> MarginTrendInfo {
+    let margin = samples.back().map_or(0.0, |&(_, m)| m);
+    let rate = slope(samples);
+
+    let trend = if rate < -stable_rate {
+        MarginTrend::Eroding
+    } else if rate > stable_rate {
+        MarginTrend::Improving
+    } else {
+        MarginTrend::Stable
+    };
+
+    let time_to_zero = if trend == MarginTrend::Eroding && margin > 0.0 {
+        Some(-margin / rate)
+    } else {
+        None
+    };
+
+    MarginTrendInfo {
+        trend,
+        margin,
+        rate,
+        time_to_zero,
+    }
+}
+
+fn slope(samples: &VecDeque<(f64, f64)>) -> f64 {
+    let n = samples.len() as f64;
+    if samples.len() < 2 {
+        return 0.0;
+    }
+
+    let mean_t = samples.iter().map(|&(t, _)| t).sum::<f64>() / n;
+    let mean_m = samples.iter().map(|&(_, m)| m).sum::<f64>() / n;
+
+    let mut covariance = 0.0;
+    let mut variance = 0.0;
+    for &(t, m) in samples {
+        covariance += (t - mean_t) * (m - mean_m);
+        variance += (t - mean_t) * (t - mean_t);
+    }
+
+    if variance > 0.0 {
+        covariance / variance
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SeparationStandards;
+    use crate::separation::check_separation;
+    use crate::AircraftState;
+
+    #[test]
+    fn test_eroding_margin_extrapolates_to_zero() {
+        let config = SafetyConfig::default();
+        let mut tracker = MarginTracker::new();
+
+        // Level at the same altitude, closing 0.1 nm per update from 6 nm
+        let mut info = None;
+        for step in 0..5 {
+            let a = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0);
+            let b = AircraftState::new(6.0 - step as f64 * 0.1, 0.0, 10000.0, 270.0, 250.0);
+            let result = check_separation(&a, &b, &config.standards());
+            info = Some(tracker.record(1, 2, step as f64, &result, &config));
+        }
+
+        let info = info.unwrap();
+        assert_eq!(info.trend, MarginTrend::Eroding);
+        // 5.6 nm now, 2.6 nm of margin closing at 0.1 nm/s
+        assert!((info.time_to_zero.unwrap() - 26.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_stable_and_pair_removal() {
+        let config = SafetyConfig::default();
+        let mut tracker = MarginTracker::new();
+        let a = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 250.0);
+        let b = AircraftState::new(2.0, 0.0, 12000.0, 0.0, 250.0);
+        let result = check_separation(&a, &b, &SeparationStandards::terminal());
+
+        tracker.record(1, 2, 0.0, &result, &config);
+        let info = tracker.record(2, 1, 1.0, &result, &config);
+        assert_eq!(info.trend, MarginTrend::Stable);
+        assert_eq!(info.margin, 1.0);
+        assert!(info.time_to_zero.is_none());
+
+        tracker.remove_aircraft(2);
+        assert!(tracker.trend(1, 2).is_none());
+    }
+}
@@ -1,71 +1,355 @@
-/**
+/*!
  * VALIDATION MODULE
  * Input validation and safety checks
  */
 
-use crate::AircraftState;
+use std::fmt;
+
+use wasm_bindgen::prelude::*;
+
+use crate::area::OperatingArea;
+use crate::atmosphere::corrected_minimum_altitude;
+use crate::{heading_difference, AircraftState};
+
+/// Limits every validation check is made against
+///
+/// Defaults suit airline traffic; widen them for GA or military operations.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationConfig {
+    pub min_speed: f64,
+    pub max_speed: f64,
+    pub min_altitude: f64,
+    pub max_altitude: f64,
+    /// Degrees per second
+    pub max_turn_rate: f64,
+    /// Knots per second
+    pub max_acceleration: f64,
+    /// Feet per minute
+    pub max_climb_rate: f64,
+    area: OperatingArea,
+}
+
+#[wasm_bindgen]
+impl ValidationConfig {
+    /// 100-600 kt, 0-60000 ft, within 100 nm of the origin
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> ValidationConfig {
+        ValidationConfig {
+            min_speed: 100.0,
+            max_speed: 600.0,
+            min_altitude: 0.0,
+            max_altitude: 60000.0,
+            max_turn_rate: 5.0,
+            max_acceleration: 20.0,
+            max_climb_rate: 3000.0,
+            area: OperatingArea::default(),
+        }
+    }
+
+    pub fn with_speed_range(mut self, min: f64, max: f64) -> ValidationConfig {
+        self.min_speed = min;
+        self.max_speed = max;
+        self
+    }
+
+    pub fn with_altitude_range(mut self, min: f64, max: f64) -> ValidationConfig {
+        self.min_altitude = min;
+        self.max_altitude = max;
+        self
+    }
+
+    /// Operating area of `radius` nm around the origin, keeping its altitude limits
+    pub fn with_airspace_radius(mut self, radius: f64) -> ValidationConfig {
+        let (floor, ceiling) = (self.area.floor, self.area.ceiling);
+        self.area = OperatingArea::circle(radius).with_altitude_limits(floor, ceiling);
+        self
+    }
+
+    pub fn with_max_rates(mut self, turn_rate: f64, acceleration: f64, climb_rate: f64) -> ValidationConfig {
+        self.max_turn_rate = turn_rate;
+        self.max_acceleration = acceleration;
+        self.max_climb_rate = climb_rate;
+        self
+    }
+}
+
+impl ValidationConfig {
+    pub fn with_operating_area(mut self, area: OperatingArea) -> Self {
+        self.area = area;
+        self
+    }
+
+    pub fn area(&self) -> &OperatingArea {
+        &self.area
+    }
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Check an aircraft state failed
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectReason {
+    Position,
+    Altitude,
+    Heading,
+    Speed,
+}
+
+/// One failed validation rule, with the offending value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValidationError {
+    /// Infinite or NaN where a number is required
+    NonFiniteValue { field: &'static str, value: f64 },
+    OutsideAirspace { x: f64, y: f64 },
+    AltitudeOutOfRange { value: f64 },
+    HeadingOutOfRange { value: f64 },
+    SpeedOutOfRange { value: f64 },
+}
+
+impl ValidationError {
+    /// Short rule name, stable for use as a UI or log key
+    pub fn rule(&self) -> &'static str {
+        match self {
+            ValidationError::NonFiniteValue { .. } => "non_finite_value",
+            ValidationError::OutsideAirspace { .. } => "outside_airspace",
+            ValidationError::AltitudeOutOfRange { .. } => "altitude_out_of_range",
+            ValidationError::HeadingOutOfRange { .. } => "heading_out_of_range",
+            ValidationError::SpeedOutOfRange { .. } => "speed_out_of_range",
+        }
+    }
+
+    /// The offending value; the distance from the origin for positions
+    pub fn value(&self) -> f64 {
+        match *self {
+            ValidationError::NonFiniteValue { value, .. } => value,
+            ValidationError::OutsideAirspace { x, y } => (x * x + y * y).sqrt(),
+            ValidationError::AltitudeOutOfRange { value }
+            | ValidationError::HeadingOutOfRange { value }
+            | ValidationError::SpeedOutOfRange { value } => value,
+        }
+    }
+
+    /// Field group the rule belongs to
+    pub fn reason(&self) -> RejectReason {
+        match self {
+            ValidationError::NonFiniteValue { field: "x" | "y", .. } | ValidationError::OutsideAirspace { .. } => {
+                RejectReason::Position
+            }
+            ValidationError::NonFiniteValue { field: "altitude", .. } | ValidationError::AltitudeOutOfRange { .. } => {
+                RejectReason::Altitude
+            }
+            ValidationError::NonFiniteValue { field: "heading", .. } | ValidationError::HeadingOutOfRange { .. } => {
+                RejectReason::Heading
+            }
+            ValidationError::NonFiniteValue { .. } | ValidationError::SpeedOutOfRange { .. } => RejectReason::Speed,
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::NonFiniteValue { field, value } => write!(f, "{} is not a finite number: {}", field, value),
+            ValidationError::OutsideAirspace { x, y } => write!(f, "position ({}, {}) is outside the operating area", x, y),
+            ValidationError::AltitudeOutOfRange { value } => write!(f, "altitude {} ft out of range", value),
+            ValidationError::HeadingOutOfRange { value } => write!(f, "heading {} out of range", value),
+            ValidationError::SpeedOutOfRange { value } => write!(f, "speed {} kt out of range", value),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Every rule an aircraft state failed
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ValidationReport {
+    errors: Vec<ValidationError>,
+}
+
+#[wasm_bindgen]
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Rule name of each failure
+    pub fn rules(&self) -> Vec<String> {
+        self.errors.iter().map(|e| e.rule().to_string()).collect()
+    }
+
+    /// Offending value of each failure
+    pub fn values(&self) -> Vec<f64> {
+        self.errors.iter().map(ValidationError::value).collect()
+    }
+
+    /// Readable description of each failure
+    pub fn messages(&self) -> Vec<String> {
+        self.errors.iter().map(|e| e.to_string()).collect()
+    }
+}
+
+impl ValidationReport {
+    pub fn errors(&self) -> &[ValidationError] {
+        &self.errors
+    }
+}
+
+/// One failed check for one entry of a snapshot
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rejection {
+    pub index: usize,
+    pub error: ValidationError,
+}
+
+/// Check a value is finite, then that it passes `in_range`
+fn check_value(
+    errors: &mut Vec<ValidationError>,
+    field: &'static str,
+    value: f64,
+    in_range: bool,
+    out_of_range: ValidationError,
+) {
+    if !value.is_finite() {
+        errors.push(ValidationError::NonFiniteValue { field, value });
+    } else if !in_range {
+        errors.push(out_of_range);
+    }
+}
+
+/// Every rule an aircraft state fails, in field order
+///
+/// A NaN altitude marks a primary-only target and is accepted.
+pub fn check_state(aircraft: &AircraftState, config: &ValidationConfig) -> ValidationReport {
+    let mut errors = Vec::new();
+
+    if !aircraft.x.is_finite() {
+        errors.push(ValidationError::NonFiniteValue { field: "x", value: aircraft.x });
+    } else if !aircraft.y.is_finite() {
+        errors.push(ValidationError::NonFiniteValue { field: "y", value: aircraft.y });
+    } else if !validate_position(aircraft.x, aircraft.y, config) {
+        errors.push(ValidationError::OutsideAirspace { x: aircraft.x, y: aircraft.y });
+    }
+
+    if !is_primary_only(aircraft) {
+        let altitude = aircraft.altitude;
+        let out_of_range = ValidationError::AltitudeOutOfRange { value: altitude };
+        check_value(&mut errors, "altitude", altitude, validate_altitude(altitude, config), out_of_range);
+    }
+    let heading = aircraft.heading;
+    let out_of_range = ValidationError::HeadingOutOfRange { value: heading };
+    check_value(&mut errors, "heading", heading, validate_heading(heading), out_of_range);
+    let speed = aircraft.speed;
+    let out_of_range = ValidationError::SpeedOutOfRange { value: speed };
+    check_value(&mut errors, "speed", speed, validate_speed(speed, config), out_of_range);
+
+    ValidationReport { errors }
+}
 
 /// Validate aircraft state parameters
-pub fn validate_state(aircraft: &AircraftState) -> bool {
-    validate_position(aircraft.x, aircraft.y)
-        && validate_altitude(aircraft.altitude)
-        && validate_heading(aircraft.heading)
-        && validate_speed(aircraft.speed)
+///
+/// A NaN altitude marks a primary-only target and is accepted.
+pub fn validate_state(aircraft: &AircraftState, config: &ValidationConfig) -> bool {
+    check_state(aircraft, config).is_valid()
+}
+
+/// Every failed check for every aircraft, in input order
+///
+/// Applies the same rules as `check_state`, so one pass over a snapshot
+/// says which entries to drop and why.
+pub fn validate_states(aircraft: &[AircraftState], config: &ValidationConfig) -> Vec<Rejection> {
+    let mut rejections = Vec::new();
+    for (index, state) in aircraft.iter().enumerate() {
+        let report = check_state(state, config);
+        rejections.extend(report.errors.into_iter().map(|error| Rejection { index, error }));
+    }
+    rejections
+}
+
+/// Check if a target has no altitude report (primary radar return)
+pub fn is_primary_only(aircraft: &AircraftState) -> bool {
+    aircraft.altitude.is_nan()
 }
 
-/// Validate position coordinates
-pub fn validate_position(x: f64, y: f64) -> bool {
-    // Check if position is within reasonable bounds (e.g., within 100nm of center)
-    let distance = (x * x + y * y).sqrt();
-    distance <= 100.0 && x.is_finite() && y.is_finite()
+/// Validate position coordinates against the operating area outline
+pub fn validate_position(x: f64, y: f64, config: &ValidationConfig) -> bool {
+    config.area.contains_position(x, y)
 }
 
 /// Validate altitude
-pub fn validate_altitude(altitude: f64) -> bool {
-    altitude >= 0.0 && altitude <= 60000.0 && altitude.is_finite()
+pub fn validate_altitude(altitude: f64, config: &ValidationConfig) -> bool {
+    (config.min_altitude..=config.max_altitude).contains(&altitude) && altitude.is_finite()
 }
 
-/// Validate heading
+/// Validate heading (360 is accepted as north)
 pub fn validate_heading(heading: f64) -> bool {
-    heading >= 0.0 && heading < 360.0 && heading.is_finite()
+    (0.0..=360.0).contains(&heading) && heading.is_finite()
 }
 
 /// Validate speed
-pub fn validate_speed(speed: f64) -> bool {
-    speed >= 100.0 && speed <= 600.0 && speed.is_finite()
+pub fn validate_speed(speed: f64, config: &ValidationConfig) -> bool {
+    (config.min_speed..=config.max_speed).contains(&speed) && speed.is_finite()
 }
 
 /// Validate command input
-pub fn validate_command(command_type: &str, value: f64) -> bool {
+pub fn validate_command(command_type: &str, value: f64, config: &ValidationConfig) -> bool {
     match command_type {
         "heading" => validate_heading(value),
-        "altitude" => validate_altitude(value),
-        "speed" => validate_speed(value),
+        "altitude" => validate_altitude(value, config),
+        "speed" => validate_speed(value, config),
         _ => false,
     }
 }
 
 /// Check if altitude is safe for current position
 pub fn is_altitude_safe(altitude: f64, x: f64, y: f64) -> bool {
+    altitude >= minimum_altitude(x, y)
+}
+
+/// Check altitude against the minimum for the position corrected for cold temperature
+///
+/// The correction is taken on the minimum's height above `aerodrome_elevation` (ft).
+pub fn is_altitude_safe_corrected(
+    altitude: f64,
+    x: f64,
+    y: f64,
+    aerodrome_elevation: f64,
+    aerodrome_temperature: f64,
+) -> bool {
+    altitude >= corrected_minimum_altitude(minimum_altitude(x, y), aerodrome_elevation, aerodrome_temperature)
+}
+
+/// Minimum altitude for a position relative to the airport
+pub fn minimum_altitude(x: f64, y: f64) -> f64 {
     let distance_from_airport = (x * x + y * y).sqrt();
     
     // Require higher altitude when far from airport
     if distance_from_airport > 20.0 {
-        altitude >= 5000.0
+        5000.0
     } else if distance_from_airport > 10.0 {
-        altitude >= 3000.0
+        3000.0
     } else {
-        altitude >= 0.0
+        0.0
     }
 }
 
 /// Validate heading change magnitude
 pub fn is_heading_change_safe(current_heading: f64, target_heading: f64) -> bool {
-    let diff = (target_heading - current_heading).abs();
-    let normalized_diff = if diff > 180.0 { 360.0 - diff } else { diff };
+    let diff = heading_difference(current_heading, target_heading).abs();
     
     // Allow heading changes up to 180 degrees
-    normalized_diff <= 180.0
+    diff <= 180.0
 }
 
 /// Validate speed change magnitude
@@ -84,11 +368,9 @@ pub fn is_altitude_change_safe(current_altitude: f64, target_altitude: f64) -> b
     diff <= 20000.0
 }
 
-/// Check if aircraft is in valid airspace
-pub fn is_in_valid_airspace(aircraft: &AircraftState) -> bool {
-    // Check if within radar coverage (50nm radius)
-    let distance = (aircraft.x * aircraft.x + aircraft.y * aircraft.y).sqrt();
-    distance <= 50.0
+/// Check if aircraft is inside the operating area, altitude limits included
+pub fn is_in_valid_airspace(aircraft: &AircraftState, config: &ValidationConfig) -> bool {
+    config.area.contains(aircraft.x, aircraft.y, aircraft.altitude)
 }
 
 /// Sanitize input value
@@ -101,10 +383,8 @@ pub fn sanitize_value(value: f64, min: f64, max: f64) -> f64 {
 
 /// Validate separation standards
 pub fn validate_separation_standards(horizontal_min: f64, vertical_min: f64) -> bool {
-    horizontal_min >= 0.0
-        && horizontal_min <= 10.0
-        && vertical_min >= 0.0
-        && vertical_min <= 5000.0
+    (0.0..=10.0).contains(&horizontal_min)
+        && (0.0..=5000.0).contains(&vertical_min)
         && horizontal_min.is_finite()
         && vertical_min.is_finite()
 }
@@ -130,9 +410,10 @@ mod tests {
 
     #[test]
     fn test_validate_altitude() {
-        assert!(validate_altitude(10000.0));
-        assert!(!validate_altitude(-1000.0));
-        assert!(!validate_altitude(70000.0));
+        let config = ValidationConfig::default();
+        assert!(validate_altitude(10000.0, &config));
+        assert!(!validate_altitude(-1000.0, &config));
+        assert!(!validate_altitude(70000.0, &config));
     }
 
     #[test]
@@ -140,24 +421,37 @@ mod tests {
         assert!(validate_heading(180.0));
         assert!(validate_heading(0.0));
         assert!(validate_heading(359.9));
-        assert!(!validate_heading(360.0));
+        assert!(validate_heading(360.0));
+        assert!(!validate_heading(360.1));
         assert!(!validate_heading(-10.0));
     }
 
     #[test]
     fn test_validate_speed() {
-        assert!(validate_speed(250.0));
-        assert!(!validate_speed(50.0));
-        assert!(!validate_speed(700.0));
+        let config = ValidationConfig::default();
+        assert!(validate_speed(250.0, &config));
+        assert!(!validate_speed(50.0, &config));
+        assert!(!validate_speed(700.0, &config));
+
+        // Light aircraft fly well below the airline floor
+        let general_aviation = ValidationConfig::new().with_speed_range(40.0, 300.0);
+        assert!(validate_speed(50.0, &general_aviation));
+        assert!(validate_command("speed", 60.0, &general_aviation));
+        assert!(!validate_command("speed", 60.0, &config));
     }
 
     #[test]
     fn test_validate_state() {
+        let config = ValidationConfig::default();
         let valid = AircraftState::new(10.0, 10.0, 10000.0, 180.0, 250.0);
-        assert!(validate_state(&valid));
+        assert!(validate_state(&valid, &config));
         
         let invalid = AircraftState::new(10.0, 10.0, -1000.0, 180.0, 250.0);
-        assert!(!validate_state(&invalid));
+        assert!(!validate_state(&invalid, &config));
+
+        let primary = AircraftState::new(10.0, 10.0, f64::NAN, 180.0, 250.0);
+        assert!(validate_state(&primary, &config));
+        assert!(!validate_state(&AircraftState::new(10.0, 10.0, f64::INFINITY, 180.0, 250.0), &config));
     }
 
     #[test]
@@ -167,4 +461,71 @@ mod tests {
         assert_eq!(sanitize_value(250.0, 100.0, 200.0), 200.0);
         assert_eq!(sanitize_value(f64::NAN, 100.0, 200.0), 100.0);
     }
+
+    #[test]
+    fn test_altitude_safe_cold_temperature() {
+        assert!(is_altitude_safe(3050.0, 15.0, 0.0));
+        assert!(is_altitude_safe_corrected(3050.0, 15.0, 0.0, 0.0, 15.0));
+        assert!(!is_altitude_safe_corrected(3050.0, 15.0, 0.0, 0.0, -30.0));
+
+        // A high aerodrome leaves only 500 ft of height to correct
+        assert!(is_altitude_safe_corrected(3100.0, 15.0, 0.0, 2500.0, -30.0));
+        assert!(!is_altitude_safe_corrected(3100.0, 15.0, 0.0, 0.0, -30.0));
+    }
+
+    #[test]
+    fn test_validate_states_reports_every_failure() {
+        let aircraft = [
+            AircraftState::new(10.0, 10.0, 10000.0, 180.0, 250.0),
+            AircraftState::new(200.0, 0.0, 10000.0, 400.0, 250.0),
+            AircraftState::new(0.0, 0.0, f64::NAN, 90.0, 50.0),
+        ];
+        let config = ValidationConfig::default();
+        let rejections = validate_states(&aircraft, &config);
+        assert_eq!(
+            rejections,
+            vec![
+                Rejection { index: 1, error: ValidationError::OutsideAirspace { x: 200.0, y: 0.0 } },
+                Rejection { index: 1, error: ValidationError::HeadingOutOfRange { value: 400.0 } },
+                Rejection { index: 2, error: ValidationError::SpeedOutOfRange { value: 50.0 } },
+            ]
+        );
+        let reasons: Vec<RejectReason> = rejections.iter().map(|r| r.error.reason()).collect();
+        assert_eq!(reasons, vec![RejectReason::Position, RejectReason::Heading, RejectReason::Speed]);
+        for (index, state) in aircraft.iter().enumerate() {
+            assert_eq!(validate_state(state, &config), !rejections.iter().any(|r| r.index == index));
+        }
+    }
+
+    #[test]
+    fn test_operating_area_governs_position_and_airspace() {
+        let far = AircraftState::new(150.0, 0.0, 10000.0, 90.0, 250.0);
+        assert!(!validate_position(far.x, far.y, &ValidationConfig::default()));
+        assert!(validate_position(far.x, far.y, &ValidationConfig::new().with_airspace_radius(200.0)));
+
+        let large = ValidationConfig::new()
+            .with_operating_area(OperatingArea::circle(250.0).with_altitude_limits(0.0, 45000.0));
+        assert!(validate_state(&far, &large));
+        assert!(is_in_valid_airspace(&far, &large));
+        assert!(!is_in_valid_airspace(&AircraftState { altitude: 50000.0, ..far }, &large));
+    }
+
+    #[test]
+    fn test_report_lists_every_failed_rule() {
+        let aircraft = AircraftState::new(f64::INFINITY, 0.0, 70000.0, 90.0, f64::NEG_INFINITY);
+        let report = check_state(&aircraft, &ValidationConfig::default());
+        assert_eq!(
+            report.errors(),
+            &[
+                ValidationError::NonFiniteValue { field: "x", value: f64::INFINITY },
+                ValidationError::AltitudeOutOfRange { value: 70000.0 },
+                ValidationError::NonFiniteValue { field: "speed", value: f64::NEG_INFINITY },
+            ][..]
+        );
+        assert_eq!(report.rules(), vec!["non_finite_value", "altitude_out_of_range", "non_finite_value"]);
+        assert_eq!(report.values()[1], 70000.0);
+        assert_eq!(report.messages()[1], "altitude 70000 ft out of range");
+        assert_eq!(report.errors()[2].reason(), RejectReason::Speed);
+        assert!(check_state(&AircraftState::new(0.0, 0.0, f64::NAN, 90.0, 250.0), &ValidationConfig::default()).is_valid());
+    }
 }
\ No newline at end of file
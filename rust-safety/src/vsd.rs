@@ -0,0 +1,182 @@
+/*!
+ * VSD MODULE
+ * Vertical situation display data: distance-vs-altitude series along a selected track
+ */
+
+use wasm_bindgen::prelude::*;
+
+use crate::msaw::TerrainModel;
+use crate::separation::predict_position;
+use crate::AircraftState;
+
+/// Values per (distance, altitude) point in profile and terrain buffers
+pub const VSD_POINT_STRIDE: usize = 2;
+
+/// Extent of the vertical situation display
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VsdSettings {
+    /// Distance ahead along track (nm)
+    pub range: f64,
+    /// Sample spacing along track (nm)
+    pub step: f64,
+    /// Traffic within this distance either side of the track is shown (nm)
+    pub corridor: f64,
+}
+
+#[wasm_bindgen]
+impl VsdSettings {
+    #[wasm_bindgen(constructor)]
+    pub fn new(range: f64, step: f64, corridor: f64) -> VsdSettings {
+        VsdSettings { range, step, corridor }
+    }
+}
+
+impl Default for VsdSettings {
+    /// 40 nm ahead in 1 nm steps, traffic within 5 nm of the track
+    fn default() -> Self {
+        VsdSettings::new(40.0, 1.0, 5.0)
+    }
+}
+
+/// Traffic projected onto the selected aircraft's track
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VsdTraffic {
+    pub callsign: String,
+    /// Distance ahead along track (nm)
+    pub distance: f64,
+    /// Offset from the track (nm), positive to the right
+    pub cross_track: f64,
+    pub altitude: f64,
+    pub vertical_rate: f64,
+    /// Protected block: altitude less and plus the vertical separation (ft)
+    pub bottom: f64,
+    pub top: f64,
+}
+
+/// Profile, terrain and traffic series for one aircraft
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VerticalSituation {
+    profile: Vec<f64>,
+    terrain: Vec<f64>,
+    traffic: Vec<VsdTraffic>,
+}
+
+#[wasm_bindgen]
+impl VerticalSituation {
+    /// Predicted (distance, altitude) pairs, flattened
+    pub fn profile(&self) -> Vec<f64> {
+        self.profile.clone()
+    }
+
+    /// Highest terrain or obstacle as (distance, elevation) pairs, flattened
+    pub fn terrain(&self) -> Vec<f64> {
+        self.terrain.clone()
+    }
+
+    /// Traffic in the corridor ordered by distance along track
+    pub fn traffic(&self) -> Vec<VsdTraffic> {
+        self.traffic.clone()
+    }
+}
+
+/// Build the vertical situation along the aircraft's current heading
+///
+/// The profile follows the aircraft's vertical rate at its current speed;
+/// a stationary aircraft has a single point. Terrain is sampled at every
+/// step with the model's lateral buffer, whatever the speed. Traffic is
+/// shown at its current position when it lies ahead within the range and
+/// corridor; primary-only targets without an altitude are left out.
+pub fn vertical_situation(
+    aircraft: &AircraftState,
+    traffic: &[(String, AircraftState)],
+    terrain: &TerrainModel,
+    settings: &VsdSettings,
+    vertical_separation: f64,
+) -> VerticalSituation {
+    let mut situation = VerticalSituation::default();
+    if settings.step.is_nan() || settings.step <= 0.0 || !settings.range.is_finite() {
+        return situation;
+    }
+
+    let (sin, cos) = aircraft.heading.to_radians().sin_cos();
+    let samples = (settings.range / settings.step).floor() as usize;
+    for i in 0..=samples {
+        let distance = i as f64 * settings.step;
+        let elevation = terrain.highest_near(aircraft.x + distance * sin, aircraft.y + distance * cos);
+        situation.terrain.extend_from_slice(&[distance, elevation]);
+
+        if aircraft.speed > 0.0 || i == 0 {
+            let time = if i == 0 { 0.0 } else { distance / aircraft.speed * 3600.0 };
+            let altitude = predict_position(aircraft, time).altitude;
+            situation.profile.extend_from_slice(&[distance, altitude]);
+        }
+    }
+
+    for (callsign, other) in traffic {
+        let (dx, dy) = (other.x - aircraft.x, other.y - aircraft.y);
+        let distance = dx * sin + dy * cos;
+        let cross_track = dx * cos - dy * sin;
+        if other.altitude.is_finite()
+            && (0.0..=settings.range).contains(&distance)
+            && cross_track.abs() <= settings.corridor
+        {
+            situation.traffic.push(VsdTraffic {
+                callsign: callsign.clone(),
+                distance,
+                cross_track,
+                altitude: other.altitude,
+                vertical_rate: other.vertical_rate,
+                bottom: other.altitude - vertical_separation,
+                top: other.altitude + vertical_separation,
+            });
+        }
+    }
+    situation.traffic.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    situation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain::TerrainGrid;
+
+    #[test]
+    fn test_profile_terrain_and_traffic() {
+        // Northbound at 240 kt descending 1000 ft/min: 4 nm per minute
+        let own = AircraftState::new(0.0, 0.0, 10000.0, 0.0, 240.0).with_vertical_rate(-1000.0);
+        let grid = TerrainGrid::new(-5.0, 5.0, 1.0, 10, 10, vec![2500.0; 100]).unwrap();
+        let terrain = TerrainModel::new().with_grid(grid).with_clearance(1000.0, 0.0);
+        let traffic = vec![
+            ("AHEAD".to_string(), AircraftState::new(2.0, 12.0, 8000.0, 180.0, 250.0)),
+            ("BEHIND".to_string(), AircraftState::new(0.0, -5.0, 9000.0, 0.0, 250.0)),
+            ("WIDE".to_string(), AircraftState::new(-8.0, 6.0, 9000.0, 0.0, 250.0)),
+            ("NEAR".to_string(), AircraftState::new(-1.0, 4.0, 11000.0, 0.0, 250.0)),
+        ];
+
+        let vsd = vertical_situation(&own, &traffic, &terrain, &VsdSettings::new(20.0, 4.0, 5.0), 1000.0);
+        let profile = vsd.profile();
+        assert_eq!(profile.len(), 6 * VSD_POINT_STRIDE);
+        assert_eq!(&profile[2..4], &[4.0, 9000.0]);
+        assert!((profile[11] - 5000.0).abs() < 1e-6);
+
+        let elevations: Vec<f64> = vsd.terrain().chunks_exact(VSD_POINT_STRIDE).map(|p| p[1]).collect();
+        assert_eq!(elevations, vec![0.0, 0.0, 2500.0, 2500.0, 0.0, 0.0]);
+
+        let shown = vsd.traffic();
+        let names: Vec<&str> = shown.iter().map(|t| t.callsign.as_str()).collect();
+        assert_eq!(names, vec!["NEAR", "AHEAD"]);
+        assert!((shown[1].cross_track - 2.0).abs() < 1e-9);
+        assert_eq!((shown[0].bottom, shown[0].top), (10000.0, 12000.0));
+    }
+
+    #[test]
+    fn test_stationary_aircraft() {
+        let own = AircraftState::new(0.0, 0.0, 500.0, 90.0, 0.0);
+        let vsd = vertical_situation(&own, &[], &TerrainModel::new(), &VsdSettings::default(), 1000.0);
+        assert_eq!(vsd.profile(), vec![0.0, 500.0]);
+        assert_eq!(vsd.terrain().len(), 41 * VSD_POINT_STRIDE);
+    }
+}
@@ -0,0 +1,102 @@
+/*!
+ * WAKE MODULE
+ * ICAO wake turbulence separation between a leader and its follower
+ */
+
+use crate::aircraft_types::WakeCategory;
+use crate::{heading_difference, AircraftState};
+
+/// Largest track difference at which one aircraft is behind another (degrees)
+const IN_TRAIL_ANGLE: f64 = 45.0;
+
+/// Followers this far or more below or above the leader are clear of its wake (ft)
+const WAKE_VERTICAL_BAND: f64 = 1000.0;
+
+/// Wake turbulence minimum for one leader/follower pair
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WakeMinimum {
+    /// Radar distance behind the leader (nm)
+    pub distance: f64,
+    /// Time behind the leader when separating departures (seconds)
+    pub time: f64,
+}
+
+/// ICAO wake minimum for a follower behind a leader, if one applies
+pub fn wake_minimum(leader: WakeCategory, follower: WakeCategory) -> Option<WakeMinimum> {
+    use WakeCategory::*;
+
+    let (distance, time) = match (leader, follower) {
+        (Super, Heavy) => (6.0, 120.0),
+        (Super, Medium) => (7.0, 180.0),
+        (Super, Light) => (8.0, 180.0),
+        (Heavy, Heavy) => (4.0, 0.0),
+        (Heavy, Medium) => (5.0, 120.0),
+        (Heavy, Light) => (6.0, 120.0),
+        (Medium, Light) => (5.0, 120.0),
+        _ => return None,
+    };
+    Some(WakeMinimum { distance, time })
+}
+
+/// The pair as (leader, follower) if one is in trail of the other
+///
+/// In trail means on a similar track and within the wake's vertical band;
+/// the leader is the aircraft ahead along the other's track.
+pub fn in_trail<'a>(a: &'a AircraftState, b: &'a AircraftState) -> Option<(&'a AircraftState, &'a AircraftState)> {
+    if heading_difference(a.heading, b.heading).abs() > IN_TRAIL_ANGLE {
+        return None;
+    }
+    // An unknown (NaN) altitude cannot rule the wake out
+    if (a.altitude - b.altitude).abs() >= WAKE_VERTICAL_BAND {
+        return None;
+    }
+
+    let track = a.heading.to_radians();
+    let along = (b.x - a.x) * track.sin() + (b.y - a.y) * track.cos();
+    if along > 0.0 {
+        Some((b, a))
+    } else {
+        Some((a, b))
+    }
+}
+
+/// Wake distance (nm) the pair needs, if both categories are known and one follows the other
+pub fn required_wake_distance(a: &AircraftState, b: &AircraftState) -> Option<f64> {
+    let (leader, follower) = in_trail(a, b)?;
+    wake_minimum(leader.wake?, follower.wake?).map(|minimum| minimum.distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SeparationStandards;
+    use crate::separation::check_separation;
+
+    #[test]
+    fn test_matrix() {
+        assert_eq!(wake_minimum(WakeCategory::Heavy, WakeCategory::Light).unwrap().distance, 6.0);
+        assert_eq!(wake_minimum(WakeCategory::Super, WakeCategory::Medium).unwrap().time, 180.0);
+        assert!(wake_minimum(WakeCategory::Light, WakeCategory::Heavy).is_none());
+        assert!(wake_minimum(WakeCategory::Medium, WakeCategory::Medium).is_none());
+    }
+
+    #[test]
+    fn test_light_behind_heavy_flagged_despite_radar_separation() {
+        let heavy = AircraftState::new(0.0, 4.0, 3000.0, 0.0, 160.0).with_wake(WakeCategory::Heavy);
+        let light = AircraftState::new(0.0, 0.0, 3000.0, 10.0, 120.0).with_wake(WakeCategory::Light);
+
+        let result = check_separation(&light, &heavy, &SeparationStandards::terminal());
+        assert!(result.horizontal_satisfied);
+        assert_eq!(result.wake_minimum, 6.0);
+        assert!(!result.wake_satisfied);
+        assert!(!result.is_safe);
+
+        // Heavy behind the light: no wake minimum
+        let swapped = AircraftState { y: -4.0, ..heavy };
+        assert!(check_separation(&light, &swapped, &SeparationStandards::terminal()).is_safe);
+
+        // 1000 ft below is outside the band, and unknown categories never apply
+        assert!(required_wake_distance(&light, &AircraftState { altitude: 4000.0, ..heavy }).is_none());
+        assert!(required_wake_distance(&AircraftState { wake: None, ..light }, &heavy).is_none());
+    }
+}
@@ -0,0 +1,258 @@
+/*!
+ * WIND MODULE
+ * Gridded winds and wind-optimal lateral routes between two fixes
+ */
+
+use crate::normalize_heading;
+
+/// Gridded winds by level, each level a regular grid laid out as in `TerrainGrid`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindGrid {
+    pub origin_x: f64,
+    pub origin_y: f64,
+    pub cell_size: f64,
+    pub columns: usize,
+    pub rows: usize,
+    /// (floor, (direction from, speed) per cell) sorted by floor
+    levels: Vec<(f64, Vec<(f64, f64)>)>,
+}
+
+impl WindGrid {
+    /// Calm grid; returns `None` for a non-positive cell size
+    pub fn new(origin_x: f64, origin_y: f64, cell_size: f64, columns: usize, rows: usize) -> Option<Self> {
+        if cell_size.is_nan() || cell_size <= 0.0 {
+            return None;
+        }
+        Some(WindGrid { origin_x, origin_y, cell_size, columns, rows, levels: Vec::new() })
+    }
+
+    /// Add the winds (direction from, speed) from `floor` (ft) upwards, row by row
+    ///
+    /// Returns `None` if the wind count does not match the dimensions.
+    pub fn with_level(mut self, floor: f64, winds: Vec<(f64, f64)>) -> Option<Self> {
+        if winds.len() != self.columns * self.rows {
+            return None;
+        }
+        self.levels.push((floor, winds));
+        self.levels.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Some(self)
+    }
+
+    /// Wind (direction from, speed) at a point and altitude; calm outside the grid
+    ///
+    /// Levels apply as in `WindField`: below every floor the lowest is used.
+    pub fn wind_at(&self, x: f64, y: f64, altitude: f64) -> (f64, f64) {
+        let column = ((x - self.origin_x) / self.cell_size).floor();
+        let row = ((y - self.origin_y) / self.cell_size).floor();
+        if column < 0.0 || row < 0.0 || !column.is_finite() || !row.is_finite() {
+            return (0.0, 0.0);
+        }
+        let (column, row) = (column as usize, row as usize);
+        if column >= self.columns || row >= self.rows {
+            return (0.0, 0.0);
+        }
+
+        self.levels
+            .iter()
+            .rev()
+            .find(|(floor, _)| *floor <= altitude)
+            .or(self.levels.first())
+            .map_or((0.0, 0.0), |(_, winds)| winds[row * self.columns + column])
+    }
+}
+
+/// Ground speed flying a track with the given wind (direction from, speed)
+///
+/// `None` when the crosswind or headwind is too strong to make good the track.
+pub fn ground_speed(track: f64, true_airspeed: f64, wind: (f64, f64)) -> Option<f64> {
+    let angle = (wind.0 - track).to_radians();
+    let headwind = wind.1 * angle.cos();
+    let crosswind = wind.1 * angle.sin();
+    if crosswind.abs() >= true_airspeed {
+        return None;
+    }
+    let speed = (true_airspeed * true_airspeed - crosswind * crosswind).sqrt() - headwind;
+    (speed > 0.0).then_some(speed)
+}
+
+/// Time to fly a straight leg (seconds), sampling the wind every half cell
+pub fn leg_time(grid: &WindGrid, from: (f64, f64), to: (f64, f64), altitude: f64, true_airspeed: f64) -> Option<f64> {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length = dx.hypot(dy);
+    if length == 0.0 {
+        return Some(0.0);
+    }
+    let track = normalize_heading(dx.atan2(dy).to_degrees());
+    let pieces = (length / (grid.cell_size / 2.0)).ceil().max(1.0) as usize;
+
+    (0..pieces).try_fold(0.0, |time, i| {
+        let t = (i as f64 + 0.5) / pieces as f64;
+        let wind = grid.wind_at(from.0 + dx * t, from.1 + dy * t, altitude);
+        ground_speed(track, true_airspeed, wind).map(|speed| time + length / pieces as f64 / speed * 3600.0)
+    })
+}
+
+/// Shape of the lattice searched between the two fixes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatticeSettings {
+    /// Number of legs the direct route is divided into
+    pub stages: usize,
+    /// Offsets either side of the direct route
+    pub lateral_steps: usize,
+    /// Distance between neighbouring offsets (nm)
+    pub lateral_spacing: f64,
+}
+
+impl LatticeSettings {
+    pub fn new(stages: usize, lateral_steps: usize, lateral_spacing: f64) -> Self {
+        LatticeSettings { stages, lateral_steps, lateral_spacing }
+    }
+}
+
+impl Default for LatticeSettings {
+    /// Ten legs, up to 20 nm either side in 5 nm steps
+    fn default() -> Self {
+        LatticeSettings::new(10, 4, 5.0)
+    }
+}
+
+/// Suggested lateral path and its time against the direct route
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindRoute {
+    /// Turn points after the first fix, ending with the second
+    pub waypoints: Vec<(f64, f64)>,
+    /// Time along the suggested path (seconds)
+    pub time: f64,
+    /// Time along the direct route (seconds); infinite when it cannot be flown
+    pub direct_time: f64,
+}
+
+impl WindRoute {
+    /// Time saved over the direct route (seconds)
+    pub fn saving(&self) -> f64 {
+        self.direct_time - self.time
+    }
+}
+
+/// Suggest the quickest lateral path between two fixes at a level
+///
+/// The direct route is cut into `stages` legs and each stage boundary may be
+/// offset sideways by up to `lateral_steps` spacings, moving at most one
+/// spacing per leg so the path never turns sharply. The fastest path through
+/// that lattice is found stage by stage. `None` when the fixes coincide, the
+/// settings are degenerate, or no path can be flown against the wind.
+pub fn wind_optimal_route(
+    grid: &WindGrid,
+    from: (f64, f64),
+    to: (f64, f64),
+    altitude: f64,
+    true_airspeed: f64,
+    settings: &LatticeSettings,
+) -> Option<WindRoute> {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length = dx.hypot(dy);
+    if length == 0.0 || settings.stages == 0 || settings.lateral_spacing.is_nan() || settings.lateral_spacing <= 0.0 {
+        return None;
+    }
+
+    // Offset k sits k spacings to the right of the direct route
+    let steps = settings.lateral_steps as isize;
+    let (right_x, right_y) = (dy / length, -dx / length);
+    let node = |stage: usize, offset: isize| {
+        let along = stage as f64 / settings.stages as f64;
+        let side = offset as f64 * settings.lateral_spacing;
+        (from.0 + dx * along + right_x * side, from.1 + dy * along + right_y * side)
+    };
+    let index = |offset: isize| (offset + steps) as usize;
+
+    // times[stage][offset] is the quickest time to that node, with the offset it came from
+    let width = 2 * settings.lateral_steps + 1;
+    let mut times = vec![vec![(f64::INFINITY, 0isize); width]; settings.stages + 1];
+    times[0][index(0)] = (0.0, 0);
+    for stage in 0..settings.stages {
+        for offset in -steps..=steps {
+            let (time, _) = times[stage][index(offset)];
+            if !time.is_finite() {
+                continue;
+            }
+            for next in (offset - 1).max(-steps)..=(offset + 1).min(steps) {
+                if stage + 1 == settings.stages && next != 0 {
+                    continue;
+                }
+                let Some(leg) = leg_time(grid, node(stage, offset), node(stage + 1, next), altitude, true_airspeed) else {
+                    continue;
+                };
+                if time + leg < times[stage + 1][index(next)].0 {
+                    times[stage + 1][index(next)] = (time + leg, offset);
+                }
+            }
+        }
+    }
+
+    let time = times[settings.stages][index(0)].0;
+    if !time.is_finite() {
+        return None;
+    }
+
+    let mut offsets = vec![0isize; settings.stages + 1];
+    for stage in (1..=settings.stages).rev() {
+        offsets[stage - 1] = times[stage][index(offsets[stage])].1;
+    }
+    // Keep only the nodes where the path changes direction
+    let waypoints = (1..=settings.stages)
+        .filter(|&stage| {
+            stage == settings.stages || offsets[stage] - offsets[stage - 1] != offsets[stage + 1] - offsets[stage]
+        })
+        .map(|stage| node(stage, offsets[stage]))
+        .collect();
+
+    let direct_time = leg_time(grid, from, to, altitude, true_airspeed).unwrap_or(f64::INFINITY);
+    Some(WindRoute { waypoints, time, direct_time })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ground_speed_and_levels() {
+        assert_eq!(ground_speed(90.0, 400.0, (270.0, 50.0)), Some(450.0));
+        assert!((ground_speed(0.0, 400.0, (90.0, 240.0)).unwrap() - 320.0).abs() < 1e-9);
+        assert_eq!(ground_speed(0.0, 100.0, (0.0, 120.0)), None);
+
+        let grid = WindGrid::new(0.0, 0.0, 10.0, 2, 1)
+            .unwrap()
+            .with_level(20000.0, vec![(270.0, 80.0), (250.0, 90.0)])
+            .and_then(|grid| grid.with_level(0.0, vec![(180.0, 10.0), (200.0, 15.0)]))
+            .unwrap();
+        assert_eq!(grid.wind_at(15.0, 5.0, 30000.0), (250.0, 90.0));
+        assert_eq!(grid.wind_at(5.0, 5.0, 8000.0), (180.0, 10.0));
+        assert_eq!(grid.wind_at(25.0, 5.0, 30000.0), (0.0, 0.0));
+        assert!(WindGrid::new(0.0, 0.0, 10.0, 2, 1).unwrap().with_level(0.0, vec![(0.0, 0.0)]).is_none());
+    }
+
+    #[test]
+    fn test_route_avoids_headwind_band() {
+        // 150 kt easterly in a 20 nm band along the direct route, calm either side
+        let winds = (0..8)
+            .flat_map(|row| {
+                let wind = if row == 3 || row == 4 { (90.0, 150.0) } else { (0.0, 0.0) };
+                vec![wind; 12]
+            })
+            .collect();
+        let grid = WindGrid::new(-10.0, -40.0, 10.0, 12, 8).unwrap().with_level(0.0, winds).unwrap();
+
+        let settings = LatticeSettings::default();
+        let route = wind_optimal_route(&grid, (0.0, 0.0), (100.0, 0.0), 35000.0, 400.0, &settings).unwrap();
+        assert!((route.direct_time - 1440.0).abs() < 1e-6);
+        assert!(route.saving() > 240.0);
+        assert!(route.waypoints.iter().any(|&(_, y)| y.abs() >= 10.0));
+        assert_eq!(route.waypoints.last(), Some(&(100.0, 0.0)));
+
+        // Nothing to gain in a uniform wind: the direct route is kept
+        let uniform = grid.clone().with_level(10000.0, vec![(45.0, 60.0); 96]).unwrap();
+        let route = wind_optimal_route(&uniform, (0.0, 0.0), (100.0, 0.0), 35000.0, 400.0, &settings).unwrap();
+        assert_eq!(route.waypoints, vec![(100.0, 0.0)]);
+        assert!(route.saving().abs() < 1e-6);
+    }
+}